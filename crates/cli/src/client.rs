@@ -5,6 +5,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 use anyhow::{anyhow, Context, Result};
+use scriptum_common::protocol::jsonrpc::{AGENT_POLICY_VIOLATION, SECRET_DETECTED};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -184,6 +185,25 @@ impl DaemonClient {
                 .context("failed to decode daemon json-rpc response")?;
 
             if let Some(error) = response.error {
+                let typed_code = match error.code {
+                    code if code == i64::from(AGENT_POLICY_VIOLATION) => {
+                        Some("AGENT_POLICY_VIOLATION")
+                    }
+                    code if code == i64::from(SECRET_DETECTED) => Some("SECRET_DETECTED"),
+                    _ => None,
+                };
+                if let Some(typed_code) = typed_code {
+                    let reason = error
+                        .data
+                        .as_ref()
+                        .and_then(|data| data.get("reason"))
+                        .and_then(Value::as_str)
+                        .unwrap_or(&error.message);
+                    return Err(anyhow::Error::new(crate::exit_code::RpcError {
+                        code: typed_code.to_string(),
+                        message: reason.to_string(),
+                    }));
+                }
                 anyhow::bail!(
                     "daemon json-rpc error {}: {} (data: {})",
                     error.code,
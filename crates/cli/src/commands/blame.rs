@@ -1,10 +1,16 @@
 // `scriptum blame` — CRDT-based per-line attribution.
+//
+// With `--git <rev>` the attribution comes from the daemon's git notes
+// instead (`git.blame`), so it survives after the CRDT history is gone.
 
 use clap::Args;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+use scriptum_common::protocol::rpc_methods;
+
 use crate::client::DaemonClient;
+use crate::commands::new::{detect_workspace_root_from_cwd, open_workspace};
 use crate::output::{self, OutputFormat};
 
 #[derive(Debug, Args)]
@@ -16,6 +22,10 @@ pub struct BlameArgs {
     #[arg(long)]
     section: Option<String>,
 
+    /// Blame the file at a git revision, crediting lines from commit notes.
+    #[arg(long, value_name = "REV")]
+    git: Option<String>,
+
     /// Force JSON output.
     #[arg(long)]
     json: bool,
@@ -36,20 +46,26 @@ pub struct BlameLine {
     pub summary: Option<String>,
     pub timestamp: String,
     pub content: String,
+    /// Commit that last changed the line (`--git` only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section_id: Option<String>,
 }
 
 pub fn run(args: BlameArgs) -> anyhow::Result<()> {
     let format = OutputFormat::detect(args.json);
     let doc = args.doc;
     let section = args.section;
+    let git_rev = args.git;
     let rt = tokio::runtime::Handle::try_current()
-        .map(|h| h.block_on(call_blame(doc.clone(), section.clone())))
+        .map(|h| h.block_on(call_blame(doc.clone(), section.clone(), git_rev.clone())))
         .unwrap_or_else(|_| {
             tokio::runtime::Builder::new_current_thread()
                 .enable_all()
                 .build()
                 .expect("tokio runtime should build")
-                .block_on(call_blame(doc, section))
+                .block_on(call_blame(doc, section, git_rev))
         });
 
     match rt {
@@ -64,8 +80,23 @@ pub fn run(args: BlameArgs) -> anyhow::Result<()> {
     }
 }
 
-async fn call_blame(doc: String, section: Option<String>) -> anyhow::Result<BlameResult> {
+async fn call_blame(
+    doc: String,
+    section: Option<String>,
+    git_rev: Option<String>,
+) -> anyhow::Result<BlameResult> {
     let client = DaemonClient::default();
+    if let Some(rev) = git_rev {
+        let workspace_root = detect_workspace_root_from_cwd()?;
+        let workspace_id = open_workspace(&client, &workspace_root).await?;
+        let mut result: BlameResult = client
+            .call(rpc_methods::GIT_BLAME, git_blame_params(&workspace_id, &doc, &rev))
+            .await?;
+        if let Some(section) = &section {
+            result.lines.retain(|line| line.section_id.as_deref() == Some(section.as_str()));
+        }
+        return Ok(result);
+    }
     let mut params = json!({ "doc": doc });
     if let Some(s) = &section {
         params["section"] = json!(s);
@@ -73,6 +104,10 @@ async fn call_blame(doc: String, section: Option<String>) -> anyhow::Result<Blam
     client.call("doc.blame", params).await
 }
 
+fn git_blame_params(workspace_id: &str, doc: &str, rev: &str) -> serde_json::Value {
+    json!({ "workspace_id": workspace_id, "path": doc, "rev": rev })
+}
+
 fn format_human(result: &BlameResult) -> String {
     if result.lines.is_empty() {
         return format!("{}: (empty)", result.doc_path);
@@ -86,8 +121,13 @@ fn format_human(result: &BlameResult) -> String {
     let agent_width = result.lines.iter().map(|l| l.agent.len()).max().unwrap_or(0);
 
     for bl in &result.lines {
+        let commit = bl
+            .commit
+            .as_deref()
+            .map(|sha| format!("{} ", &sha[..sha.len().min(8)]))
+            .unwrap_or_default();
         lines.push(format!(
-            "{:>nw$} | {:<aw$} | {} | {}",
+            "{commit}{:>nw$} | {:<aw$} | {} | {}",
             bl.line_number,
             bl.agent,
             &bl.timestamp,
@@ -120,6 +160,8 @@ mod tests {
                     summary: Some("initial draft".into()),
                     timestamp: "2025-01-15T10:00:00Z".into(),
                     content: "# README".into(),
+                    commit: None,
+                    section_id: None,
                 },
                 BlameLine {
                     line_number: 2,
@@ -127,6 +169,8 @@ mod tests {
                     summary: None,
                     timestamp: "2025-01-16T14:30:00Z".into(),
                     content: "".into(),
+                    commit: None,
+                    section_id: None,
                 },
                 BlameLine {
                     line_number: 3,
@@ -134,6 +178,8 @@ mod tests {
                     summary: Some("added intro".into()),
                     timestamp: "2025-01-15T10:00:00Z".into(),
                     content: "Welcome to the project.".into(),
+                    commit: None,
+                    section_id: None,
                 },
             ],
         }
@@ -179,6 +225,30 @@ mod tests {
         assert_eq!(parsed.lines[1].line_number, 2);
     }
 
+    #[test]
+    fn git_blame_reads_commit_and_section() {
+        assert_eq!(
+            git_blame_params("ws-1", "docs/readme.md", "HEAD~1"),
+            json!({ "workspace_id": "ws-1", "path": "docs/readme.md", "rev": "HEAD~1" })
+        );
+        let result: BlameResult = serde_json::from_value(json!({
+            "doc_path": "docs/readme.md",
+            "rev": "HEAD~1",
+            "lines": [{
+                "line_number": 1,
+                "agent": "claude",
+                "author_type": "agent",
+                "timestamp": "2025-01-15T10:00:00Z",
+                "content": "# README",
+                "commit": "0123456789abcdef",
+                "section_id": "readme",
+            }],
+        }))
+        .unwrap();
+        assert_eq!(result.lines[0].section_id.as_deref(), Some("readme"));
+        assert!(format_human(&result).contains("01234567 1 | claude"));
+    }
+
     #[test]
     fn digit_count_works() {
         assert_eq!(digit_count(0), 1);
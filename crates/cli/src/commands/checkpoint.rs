@@ -7,6 +7,7 @@ use serde_json::json;
 use scriptum_common::protocol::rpc_methods;
 
 use crate::client::DaemonClient;
+use crate::commands::new::{detect_workspace_root_from_cwd, open_workspace};
 use crate::output::{self, OutputFormat};
 
 const DEFAULT_CHECKPOINT_MESSAGE: &str = "chore: manual checkpoint";
@@ -25,6 +26,8 @@ pub struct CheckpointArgs {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckpointResult {
     pub job_id: String,
+    #[serde(default)]
+    pub status: Option<String>,
 }
 
 pub fn run(args: CheckpointArgs) -> anyhow::Result<()> {
@@ -67,11 +70,14 @@ fn resolve_checkpoint_message(message: Option<String>) -> anyhow::Result<String>
 }
 
 async fn call_checkpoint(message: String) -> anyhow::Result<CheckpointResult> {
+    let workspace_root = detect_workspace_root_from_cwd()?;
     let client = DaemonClient::default();
+    let workspace_id = open_workspace(&client, &workspace_root).await?;
     client
         .call(
             rpc_methods::GIT_SYNC,
             json!({
+                "workspace_id": workspace_id,
                 "action": {
                     "commit": {
                         "message": message,
@@ -83,7 +89,10 @@ async fn call_checkpoint(message: String) -> anyhow::Result<CheckpointResult> {
 }
 
 fn format_human(result: &CheckpointResult) -> String {
-    format!("Checkpoint queued (job: {})", result.job_id)
+    format!(
+        "Checkpoint queued (job: {}); track it with `scriptum git jobs {}`",
+        result.job_id, result.job_id
+    )
 }
 
 #[cfg(test)]
@@ -91,7 +100,10 @@ mod tests {
     use super::*;
 
     fn sample_result() -> CheckpointResult {
-        CheckpointResult { job_id: "be7f8e6e-b3a0-4f1e-8bd3-4b40dfc78d67".to_string() }
+        CheckpointResult {
+            job_id: "be7f8e6e-b3a0-4f1e-8bd3-4b40dfc78d67".to_string(),
+            status: Some("queued".to_string()),
+        }
     }
 
     #[test]
@@ -0,0 +1,196 @@
+// `scriptum context` — multi-document context pack for a query.
+//
+// Fronts `workspace.context_pack`: search, backlink and parent expansion and
+// budget packing happen in the daemon in one round trip.
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use scriptum_common::protocol::rpc_methods;
+
+use crate::client::DaemonClient;
+use crate::output::{self, OutputFormat};
+
+#[derive(Debug, Args)]
+pub struct ContextArgs {
+    /// Natural-language query; optional when seeds are given.
+    pub query: Option<String>,
+
+    /// Token budget for the whole pack.
+    #[arg(long, default_value = "8000")]
+    budget: usize,
+
+    /// Seed document, optionally with a section: `docs/auth.md#root/tokens`.
+    #[arg(long, value_name = "DOC[#SECTION]")]
+    seed: Vec<String>,
+
+    /// Search hits to expand from.
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// Force JSON output.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextPackResult {
+    #[serde(default)]
+    pub items: Vec<ContextPackItem>,
+    pub tokens_used: usize,
+    pub token_budget: usize,
+    #[serde(default)]
+    pub omitted: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContextPackItem {
+    pub citation: String,
+    pub path: String,
+    #[serde(default)]
+    pub section_id: Option<String>,
+    pub reason: String,
+    pub tokens: usize,
+    pub content_md: String,
+}
+
+pub fn run(args: ContextArgs) -> anyhow::Result<()> {
+    let format = OutputFormat::detect(args.json);
+    if args.query.as_deref().is_none_or(|query| query.trim().is_empty()) && args.seed.is_empty() {
+        let e = anyhow::anyhow!("give a query or at least one --seed");
+        output::print_anyhow_error(format, &e);
+        return Err(e);
+    }
+
+    let params = context_params(&args);
+    let rt = tokio::runtime::Handle::try_current()
+        .map(|h| h.block_on(call_context(params.clone())))
+        .unwrap_or_else(|_| {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("tokio runtime should build")
+                .block_on(call_context(params))
+        });
+
+    match rt {
+        Ok(result) => {
+            output::print_output(format, &result, format_human)?;
+            Ok(())
+        }
+        Err(e) => {
+            output::print_anyhow_error(format, &e);
+            Err(e)
+        }
+    }
+}
+
+fn context_params(args: &ContextArgs) -> serde_json::Value {
+    let mut params = json!({ "token_budget": args.budget });
+    if let Some(query) = &args.query {
+        params["query"] = json!(query);
+    }
+    if !args.seed.is_empty() {
+        let seeds = args
+            .seed
+            .iter()
+            .map(|seed| match seed.split_once('#') {
+                Some((path, section_id)) => json!({ "path": path, "section_id": section_id }),
+                None => json!({ "path": seed }),
+            })
+            .collect::<Vec<_>>();
+        params["seeds"] = json!(seeds);
+    }
+    if let Some(limit) = args.limit {
+        params["limit"] = json!(limit);
+    }
+    params
+}
+
+async fn call_context(params: serde_json::Value) -> anyhow::Result<ContextPackResult> {
+    let client = DaemonClient::default();
+    client.call(rpc_methods::WORKSPACE_CONTEXT_PACK, params).await
+}
+
+fn format_human(result: &ContextPackResult) -> String {
+    if result.items.is_empty() {
+        return "No matching context.".to_string();
+    }
+
+    let mut lines = Vec::new();
+    let omitted = match result.omitted {
+        0 => String::new(),
+        n => format!(", {n} section(s) over budget"),
+    };
+    lines.push(format!(
+        "Context pack: {} section(s) — {}/{} tokens{}",
+        result.items.len(),
+        result.tokens_used,
+        result.token_budget,
+        omitted
+    ));
+    for item in &result.items {
+        lines.push(String::new());
+        lines.push(format!("── {} ({}, {} tokens)", item.citation, item.reason, item.tokens));
+        lines.push(item.content_md.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(argv: &[&str]) -> ContextArgs {
+        #[derive(clap::Parser)]
+        struct Cli {
+            #[command(flatten)]
+            context: ContextArgs,
+        }
+        let argv = std::iter::once("context").chain(argv.iter().copied());
+        <Cli as clap::Parser>::parse_from(argv).context
+    }
+
+    #[test]
+    fn params_carry_query_budget_and_seeds() {
+        let args = parse(&["auth flow", "--budget", "500", "--seed", "docs/auth.md#root/tokens"]);
+        assert_eq!(
+            context_params(&args),
+            json!({
+                "query": "auth flow",
+                "token_budget": 500,
+                "seeds": [{ "path": "docs/auth.md", "section_id": "root/tokens" }],
+            })
+        );
+        assert_eq!(context_params(&parse(&["auth"]))["token_budget"], 8000);
+    }
+
+    #[test]
+    fn human_format_lists_citations() {
+        let result = ContextPackResult {
+            items: vec![ContextPackItem {
+                citation: "docs/auth.md#root/tokens".into(),
+                path: "docs/auth.md".into(),
+                section_id: Some("root/tokens".into()),
+                reason: "search".into(),
+                tokens: 42,
+                content_md: "## Tokens\nRotate hourly.\n".into(),
+            }],
+            tokens_used: 42,
+            token_budget: 8000,
+            omitted: 2,
+        };
+        let output = format_human(&result);
+        assert!(output.contains("1 section(s) — 42/8000 tokens, 2 section(s) over budget"));
+        assert!(output.contains("── docs/auth.md#root/tokens (search, 42 tokens)"));
+        assert!(output.contains("Rotate hourly."));
+    }
+
+    #[test]
+    fn human_format_empty_pack() {
+        let result =
+            ContextPackResult { items: Vec::new(), tokens_used: 0, token_budget: 8000, omitted: 0 };
+        assert_eq!(format_human(&result), "No matching context.");
+    }
+}
@@ -1,6 +1,9 @@
-// `scriptum edit` — replace section body (heading preserved).
+// `scriptum edit` — replace section body (heading preserved), or splice one
+// anchored span with `--replace OLD --with NEW`. `--dry-run` prints the diff
+// the edit would produce without applying it.
 
 use clap::Args;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -14,9 +17,10 @@ pub struct EditArgs {
     /// Document path.
     pub doc: String,
 
-    /// Section heading to edit (e.g. `## Auth`).
-    #[arg(long)]
-    section: String,
+    /// Section heading to edit (e.g. `## Auth`). With `--replace`, limits
+    /// matching to this section.
+    #[arg(long, required_unless_present = "replace")]
+    section: Option<String>,
 
     /// New content for the section body (heading preserved).
     #[arg(long, group = "content_source")]
@@ -26,6 +30,18 @@ pub struct EditArgs {
     #[arg(long, group = "content_source")]
     file: Option<String>,
 
+    /// Exact text to replace; must match exactly once unless `--occurrence` is set.
+    #[arg(long, requires = "with", conflicts_with = "content_source")]
+    replace: Option<String>,
+
+    /// Replacement text for `--replace`.
+    #[arg(long = "with", requires = "replace")]
+    with: Option<String>,
+
+    /// Which match of `--replace` to edit (1-based) when it is not unique.
+    #[arg(long, requires = "replace")]
+    occurrence: Option<usize>,
+
     /// Agent name performing the edit.
     #[arg(long)]
     agent: String,
@@ -34,6 +50,10 @@ pub struct EditArgs {
     #[arg(long)]
     summary: Option<String>,
 
+    /// Show the resulting diff without applying the edit.
+    #[arg(long)]
+    dry_run: bool,
+
     /// Force JSON output.
     #[arg(long)]
     json: bool,
@@ -48,9 +68,58 @@ pub struct EditResult {
     pub etag: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaceResult {
+    pub doc_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section_id: Option<String>,
+    pub matches: usize,
+    pub etag: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunResult {
+    #[serde(default)]
+    pub docs: Vec<DryRunDoc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunDoc {
+    pub doc_path: String,
+    pub diff: String,
+    #[serde(default)]
+    pub conflicts: Vec<DryRunConflict>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunConflict {
+    pub kind: String,
+    pub section_id: String,
+    pub agent_id: String,
+}
+
 pub fn run(args: EditArgs) -> anyhow::Result<()> {
     let format = OutputFormat::detect(args.json);
 
+    if let (Some(old_text), Some(new_text)) = (args.replace.clone(), args.with.clone()) {
+        let params = ReplaceParams {
+            doc: args.doc,
+            section: args.section,
+            old_text,
+            new_text,
+            occurrence: args.occurrence,
+            agent: args.agent,
+            summary: args.summary,
+            dry_run: args.dry_run,
+        };
+        return if args.dry_run {
+            report(format, block_on(call_replace(params)), format_dry_run_human)
+        } else {
+            report(format, block_on(call_replace(params)), format_replace_human)
+        };
+    }
+    let section = args.section.ok_or_else(|| anyhow::anyhow!("--section is required"))?;
+
     // Resolve content from --content or --file.
     let body = match (&args.content, &args.file) {
         (Some(c), _) => c.clone(),
@@ -61,23 +130,26 @@ pub fn run(args: EditArgs) -> anyhow::Result<()> {
 
     let params = EditParams {
         doc: args.doc,
-        section: args.section,
+        section,
         content: body,
         agent: args.agent,
         summary: args.summary,
+        dry_run: args.dry_run,
     };
 
-    let rt = tokio::runtime::Handle::try_current()
-        .map(|h| h.block_on(call_edit(params.clone())))
-        .unwrap_or_else(|_| {
-            tokio::runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()
-                .expect("tokio runtime should build")
-                .block_on(call_edit(params))
-        });
-
-    match rt {
+    if args.dry_run {
+        report(format, block_on(call_edit(params)), format_dry_run_human)
+    } else {
+        report(format, block_on(call_edit(params)), format_human)
+    }
+}
+
+fn report<T: Serialize>(
+    format: OutputFormat,
+    result: anyhow::Result<T>,
+    format_human: fn(&T) -> String,
+) -> anyhow::Result<()> {
+    match result {
         Ok(result) => {
             output::print_output(format, &result, format_human)?;
             Ok(())
@@ -89,6 +161,17 @@ pub fn run(args: EditArgs) -> anyhow::Result<()> {
     }
 }
 
+fn block_on<T>(future: impl std::future::Future<Output = T>) -> T {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => handle.block_on(future),
+        Err(_) => tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime should build")
+            .block_on(future),
+    }
+}
+
 #[derive(Debug, Clone)]
 struct EditParams {
     doc: String,
@@ -96,9 +179,10 @@ struct EditParams {
     content: String,
     agent: String,
     summary: Option<String>,
+    dry_run: bool,
 }
 
-async fn call_edit(params: EditParams) -> anyhow::Result<EditResult> {
+async fn call_edit<R: DeserializeOwned>(params: EditParams) -> anyhow::Result<R> {
     let client = DaemonClient::default();
     let mut rpc_params = json!({
         "doc": params.doc,
@@ -109,9 +193,79 @@ async fn call_edit(params: EditParams) -> anyhow::Result<EditResult> {
     if let Some(summary) = &params.summary {
         rpc_params["summary"] = json!(summary);
     }
+    if params.dry_run {
+        rpc_params["dry_run"] = json!(true);
+    }
     client.call(rpc_methods::DOC_EDIT_SECTION, rpc_params).await
 }
 
+#[derive(Debug, Clone)]
+struct ReplaceParams {
+    doc: String,
+    section: Option<String>,
+    old_text: String,
+    new_text: String,
+    occurrence: Option<usize>,
+    agent: String,
+    summary: Option<String>,
+    dry_run: bool,
+}
+
+async fn call_replace<R: DeserializeOwned>(params: ReplaceParams) -> anyhow::Result<R> {
+    let client = DaemonClient::default();
+    let mut rpc_params = json!({
+        "doc": params.doc,
+        "old_text": params.old_text,
+        "new_text": params.new_text,
+        "agent_id": params.agent,
+    });
+    if let Some(section) = &params.section {
+        rpc_params["section_id"] = json!(section);
+    }
+    if let Some(occurrence) = params.occurrence {
+        rpc_params["occurrence"] = json!(occurrence);
+    }
+    if let Some(summary) = &params.summary {
+        rpc_params["summary"] = json!(summary);
+    }
+    if params.dry_run {
+        rpc_params["dry_run"] = json!(true);
+    }
+    client.call(rpc_methods::DOC_REPLACE, rpc_params).await
+}
+
+fn format_replace_human(result: &ReplaceResult) -> String {
+    let scope = match &result.section_id {
+        Some(section_id) => format!("{} [{}]", result.doc_path, section_id),
+        None => result.doc_path.clone(),
+    };
+    let ambiguity = if result.matches > 1 {
+        format!(", 1 of {} matches", result.matches)
+    } else {
+        String::new()
+    };
+    format!("Replaced text in {scope}{ambiguity} (etag: {})", result.etag)
+}
+
+fn format_dry_run_human(result: &DryRunResult) -> String {
+    let mut out = String::new();
+    for doc in &result.docs {
+        if doc.diff.is_empty() {
+            out.push_str(&format!("No changes to {}\n", doc.doc_path));
+        } else {
+            out.push_str(&doc.diff);
+        }
+        for conflict in &doc.conflicts {
+            out.push_str(&format!(
+                "! {} conflict: {} holds `{}`\n",
+                conflict.kind, conflict.agent_id, conflict.section_id
+            ));
+        }
+    }
+    out.push_str("(dry run, nothing applied)");
+    out
+}
+
 fn format_human(result: &EditResult) -> String {
     format!(
         "Edited {} > {} [{}] ({} bytes, etag: {})",
@@ -153,6 +307,41 @@ mod tests {
         assert_eq!(parsed.bytes_written, 256);
     }
 
+    #[test]
+    fn replace_human_format_mentions_selected_occurrence() {
+        let result = ReplaceResult {
+            doc_path: "docs/readme.md".into(),
+            section_id: Some("readme/auth".into()),
+            matches: 3,
+            etag: "doc:abc:4".into(),
+        };
+        let output = format_replace_human(&result);
+        assert!(output.contains("docs/readme.md [readme/auth]"));
+        assert!(output.contains("1 of 3 matches"));
+        assert!(output.contains("doc:abc:4"));
+    }
+
+    #[test]
+    fn dry_run_human_format_prints_diff_and_conflicts() {
+        let result = DryRunResult {
+            docs: vec![DryRunDoc {
+                doc_path: "docs/readme.md".into(),
+                diff: "--- a/docs/readme.md\n+++ b/docs/readme.md\n@@ -1 +1 @@\n-old\n+new\n"
+                    .into(),
+                conflicts: vec![DryRunConflict {
+                    kind: "lease".into(),
+                    section_id: "readme/auth".into(),
+                    agent_id: "cursor-1".into(),
+                }],
+            }],
+        };
+        let output = format_dry_run_human(&result);
+        assert!(output.starts_with("--- a/docs/readme.md\n+++ b/docs/readme.md\n"));
+        assert!(output.contains("-old\n+new\n"));
+        assert!(output.contains("! lease conflict: cursor-1 holds `readme/auth`"));
+        assert!(output.ends_with("(dry run, nothing applied)"));
+    }
+
     #[test]
     fn content_file_reads_from_disk() {
         let dir = tempfile::TempDir::new().unwrap();
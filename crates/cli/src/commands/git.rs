@@ -0,0 +1,241 @@
+// `scriptum git` — inspect the daemon's git sync for the current workspace.
+//
+// `scriptum git jobs` lists queued and finished commit/push jobs via
+// `git.jobs`, with the session branch and pull request of each; given a
+// job id it shows that job via `git.job_status`.
+
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use scriptum_common::protocol::rpc_methods;
+
+use crate::client::DaemonClient;
+use crate::commands::new::{detect_workspace_root_from_cwd, open_workspace};
+use crate::output::{self, OutputFormat};
+
+#[derive(Debug, Args)]
+pub struct GitArgs {
+    #[command(subcommand)]
+    pub command: GitCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum GitCommand {
+    /// List git sync jobs, or show one job
+    Jobs(JobsArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct JobsArgs {
+    /// Show only this job.
+    pub job_id: Option<String>,
+
+    /// Only jobs in this state: queued, running, retrying, succeeded or failed.
+    #[arg(long)]
+    state: Option<String>,
+
+    /// Maximum number of jobs to list.
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// Force JSON output.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitJobsResult {
+    #[serde(default)]
+    pub items: Vec<GitJob>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitJob {
+    pub job_id: String,
+    pub state: String,
+    pub action: String,
+    pub message: String,
+    #[serde(default)]
+    pub trigger_type: Option<String>,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub pull_request_url: Option<String>,
+    pub attempt_count: u32,
+    #[serde(default)]
+    pub next_attempt_at: Option<String>,
+    #[serde(default)]
+    pub last_error: Option<GitJobError>,
+    pub created_at: String,
+    #[serde(default)]
+    pub finished_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitJobError {
+    #[serde(default)]
+    pub code: Option<String>,
+    pub message: String,
+    #[serde(default)]
+    pub stderr: Option<String>,
+}
+
+pub fn run(args: GitArgs) -> anyhow::Result<()> {
+    match args.command {
+        GitCommand::Jobs(args) => run_jobs(args),
+    }
+}
+
+fn run_jobs(args: JobsArgs) -> anyhow::Result<()> {
+    let format = OutputFormat::detect(args.json);
+    let rt = tokio::runtime::Handle::try_current()
+        .map(|h| h.block_on(call_jobs(&args)))
+        .unwrap_or_else(|_| {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("tokio runtime should build")
+                .block_on(call_jobs(&args))
+        });
+
+    match rt {
+        Ok(result) => {
+            output::print_output(format, &result, format_human)?;
+            Ok(())
+        }
+        Err(e) => {
+            output::print_anyhow_error(format, &e);
+            Err(e)
+        }
+    }
+}
+
+fn jobs_params(workspace_id: &str, args: &JobsArgs) -> serde_json::Value {
+    let mut params = json!({ "workspace_id": workspace_id });
+    if let Some(state) = &args.state {
+        params["state"] = json!(state);
+    }
+    if let Some(limit) = args.limit {
+        params["limit"] = json!(limit);
+    }
+    params
+}
+
+async fn call_jobs(args: &JobsArgs) -> anyhow::Result<GitJobsResult> {
+    let workspace_root = detect_workspace_root_from_cwd()?;
+    let client = DaemonClient::default();
+    let workspace_id = open_workspace(&client, &workspace_root).await?;
+
+    match &args.job_id {
+        Some(job_id) => {
+            let job: GitJob = client
+                .call(
+                    rpc_methods::GIT_JOB_STATUS,
+                    json!({ "workspace_id": workspace_id, "job_id": job_id }),
+                )
+                .await?;
+            Ok(GitJobsResult { items: vec![job] })
+        }
+        None => client.call(rpc_methods::GIT_JOBS, jobs_params(&workspace_id, args)).await,
+    }
+}
+
+fn format_human(result: &GitJobsResult) -> String {
+    if result.items.is_empty() {
+        return "No git jobs.".to_string();
+    }
+
+    let mut lines = Vec::new();
+    for job in &result.items {
+        let mut line = format!(
+            "{}  {:<9} {:<15} attempts={}  {}",
+            job.job_id, job.state, job.action, job.attempt_count, job.message
+        );
+        if let Some(trigger) = &job.trigger_type {
+            line.push_str(&format!(" [{trigger}]"));
+        }
+        lines.push(line);
+        if let Some(branch) = &job.branch {
+            lines.push(format!("    branch {branch}"));
+        }
+        if let Some(url) = &job.pull_request_url {
+            lines.push(format!("    pull request {url}"));
+        }
+        if let Some(next_attempt_at) = &job.next_attempt_at {
+            lines.push(format!("    next attempt at {next_attempt_at}"));
+        }
+        if let Some(error) = &job.last_error {
+            lines.push(format!("    error: {}", error.message));
+            if let Some(stderr) = error.stderr.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+                for stderr_line in stderr.lines() {
+                    lines.push(format!("      {stderr_line}"));
+                }
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(argv: &[&str]) -> GitArgs {
+        #[derive(clap::Parser)]
+        struct Cli {
+            #[command(flatten)]
+            git: GitArgs,
+        }
+        let argv = std::iter::once("git").chain(argv.iter().copied());
+        <Cli as clap::Parser>::parse_from(argv).git
+    }
+
+    #[test]
+    fn jobs_params_carry_state_and_limit() {
+        let GitCommand::Jobs(args) = parse(&["jobs", "--state", "failed", "--limit", "5"]).command;
+        assert_eq!(
+            jobs_params("ws-1", &args),
+            json!({ "workspace_id": "ws-1", "state": "failed", "limit": 5 })
+        );
+        let GitCommand::Jobs(args) = parse(&["jobs", "job-1"]).command;
+        assert_eq!(args.job_id.as_deref(), Some("job-1"));
+        assert_eq!(jobs_params("ws-1", &args), json!({ "workspace_id": "ws-1" }));
+    }
+
+    #[test]
+    fn human_format_shows_retry_and_stderr() {
+        let result = GitJobsResult {
+            items: vec![GitJob {
+                job_id: "job-1".into(),
+                state: "retrying".into(),
+                action: "commit_and_push".into(),
+                message: "docs: update".into(),
+                trigger_type: Some("checkpoint".into()),
+                branch: Some("scriptum/claude/2026-01-01".into()),
+                pull_request_url: Some("https://forge.example/pulls/3".into()),
+                attempt_count: 2,
+                next_attempt_at: Some("2026-01-01T00:00:04Z".into()),
+                last_error: Some(GitJobError {
+                    code: Some("1".into()),
+                    message: "`git push` failed with code Some(1)".into(),
+                    stderr: Some("! [rejected] main -> main (fetch first)\n".into()),
+                }),
+                created_at: "2026-01-01T00:00:00Z".into(),
+                finished_at: None,
+            }],
+        };
+        let output = format_human(&result);
+        assert!(output.contains("job-1  retrying  commit_and_push attempts=2  docs: update"));
+        assert!(output.contains("[checkpoint]"));
+        assert!(output.contains("    branch scriptum/claude/2026-01-01"));
+        assert!(output.contains("    pull request https://forge.example/pulls/3"));
+        assert!(output.contains("next attempt at 2026-01-01T00:00:04Z"));
+        assert!(output.contains("      ! [rejected] main -> main (fetch first)"));
+    }
+
+    #[test]
+    fn human_format_empty_list() {
+        assert_eq!(format_human(&GitJobsResult { items: Vec::new() }), "No git jobs.");
+    }
+}
@@ -8,19 +8,24 @@ pub mod bundle;
 pub mod checkpoint;
 pub mod claim;
 pub mod conflicts;
+pub mod context;
 pub mod diff;
 pub mod doctor;
 pub mod edit;
+pub mod git;
 pub mod init;
 pub mod ls;
 pub mod new;
 pub mod peek;
+pub mod plan;
 pub mod read;
 pub mod search;
 pub mod sections;
 pub mod setup;
+pub mod stale;
 pub mod status;
 pub mod tree;
+pub mod undo;
 pub mod whoami;
 
 #[derive(Subcommand)]
@@ -47,12 +52,20 @@ pub enum Command {
     Ls(ls::LsArgs),
     /// CRDT-based per-line attribution
     Blame(blame::BlameArgs),
+    /// List sections whose referenced code changed since their last edit
+    Stale(stale::StaleArgs),
     /// Claim an advisory lease on a section
     Claim(claim::ClaimArgs),
+    /// Declare planned edits from a YAML file and show overlaps
+    Plan(plan::PlanArgs),
     /// Context bundling with token budget
     Bundle(bundle::BundleArgs),
+    /// Pack context from many documents for a query
+    Context(context::ContextArgs),
     /// Trigger an explicit git checkpoint commit
     Checkpoint(checkpoint::CheckpointArgs),
+    /// Inspect git sync jobs
+    Git(git::GitArgs),
     /// Show agent identity and workspace state
     Whoami(whoami::WhoamiArgs),
     /// Show agent's active sections and overlaps
@@ -65,6 +78,8 @@ pub enum Command {
     Setup(setup::SetupArgs),
     /// Read without registering intent
     Peek(peek::PeekArgs),
+    /// Revert an agent's recent edits
+    Undo(undo::UndoArgs),
 }
 
 pub fn run(cmd: Command) -> anyhow::Result<()> {
@@ -80,14 +95,19 @@ pub fn run(cmd: Command) -> anyhow::Result<()> {
         Command::Doctor(args) => doctor::run(args),
         Command::Ls(args) => ls::run(args),
         Command::Blame(args) => blame::run(args),
+        Command::Stale(args) => stale::run(args),
         Command::Claim(args) => claim::run(args),
+        Command::Plan(args) => plan::run(args),
         Command::Bundle(args) => bundle::run(args),
+        Command::Context(args) => context::run(args),
         Command::Checkpoint(args) => checkpoint::run(args),
+        Command::Git(args) => git::run(args),
         Command::Whoami(args) => whoami::run(args),
         Command::Status(args) => status::run(args),
         Command::Conflicts(args) => conflicts::run(args),
         Command::Agents(args) => agents::run(args),
         Command::Setup(args) => setup::run(args),
         Command::Peek(args) => peek::run(args),
+        Command::Undo(args) => undo::run(args),
     }
 }
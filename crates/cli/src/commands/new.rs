@@ -98,19 +98,10 @@ async fn create_document_with_client(
     workspace_root: &Path,
     request: &NewRequest,
 ) -> anyhow::Result<NewResult> {
-    let workspace_root_text = workspace_root.to_string_lossy().to_string();
-    let workspace: WorkspaceOpenRpcResult = client
-        .call(
-            rpc_methods::WORKSPACE_OPEN,
-            json!({
-                "root_path": workspace_root_text,
-            }),
-        )
-        .await
-        .context("workspace.open request failed")?;
+    let workspace_id = open_workspace(client, workspace_root).await?;
 
     let mut params = json!({
-        "workspace_id": workspace.workspace_id,
+        "workspace_id": workspace_id,
         "path": request.path,
     });
     if let Some(title) = &request.title {
@@ -123,7 +114,25 @@ async fn create_document_with_client(
     client.call(rpc_methods::DOC_CREATE, params).await.context("doc.create request failed")
 }
 
-fn detect_workspace_root_from_cwd() -> anyhow::Result<PathBuf> {
+/// Register `workspace_root` with the daemon and return its workspace id.
+pub(crate) async fn open_workspace(
+    client: &DaemonClient,
+    workspace_root: &Path,
+) -> anyhow::Result<String> {
+    let workspace_root_text = workspace_root.to_string_lossy().to_string();
+    let workspace: WorkspaceOpenRpcResult = client
+        .call(
+            rpc_methods::WORKSPACE_OPEN,
+            json!({
+                "root_path": workspace_root_text,
+            }),
+        )
+        .await
+        .context("workspace.open request failed")?;
+    Ok(workspace.workspace_id)
+}
+
+pub(crate) fn detect_workspace_root_from_cwd() -> anyhow::Result<PathBuf> {
     let cwd = std::env::current_dir().context("failed to resolve current working directory")?;
     find_workspace_root(&cwd).ok_or_else(|| {
         anyhow::anyhow!(
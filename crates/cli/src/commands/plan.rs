@@ -0,0 +1,255 @@
+// `scriptum plan` — declare which docs and sections an agent will touch.
+//
+// Reads a YAML plan file, registers it as soft leases via `agent.plan` and
+// prints the overlaps with other agents' plans, leases and recent edits.
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use scriptum_common::protocol::rpc_methods;
+
+use crate::client::DaemonClient;
+use crate::output::{self, OutputFormat};
+
+#[derive(Debug, Args)]
+pub struct PlanArgs {
+    /// YAML plan file.
+    pub file: String,
+
+    /// Agent the plan belongs to (overrides the file).
+    #[arg(long)]
+    agent: Option<String>,
+
+    /// Soft lease TTL in seconds (overrides the file).
+    #[arg(long)]
+    ttl: Option<u32>,
+
+    /// Force JSON output.
+    #[arg(long)]
+    json: bool,
+}
+
+/// A plan file:
+///
+/// ```yaml
+/// agent: codex-1
+/// entries:
+///   - doc: docs/api.md
+///     section: root/auth
+///     intent: rotate tokens
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlanFile {
+    #[serde(default)]
+    workspace_id: Option<String>,
+    #[serde(default)]
+    agent: Option<String>,
+    #[serde(default)]
+    ttl_sec: Option<u32>,
+    entries: Vec<PlanFileEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlanFileEntry {
+    /// Workspace-relative doc path.
+    #[serde(default)]
+    doc: Option<String>,
+    #[serde(default)]
+    doc_id: Option<String>,
+    section: String,
+    intent: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanResult {
+    pub agent_id: String,
+    pub expires_at: String,
+    #[serde(default)]
+    pub entries: Vec<PlanEntry>,
+    #[serde(default)]
+    pub conflicts: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanEntry {
+    pub doc_id: String,
+    pub path: String,
+    pub section_id: String,
+    pub intent: String,
+    #[serde(default)]
+    pub overlaps: Vec<PlanOverlap>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanOverlap {
+    pub kind: String,
+    pub agent_id: String,
+    pub section_id: String,
+    #[serde(default)]
+    pub intent: Option<String>,
+    pub at: String,
+}
+
+pub fn run(args: PlanArgs) -> anyhow::Result<()> {
+    let format = OutputFormat::detect(args.json);
+    let params = std::fs::read_to_string(&args.file)
+        .map_err(|e| anyhow::anyhow!("failed to read plan file `{}`: {e}", args.file))
+        .and_then(|raw| plan_params(&raw, args.agent, args.ttl));
+    let params = match params {
+        Ok(params) => params,
+        Err(e) => {
+            output::print_anyhow_error(format, &e);
+            return Err(e);
+        }
+    };
+
+    let rt = tokio::runtime::Handle::try_current()
+        .map(|h| h.block_on(call_plan(params.clone())))
+        .unwrap_or_else(|_| {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("tokio runtime should build")
+                .block_on(call_plan(params))
+        });
+
+    match rt {
+        Ok(result) => {
+            output::print_output(format, &result, format_human)?;
+            Ok(())
+        }
+        Err(e) => {
+            output::print_anyhow_error(format, &e);
+            Err(e)
+        }
+    }
+}
+
+/// Turn a YAML plan file into `agent.plan` params; flags win over the file.
+fn plan_params(
+    raw: &str,
+    agent: Option<String>,
+    ttl: Option<u32>,
+) -> anyhow::Result<serde_json::Value> {
+    let plan: PlanFile =
+        serde_yaml::from_str(raw).map_err(|e| anyhow::anyhow!("invalid plan file: {e}"))?;
+    if plan.entries.is_empty() {
+        anyhow::bail!("plan file has no entries");
+    }
+
+    let entries = plan
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let mut value = match (entry.doc_id, entry.doc) {
+                (Some(doc_id), _) => json!({ "doc_id": doc_id }),
+                (None, Some(path)) => json!({ "path": path }),
+                (None, None) => anyhow::bail!("plan entry `{}` needs doc or doc_id", entry.section),
+            };
+            value["section_id"] = json!(entry.section);
+            value["intent"] = json!(entry.intent);
+            Ok(value)
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    let mut params = json!({ "entries": entries });
+    if let Some(workspace_id) = plan.workspace_id {
+        params["workspace_id"] = json!(workspace_id);
+    }
+    if let Some(agent_id) = agent.or(plan.agent) {
+        params["agent_id"] = json!(agent_id);
+    }
+    if let Some(ttl_sec) = ttl.or(plan.ttl_sec) {
+        params["ttl_sec"] = json!(ttl_sec);
+    }
+    Ok(params)
+}
+
+async fn call_plan(params: serde_json::Value) -> anyhow::Result<PlanResult> {
+    let client = DaemonClient::default();
+    client.call(rpc_methods::AGENT_PLAN, params).await
+}
+
+fn format_human(result: &PlanResult) -> String {
+    let mut lines = vec![format!(
+        "Plan for {}: {} entries, {} with overlaps (leases until {})",
+        result.agent_id,
+        result.entries.len(),
+        result.conflicts,
+        result.expires_at
+    )];
+    for entry in &result.entries {
+        lines.push(format!("  {} [{}] — {}", entry.path, entry.section_id, entry.intent));
+        for overlap in &entry.overlaps {
+            let mut line = format!(
+                "    ! {} by {} on [{}]",
+                overlap.kind, overlap.agent_id, overlap.section_id
+            );
+            if let Some(intent) = &overlap.intent {
+                line.push_str(&format!(" ({intent})"));
+            }
+            lines.push(line);
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_file_maps_to_rpc_params_with_flag_overrides() {
+        let raw = "\
+agent: codex-1
+ttl_sec: 600
+entries:
+  - doc: docs/api.md
+    section: root/auth
+    intent: rotate tokens
+  - doc_id: 6f1c2b1e-1111-4a4a-9b9b-000000000001
+    section: root/data
+    intent: add schema
+";
+        let params = plan_params(raw, Some("codex-2".into()), None).unwrap();
+        assert_eq!(params["agent_id"], "codex-2");
+        assert_eq!(params["ttl_sec"], 600);
+        assert_eq!(params["entries"][0]["path"], "docs/api.md");
+        assert_eq!(params["entries"][0]["section_id"], "root/auth");
+        assert_eq!(params["entries"][1]["doc_id"], "6f1c2b1e-1111-4a4a-9b9b-000000000001");
+        assert!(params.get("workspace_id").is_none());
+
+        assert!(plan_params("entries: []\n", None, None).is_err());
+        let missing_doc = "entries:\n  - section: root\n    intent: x\n";
+        assert!(plan_params(missing_doc, None, None).is_err());
+    }
+
+    #[test]
+    fn human_format_lists_overlaps_under_entries() {
+        let result = PlanResult {
+            agent_id: "codex-1".into(),
+            expires_at: "2026-01-01T00:30:00Z".into(),
+            entries: vec![PlanEntry {
+                doc_id: "d1".into(),
+                path: "docs/api.md".into(),
+                section_id: "root/auth".into(),
+                intent: "rotate tokens".into(),
+                overlaps: vec![PlanOverlap {
+                    kind: "plan".into(),
+                    agent_id: "claude-1".into(),
+                    section_id: "root".into(),
+                    intent: Some("restructure".into()),
+                    at: "2026-01-01T00:30:00Z".into(),
+                }],
+            }],
+            conflicts: 1,
+        };
+        let output = format_human(&result);
+        assert!(output.contains("1 entries, 1 with overlaps"));
+        assert!(output.contains("docs/api.md [root/auth] — rotate tokens"));
+        assert!(output.contains("! plan by claude-1 on [root] (restructure)"));
+    }
+}
@@ -1,9 +1,14 @@
 // `scriptum read` — read document content, optionally scoped to a section.
+//
+// `--outline`, `--excerpt` and `--budget` map onto the `doc.read` modes so
+// agents can page through large docs instead of reading them whole.
 
 use clap::Args;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+use scriptum_common::protocol::rpc_methods;
+
 use crate::client::DaemonClient;
 use crate::output::{self, OutputFormat};
 
@@ -20,6 +25,23 @@ pub struct ReadArgs {
     #[arg(long)]
     agent: Option<String>,
 
+    /// Show headings with per-section token counts instead of content.
+    #[arg(long, conflicts_with_all = ["section", "excerpt", "budget"])]
+    outline: bool,
+
+    /// Show the first TOKENS tokens of every section.
+    #[arg(long, value_name = "TOKENS", conflicts_with = "section")]
+    excerpt: Option<usize>,
+
+    /// Read at most TOKENS tokens of whole sections; with `--excerpt`, cap
+    /// all excerpts together.
+    #[arg(long, value_name = "TOKENS", conflicts_with = "section")]
+    budget: Option<usize>,
+
+    /// Section ID a budgeted read starts from (the previous page's `next`).
+    #[arg(long, value_name = "SECTION_ID", requires = "budget", conflicts_with = "excerpt")]
+    from: Option<String>,
+
     /// Force JSON output.
     #[arg(long)]
     json: bool,
@@ -35,18 +57,71 @@ pub struct ReadResult {
     pub section_id: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadModeResult {
+    pub document: ReadDocument,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_tokens: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outline: Option<Vec<OutlineEntry>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excerpts: Option<Vec<SectionExcerpt>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_md: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub range: Option<ReadRange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadDocument {
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutlineEntry {
+    pub section_id: String,
+    pub heading: String,
+    pub level: u8,
+    pub tokens: usize,
+    pub subtree_tokens: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionExcerpt {
+    pub section_id: String,
+    pub heading: String,
+    pub text: String,
+    pub tokens: usize,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadRange {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_section_id: Option<String>,
+    pub tokens: usize,
+    #[serde(default)]
+    pub truncated: bool,
+}
+
 pub fn run(args: ReadArgs) -> anyhow::Result<()> {
     let format = OutputFormat::detect(args.json);
+    if let Some(mode) = read_mode_params(&args) {
+        let rt = block_on(call_read_mode(args.doc, mode));
+        return match rt {
+            Ok(result) => {
+                output::print_output(format, &result, format_mode_human)?;
+                Ok(())
+            }
+            Err(e) => {
+                output::print_anyhow_error(format, &e);
+                Err(e)
+            }
+        };
+    }
+
     let params = ReadParams { doc: args.doc, section: args.section, agent: args.agent };
-    let rt = tokio::runtime::Handle::try_current()
-        .map(|h| h.block_on(call_read(params.clone())))
-        .unwrap_or_else(|_| {
-            tokio::runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()
-                .expect("tokio runtime should build")
-                .block_on(call_read(params))
-        });
+    let rt = block_on(call_read(params));
 
     match rt {
         Ok(result) => {
@@ -67,6 +142,47 @@ struct ReadParams {
     agent: Option<String>,
 }
 
+fn block_on<T>(future: impl std::future::Future<Output = T>) -> T {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => handle.block_on(future),
+        Err(_) => tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("tokio runtime should build")
+            .block_on(future),
+    }
+}
+
+/// `doc.read` mode params for `--outline`, `--excerpt` and `--budget`; None
+/// for a plain read.
+fn read_mode_params(args: &ReadArgs) -> Option<serde_json::Value> {
+    let mut params = if args.outline {
+        json!({ "mode": "outline" })
+    } else if let Some(excerpt_tokens) = args.excerpt {
+        json!({ "mode": "excerpt", "excerpt_tokens": excerpt_tokens })
+    } else if args.budget.is_some() {
+        json!({ "mode": "section_range" })
+    } else {
+        return None;
+    };
+    if let Some(budget) = args.budget {
+        params["token_budget"] = json!(budget);
+    }
+    if let Some(from) = &args.from {
+        params["from_section_id"] = json!(from);
+    }
+    Some(params)
+}
+
+async fn call_read_mode(
+    doc: String,
+    mut params: serde_json::Value,
+) -> anyhow::Result<ReadModeResult> {
+    let client = DaemonClient::default();
+    params["doc"] = json!(doc);
+    client.call(rpc_methods::DOC_READ, params).await
+}
+
 async fn call_read(params: ReadParams) -> anyhow::Result<ReadResult> {
     let client = DaemonClient::default();
     let mut rpc_params = json!({ "doc": params.doc });
@@ -91,6 +207,50 @@ fn format_human(result: &ReadResult) -> String {
     lines.join("\n")
 }
 
+fn format_mode_human(result: &ReadModeResult) -> String {
+    let mut lines = vec![match result.total_tokens {
+        Some(total) => format!("# {} ({total} tokens)", result.document.path),
+        None => format!("# {}", result.document.path),
+    }];
+    lines.push(String::new());
+
+    if let Some(outline) = &result.outline {
+        for entry in outline {
+            let indent = "  ".repeat(usize::from(entry.level.saturating_sub(1)));
+            lines.push(format!(
+                "{indent}{} [{}] {} tokens ({} with subsections)",
+                entry.heading, entry.section_id, entry.tokens, entry.subtree_tokens
+            ));
+        }
+    }
+    if let Some(excerpts) = &result.excerpts {
+        for excerpt in excerpts {
+            lines.push(format!("[{}]", excerpt.section_id));
+            lines.push(excerpt.text.trim_end().to_string());
+            if excerpt.truncated {
+                lines.push("…".into());
+            }
+            lines.push(String::new());
+        }
+    }
+    if let Some(content) = &result.content_md {
+        lines.push(content.trim_end().to_string());
+    }
+    if let Some(range) = &result.range {
+        lines.push(String::new());
+        let mut footer = format!("({} tokens", range.tokens);
+        if range.truncated {
+            footer.push_str(", last section cut short");
+        }
+        match &range.next_section_id {
+            Some(next) => footer.push_str(&format!("; next page: --from {next})")),
+            None => footer.push_str("; end of range)"),
+        }
+        lines.push(footer);
+    }
+    lines.join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -139,6 +299,74 @@ mod tests {
         assert_eq!(parsed.section_id.as_deref(), Some("sec-auth"));
     }
 
+    #[test]
+    fn mode_flags_map_onto_read_modes() {
+        let args = |extra: &[&str]| {
+            #[derive(clap::Parser)]
+            struct Cli {
+                #[command(flatten)]
+                read: ReadArgs,
+            }
+            let argv = ["read", "docs/spec.md"].iter().chain(extra).copied();
+            <Cli as clap::Parser>::parse_from(argv).read
+        };
+
+        assert!(read_mode_params(&args(&[])).is_none());
+        assert_eq!(read_mode_params(&args(&["--outline"])).unwrap(), json!({ "mode": "outline" }));
+        assert_eq!(
+            read_mode_params(&args(&["--budget", "500", "--from", "spec/auth"])).unwrap(),
+            json!({ "mode": "section_range", "token_budget": 500, "from_section_id": "spec/auth" })
+        );
+        assert_eq!(
+            read_mode_params(&args(&["--excerpt", "40", "--budget", "200"])).unwrap(),
+            json!({ "mode": "excerpt", "excerpt_tokens": 40, "token_budget": 200 })
+        );
+    }
+
+    #[test]
+    fn human_format_outline_and_range_pages() {
+        let outline = ReadModeResult {
+            document: ReadDocument { path: "docs/spec.md".into() },
+            total_tokens: Some(1200),
+            outline: Some(vec![
+                OutlineEntry {
+                    section_id: "spec".into(),
+                    heading: "# Spec".into(),
+                    level: 1,
+                    tokens: 20,
+                    subtree_tokens: 1190,
+                },
+                OutlineEntry {
+                    section_id: "spec/auth".into(),
+                    heading: "## Auth".into(),
+                    level: 2,
+                    tokens: 1170,
+                    subtree_tokens: 1170,
+                },
+            ]),
+            excerpts: None,
+            content_md: None,
+            range: None,
+        };
+        let output = format_mode_human(&outline);
+        assert!(output.contains("# docs/spec.md (1200 tokens)"));
+        assert!(output.contains("  ## Auth [spec/auth] 1170 tokens"));
+
+        let page = ReadModeResult {
+            document: ReadDocument { path: "docs/spec.md".into() },
+            total_tokens: Some(1200),
+            outline: None,
+            excerpts: None,
+            content_md: Some("# Spec\n\nOverview.\n".into()),
+            range: Some(ReadRange {
+                next_section_id: Some("spec/auth".into()),
+                tokens: 20,
+                truncated: false,
+            }),
+        };
+        assert!(format_mode_human(&page).contains("(20 tokens; next page: --from spec/auth)"));
+    }
+
     #[test]
     fn json_format_full_doc() {
         let result = full_doc_result();
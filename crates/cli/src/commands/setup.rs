@@ -188,7 +188,7 @@ echo "=== Scriptum Agent State ==="
 scriptum whoami 2>/dev/null || echo "(agent identity unavailable)"
 echo ""
 echo "=== Scriptum Workspace Status ==="
-scriptum status 2>/dev/null || echo "(scriptum daemon not running)"
+scriptum status --agent "${SCRIPTUM_AGENT:-}" 2>/dev/null || echo "(scriptum daemon not running)"
 echo ""
 echo "=== Scriptum Overlap Warnings ==="
 scriptum conflicts 2>/dev/null || true
@@ -198,7 +198,7 @@ echo "  scriptum read <doc>           Read document or section"
 echo "  scriptum edit <doc>           Edit document or section"
 echo "  scriptum tree <doc>           Show section tree"
 echo "  scriptum ls                   List workspace documents"
-echo "  scriptum status               Show agent state and overlaps"
+echo "  scriptum status [--agent]     Show agent state, overlaps and notes"
 echo "  scriptum conflicts            Show section overlap warnings"
 echo "  scriptum claim <section>      Claim advisory lease"
 echo "  scriptum blame <doc>          CRDT-based attribution"
@@ -219,7 +219,7 @@ echo "=== Scriptum Agent State ==="
 scriptum whoami 2>/dev/null || echo "(agent identity unavailable)"
 echo ""
 echo "=== Scriptum Workspace Status ==="
-scriptum status 2>/dev/null || echo "(status unavailable)"
+scriptum status --agent "${SCRIPTUM_AGENT:-}" 2>/dev/null || echo "(status unavailable)"
 echo ""
 echo "=== Scriptum Active Overlaps ==="
 scriptum conflicts 2>/dev/null || true
@@ -227,7 +227,7 @@ echo ""
 echo "=== Scriptum CLI Quick Reference ==="
 echo "  scriptum read <doc>           Read document or section"
 echo "  scriptum edit <doc>           Edit document or section"
-echo "  scriptum status               Show agent state and overlaps"
+echo "  scriptum status [--agent]     Show agent state, overlaps and notes"
 echo "  scriptum conflicts            Show section overlap warnings"
 echo "  scriptum claim <section>      Claim advisory lease"
 "#,
@@ -350,7 +350,7 @@ mod tests {
         assert!(script.contains("=== Scriptum Agent State ==="));
         assert!(script.contains("scriptum whoami"));
         assert!(script.contains("=== Scriptum Workspace Status ==="));
-        assert!(script.contains("scriptum status"));
+        assert!(script.contains("scriptum status --agent"), "should include agent notes");
         assert!(script.contains("=== Scriptum Overlap Warnings ==="));
         assert!(script.contains("scriptum conflicts"));
         assert!(script.contains("=== Scriptum CLI Quick Reference ==="));
@@ -364,7 +364,7 @@ mod tests {
         assert!(script.contains("=== Scriptum Agent State ==="));
         assert!(script.contains("scriptum whoami"));
         assert!(script.contains("=== Scriptum Workspace Status ==="));
-        assert!(script.contains("scriptum status"));
+        assert!(script.contains("scriptum status --agent"), "should preserve agent notes");
         assert!(script.contains("=== Scriptum Active Overlaps ==="));
         assert!(script.contains("scriptum conflicts"));
         assert!(script.contains("=== Scriptum CLI Quick Reference ==="));
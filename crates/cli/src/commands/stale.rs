@@ -0,0 +1,180 @@
+// `scriptum stale` — sections whose referenced code changed since their
+// last edit (`doc.staleness`).
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use scriptum_common::protocol::rpc_methods;
+
+use crate::client::DaemonClient;
+use crate::commands::new::{detect_workspace_root_from_cwd, open_workspace};
+use crate::output::{self, OutputFormat};
+
+#[derive(Debug, Args)]
+pub struct StaleArgs {
+    /// Document path. Omit to check every document in the workspace.
+    pub doc: Option<String>,
+
+    /// Force JSON output.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleResult {
+    #[serde(default)]
+    pub items: Vec<StaleSection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleSection {
+    pub doc_id: String,
+    pub path: String,
+    pub section_id: String,
+    pub last_edited_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_edited_by: Option<String>,
+    #[serde(default)]
+    pub references: Vec<StaleReference>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleReference {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(default)]
+    pub commits: Vec<StaleCommit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleCommit {
+    pub commit: String,
+    pub author: String,
+    pub committed_at: String,
+    pub subject: String,
+}
+
+pub fn run(args: StaleArgs) -> anyhow::Result<()> {
+    let format = OutputFormat::detect(args.json);
+    let doc = args.doc;
+    let rt = tokio::runtime::Handle::try_current()
+        .map(|h| h.block_on(call_stale(doc.clone())))
+        .unwrap_or_else(|_| {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("tokio runtime should build")
+                .block_on(call_stale(doc))
+        });
+
+    match rt {
+        Ok(result) => {
+            output::print_output(format, &result, format_human)?;
+            Ok(())
+        }
+        Err(e) => {
+            output::print_anyhow_error(format, &e);
+            Err(e)
+        }
+    }
+}
+
+async fn call_stale(doc: Option<String>) -> anyhow::Result<StaleResult> {
+    let client = DaemonClient::default();
+    let workspace_root = detect_workspace_root_from_cwd()?;
+    let workspace_id = open_workspace(&client, &workspace_root).await?;
+    let mut result: StaleResult =
+        client.call(rpc_methods::DOC_STALENESS, json!({ "workspace_id": workspace_id })).await?;
+    if let Some(doc) = &doc {
+        result.items.retain(|section| section.path == *doc);
+    }
+    Ok(result)
+}
+
+fn format_human(result: &StaleResult) -> String {
+    if result.items.is_empty() {
+        return "No stale sections.".to_string();
+    }
+
+    let mut lines = Vec::new();
+    for section in &result.items {
+        let by =
+            section.last_edited_by.as_deref().map(|by| format!(" by {by}")).unwrap_or_default();
+        lines.push(format!(
+            "{}#{} (last edited {}{by})",
+            section.path, section.section_id, section.last_edited_at
+        ));
+        for reference in &section.references {
+            let target = match reference.line {
+                Some(line) => format!("{}:{line}", reference.path),
+                None => reference.path.clone(),
+            };
+            let count = reference.commits.len();
+            let plural = if count == 1 { "" } else { "s" };
+            lines.push(format!("  {target} changed in {count} commit{plural}"));
+            for commit in &reference.commits {
+                lines.push(format!(
+                    "    {} {} {}",
+                    &commit.commit[..commit.commit.len().min(8)],
+                    commit.author,
+                    commit.subject
+                ));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_result() -> StaleResult {
+        serde_json::from_value(json!({
+            "items": [{
+                "doc_id": "doc-1",
+                "path": "docs/spec.md",
+                "section_id": "spec/auth",
+                "last_edited_at": "2026-01-02T00:00:00Z",
+                "last_edited_by": "cursor-1",
+                "references": [{
+                    "path": "src/auth.rs",
+                    "line": 12,
+                    "commits": [{
+                        "commit": "0123456789abcdef",
+                        "author": "Ada",
+                        "committed_at": "2026-01-03T00:00:00Z",
+                        "subject": "auth: rotate keys",
+                    }],
+                }],
+            }],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn human_format_lists_sections_and_commits() {
+        let output = format_human(&sample_result());
+        assert!(output
+            .contains("docs/spec.md#spec/auth (last edited 2026-01-02T00:00:00Z by cursor-1)"));
+        assert!(output.contains("src/auth.rs:12 changed in 1 commit"));
+        assert!(output.contains("01234567 Ada auth: rotate keys"));
+    }
+
+    #[test]
+    fn human_format_when_nothing_is_stale() {
+        assert_eq!(format_human(&StaleResult { items: vec![] }), "No stale sections.");
+    }
+
+    #[test]
+    fn json_format_roundtrips() {
+        let result = sample_result();
+        let mut buf = Vec::new();
+        output::write_output(&mut buf, OutputFormat::Json, &result, format_human).unwrap();
+        let parsed: StaleResult = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(parsed.items.len(), 1);
+        assert_eq!(parsed.items[0].references[0].line, Some(12));
+    }
+}
@@ -1,4 +1,5 @@
-// `scriptum status` — show agent's active sections and overlaps.
+// `scriptum status` — show agent's active sections and overlaps, plus an
+// agent's scratchpad notes with `--agent`.
 
 use clap::Args;
 use serde::{Deserialize, Serialize};
@@ -7,10 +8,15 @@ use serde_json::json;
 use scriptum_common::protocol::rpc_methods;
 
 use crate::client::DaemonClient;
+use crate::commands::new::{detect_workspace_root_from_cwd, open_workspace};
 use crate::output::{self, OutputFormat};
 
 #[derive(Debug, Args)]
 pub struct StatusArgs {
+    /// Include this agent's notes and journal; bare `--agent` means this agent.
+    #[arg(long, num_args = 0..=1, default_missing_value = "")]
+    agent: Option<String>,
+
     /// Force JSON output.
     #[arg(long)]
     json: bool,
@@ -26,6 +32,42 @@ pub struct AgentStatusResult {
     pub active_sections: Vec<ActiveSection>,
     #[serde(default)]
     pub overlaps: Vec<SectionOverlap>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<AgentNotes>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentNotes {
+    pub agent_id: String,
+    #[serde(default)]
+    pub notes: Vec<AgentNote>,
+    #[serde(default)]
+    pub journal: Vec<JournalEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentNote {
+    pub key: String,
+    pub value: String,
+    #[serde(default)]
+    pub doc_id: Option<String>,
+    #[serde(default)]
+    pub section_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub entry: String,
+    pub ts: String,
+    #[serde(default)]
+    pub doc_id: Option<String>,
+    #[serde(default)]
+    pub section_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct WhoamiResult {
+    agent_id: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -57,14 +99,15 @@ pub struct SectionOverlap {
 
 pub fn run(args: StatusArgs) -> anyhow::Result<()> {
     let format = OutputFormat::detect(args.json);
+    let agent = args.agent;
     let rt = tokio::runtime::Handle::try_current()
-        .map(|h| h.block_on(call_status()))
+        .map(|h| h.block_on(call_status(agent.clone())))
         .unwrap_or_else(|_| {
             tokio::runtime::Builder::new_current_thread()
                 .enable_all()
                 .build()
                 .expect("tokio runtime should build")
-                .block_on(call_status())
+                .block_on(call_status(agent))
         });
 
     match rt {
@@ -79,15 +122,36 @@ pub fn run(args: StatusArgs) -> anyhow::Result<()> {
     }
 }
 
-async fn call_status() -> anyhow::Result<AgentStatusResult> {
+async fn call_status(agent: Option<String>) -> anyhow::Result<AgentStatusResult> {
     let client = DaemonClient::default();
-    let mut status: AgentStatusResult = client.call(rpc_methods::AGENT_STATUS, json!({})).await?;
+    let agent_id = match agent {
+        Some(agent) if agent.is_empty() => Some(
+            client.call::<_, WhoamiResult>(rpc_methods::AGENT_WHOAMI, json!({})).await?.agent_id,
+        ),
+        agent => agent,
+    };
+    let params = match &agent_id {
+        Some(agent_id) => json!({ "agent_id": agent_id }),
+        None => json!({}),
+    };
+    let mut status: AgentStatusResult = client.call(rpc_methods::AGENT_STATUS, params).await?;
 
-    if let Ok(git_status) =
-        client.call::<_, GitStatusResult>(rpc_methods::GIT_STATUS, json!({})).await
-    {
-        status.ai_commits_configured =
-            git_status.ai_configured.or(git_status.ai_commit_enabled).or(git_status.ai_enabled);
+    // Git status is per workspace; outside one there is nothing to report.
+    if let Ok(workspace_root) = detect_workspace_root_from_cwd() {
+        if let Ok(workspace_id) = open_workspace(&client, &workspace_root).await {
+            if let Ok(git_status) = client
+                .call::<_, GitStatusResult>(
+                    rpc_methods::GIT_STATUS,
+                    json!({ "workspace_id": workspace_id }),
+                )
+                .await
+            {
+                status.ai_commits_configured = git_status
+                    .ai_configured
+                    .or(git_status.ai_commit_enabled)
+                    .or(git_status.ai_enabled);
+            }
+        }
     }
 
     Ok(status)
@@ -126,9 +190,35 @@ fn format_human(result: &AgentStatusResult) -> String {
         }
     }
 
+    if let Some(notes) = &result.notes {
+        lines.push(String::new());
+        if notes.notes.is_empty() {
+            lines.push(format!("  No notes for {}.", notes.agent_id));
+        } else {
+            lines.push(format!("  Notes for {} ({}):", notes.agent_id, notes.notes.len()));
+            for note in &notes.notes {
+                lines.push(format!("    {}{}: {}", note.key, scope_label(note), note.value));
+            }
+        }
+        if !notes.journal.is_empty() {
+            lines.push(format!("  Journal (last {}):", notes.journal.len()));
+            for entry in &notes.journal {
+                lines.push(format!("    {} {}", entry.ts, entry.entry));
+            }
+        }
+    }
+
     lines.join("\n")
 }
 
+fn scope_label(note: &AgentNote) -> String {
+    match (&note.doc_id, &note.section_id) {
+        (Some(doc_id), Some(section_id)) => format!(" [{doc_id} > {section_id}]"),
+        (Some(doc_id), None) => format!(" [{doc_id}]"),
+        _ => String::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,6 +241,7 @@ mod tests {
                 other_agent: "copilot".into(),
                 other_intent: "reading".into(),
             }],
+            notes: None,
         }
     }
 
@@ -173,12 +264,37 @@ mod tests {
             ai_commits_configured: Some(false),
             active_sections: vec![],
             overlaps: vec![],
+            notes: None,
         };
         let output = format_human(&result);
         assert!(output.contains("AI commits: not configured"));
         assert!(output.contains("No active sections"));
     }
 
+    #[test]
+    fn human_format_shows_agent_notes_and_journal() {
+        let mut result = sample_result();
+        result.notes = Some(AgentNotes {
+            agent_id: "claude-1".into(),
+            notes: vec![AgentNote {
+                key: "plan".into(),
+                value: "draft auth, then review".into(),
+                doc_id: Some("doc-1".into()),
+                section_id: Some("root/auth".into()),
+            }],
+            journal: vec![JournalEntry {
+                entry: "read spec".into(),
+                ts: "2026-10-18T12:00:00Z".into(),
+                doc_id: None,
+                section_id: None,
+            }],
+        });
+        let output = format_human(&result);
+        assert!(output.contains("Notes for claude-1 (1):"));
+        assert!(output.contains("plan [doc-1 > root/auth]: draft auth, then review"));
+        assert!(output.contains("2026-10-18T12:00:00Z read spec"));
+    }
+
     #[test]
     fn json_format_roundtrips() {
         let result = sample_result();
@@ -0,0 +1,140 @@
+// `scriptum undo` — revert one agent's recent edits, leaving everyone else's.
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use scriptum_common::protocol::rpc_methods;
+
+use crate::client::DaemonClient;
+use crate::output::{self, OutputFormat};
+
+#[derive(Debug, Args)]
+pub struct UndoArgs {
+    /// Agent whose edits are reverted.
+    #[arg(long)]
+    agent: String,
+
+    /// Number of edits to revert, newest first.
+    #[arg(long, default_value_t = 1)]
+    steps: usize,
+
+    /// Only revert edits in this document.
+    #[arg(long)]
+    doc: Option<String>,
+
+    /// Re-apply previously undone edits instead.
+    #[arg(long)]
+    redo: bool,
+
+    /// Force JSON output.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoDocResult {
+    pub doc_path: String,
+    pub steps: usize,
+    pub etag: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoResult {
+    pub agent_id: String,
+    pub steps_applied: usize,
+    #[serde(default)]
+    pub docs: Vec<UndoDocResult>,
+    pub summary: String,
+}
+
+pub fn run(args: UndoArgs) -> anyhow::Result<()> {
+    let format = OutputFormat::detect(args.json);
+    let params =
+        UndoParams { agent: args.agent, steps: args.steps, doc: args.doc, redo: args.redo };
+    let rt = tokio::runtime::Handle::try_current()
+        .map(|h| h.block_on(call_undo(params.clone())))
+        .unwrap_or_else(|_| {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("tokio runtime should build")
+                .block_on(call_undo(params))
+        });
+
+    match rt {
+        Ok(result) => {
+            output::print_output(format, &result, format_human)?;
+            Ok(())
+        }
+        Err(e) => {
+            output::print_anyhow_error(format, &e);
+            Err(e)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UndoParams {
+    agent: String,
+    steps: usize,
+    doc: Option<String>,
+    redo: bool,
+}
+
+async fn call_undo(params: UndoParams) -> anyhow::Result<UndoResult> {
+    let client = DaemonClient::default();
+    let mut rpc_params = json!({
+        "agent_id": params.agent,
+        "steps": params.steps,
+    });
+    if let Some(doc) = &params.doc {
+        rpc_params["doc"] = json!(doc);
+    }
+    let method = if params.redo { rpc_methods::AGENT_REDO } else { rpc_methods::AGENT_UNDO };
+    client.call(method, rpc_params).await
+}
+
+fn format_human(result: &UndoResult) -> String {
+    if result.steps_applied == 0 {
+        return format!("Nothing to undo for {}", result.agent_id);
+    }
+    let mut lines = vec![format!("{} ({} edit(s))", result.summary, result.steps_applied)];
+    for doc in &result.docs {
+        lines.push(format!("  {}: {} edit(s) (etag: {})", doc.doc_path, doc.steps, doc.etag));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn human_format_lists_touched_docs() {
+        let result = UndoResult {
+            agent_id: "claude-1".into(),
+            steps_applied: 3,
+            docs: vec![
+                UndoDocResult { doc_path: "docs/a.md".into(), steps: 2, etag: "doc:a:7".into() },
+                UndoDocResult { doc_path: "docs/b.md".into(), steps: 1, etag: "doc:b:2".into() },
+            ],
+            summary: "agent.undo: reverted edits by claude-1".into(),
+        };
+        let output = format_human(&result);
+        assert!(output.starts_with("agent.undo: reverted edits by claude-1 (3 edit(s))"));
+        assert!(output.contains("docs/a.md: 2 edit(s)"));
+        assert!(output.contains("docs/b.md: 1 edit(s)"));
+    }
+
+    #[test]
+    fn human_format_reports_empty_history() {
+        let result = UndoResult {
+            agent_id: "claude-1".into(),
+            steps_applied: 0,
+            docs: Vec::new(),
+            summary: String::new(),
+        };
+        assert_eq!(format_human(&result), "Nothing to undo for claude-1");
+    }
+}
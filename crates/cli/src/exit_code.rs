@@ -7,6 +7,8 @@
 //   11 = authentication error
 //   12 = conflict/overlap detected
 //   13 = network error
+//   14 = agent policy violation
+//   15 = secret or PII detected in an edit
 
 use std::process;
 
@@ -21,6 +23,8 @@ pub enum ExitCode {
     Auth = 11,
     Conflict = 12,
     Network = 13,
+    PolicyViolation = 14,
+    SecretDetected = 15,
 }
 
 impl ExitCode {
@@ -71,6 +75,9 @@ impl ExitCode {
 
             "VALIDATION_FAILED" | "PRECONDITION_REQUIRED" => Self::Usage,
 
+            "AGENT_POLICY_VIOLATION" => Self::PolicyViolation,
+            "SECRET_DETECTED" => Self::SecretDetected,
+
             _ => Self::Error,
         }
     }
@@ -115,6 +122,8 @@ mod tests {
         assert_eq!(ExitCode::Auth.code(), 11);
         assert_eq!(ExitCode::Conflict.code(), 12);
         assert_eq!(ExitCode::Network.code(), 13);
+        assert_eq!(ExitCode::PolicyViolation.code(), 14);
+        assert_eq!(ExitCode::SecretDetected.code(), 15);
     }
 
     #[test]
@@ -136,6 +145,12 @@ mod tests {
         assert_eq!(ExitCode::from_rpc_code("PRECONDITION_REQUIRED"), ExitCode::Usage);
     }
 
+    #[test]
+    fn from_rpc_code_policy_violation() {
+        assert_eq!(ExitCode::from_rpc_code("AGENT_POLICY_VIOLATION"), ExitCode::PolicyViolation);
+        assert_eq!(ExitCode::from_rpc_code("SECRET_DETECTED"), ExitCode::SecretDetected);
+    }
+
     #[test]
     fn from_rpc_code_unknown_is_general_error() {
         assert_eq!(ExitCode::from_rpc_code("UNKNOWN_CODE"), ExitCode::Error);
@@ -25,5 +25,12 @@ async fn main() -> anyhow::Result<()> {
     if should_boot_daemon {
         daemon_launcher::ensure_daemon_running().await?;
     }
-    commands::run(cli.command)
+    let result = commands::run(cli.command);
+    if let Err(error) = &result {
+        // Typed daemon errors (e.g. agent policy violations) carry their own exit code.
+        if error.chain().any(|cause| cause.is::<exit_code::RpcError>()) {
+            exit_code::ExitCode::from_error(error).exit();
+        }
+    }
+    result
 }
@@ -142,6 +142,29 @@ fn actionable_error(error: &anyhow::Error) -> (&'static str, String) {
     let message = format!("{error:#}");
     let lower = message.to_ascii_lowercase();
 
+    if let Some(rpc_error) =
+        error.chain().filter_map(|cause| cause.downcast_ref::<crate::exit_code::RpcError>()).next()
+    {
+        match rpc_error.code.as_str() {
+            "AGENT_POLICY_VIOLATION" => {
+                return (
+                    "AGENT_POLICY_VIOLATION",
+                    format!("Blocked by agent policy: {}", rpc_error.message),
+                );
+            }
+            "SECRET_DETECTED" => {
+                return (
+                    "SECRET_DETECTED",
+                    format!(
+                        "Blocked by secret guard: {}. Remove the value or see `doc.findings`.",
+                        rpc_error.message
+                    ),
+                );
+            }
+            _ => {}
+        }
+    }
+
     if daemon_unavailable_exit_code(error).is_some()
         || (lower.contains("daemon")
             && lower.contains("socket")
@@ -401,6 +424,17 @@ mod tests {
         assert!(message.contains("scriptum setup"));
     }
 
+    #[test]
+    fn actionable_error_agent_policy_violation_message() {
+        let err = anyhow::Error::new(crate::exit_code::RpcError {
+            code: "AGENT_POLICY_VIOLATION".into(),
+            message: "agent `claude-1` may not edit `ops/keys.md`".into(),
+        });
+        let (code, message) = actionable_error(&err);
+        assert_eq!(code, "AGENT_POLICY_VIOLATION");
+        assert!(message.contains("may not edit `ops/keys.md`"));
+    }
+
     #[test]
     fn actionable_error_timeout_message() {
         let err = anyhow::anyhow!("timed out waiting for json-rpc response");
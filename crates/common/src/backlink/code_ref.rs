@@ -0,0 +1,280 @@
+// Code references: markdown that points at source files in the same repo.
+//
+// Supported forms:
+// - [text](src/foo.rs) and [text](src/foo.rs#L42) (relative to the doc)
+// - src/foo.rs:42, bare or in a code span (relative to the repo root)
+// - [[src/foo.rs]], [[src/foo.rs:42]], [[src/foo.rs#L42]] (repo root)
+//
+// Fenced and indented code blocks are skipped, as are links to other
+// markdown documents (those are backlinks).
+
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+use super::parse_wiki_links;
+
+/// Which syntax a code reference was written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeRefKind {
+    /// `[text](path)`: resolved relative to the referencing document.
+    Link,
+    /// Inline `path:line`: resolved relative to the repo root.
+    PathLine,
+    /// `[[path]]`: resolved relative to the repo root.
+    WikiLink,
+}
+
+/// A reference from markdown to a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeRef {
+    /// Path as written, without any line suffix or `#L` fragment.
+    pub path: String,
+    /// 1-based line number, when the reference names one.
+    pub line: Option<u32>,
+    pub kind: CodeRefKind,
+    /// Byte offset where the reference starts.
+    pub start_offset: usize,
+    /// Byte offset just after the reference.
+    pub end_offset: usize,
+}
+
+/// Parse code references from markdown, in document order.
+pub fn parse_code_refs(markdown: &str) -> Vec<CodeRef> {
+    let mut refs = Vec::new();
+    let mut covered = Vec::new();
+
+    for link in parse_wiki_links(markdown) {
+        covered.push((link.start_offset, link.end_offset));
+        let line = link.heading.as_deref().and_then(parse_line_fragment);
+        let (path, line) = match parse_path_line(&link.target) {
+            Some((path, line)) => (path, Some(line)),
+            None => (link.target.as_str(), line),
+        };
+        if is_code_path(path) {
+            refs.push(CodeRef {
+                path: path.to_string(),
+                line,
+                kind: CodeRefKind::WikiLink,
+                start_offset: link.start_offset,
+                end_offset: link.end_offset,
+            });
+        }
+    }
+
+    let mut in_code_block = false;
+    for (event, range) in Parser::new(markdown).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                if overlaps(&covered, range.start) {
+                    continue;
+                }
+                covered.push((range.start, range.end));
+                if let Some((path, line)) = parse_link_destination(&dest_url) {
+                    refs.push(CodeRef {
+                        path: path.to_string(),
+                        line,
+                        kind: CodeRefKind::Link,
+                        start_offset: range.start,
+                        end_offset: range.end,
+                    });
+                }
+            }
+            Event::Text(text) | Event::Code(text) if !in_code_block => {
+                // Text maps onto the source verbatim; code spans are offset
+                // by their backticks.
+                let base = markdown[range.clone()].find(text.as_ref()).unwrap_or(0) + range.start;
+                refs.extend(
+                    scan_path_lines(&text, base)
+                        .into_iter()
+                        .filter(|code_ref| !overlaps(&covered, code_ref.start_offset)),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    refs.sort_by_key(|code_ref| code_ref.start_offset);
+    refs
+}
+
+/// Whether `path` names a source file: it has a file extension that is not
+/// markdown and is not a URL.
+pub fn is_code_path(path: &str) -> bool {
+    if path.is_empty()
+        || path.contains("://")
+        || path.starts_with("mailto:")
+        || path.contains(char::is_whitespace)
+    {
+        return false;
+    }
+    let file = path.rsplit('/').next().unwrap_or(path);
+    let Some((stem, extension)) = file.rsplit_once('.') else {
+        return false;
+    };
+    !stem.is_empty()
+        && !extension.is_empty()
+        && extension.chars().all(|c| c.is_ascii_alphanumeric())
+        && !extension.eq_ignore_ascii_case("md")
+        && !extension.eq_ignore_ascii_case("markdown")
+}
+
+/// Resolve `code_ref` to a repo-relative path for a reference in the
+/// document at `doc_path`. None when it escapes the repo root.
+pub fn resolve_code_ref_path(doc_path: &str, code_ref: &CodeRef) -> Option<String> {
+    let mut components: Vec<&str> = Vec::new();
+    if code_ref.kind == CodeRefKind::Link && !code_ref.path.starts_with('/') {
+        components.extend(doc_path.split('/').filter(|c| !c.is_empty()));
+        components.pop();
+    }
+    for component in code_ref.path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop()?;
+            }
+            other => components.push(other),
+        }
+    }
+    (!components.is_empty()).then(|| components.join("/"))
+}
+
+fn overlaps(ranges: &[(usize, usize)], offset: usize) -> bool {
+    ranges.iter().any(|(start, end)| offset >= *start && offset < *end)
+}
+
+/// `path#L42` or `path#L42-L50` link destinations to code files.
+fn parse_link_destination(dest: &str) -> Option<(&str, Option<u32>)> {
+    let (dest, fragment) = match dest.split_once('#') {
+        Some((dest, fragment)) => (dest, Some(fragment)),
+        None => (dest, None),
+    };
+    let path = dest.split_once('?').map_or(dest, |(path, _)| path);
+    is_code_path(path).then(|| (path, fragment.and_then(parse_line_fragment)))
+}
+
+/// `L42` or `L42-L50` → 42.
+fn parse_line_fragment(fragment: &str) -> Option<u32> {
+    let digits = fragment.strip_prefix('L')?;
+    let end = digits.find(|c: char| !c.is_ascii_digit()).unwrap_or(digits.len());
+    digits[..end].parse().ok().filter(|line| *line > 0)
+}
+
+/// `path:42`, also `path:42:7` and `path:42-50`.
+fn parse_path_line(token: &str) -> Option<(&str, u32)> {
+    let (path, rest) = token.split_once(':')?;
+    let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    let line = rest[..end].parse().ok().filter(|line| *line > 0)?;
+    is_code_path(path).then_some((path, line))
+}
+
+fn scan_path_lines(text: &str, base_offset: usize) -> Vec<CodeRef> {
+    let is_delimiter = |c: char| c.is_whitespace() || "()[]<>{},;'\"`".contains(c);
+    let mut refs = Vec::new();
+    let mut start = 0;
+    for (index, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        if !is_delimiter(c) {
+            continue;
+        }
+        let token = text[start..index].trim_end_matches(['.', ':']);
+        if let Some((path, line)) = parse_path_line(token) {
+            refs.push(CodeRef {
+                path: path.to_string(),
+                line: Some(line),
+                kind: CodeRefKind::PathLine,
+                start_offset: base_offset + start,
+                end_offset: base_offset + start + token.len(),
+            });
+        }
+        start = index + c.len_utf8();
+    }
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_code_path, parse_code_refs, resolve_code_ref_path, CodeRef, CodeRefKind};
+
+    fn paths(refs: &[CodeRef]) -> Vec<(&str, Option<u32>, CodeRefKind)> {
+        refs.iter().map(|r| (r.path.as_str(), r.line, r.kind)).collect()
+    }
+
+    #[test]
+    fn parses_markdown_links_to_code() {
+        let refs = parse_code_refs("See [the parser](src/parser.rs#L10-L20) and [docs](guide.md).");
+
+        assert_eq!(paths(&refs), vec![("src/parser.rs", Some(10), CodeRefKind::Link)]);
+    }
+
+    #[test]
+    fn parses_inline_path_line_references() {
+        let markdown = "Handled in src/auth.rs:42, see also `crates/cli/src/main.rs:7:3`.";
+        let refs = parse_code_refs(markdown);
+
+        assert_eq!(
+            paths(&refs),
+            vec![
+                ("src/auth.rs", Some(42), CodeRefKind::PathLine),
+                ("crates/cli/src/main.rs", Some(7), CodeRefKind::PathLine),
+            ]
+        );
+        assert_eq!(&markdown[refs[0].start_offset..refs[0].end_offset], "src/auth.rs:42");
+        assert_eq!(
+            &markdown[refs[1].start_offset..refs[1].end_offset],
+            "crates/cli/src/main.rs:7:3"
+        );
+    }
+
+    #[test]
+    fn parses_wiki_links_to_code_but_not_docs() {
+        let refs = parse_code_refs("[[src/foo.rs]] [[src/bar.rs:9]] [[src/baz.rs#L4]] [[Auth]]");
+
+        assert_eq!(
+            paths(&refs),
+            vec![
+                ("src/foo.rs", None, CodeRefKind::WikiLink),
+                ("src/bar.rs", Some(9), CodeRefKind::WikiLink),
+                ("src/baz.rs", Some(4), CodeRefKind::WikiLink),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_urls_code_blocks_and_times() {
+        let markdown = "Meet at 10:30 on https://example.com/a.rs:3\n\n```\nsrc/skip.rs:1\n```\n";
+        assert!(parse_code_refs(markdown).is_empty());
+    }
+
+    #[test]
+    fn recognizes_code_paths() {
+        assert!(is_code_path("src/lib.rs"));
+        assert!(is_code_path("Cargo.toml"));
+        assert!(!is_code_path("docs/readme.md"));
+        assert!(!is_code_path("Makefile"));
+        assert!(!is_code_path(".gitignore"));
+        assert!(!is_code_path("https://example.com/a.rs"));
+    }
+
+    #[test]
+    fn resolves_links_relative_to_the_document() {
+        let refs = parse_code_refs("[a](../src/a.rs) [b](/src/b.rs) src/c.rs:1 [[./src/d.rs]]");
+        let resolved: Vec<_> =
+            refs.iter().map(|r| resolve_code_ref_path("docs/design.md", r)).collect();
+
+        assert_eq!(
+            resolved,
+            vec![
+                Some("src/a.rs".to_string()),
+                Some("src/b.rs".to_string()),
+                Some("src/c.rs".to_string()),
+                Some("src/d.rs".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_paths_escaping_the_repo() {
+        let refs = parse_code_refs("[a](../../outside.rs)");
+        assert_eq!(resolve_code_ref_path("docs/design.md", &refs[0]), None);
+    }
+}
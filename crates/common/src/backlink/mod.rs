@@ -6,6 +6,10 @@
 // - [[target#heading]]
 // - [[target#heading|alias]] (Obsidian-compatible superset)
 
+pub mod code_ref;
+
+pub use code_ref::{is_code_path, parse_code_refs, resolve_code_ref_path, CodeRef, CodeRefKind};
+
 /// A parsed wiki-style link from markdown content.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WikiLink {
@@ -0,0 +1,226 @@
+use super::patch::{myers_line_edits, LineEdit};
+
+/// Result of a line-level three-way merge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeResult {
+    /// Merged text. Conflicting regions keep the local (`ours`) version.
+    pub text: String,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// A region changed differently on both sides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// 1-based line in `MergeResult::text` where the local version starts.
+    pub start_line: usize,
+    pub base: String,
+    pub ours: String,
+    pub theirs: String,
+}
+
+/// A run of base lines `[base_start, base_end)` replaced by `lines`.
+#[derive(Debug)]
+struct Hunk<'a> {
+    base_start: usize,
+    base_end: usize,
+    lines: Vec<&'a str>,
+}
+
+/// Merges `ours` and `theirs`, two edits of `base`, line by line (diff3).
+///
+/// Changes made on one side only are applied. Changes that overlap or touch
+/// on both sides conflict, as in `git merge`, unless both sides made the
+/// same change.
+pub fn three_way_merge(base: &str, ours: &str, theirs: &str) -> MergeResult {
+    if ours == theirs || base == theirs {
+        return MergeResult { text: ours.to_string(), conflicts: Vec::new() };
+    }
+    if base == ours {
+        return MergeResult { text: theirs.to_string(), conflicts: Vec::new() };
+    }
+
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let ours_hunks = hunks(&base_lines, &ours.split_inclusive('\n').collect::<Vec<_>>());
+    let theirs_hunks = hunks(&base_lines, &theirs.split_inclusive('\n').collect::<Vec<_>>());
+
+    let mut text = String::with_capacity(ours.len().max(theirs.len()));
+    let mut conflicts = Vec::new();
+    let mut base_pos = 0;
+    let (mut next_ours, mut next_theirs) = (0, 0);
+
+    loop {
+        let start = match (ours_hunks.get(next_ours), theirs_hunks.get(next_theirs)) {
+            (Some(a), Some(b)) => a.base_start.min(b.base_start),
+            (Some(a), None) => a.base_start,
+            (None, Some(b)) => b.base_start,
+            (None, None) => break,
+        };
+
+        // Grow the region until no hunk from either side touches it.
+        let (first_ours, first_theirs) = (next_ours, next_theirs);
+        let mut end = start;
+        loop {
+            if let Some(hunk) = ours_hunks.get(next_ours).filter(|hunk| hunk.base_start <= end) {
+                end = end.max(hunk.base_end);
+                next_ours += 1;
+            } else if let Some(hunk) =
+                theirs_hunks.get(next_theirs).filter(|hunk| hunk.base_start <= end)
+            {
+                end = end.max(hunk.base_end);
+                next_theirs += 1;
+            } else {
+                break;
+            }
+        }
+
+        text.extend(base_lines[base_pos..start].iter().copied());
+        base_pos = end;
+
+        let ours_region = &ours_hunks[first_ours..next_ours];
+        let theirs_region = &theirs_hunks[first_theirs..next_theirs];
+        let ours_text = render_region(&base_lines, start, end, ours_region);
+        if theirs_region.is_empty() {
+            text.push_str(&ours_text);
+            continue;
+        }
+        let theirs_text = render_region(&base_lines, start, end, theirs_region);
+        if ours_region.is_empty() || ours_text == theirs_text {
+            text.push_str(&theirs_text);
+            continue;
+        }
+
+        conflicts.push(MergeConflict {
+            start_line: text.matches('\n').count() + 1,
+            base: base_lines[start..end].concat(),
+            theirs: theirs_text,
+            ours: ours_text.clone(),
+        });
+        text.push_str(&ours_text);
+    }
+
+    text.extend(base_lines[base_pos..].iter().copied());
+    MergeResult { text, conflicts }
+}
+
+fn hunks<'a>(base_lines: &[&'a str], other_lines: &[&'a str]) -> Vec<Hunk<'a>> {
+    let mut hunks: Vec<Hunk<'a>> = Vec::new();
+    let mut base_pos = 0;
+    let mut open = false;
+
+    for edit in myers_line_edits(base_lines, other_lines) {
+        match edit {
+            LineEdit::Equal(_) => {
+                base_pos += 1;
+                open = false;
+            }
+            LineEdit::Delete(_) | LineEdit::Insert(_) => {
+                if !open {
+                    hunks.push(Hunk {
+                        base_start: base_pos,
+                        base_end: base_pos,
+                        lines: Vec::new(),
+                    });
+                    open = true;
+                }
+                let hunk = hunks.last_mut().expect("hunk was just opened");
+                match edit {
+                    LineEdit::Delete(_) => {
+                        base_pos += 1;
+                        hunk.base_end = base_pos;
+                    }
+                    LineEdit::Insert(line) => hunk.lines.push(line),
+                    LineEdit::Equal(_) => unreachable!("equal lines close the hunk"),
+                }
+            }
+        }
+    }
+
+    hunks
+}
+
+/// One side's text for base lines `[start, end)`, given its hunks there.
+fn render_region(base_lines: &[&str], start: usize, end: usize, hunks: &[Hunk<'_>]) -> String {
+    let mut rendered = String::new();
+    let mut pos = start;
+    for hunk in hunks {
+        rendered.extend(base_lines[pos..hunk.base_start].iter().copied());
+        rendered.extend(hunk.lines.iter().copied());
+        pos = hunk.base_end;
+    }
+    rendered.extend(base_lines[pos..end].iter().copied());
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "# Title\n\nintro\n\n## A\n\nalpha\n\n## B\n\nbeta\n";
+
+    #[test]
+    fn one_sided_changes_are_taken() {
+        let ours = BASE.replace("alpha", "alpha (local)");
+        let theirs = BASE.replace("beta", "beta (remote)");
+
+        let merged = three_way_merge(BASE, &ours, &theirs);
+        assert!(merged.conflicts.is_empty());
+        assert_eq!(
+            merged.text,
+            BASE.replace("alpha", "alpha (local)").replace("beta", "beta (remote)")
+        );
+
+        assert_eq!(three_way_merge(BASE, BASE, &theirs).text, theirs);
+        assert_eq!(three_way_merge(BASE, &ours, BASE).text, ours);
+    }
+
+    #[test]
+    fn remote_insertions_and_deletions_merge_around_local_edits() {
+        let ours = BASE.replace("intro", "introduction");
+        let theirs = format!("{}\n## C\n\ngamma\n", BASE.replace("## A\n\nalpha\n\n", ""));
+
+        let merged = three_way_merge(BASE, &ours, &theirs);
+        assert!(merged.conflicts.is_empty());
+        assert_eq!(merged.text, "# Title\n\nintroduction\n\n## B\n\nbeta\n\n## C\n\ngamma\n");
+    }
+
+    #[test]
+    fn identical_changes_on_both_sides_do_not_conflict() {
+        let both = BASE.replace("alpha", "ALPHA").replace("beta", "BETA");
+        let theirs = BASE.replace("alpha", "ALPHA");
+
+        let merged = three_way_merge(BASE, &both, &theirs);
+        assert!(merged.conflicts.is_empty());
+        assert_eq!(merged.text, both);
+    }
+
+    #[test]
+    fn overlapping_changes_conflict_and_keep_the_local_version() {
+        let ours = BASE.replace("alpha", "alpha local").replace("intro", "intro local");
+        let theirs = BASE.replace("alpha", "alpha remote");
+
+        let merged = three_way_merge(BASE, &ours, &theirs);
+        assert_eq!(merged.text, ours);
+        assert_eq!(
+            merged.conflicts,
+            vec![MergeConflict {
+                start_line: 7,
+                base: "alpha\n".to_string(),
+                ours: "alpha local\n".to_string(),
+                theirs: "alpha remote\n".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn insertions_at_the_same_place_conflict() {
+        let merged = three_way_merge("a\nb\n", "a\nlocal\nb\n", "a\nremote\nb\n");
+        assert_eq!(merged.text, "a\nlocal\nb\n");
+        assert_eq!(merged.conflicts.len(), 1);
+        assert_eq!(merged.conflicts[0].base, "");
+        assert_eq!(merged.conflicts[0].theirs, "remote\n");
+
+        let created = three_way_merge("", "local\n", "remote\n");
+        assert_eq!(created.text, "local\n");
+        assert_eq!(created.conflicts[0].start_line, 1);
+    }
+}
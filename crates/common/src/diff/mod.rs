@@ -1 +1,3 @@
+pub mod merge;
 pub mod patch;
+pub mod unified;
@@ -175,13 +175,16 @@ fn line_then_char_diff(old_text: &str, new_text: &str) -> Vec<TextPatchOp> {
 }
 
 #[derive(Debug, Clone)]
-enum LineEdit<'a> {
+pub(super) enum LineEdit<'a> {
     Equal(&'a str),
     Insert(&'a str),
     Delete(&'a str),
 }
 
-fn myers_line_edits<'a>(old_lines: &[&'a str], new_lines: &[&'a str]) -> Vec<LineEdit<'a>> {
+pub(super) fn myers_line_edits<'a>(
+    old_lines: &[&'a str],
+    new_lines: &[&'a str],
+) -> Vec<LineEdit<'a>> {
     let old_len = old_lines.len();
     let new_len = new_lines.len();
 
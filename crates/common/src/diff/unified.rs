@@ -0,0 +1,121 @@
+use super::patch::{myers_line_edits, LineEdit};
+
+/// Unchanged lines shown around each hunk, as in `diff -u`.
+const CONTEXT_LINES: usize = 3;
+
+/// Renders a line-level unified diff from `old_text` to `new_text`.
+///
+/// Returns an empty string when the texts are identical. Labels are used
+/// verbatim for the `---`/`+++` headers (e.g. `a/docs/spec.md`).
+pub fn unified_diff(old_text: &str, new_text: &str, old_label: &str, new_label: &str) -> String {
+    if old_text == new_text {
+        return String::new();
+    }
+
+    let old_lines: Vec<&str> = old_text.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new_text.split_inclusive('\n').collect();
+    let edits = myers_line_edits(&old_lines, &new_lines);
+
+    // Line positions (0-based) in the old and new text before each edit.
+    let mut positions = Vec::with_capacity(edits.len() + 1);
+    let (mut old_pos, mut new_pos) = (0usize, 0usize);
+    for edit in &edits {
+        positions.push((old_pos, new_pos));
+        match edit {
+            LineEdit::Equal(_) => {
+                old_pos += 1;
+                new_pos += 1;
+            }
+            LineEdit::Delete(_) => old_pos += 1,
+            LineEdit::Insert(_) => new_pos += 1,
+        }
+    }
+    positions.push((old_pos, new_pos));
+
+    let changes: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, edit)| !matches!(edit, LineEdit::Equal(_)))
+        .map(|(index, _)| index)
+        .collect();
+
+    let mut out = format!("--- {old_label}\n+++ {new_label}\n");
+    let mut next = 0;
+    while next < changes.len() {
+        let first = changes[next];
+        let mut last = first;
+        next += 1;
+        while next < changes.len() && changes[next] - last <= 2 * CONTEXT_LINES + 1 {
+            last = changes[next];
+            next += 1;
+        }
+
+        let start = first.saturating_sub(CONTEXT_LINES);
+        let end = (last + 1 + CONTEXT_LINES).min(edits.len());
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_end - old_start),
+            hunk_range(new_start, new_end - new_start)
+        ));
+
+        for edit in &edits[start..end] {
+            let (marker, line) = match edit {
+                LineEdit::Equal(line) => (' ', *line),
+                LineEdit::Delete(line) => ('-', *line),
+                LineEdit::Insert(line) => ('+', *line),
+            };
+            out.push(marker);
+            out.push_str(line);
+            if !line.ends_with('\n') {
+                out.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+
+    out
+}
+
+/// `start,count` with 1-based `start`; empty ranges point at the line before.
+fn hunk_range(start: usize, count: usize) -> String {
+    match count {
+        0 => format!("{start},0"),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{count}", start + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_texts_produce_no_diff() {
+        assert_eq!(unified_diff("same\n", "same\n", "a/doc.md", "b/doc.md"), "");
+    }
+
+    #[test]
+    fn changed_line_is_shown_with_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n";
+        assert_eq!(
+            unified_diff(old, new, "a/doc.md", "b/doc.md"),
+            "--- a/doc.md\n+++ b/doc.md\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n"
+        );
+    }
+
+    #[test]
+    fn distant_changes_split_into_hunks_and_missing_newline_is_marked() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let new = "A\nb\nc\nd\ne\nf\ng\nh\ni\nj";
+        let diff = unified_diff(old, new, "a/doc.md", "b/doc.md");
+        assert!(diff.contains("@@ -1,4 +1,4 @@\n-a\n+A\n b\n c\n d\n"));
+        assert!(
+            diff.contains("@@ -7,4 +7,4 @@\n g\n h\n i\n-j\n+j\n\\ No newline at end of file\n")
+        );
+
+        let created = unified_diff("", "# New\n", "/dev/null", "b/new.md");
+        assert!(created.ends_with("@@ -0,0 +1 @@\n+# New\n"));
+    }
+}
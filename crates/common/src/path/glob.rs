@@ -0,0 +1,63 @@
+// Minimal glob matching for workspace-relative paths and names.
+//
+//   `*`   any run of characters except `/`
+//   `**`  any run of characters, including `/` (`**/` also matches nothing)
+//   `?`   one character except `/`
+//
+// Everything else matches literally. Patterns are anchored at both ends.
+
+/// Returns true when `pattern` matches the whole of `text`.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    match_from(&pattern, &text)
+}
+
+fn match_from(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            let mut rest = &pattern[2..];
+            // `**/` may also match zero directories.
+            if rest.first() == Some(&'/') && match_from(&rest[1..], text) {
+                return true;
+            }
+            while rest.first() == Some(&'*') {
+                rest = &rest[1..];
+            }
+            (0..=text.len()).any(|skip| match_from(rest, &text[skip..]))
+        }
+        Some('*') => {
+            let rest = &pattern[1..];
+            let limit = text.iter().position(|ch| *ch == '/').unwrap_or(text.len());
+            (0..=limit).any(|skip| match_from(rest, &text[skip..]))
+        }
+        Some('?') => {
+            matches!(text.first(), Some(ch) if *ch != '/') && match_from(&pattern[1..], &text[1..])
+        }
+        Some(literal) => text.first() == Some(literal) && match_from(&pattern[1..], &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_star_stays_within_one_component() {
+        assert!(glob_match("docs/*.md", "docs/spec.md"));
+        assert!(!glob_match("docs/*.md", "docs/api/spec.md"));
+        assert!(glob_match("claude-*", "claude-1"));
+        assert!(glob_match("agent-?", "agent-7"));
+        assert!(!glob_match("agent-?", "agent-17"));
+    }
+
+    #[test]
+    fn double_star_crosses_directories() {
+        assert!(glob_match("docs/**", "docs/api/v1/spec.md"));
+        assert!(glob_match("docs/**/*.md", "docs/spec.md"));
+        assert!(glob_match("docs/**/*.md", "docs/api/spec.md"));
+        assert!(glob_match("**", "anything/at/all.md"));
+        assert!(!glob_match("docs/**/*.md", "notes/spec.md"));
+    }
+}
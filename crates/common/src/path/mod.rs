@@ -1,4 +1,6 @@
 // Path canonicalization and safety utilities.
 
+pub mod glob;
 pub mod normalize;
+pub use glob::glob_match;
 pub use normalize::normalize_path;
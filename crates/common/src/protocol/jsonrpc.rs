@@ -61,6 +61,10 @@ pub const METHOD_NOT_FOUND: i32 = -32601;
 pub const INVALID_PARAMS: i32 = -32602;
 pub const INTERNAL_ERROR: i32 = -32603;
 
+// Daemon-specific error codes (implementation-defined server error range).
+pub const AGENT_POLICY_VIOLATION: i32 = -32040;
+pub const SECRET_DETECTED: i32 = -32041;
+
 impl Request {
     pub fn new(method: impl Into<String>, params: Option<Value>, id: RequestId) -> Self {
         Self {
@@ -9,10 +9,14 @@ pub const DOC_READ: &str = "doc.read";
 pub const DOC_CREATE: &str = "doc.create";
 pub const DOC_EDIT: &str = "doc.edit";
 pub const DOC_EDIT_SECTION: &str = "doc.edit_section";
+pub const DOC_REPLACE: &str = "doc.replace";
+pub const DOC_BATCH: &str = "doc.batch";
 pub const DOC_BUNDLE: &str = "doc.bundle";
 pub const DOC_SECTIONS: &str = "doc.sections";
 pub const DOC_DIFF: &str = "doc.diff";
 pub const DOC_HISTORY: &str = "doc.history";
+pub const DOC_FINDINGS: &str = "doc.findings";
+pub const DOC_STALENESS: &str = "doc.staleness";
 pub const DOC_SEARCH: &str = "doc.search";
 pub const DOC_TREE: &str = "doc.tree";
 
@@ -22,16 +26,30 @@ pub const AGENT_STATUS: &str = "agent.status";
 pub const AGENT_CONFLICTS: &str = "agent.conflicts";
 pub const AGENT_LIST: &str = "agent.list";
 pub const AGENT_CLAIM: &str = "agent.claim";
+pub const AGENT_PLAN: &str = "agent.plan";
+pub const AGENT_UNDO: &str = "agent.undo";
+pub const AGENT_REDO: &str = "agent.redo";
+pub const AGENT_NOTES_SET: &str = "agent.notes.set";
+pub const AGENT_NOTES_GET: &str = "agent.notes.get";
+pub const AGENT_NOTES_LIST: &str = "agent.notes.list";
+pub const AGENT_NOTES_APPEND: &str = "agent.notes.append";
 
 // ── Workspace ──────────────────────────────────────────────────────
 pub const WORKSPACE_LIST: &str = "workspace.list";
 pub const WORKSPACE_OPEN: &str = "workspace.open";
 pub const WORKSPACE_CREATE: &str = "workspace.create";
+pub const WORKSPACE_CONTEXT_PACK: &str = "workspace.context_pack";
 
 // ── Git ────────────────────────────────────────────────────────────
 pub const GIT_STATUS: &str = "git.status";
 pub const GIT_SYNC: &str = "git.sync";
 pub const GIT_CONFIGURE: &str = "git.configure";
+pub const GIT_JOB_STATUS: &str = "git.job_status";
+pub const GIT_JOBS: &str = "git.jobs";
+pub const GIT_RECONCILIATIONS: &str = "git.reconciliations";
+pub const GIT_RESOLVE_RECONCILIATION: &str = "git.resolve_reconciliation";
+pub const GIT_SET_DOC_SYNC: &str = "git.set_doc_sync";
+pub const GIT_BLAME: &str = "git.blame";
 
 /// All methods the daemon currently dispatches.
 pub const IMPLEMENTED_METHODS: &[&str] = &[
@@ -41,10 +59,14 @@ pub const IMPLEMENTED_METHODS: &[&str] = &[
     DOC_CREATE,
     DOC_EDIT,
     DOC_EDIT_SECTION,
+    DOC_REPLACE,
+    DOC_BATCH,
     DOC_BUNDLE,
     DOC_SECTIONS,
     DOC_DIFF,
     DOC_HISTORY,
+    DOC_FINDINGS,
+    DOC_STALENESS,
     DOC_SEARCH,
     DOC_TREE,
     AGENT_WHOAMI,
@@ -52,12 +74,26 @@ pub const IMPLEMENTED_METHODS: &[&str] = &[
     AGENT_CONFLICTS,
     AGENT_LIST,
     AGENT_CLAIM,
+    AGENT_PLAN,
+    AGENT_UNDO,
+    AGENT_REDO,
+    AGENT_NOTES_SET,
+    AGENT_NOTES_GET,
+    AGENT_NOTES_LIST,
+    AGENT_NOTES_APPEND,
     WORKSPACE_LIST,
     WORKSPACE_OPEN,
     WORKSPACE_CREATE,
+    WORKSPACE_CONTEXT_PACK,
     GIT_STATUS,
     GIT_SYNC,
     GIT_CONFIGURE,
+    GIT_JOB_STATUS,
+    GIT_JOBS,
+    GIT_RECONCILIATIONS,
+    GIT_RESOLVE_RECONCILIATION,
+    GIT_SET_DOC_SYNC,
+    GIT_BLAME,
 ];
 
 /// Methods acknowledged in the contract as planned but not yet implemented.
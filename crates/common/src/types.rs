@@ -92,3 +92,46 @@ pub struct AgentSession {
     pub last_seen_at: DateTime<Utc>,
     pub active_sections: u32,
 }
+
+/// Lifecycle state of a queued git sync job. Also the `state` labels of the
+/// relay's `git_sync_jobs_total` metric; daemons run their jobs locally and
+/// do not report transitions to the relay, so daemon jobs are not counted there.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum GitJobState {
+    Queued,
+    Running,
+    /// A push failed and is waiting out its backoff.
+    Retrying,
+    Succeeded,
+    Failed,
+}
+
+impl GitJobState {
+    pub const ALL: [GitJobState; 5] = [
+        GitJobState::Queued,
+        GitJobState::Running,
+        GitJobState::Retrying,
+        GitJobState::Succeeded,
+        GitJobState::Failed,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GitJobState::Queued => "queued",
+            GitJobState::Running => "running",
+            GitJobState::Retrying => "retrying",
+            GitJobState::Succeeded => "succeeded",
+            GitJobState::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|state| state.as_str() == value)
+    }
+
+    /// Succeeded and failed jobs never run again.
+    pub fn is_terminal(self) -> bool {
+        matches!(self, GitJobState::Succeeded | GitJobState::Failed)
+    }
+}
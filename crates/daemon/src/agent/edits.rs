@@ -2,6 +2,7 @@
 //
 // Records character-level edit spans per agent per document.
 // Used for attribution, activity feeds, and reconciliation UI.
+// Rejected edits (agent policy violations) are logged alongside.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -17,6 +18,8 @@ pub struct NewEdit {
     pub start_offset_utf16: i64,
     pub end_offset_utf16: i64,
     pub ts: DateTime<Utc>,
+    /// Shared description when several edits belong to one logical change.
+    pub summary: Option<String>,
 }
 
 /// A persisted recent edit record.
@@ -28,6 +31,18 @@ pub struct AgentRecentEdit {
     pub start_offset_utf16: i64,
     pub end_offset_utf16: i64,
     pub ts: DateTime<Utc>,
+    pub summary: Option<String>,
+}
+
+/// An edit rejected by the workspace agent policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyViolationRecord {
+    pub workspace_id: String,
+    pub doc_id: Option<String>,
+    pub agent_id: String,
+    pub rule: String,
+    pub reason: String,
+    pub ts: DateTime<Utc>,
 }
 
 // ── Store ────────────────────────────────────────────────────────────
@@ -40,14 +55,15 @@ impl EditStore {
     pub fn record(conn: &Connection, edit: &NewEdit) -> Result<i64> {
         conn.execute(
             "INSERT INTO agent_recent_edits \
-             (doc_id, agent_id, start_offset_utf16, end_offset_utf16, ts) \
-             VALUES (?1, ?2, ?3, ?4, ?5)",
+             (doc_id, agent_id, start_offset_utf16, end_offset_utf16, ts, summary) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
             params![
                 edit.doc_id,
                 edit.agent_id,
                 edit.start_offset_utf16,
                 edit.end_offset_utf16,
                 edit.ts.to_rfc3339(),
+                edit.summary,
             ],
         )
         .context("failed to insert agent recent edit")?;
@@ -62,7 +78,7 @@ impl EditStore {
     ) -> Result<Vec<AgentRecentEdit>> {
         let mut stmt = conn
             .prepare(
-                "SELECT id, doc_id, agent_id, start_offset_utf16, end_offset_utf16, ts \
+                "SELECT id, doc_id, agent_id, start_offset_utf16, end_offset_utf16, ts, summary \
                  FROM agent_recent_edits WHERE doc_id = ?1 \
                  ORDER BY ts DESC LIMIT ?2",
             )
@@ -83,7 +99,7 @@ impl EditStore {
     ) -> Result<Vec<AgentRecentEdit>> {
         let mut stmt = conn
             .prepare(
-                "SELECT id, doc_id, agent_id, start_offset_utf16, end_offset_utf16, ts \
+                "SELECT id, doc_id, agent_id, start_offset_utf16, end_offset_utf16, ts, summary \
                  FROM agent_recent_edits WHERE agent_id = ?1 \
                  ORDER BY ts DESC LIMIT ?2",
             )
@@ -118,6 +134,64 @@ impl EditStore {
         conn.execute("DELETE FROM agent_recent_edits WHERE ts < ?1", params![cutoff.to_rfc3339()])
             .context("failed to prune old agent edits")
     }
+
+    /// Log an edit rejected by the agent policy.
+    pub fn record_violation(conn: &Connection, violation: &PolicyViolationRecord) -> Result<i64> {
+        conn.execute(
+            "INSERT INTO agent_policy_violations \
+             (workspace_id, doc_id, agent_id, rule, reason, ts) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                violation.workspace_id,
+                violation.doc_id,
+                violation.agent_id,
+                violation.rule,
+                violation.reason,
+                violation.ts.to_rfc3339(),
+            ],
+        )
+        .context("failed to insert agent policy violation")?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// List policy violations by an agent, newest first.
+    pub fn list_violations_by_agent(
+        conn: &Connection,
+        agent_id: &str,
+        limit: usize,
+    ) -> Result<Vec<PolicyViolationRecord>> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT workspace_id, doc_id, agent_id, rule, reason, ts \
+                 FROM agent_policy_violations WHERE agent_id = ?1 \
+                 ORDER BY ts DESC, id DESC LIMIT ?2",
+            )
+            .context("failed to prepare policy violations query")?;
+
+        let rows = stmt
+            .query_map(params![agent_id, limit as i64], |row| {
+                let ts_raw: String = row.get(5)?;
+                let ts = ts_raw.parse::<DateTime<Utc>>().map_err(|e| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        5,
+                        rusqlite::types::Type::Text,
+                        Box::new(e),
+                    )
+                })?;
+                Ok(PolicyViolationRecord {
+                    workspace_id: row.get(0)?,
+                    doc_id: row.get(1)?,
+                    agent_id: row.get(2)?,
+                    rule: row.get(3)?,
+                    reason: row.get(4)?,
+                    ts,
+                })
+            })
+            .context("failed to query policy violations")?;
+
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .context("failed to collect policy violations")
+    }
 }
 
 // ── Helpers ──────────────────────────────────────────────────────────
@@ -135,6 +209,7 @@ fn row_to_edit(row: &rusqlite::Row<'_>) -> rusqlite::Result<AgentRecentEdit> {
         start_offset_utf16: row.get(3)?,
         end_offset_utf16: row.get(4)?,
         ts,
+        summary: row.get(6)?,
     })
 }
 
@@ -184,6 +259,7 @@ mod tests {
             start_offset_utf16: start,
             end_offset_utf16: end,
             ts: at,
+            summary: None,
         }
     }
 
@@ -311,6 +387,33 @@ mod tests {
         cleanup(&path);
     }
 
+    #[test]
+    fn violations_are_logged_per_agent() {
+        let (db, path) = setup();
+        let conn = db.connection();
+        let violation = |agent: &str, rule: &str, at| PolicyViolationRecord {
+            workspace_id: "ws-1".into(),
+            doc_id: Some("doc-1".into()),
+            agent_id: agent.into(),
+            rule: rule.into(),
+            reason: format!("{rule} denied"),
+            ts: at,
+        };
+
+        EditStore::record_violation(conn, &violation("bot-1", "read_only", ts(10))).unwrap();
+        EditStore::record_violation(conn, &violation("bot-1", "path", ts(20))).unwrap();
+        EditStore::record_violation(conn, &violation("bot-2", "path", ts(30))).unwrap();
+
+        let logged = EditStore::list_violations_by_agent(conn, "bot-1", 10).unwrap();
+        assert_eq!(
+            logged,
+            vec![violation("bot-1", "path", ts(20)), violation("bot-1", "read_only", ts(10))]
+        );
+
+        drop(db);
+        cleanup(&path);
+    }
+
     #[test]
     fn empty_queries_return_empty() {
         let (db, path) = setup();
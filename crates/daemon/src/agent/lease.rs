@@ -16,6 +16,9 @@ use rusqlite::{params, Connection};
 pub enum LeaseMode {
     Exclusive,
     Shared,
+    /// Soft lease from a declared edit plan (`agent.plan`); the note holds
+    /// the intent.
+    Planned,
 }
 
 impl LeaseMode {
@@ -23,6 +26,7 @@ impl LeaseMode {
         match self {
             Self::Exclusive => "exclusive",
             Self::Shared => "shared",
+            Self::Planned => "planned",
         }
     }
 
@@ -30,6 +34,7 @@ impl LeaseMode {
         match value {
             "exclusive" => Some(Self::Exclusive),
             "shared" => Some(Self::Shared),
+            "planned" => Some(Self::Planned),
             _ => None,
         }
     }
@@ -440,6 +445,39 @@ mod tests {
         cleanup(&path);
     }
 
+    #[test]
+    fn planned_lease_round_trips_through_sqlite() {
+        let (db, path) = setup();
+        let now = ts(1_700_000_050);
+        let mut store = LeaseStore::new(db.connection(), now).expect("store should load");
+        store
+            .claim(
+                db.connection(),
+                LeaseClaim {
+                    workspace_id: "ws-1".into(),
+                    doc_id: "doc-1".into(),
+                    section_id: "root/auth".into(),
+                    agent_id: "codex-1".into(),
+                    ttl_sec: 1800,
+                    mode: LeaseMode::Planned,
+                    note: Some("rotate tokens".into()),
+                },
+                now,
+            )
+            .expect("planned claim should succeed");
+
+        let mut reloaded = LeaseStore::new(db.connection(), now).expect("reload should succeed");
+        let active = reloaded
+            .active_leases(db.connection(), "ws-1", Some("doc-1"), now)
+            .expect("active lease query should succeed");
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].mode, LeaseMode::Planned);
+        assert_eq!(active[0].note.as_deref(), Some("rotate tokens"));
+
+        drop(db);
+        cleanup(&path);
+    }
+
     #[test]
     fn activity_extends_ttl_and_updates_sqlite() {
         let (db, path) = setup();
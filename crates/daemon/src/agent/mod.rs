@@ -1,5 +1,9 @@
-// Agent management: session tracking, lease storage, edit history.
+// Agent management: session tracking, lease storage, edit history, undo,
+// local policy, scratchpad notes.
 
 pub mod edits;
 pub mod lease;
+pub mod notes;
+pub mod policy;
 pub mod session;
+pub mod undo;
@@ -0,0 +1,259 @@
+// Agent scratchpad notes: key-value notes plus an append-only journal.
+//
+// Both are per agent and per workspace and persist in meta.db, so an
+// agent's working plan survives context resets and fresh sub-agents can
+// pick it up. Notes and journal entries may be scoped to a doc or section.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+
+// ── Types ────────────────────────────────────────────────────────────
+
+/// Optional doc/section a note or journal entry is about.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NoteScope {
+    pub doc_id: Option<String>,
+    pub section_id: Option<String>,
+}
+
+/// A key-value note; setting an existing key replaces it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentNote {
+    pub workspace_id: String,
+    pub agent_id: String,
+    pub key: String,
+    pub value: String,
+    pub scope: NoteScope,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One append-only journal entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalEntry {
+    pub id: i64,
+    pub workspace_id: String,
+    pub agent_id: String,
+    pub entry: String,
+    pub scope: NoteScope,
+    pub ts: DateTime<Utc>,
+}
+
+// ── Store ────────────────────────────────────────────────────────────
+
+/// Stateless operations on the `agent_notes` and `agent_journal` tables.
+pub struct NoteStore;
+
+impl NoteStore {
+    /// Insert or replace a note by (workspace, agent, key).
+    pub fn set(conn: &Connection, note: &AgentNote) -> Result<()> {
+        conn.execute(
+            "INSERT INTO agent_notes \
+             (workspace_id, agent_id, key, value, doc_id, section_id, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7) \
+             ON CONFLICT (workspace_id, agent_id, key) DO UPDATE SET \
+             value = excluded.value, doc_id = excluded.doc_id, \
+             section_id = excluded.section_id, updated_at = excluded.updated_at",
+            params![
+                note.workspace_id,
+                note.agent_id,
+                note.key,
+                note.value,
+                note.scope.doc_id,
+                note.scope.section_id,
+                note.updated_at.to_rfc3339(),
+            ],
+        )
+        .context("failed to upsert agent note")?;
+        Ok(())
+    }
+
+    /// Get one note by key.
+    pub fn get(
+        conn: &Connection,
+        workspace_id: &str,
+        agent_id: &str,
+        key: &str,
+    ) -> Result<Option<AgentNote>> {
+        conn.query_row(
+            "SELECT workspace_id, agent_id, key, value, doc_id, section_id, updated_at \
+             FROM agent_notes WHERE workspace_id = ?1 AND agent_id = ?2 AND key = ?3",
+            params![workspace_id, agent_id, key],
+            row_to_note,
+        )
+        .optional()
+        .context("failed to query agent note")
+    }
+
+    /// List an agent's notes by key, optionally only those scoped to a doc
+    /// (and section).
+    pub fn list(
+        conn: &Connection,
+        workspace_id: &str,
+        agent_id: &str,
+        scope: &NoteScope,
+    ) -> Result<Vec<AgentNote>> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT workspace_id, agent_id, key, value, doc_id, section_id, updated_at \
+                 FROM agent_notes WHERE workspace_id = ?1 AND agent_id = ?2 \
+                 AND (?3 IS NULL OR doc_id = ?3) AND (?4 IS NULL OR section_id = ?4) \
+                 ORDER BY key",
+            )
+            .context("failed to prepare agent notes query")?;
+
+        let rows = stmt
+            .query_map(params![workspace_id, agent_id, scope.doc_id, scope.section_id], row_to_note)
+            .context("failed to query agent notes")?;
+
+        rows.collect::<std::result::Result<Vec<_>, _>>().context("failed to collect agent notes")
+    }
+
+    /// Append a journal entry; `entry.id` is ignored. Returns the new row ID.
+    pub fn append(conn: &Connection, entry: &JournalEntry) -> Result<i64> {
+        conn.execute(
+            "INSERT INTO agent_journal \
+             (workspace_id, agent_id, entry, doc_id, section_id, ts) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                entry.workspace_id,
+                entry.agent_id,
+                entry.entry,
+                entry.scope.doc_id,
+                entry.scope.section_id,
+                entry.ts.to_rfc3339(),
+            ],
+        )
+        .context("failed to append agent journal entry")?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// The newest `limit` journal entries, oldest first so they read as a log.
+    pub fn journal(
+        conn: &Connection,
+        workspace_id: &str,
+        agent_id: &str,
+        scope: &NoteScope,
+        limit: usize,
+    ) -> Result<Vec<JournalEntry>> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, workspace_id, agent_id, entry, doc_id, section_id, ts \
+                 FROM agent_journal WHERE workspace_id = ?1 AND agent_id = ?2 \
+                 AND (?3 IS NULL OR doc_id = ?3) AND (?4 IS NULL OR section_id = ?4) \
+                 ORDER BY ts DESC, id DESC LIMIT ?5",
+            )
+            .context("failed to prepare agent journal query")?;
+
+        let rows = stmt
+            .query_map(
+                params![workspace_id, agent_id, scope.doc_id, scope.section_id, limit as i64],
+                row_to_journal_entry,
+            )
+            .context("failed to query agent journal")?;
+
+        let mut entries = rows
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .context("failed to collect agent journal")?;
+        entries.reverse();
+        Ok(entries)
+    }
+}
+
+// ── Helpers ──────────────────────────────────────────────────────────
+
+fn parse_ts(row: &rusqlite::Row<'_>, index: usize) -> rusqlite::Result<DateTime<Utc>> {
+    let raw: String = row.get(index)?;
+    raw.parse::<DateTime<Utc>>().map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+fn row_to_note(row: &rusqlite::Row<'_>) -> rusqlite::Result<AgentNote> {
+    Ok(AgentNote {
+        workspace_id: row.get(0)?,
+        agent_id: row.get(1)?,
+        key: row.get(2)?,
+        value: row.get(3)?,
+        scope: NoteScope { doc_id: row.get(4)?, section_id: row.get(5)? },
+        updated_at: parse_ts(row, 6)?,
+    })
+}
+
+fn row_to_journal_entry(row: &rusqlite::Row<'_>) -> rusqlite::Result<JournalEntry> {
+    Ok(JournalEntry {
+        id: row.get(0)?,
+        workspace_id: row.get(1)?,
+        agent_id: row.get(2)?,
+        entry: row.get(3)?,
+        scope: NoteScope { doc_id: row.get(4)?, section_id: row.get(5)? },
+        ts: parse_ts(row, 6)?,
+    })
+}
+
+// ── Tests ────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::meta_db::MetaDb;
+
+    fn note(key: &str, value: &str, doc_id: Option<&str>) -> AgentNote {
+        AgentNote {
+            workspace_id: "ws-1".into(),
+            agent_id: "claude-1".into(),
+            key: key.into(),
+            value: value.into(),
+            scope: NoteScope { doc_id: doc_id.map(Into::into), section_id: None },
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn set_replaces_by_key_and_list_filters_by_scope() {
+        let db = MetaDb::open(":memory:").expect("meta db should open");
+        let conn = db.connection();
+        NoteStore::set(conn, &note("plan", "draft auth section", None)).unwrap();
+        NoteStore::set(conn, &note("todo", "fix links", Some("doc-a"))).unwrap();
+        NoteStore::set(conn, &note("plan", "review auth section", None)).unwrap();
+
+        let plan = NoteStore::get(conn, "ws-1", "claude-1", "plan").unwrap().unwrap();
+        assert_eq!(plan.value, "review auth section");
+        assert!(NoteStore::get(conn, "ws-1", "copilot-1", "plan").unwrap().is_none());
+
+        let all = NoteStore::list(conn, "ws-1", "claude-1", &NoteScope::default()).unwrap();
+        assert_eq!(all.iter().map(|n| n.key.as_str()).collect::<Vec<_>>(), vec!["plan", "todo"]);
+        let scoped = NoteScope { doc_id: Some("doc-a".into()), section_id: None };
+        let doc_a = NoteStore::list(conn, "ws-1", "claude-1", &scoped).unwrap();
+        assert_eq!(doc_a.len(), 1);
+        assert_eq!(doc_a[0].key, "todo");
+    }
+
+    #[test]
+    fn journal_returns_newest_entries_in_append_order() {
+        let db = MetaDb::open(":memory:").expect("meta db should open");
+        let conn = db.connection();
+        let start = Utc::now();
+        for (offset, text) in ["read spec", "drafted intro", "opened PR"].iter().enumerate() {
+            NoteStore::append(
+                conn,
+                &JournalEntry {
+                    id: 0,
+                    workspace_id: "ws-1".into(),
+                    agent_id: "claude-1".into(),
+                    entry: (*text).into(),
+                    scope: NoteScope::default(),
+                    ts: start + chrono::Duration::seconds(offset as i64),
+                },
+            )
+            .unwrap();
+        }
+
+        let recent =
+            NoteStore::journal(conn, "ws-1", "claude-1", &NoteScope::default(), 2).unwrap();
+        assert_eq!(
+            recent.iter().map(|e| e.entry.as_str()).collect::<Vec<_>>(),
+            vec!["drafted intro", "opened PR"]
+        );
+    }
+}
@@ -0,0 +1,301 @@
+// Local agent policy enforcement (`.scriptum/agents.toml`).
+//
+// The policy maps agent id globs to the paths and sections an agent may
+// edit, a per-edit size cap, a per-minute edit budget and a read-only flag.
+// Checks are pure; the per-agent edit rate lives in `AgentRateLimiter` and
+// parsed policy files in `AgentPolicyCache`, which the daemon keeps for its
+// lifetime.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Duration, Utc};
+use scriptum_common::path::glob_match;
+use scriptum_common::types::Section;
+
+use crate::config::{AgentPolicyConfig, AgentRuleConfig};
+
+/// Which rule an edit broke.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyRule {
+    /// `agents.toml` exists but could not be loaded.
+    PolicyFile,
+    ReadOnly,
+    Path,
+    Section,
+    MaxEditBytes,
+    MaxEditsPerMinute,
+}
+
+impl PolicyRule {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PolicyFile => "policy_file",
+            Self::ReadOnly => "read_only",
+            Self::Path => "path",
+            Self::Section => "section",
+            Self::MaxEditBytes => "max_edit_bytes",
+            Self::MaxEditsPerMinute => "max_edits_per_minute",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyViolation {
+    pub agent_id: String,
+    pub rule: PolicyRule,
+    pub reason: String,
+}
+
+impl PolicyViolation {
+    pub fn new(agent_id: &str, rule: PolicyRule, reason: impl Into<String>) -> Self {
+        Self { agent_id: agent_id.to_string(), rule, reason: reason.into() }
+    }
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "agent policy violation ({}): {}", self.rule.as_str(), self.reason)
+    }
+}
+
+/// An agent's proposed change to one doc.
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyEdit<'a> {
+    pub path: &'a str,
+    /// Sections the edit adds, removes or modifies.
+    pub sections: &'a [Section],
+    /// Bytes inserted plus bytes removed.
+    pub edit_bytes: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AgentPolicy {
+    config: AgentPolicyConfig,
+}
+
+impl AgentPolicy {
+    pub fn new(config: AgentPolicyConfig) -> Self {
+        Self { config }
+    }
+
+    /// The rule governing `agent_id`, if any.
+    pub fn rule_for(&self, agent_id: &str) -> Option<&AgentRuleConfig> {
+        self.config
+            .agents
+            .iter()
+            .find(|rule| glob_match(&rule.name, agent_id))
+            .or(self.config.default.as_ref())
+    }
+
+    /// Check that the agent may mutate `path` at all.
+    pub fn check_path(&self, agent_id: &str, path: &str) -> Result<(), PolicyViolation> {
+        let Some(rule) = self.rule_for(agent_id) else {
+            return Ok(());
+        };
+        if rule.read_only {
+            return Err(PolicyViolation::new(
+                agent_id,
+                PolicyRule::ReadOnly,
+                format!("agent `{agent_id}` is read-only"),
+            ));
+        }
+        if !rule.paths.is_empty() && !rule.paths.iter().any(|glob| glob_match(glob, path)) {
+            return Err(PolicyViolation::new(
+                agent_id,
+                PolicyRule::Path,
+                format!("agent `{agent_id}` may not edit `{path}`"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check a concrete edit: path, touched sections and size.
+    pub fn check_edit(&self, agent_id: &str, edit: PolicyEdit<'_>) -> Result<(), PolicyViolation> {
+        self.check_path(agent_id, edit.path)?;
+        let Some(rule) = self.rule_for(agent_id) else {
+            return Ok(());
+        };
+        if let Some(section) = edit.sections.iter().find(|section| !section_allowed(rule, section))
+        {
+            return Err(PolicyViolation::new(
+                agent_id,
+                PolicyRule::Section,
+                format!(
+                    "agent `{agent_id}` may not edit section `{}` in `{}`",
+                    section.id, edit.path
+                ),
+            ));
+        }
+        if let Some(max) = rule.max_edit_bytes {
+            if edit.edit_bytes > max {
+                return Err(PolicyViolation::new(
+                    agent_id,
+                    PolicyRule::MaxEditBytes,
+                    format!("edit of {} bytes exceeds the {max} byte limit", edit.edit_bytes),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn max_edits_per_minute(&self, agent_id: &str) -> Option<u32> {
+        self.rule_for(agent_id).and_then(|rule| rule.max_edits_per_minute)
+    }
+}
+
+/// A section is allowed when the rule lists no patterns or one matches its
+/// id or heading text.
+fn section_allowed(rule: &AgentRuleConfig, section: &Section) -> bool {
+    rule.sections.is_empty()
+        || rule
+            .sections
+            .iter()
+            .any(|glob| glob_match(glob, &section.id) || glob_match(glob, &section.heading))
+}
+
+/// Identifies one version of an `agents.toml`: its modification time and
+/// size, or None when the file does not exist.
+pub type PolicyFileStamp = Option<(SystemTime, u64)>;
+
+/// Loaded `agents.toml` per workspace root, reused while the file's stamp is
+/// unchanged. Load errors are cached too, so a broken file keeps failing
+/// closed without being re-read on every edit.
+#[derive(Debug, Default)]
+pub struct AgentPolicyCache {
+    entries: HashMap<PathBuf, (PolicyFileStamp, Result<Option<AgentPolicy>, String>)>,
+}
+
+impl AgentPolicyCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The policy loaded for `root`, if it was loaded from the file version
+    /// `stamp` identifies.
+    pub fn get(
+        &self,
+        root: &Path,
+        stamp: PolicyFileStamp,
+    ) -> Option<Result<Option<AgentPolicy>, String>> {
+        self.entries
+            .get(root)
+            .filter(|(cached, _)| *cached == stamp)
+            .map(|(_, policy)| policy.clone())
+    }
+
+    pub fn insert(
+        &mut self,
+        root: PathBuf,
+        stamp: PolicyFileStamp,
+        policy: Result<Option<AgentPolicy>, String>,
+    ) {
+        self.entries.insert(root, (stamp, policy));
+    }
+}
+
+/// Sliding 60 second edit windows per agent.
+#[derive(Debug, Default)]
+pub struct AgentRateLimiter {
+    windows: HashMap<String, VecDeque<DateTime<Utc>>>,
+}
+
+impl AgentRateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count `edits` more edits for the agent if that stays within `max` per
+    /// minute; otherwise count nothing and report the violation.
+    pub fn try_acquire(
+        &mut self,
+        agent_id: &str,
+        max: u32,
+        edits: usize,
+        now: DateTime<Utc>,
+    ) -> Result<(), PolicyViolation> {
+        let window = self.windows.entry(agent_id.to_string()).or_default();
+        let cutoff = now - Duration::seconds(60);
+        while window.front().is_some_and(|at| *at <= cutoff) {
+            window.pop_front();
+        }
+        if window.len() + edits > max as usize {
+            return Err(PolicyViolation::new(
+                agent_id,
+                PolicyRule::MaxEditsPerMinute,
+                format!("agent `{agent_id}` exceeded {max} edits per minute"),
+            ));
+        }
+        window.extend(std::iter::repeat(now).take(edits));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(id: &str, heading: &str) -> Section {
+        Section {
+            id: id.into(),
+            parent_id: None,
+            heading: heading.into(),
+            level: 2,
+            start_line: 1,
+            end_line: 2,
+        }
+    }
+
+    fn policy() -> AgentPolicy {
+        AgentPolicy::new(AgentPolicyConfig {
+            default: Some(AgentRuleConfig { read_only: true, ..Default::default() }),
+            agents: vec![AgentRuleConfig {
+                name: "claude-*".into(),
+                paths: vec!["docs/**".into()],
+                sections: vec!["spec/*".into(), "Open questions".into()],
+                max_edit_bytes: Some(100),
+                ..Default::default()
+            }],
+        })
+    }
+
+    #[test]
+    fn first_matching_rule_applies_and_default_covers_the_rest() {
+        let policy = policy();
+        let edit = |path, sections| PolicyEdit { path, sections, edit_bytes: 10 };
+        let allowed = [section("spec/auth", "Auth"), section("notes/x", "Open questions")];
+
+        assert!(policy.check_edit("claude-1", edit("docs/spec.md", &allowed)).is_ok());
+        let error = policy.check_edit("claude-1", edit("src/main.md", &allowed)).unwrap_err();
+        assert_eq!(error.rule, PolicyRule::Path);
+        let error = policy
+            .check_edit("claude-1", edit("docs/spec.md", &[section("intro", "Intro")]))
+            .unwrap_err();
+        assert_eq!(error.rule, PolicyRule::Section);
+        let error = policy
+            .check_edit(
+                "claude-1",
+                PolicyEdit { path: "docs/a.md", sections: &[], edit_bytes: 101 },
+            )
+            .unwrap_err();
+        assert_eq!(error.rule, PolicyRule::MaxEditBytes);
+
+        let error = policy.check_path("cursor-1", "docs/spec.md").unwrap_err();
+        assert_eq!(error.rule, PolicyRule::ReadOnly);
+        assert!(AgentPolicy::default().check_path("anyone", "anything.md").is_ok());
+    }
+
+    #[test]
+    fn rate_limiter_uses_a_sliding_minute() {
+        let mut limiter = AgentRateLimiter::new();
+        let start = Utc::now();
+        assert!(limiter.try_acquire("bot", 2, 1, start).is_ok());
+        assert!(limiter.try_acquire("bot", 2, 1, start + Duration::seconds(10)).is_ok());
+        let error = limiter.try_acquire("bot", 2, 1, start + Duration::seconds(20)).unwrap_err();
+        assert_eq!(error.rule, PolicyRule::MaxEditsPerMinute);
+        assert!(limiter.try_acquire("other", 2, 2, start).is_ok());
+        assert!(limiter.try_acquire("bot", 2, 1, start + Duration::seconds(61)).is_ok());
+    }
+}
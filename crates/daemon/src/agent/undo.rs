@@ -0,0 +1,154 @@
+// Per-agent undo/redo ordering across documents.
+//
+// The CRDT-level undo stacks live on each loaded `YDoc` (one per agent,
+// scoped by origin). This log only remembers which doc each of an agent's
+// edits landed in, newest last, so `agent.undo` can walk back across docs.
+// Like an editor's undo history it is session-scoped and not persisted, and
+// entries for a doc instance that has since been evicted are stale.
+
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+/// One agent edit that can be undone (or one undone edit that can be redone).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UndoEntry {
+    pub workspace_id: Uuid,
+    pub doc_id: Uuid,
+    /// `YDoc::instance_id` of the loaded doc holding the CRDT undo step.
+    pub doc_instance: Uuid,
+}
+
+#[derive(Debug, Default)]
+pub struct AgentUndoLog {
+    undo: HashMap<String, Vec<UndoEntry>>,
+    redo: HashMap<String, Vec<UndoEntry>>,
+}
+
+impl AgentUndoLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a fresh edit. As in any editor, a new edit discards the redo stack.
+    pub fn record_edit(&mut self, agent_id: &str, entry: UndoEntry) {
+        self.undo.entry(agent_id.to_string()).or_default().push(entry);
+        self.redo.remove(agent_id);
+    }
+
+    /// Pop up to `steps` of the agent's most recent edits in `workspace_id`,
+    /// optionally restricted to one doc. Newest first.
+    pub fn take_undo(
+        &mut self,
+        agent_id: &str,
+        workspace_id: Uuid,
+        doc_id: Option<Uuid>,
+        steps: usize,
+    ) -> Vec<UndoEntry> {
+        take_matching(self.undo.get_mut(agent_id), workspace_id, doc_id, steps)
+    }
+
+    /// Pop up to `steps` of the agent's most recently undone edits. Newest first.
+    pub fn take_redo(
+        &mut self,
+        agent_id: &str,
+        workspace_id: Uuid,
+        doc_id: Option<Uuid>,
+        steps: usize,
+    ) -> Vec<UndoEntry> {
+        take_matching(self.redo.get_mut(agent_id), workspace_id, doc_id, steps)
+    }
+
+    /// Entries reverted by an undo, in the order they were taken.
+    pub fn push_redo(&mut self, agent_id: &str, entries: impl IntoIterator<Item = UndoEntry>) {
+        self.redo.entry(agent_id.to_string()).or_default().extend(entries);
+    }
+
+    /// Entries re-applied by a redo, in the order they were taken.
+    pub fn push_undo(&mut self, agent_id: &str, entries: impl IntoIterator<Item = UndoEntry>) {
+        self.undo.entry(agent_id.to_string()).or_default().extend(entries);
+    }
+
+    /// Drop the agent's entries for `doc_id` that belong to another instance
+    /// than `live_instance`: their CRDT undo steps went with that instance.
+    pub fn forget_stale(&mut self, agent_id: &str, doc_id: Uuid, live_instance: Option<Uuid>) {
+        let is_live =
+            |entry: &UndoEntry| entry.doc_id != doc_id || Some(entry.doc_instance) == live_instance;
+        for stacks in [&mut self.undo, &mut self.redo] {
+            if let Some(stack) = stacks.get_mut(agent_id) {
+                stack.retain(is_live);
+            }
+        }
+    }
+}
+
+fn take_matching(
+    stack: Option<&mut Vec<UndoEntry>>,
+    workspace_id: Uuid,
+    doc_id: Option<Uuid>,
+    steps: usize,
+) -> Vec<UndoEntry> {
+    let Some(stack) = stack else {
+        return Vec::new();
+    };
+
+    let mut taken = Vec::new();
+    let mut index = stack.len();
+    while index > 0 && taken.len() < steps {
+        index -= 1;
+        let entry = stack[index];
+        if entry.workspace_id == workspace_id
+            && doc_id.map_or(true, |doc_id| doc_id == entry.doc_id)
+        {
+            taken.push(stack.remove(index));
+        }
+    }
+    taken
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_walks_back_across_docs_and_respects_doc_filter() {
+        let workspace_id = Uuid::new_v4();
+        let (doc_a, doc_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let doc_instance = Uuid::new_v4();
+        let entry = |doc_id| UndoEntry { workspace_id, doc_id, doc_instance };
+        let mut log = AgentUndoLog::new();
+        log.record_edit("claude-1", entry(doc_a));
+        log.record_edit("claude-1", entry(doc_b));
+        log.record_edit("claude-1", entry(doc_a));
+        log.record_edit("cursor-1", entry(doc_b));
+
+        assert_eq!(log.take_undo("claude-1", workspace_id, Some(doc_b), 5), vec![entry(doc_b)]);
+        let taken = log.take_undo("claude-1", workspace_id, None, 5);
+        assert_eq!(taken, vec![entry(doc_a), entry(doc_a)]);
+
+        log.push_redo("claude-1", taken);
+        assert_eq!(log.take_redo("claude-1", workspace_id, None, 1), vec![entry(doc_a)]);
+
+        log.record_edit("claude-1", entry(doc_b));
+        assert!(log.take_redo("claude-1", workspace_id, None, 1).is_empty());
+        assert_eq!(log.take_undo("cursor-1", workspace_id, None, 1), vec![entry(doc_b)]);
+    }
+
+    #[test]
+    fn forget_stale_drops_entries_of_evicted_instances() {
+        let workspace_id = Uuid::new_v4();
+        let (doc_a, doc_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let (old, live) = (Uuid::new_v4(), Uuid::new_v4());
+        let entry = |doc_id, doc_instance| UndoEntry { workspace_id, doc_id, doc_instance };
+        let mut log = AgentUndoLog::new();
+        log.record_edit("claude-1", entry(doc_a, old));
+        log.record_edit("claude-1", entry(doc_b, old));
+        log.record_edit("claude-1", entry(doc_a, live));
+
+        log.forget_stale("claude-1", doc_a, Some(live));
+        assert_eq!(
+            log.take_undo("claude-1", workspace_id, None, 5),
+            vec![entry(doc_a, live), entry(doc_b, old)]
+        );
+    }
+}
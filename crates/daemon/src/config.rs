@@ -2,6 +2,7 @@
 //
 // Global config: `~/.scriptum/config.toml`
 // Workspace config: `<workspace>/.scriptum/workspace.toml`
+// Agent policy: `<workspace>/.scriptum/agents.toml`
 
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
@@ -23,6 +24,11 @@ pub fn workspace_config_path(workspace_root: &Path) -> PathBuf {
     workspace_root.join(".scriptum").join("workspace.toml")
 }
 
+/// Path to the agent policy file: `<root>/.scriptum/agents.toml`.
+pub fn agents_config_path(workspace_root: &Path) -> PathBuf {
+    workspace_root.join(".scriptum").join("agents.toml")
+}
+
 // ── Global config ──────────────────────────────────────────────────
 
 /// Global daemon configuration at `~/.scriptum/config.toml`.
@@ -109,20 +115,90 @@ impl GlobalConfig {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct AiConfig {
-    /// Anthropic API key used for AI-generated commit messages.
+    /// API key for the selected backend.
     pub api_key: Option<String>,
     /// Model to use (e.g. `claude-haiku-4-5-20251001`).
     pub model: Option<String>,
     /// Enables or disables AI commit message generation globally.
     pub enabled: bool,
+    /// Which service generates commit messages.
+    pub backend: AiBackend,
+    /// Base URL of an OpenAI-compatible API, up to and including `/v1`.
+    pub base_url: Option<String>,
+    /// Program and arguments for the `command` backend.
+    pub command: Vec<String>,
 }
 
 impl Default for AiConfig {
     fn default() -> Self {
-        Self { api_key: None, model: None, enabled: true }
+        Self {
+            api_key: None,
+            model: None,
+            enabled: true,
+            backend: AiBackend::Anthropic,
+            base_url: None,
+            command: Vec::new(),
+        }
+    }
+}
+
+impl AiConfig {
+    /// This config with a workspace's `[git.ai]` settings applied. The
+    /// key belongs to the global backend, so switching backends drops it.
+    pub fn with_override(&self, overrides: &AiOverride) -> Self {
+        let mut config = self.clone();
+        if let Some(backend) = overrides.backend.filter(|backend| *backend != config.backend) {
+            config.backend = backend;
+            config.api_key = None;
+        }
+        if let Some(model) = &overrides.model {
+            config.model = Some(model.clone());
+        }
+        if let Some(base_url) = &overrides.base_url {
+            config.base_url = Some(base_url.clone());
+        }
+        if let Some(command) = &overrides.command {
+            config.command = command.clone();
+        }
+        config
     }
 }
 
+/// Service behind AI commit messages.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AiBackend {
+    /// The Anthropic messages API.
+    #[default]
+    Anthropic,
+    /// Any OpenAI-compatible chat completions API: OpenAI, vLLM, Ollama,
+    /// llama.cpp.
+    #[serde(rename = "openai")]
+    OpenAi,
+    /// A local program that reads the prompt on stdin and prints the
+    /// message.
+    Command,
+}
+
+/// Per-workspace AI backend settings, over the global `[ai]` section.
+///
+/// ```toml
+/// [git.ai]
+/// backend = "openai"
+/// base_url = "http://localhost:11434/v1"
+/// model = "llama3.1"
+/// ```
+///
+/// API keys stay in the global config or the environment.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct AiOverride {
+    pub backend: Option<AiBackend>,
+    pub model: Option<String>,
+    pub base_url: Option<String>,
+    pub command: Option<Vec<String>>,
+}
+
 /// Editor type for this client.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -142,6 +218,8 @@ pub struct WorkspaceConfig {
     pub git: GitConfig,
     /// Sync settings.
     pub sync: SyncConfig,
+    /// Secret and PII guard for incoming edits.
+    pub guard: GuardConfig,
 }
 
 impl WorkspaceConfig {
@@ -158,6 +236,34 @@ impl WorkspaceConfig {
         toml::from_str(&contents).map_err(ConfigError::Parse)
     }
 
+    /// Load the agent policy from `<root>/.scriptum/agents.toml`. Returns
+    /// `Ok(None)` when the workspace has no policy file; a file that fails
+    /// to parse is an error so callers can fail closed.
+    pub fn load_agent_policy(
+        workspace_root: &Path,
+    ) -> Result<Option<AgentPolicyConfig>, ConfigError> {
+        let path = agents_config_path(workspace_root);
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str(&contents).map(Some).map_err(ConfigError::Parse),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(ConfigError::Io(error)),
+        }
+    }
+
+    /// Load the secret guard mode from `<root>/.scriptum/workspace.toml`.
+    /// A missing file means the default mode; a file that fails to load is
+    /// an error so callers can fail closed.
+    pub fn load_guard_mode(workspace_root: &Path) -> Result<GuardMode, ConfigError> {
+        let path = workspace_config_path(workspace_root);
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => toml::from_str::<Self>(&contents)
+                .map(|config| config.guard.mode)
+                .map_err(ConfigError::Parse),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(GuardMode::default()),
+            Err(error) => Err(ConfigError::Io(error)),
+        }
+    }
+
     /// Save to `<root>/.scriptum/workspace.toml`.
     pub fn save(&self, workspace_root: &Path) -> Result<(), ConfigError> {
         let path = workspace_config_path(workspace_root);
@@ -195,6 +301,25 @@ pub struct GitConfig {
     pub ai_commit: bool,
     /// Redaction policy for AI commit messages.
     pub redaction_policy: RedactionPolicy,
+    /// Where commits go: `direct`, `branch_per_session`, or `pull_request`.
+    pub strategy: GitStrategy,
+    /// Forge that hosts pull requests for the `pull_request` strategy.
+    pub forge: Option<ForgeConfig>,
+    /// Path globs to stage. Empty stages every path not excluded.
+    pub include: Vec<String>,
+    /// Path globs never staged. Matching docs still sync via the relay.
+    pub exclude: Vec<String>,
+    /// AI commit backend for this workspace, over the global `[ai]` section.
+    pub ai: AiOverride,
+    /// Committer recorded on daemon commits. Unset uses git's own config.
+    pub committer: Option<GitIdentity>,
+    /// Sign daemon commits with an SSH or GPG key.
+    pub signing: Option<SigningConfig>,
+    /// Split each auto-commit into one commit per primary author, so each
+    /// commit's author is whoever wrote most of its files.
+    pub commit_per_author: bool,
+    /// How the daemon talks to git: the `git` CLI, or in-process.
+    pub backend: GitBackend,
 }
 
 impl Default for GitConfig {
@@ -206,10 +331,104 @@ impl Default for GitConfig {
             push_policy: PushPolicy::Disabled,
             ai_commit: true,
             redaction_policy: RedactionPolicy::Redacted,
+            strategy: GitStrategy::Direct,
+            forge: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            ai: AiOverride::default(),
+            committer: None,
+            signing: None,
+            commit_per_author: false,
+            backend: GitBackend::Cli,
         }
     }
 }
 
+/// Branch strategy for auto-commits.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GitStrategy {
+    /// Commit to the checked-out branch.
+    #[default]
+    Direct,
+    /// Commit each agent's work to `scriptum/<agent>/<date>`.
+    BranchPerSession,
+    /// Like `branch_per_session`, and open or update a pull request from
+    /// each session branch into `branch`.
+    PullRequest,
+}
+
+/// Git implementation behind commits, pushes and merges.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GitBackend {
+    /// Run the `git` executable on `PATH`.
+    #[default]
+    Cli,
+    /// libgit2 in-process; needs no `git` install.
+    Native,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GitIdentity {
+    pub name: String,
+    pub email: String,
+}
+
+/// Commit signing.
+///
+/// ```toml
+/// [git.signing]
+/// format = "ssh"
+/// key = "~/.ssh/scriptum_signing.pub"   # else the stored signing key
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SigningConfig {
+    pub format: SigningFormat,
+    /// `user.signingkey` value: an SSH key path or literal, or a GPG key
+    /// id. Unset reads it from the keychain, then falls back to git's
+    /// own `user.signingkey`.
+    #[serde(default)]
+    pub key: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SigningFormat {
+    Ssh,
+    Openpgp,
+}
+
+/// Forge API used to open pull requests.
+///
+/// ```toml
+/// [git.forge]
+/// kind = "github"
+/// repo = "acme/handbook"
+/// token_env = "GITHUB_TOKEN"   # else the stored git credentials
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ForgeConfig {
+    pub kind: ForgeKind,
+    /// `owner/name` on GitHub and Gitea, the project path on GitLab.
+    pub repo: String,
+    /// REST API base URL. Defaults to the public GitHub or GitLab API;
+    /// required for Gitea.
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// Environment variable holding the API token.
+    #[serde(default)]
+    pub token_env: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ForgeKind {
+    Github,
+    Gitlab,
+    Gitea,
+}
+
 /// Push policy for git operations.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -244,6 +463,71 @@ pub struct SyncConfig {
     pub workspace_name: Option<String>,
 }
 
+/// Secret and PII guard configuration per workspace.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct GuardConfig {
+    /// What to do when an edit introduces a secret or PII value.
+    pub mode: GuardMode,
+}
+
+/// Guard mode for incoming edits.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardMode {
+    /// Apply the edit and record the findings.
+    #[default]
+    Warn,
+    /// Reject the edit.
+    Block,
+    /// Replace the sensitive values before applying the edit.
+    Redact,
+}
+
+// ── Agent policy ───────────────────────────────────────────────────
+
+/// Local agent policy at `<root>/.scriptum/agents.toml`.
+///
+/// ```toml
+/// [default]            # agents matching no [[agent]] rule
+/// read_only = true
+///
+/// [[agent]]
+/// name = "claude-*"
+/// paths = ["docs/**"]
+/// sections = ["spec/*", "Open questions"]
+/// max_edit_bytes = 20000
+/// max_edits_per_minute = 30
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct AgentPolicyConfig {
+    /// Rule for agents that match no `[[agent]]` entry. Unrestricted if unset.
+    pub default: Option<AgentRuleConfig>,
+    /// Rules in priority order; the first whose `name` glob matches applies.
+    #[serde(rename = "agent")]
+    pub agents: Vec<AgentRuleConfig>,
+}
+
+/// Restrictions for the agents whose id matches `name`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct AgentRuleConfig {
+    /// Agent id glob (ignored for `[default]`).
+    pub name: String,
+    /// Doc path globs the agent may edit. Empty allows every path.
+    pub paths: Vec<String>,
+    /// Globs over section ids or heading text the agent may edit. Empty
+    /// allows every section.
+    pub sections: Vec<String>,
+    /// Largest single edit, in bytes inserted plus removed.
+    pub max_edit_bytes: Option<usize>,
+    /// Edits accepted per agent in any 60 second window.
+    pub max_edits_per_minute: Option<u32>,
+    /// Reject every mutation from the agent.
+    pub read_only: bool,
+}
+
 // ── Errors ─────────────────────────────────────────────────────────
 
 #[derive(Debug)]
@@ -281,6 +565,7 @@ mod tests {
         assert!(cfg.ai.api_key.is_none());
         assert!(cfg.ai.model.is_none());
         assert!(cfg.ai.enabled);
+        assert_eq!(cfg.ai.backend, AiBackend::Anthropic);
         assert!(cfg.workspace_paths.is_empty());
     }
 
@@ -297,6 +582,9 @@ mod tests {
                 api_key: Some("sk-ant-test".into()),
                 model: Some("claude-haiku-4-5-20251001".into()),
                 enabled: false,
+                backend: AiBackend::OpenAi,
+                base_url: Some("http://localhost:8000/v1".into()),
+                command: Vec::new(),
             },
             workspace_paths: vec!["/tmp/ws-a".into(), "/tmp/ws-b".into()],
         };
@@ -372,6 +660,11 @@ api_key = "sk-prod"
         assert_eq!(cfg.git.push_policy, PushPolicy::Disabled);
         assert!(cfg.git.ai_commit);
         assert_eq!(cfg.git.redaction_policy, RedactionPolicy::Redacted);
+        assert_eq!(cfg.git.strategy, GitStrategy::Direct);
+        assert!(cfg.git.forge.is_none());
+        assert!(cfg.git.include.is_empty() && cfg.git.exclude.is_empty());
+        assert!(cfg.git.committer.is_none() && cfg.git.signing.is_none());
+        assert!(!cfg.git.commit_per_author);
         assert!(cfg.sync.relay_url.is_none());
     }
 
@@ -388,12 +681,34 @@ api_key = "sk-prod"
                 push_policy: PushPolicy::AutoRebase,
                 ai_commit: false,
                 redaction_policy: RedactionPolicy::Full,
+                strategy: GitStrategy::PullRequest,
+                forge: Some(ForgeConfig {
+                    kind: ForgeKind::Gitea,
+                    repo: "docs/handbook".into(),
+                    api_url: Some("https://git.example.com/api/v1".into()),
+                    token_env: Some("GITEA_TOKEN".into()),
+                }),
+                include: vec!["docs/**".into()],
+                exclude: vec!["docs/meetings/**".into()],
+                ai: AiOverride {
+                    backend: Some(AiBackend::Command),
+                    command: Some(vec!["/usr/local/bin/commit-msg".into(), "--short".into()]),
+                    ..AiOverride::default()
+                },
+                committer: Some(GitIdentity {
+                    name: "Scriptum".into(),
+                    email: "scriptum@example.com".into(),
+                }),
+                signing: Some(SigningConfig { format: SigningFormat::Ssh, key: None }),
+                commit_per_author: true,
+                backend: GitBackend::Native,
             },
             sync: SyncConfig {
                 relay_url: Some("https://custom-relay.example.com".into()),
                 workspace_id: Some("ws-123".into()),
                 workspace_name: Some("Workspace 123".into()),
             },
+            guard: GuardConfig { mode: GuardMode::Redact },
         };
         cfg.save_to(&path).unwrap();
         let loaded = WorkspaceConfig::load_from(&path).unwrap();
@@ -415,14 +730,90 @@ redaction_policy = "disabled"
 relay_url = "https://relay.example.com"
 workspace_id = "ws-456"
 workspace_name = "My Workspace"
+
+[guard]
+mode = "block"
 "#;
         let cfg: WorkspaceConfig = toml::from_str(toml_str).unwrap();
         assert_eq!(cfg.git.push_policy, PushPolicy::Manual);
+        assert_eq!(cfg.guard.mode, GuardMode::Block);
         assert_eq!(cfg.git.redaction_policy, RedactionPolicy::Disabled);
         assert_eq!(cfg.sync.workspace_id.as_deref(), Some("ws-456"));
         assert_eq!(cfg.sync.workspace_name.as_deref(), Some("My Workspace"));
     }
 
+    #[test]
+    fn workspace_config_parses_pull_request_strategy() {
+        let toml_str = r#"
+[git]
+strategy = "pull_request"
+
+[git.forge]
+kind = "github"
+repo = "acme/handbook"
+"#;
+        let cfg: WorkspaceConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(cfg.git.strategy, GitStrategy::PullRequest);
+        let forge = cfg.git.forge.expect("forge should parse");
+        assert_eq!(forge.kind, ForgeKind::Github);
+        assert_eq!(forge.repo, "acme/handbook");
+        assert!(forge.api_url.is_none());
+    }
+
+    #[test]
+    fn workspace_ai_override_replaces_only_the_fields_it_sets() {
+        let toml_str = r#"
+[git.ai]
+backend = "openai"
+base_url = "http://localhost:11434/v1"
+model = "llama3.1"
+"#;
+        let cfg: WorkspaceConfig = toml::from_str(toml_str).unwrap();
+        let global = AiConfig {
+            api_key: Some("sk-global".into()),
+            model: Some("claude-haiku-4-5-20251001".into()),
+            ..AiConfig::default()
+        };
+
+        let merged = global.with_override(&cfg.git.ai);
+        assert_eq!(merged.backend, AiBackend::OpenAi);
+        assert_eq!(merged.base_url.as_deref(), Some("http://localhost:11434/v1"));
+        assert_eq!(merged.model.as_deref(), Some("llama3.1"));
+        assert_eq!(merged.api_key, None, "the anthropic key must not reach another backend");
+        assert_eq!(global.with_override(&AiOverride::default()), global);
+
+        let with_key = "[git.ai]\napi_key = \"sk-leaked\"\n";
+        assert!(toml::from_str::<WorkspaceConfig>(with_key).is_err());
+    }
+
+    #[test]
+    fn workspace_config_parses_signing_and_committer() {
+        let toml_str = r#"
+[git]
+commit_per_author = true
+backend = "native"
+
+[git.committer]
+name = "Scriptum Bot"
+email = "bot@example.com"
+
+[git.signing]
+format = "openpgp"
+key = "3AA5C34371567BD2"
+"#;
+        let cfg: WorkspaceConfig = toml::from_str(toml_str).unwrap();
+        assert!(cfg.git.commit_per_author);
+        assert_eq!(cfg.git.backend, GitBackend::Native);
+        assert_eq!(cfg.git.committer.expect("committer").email, "bot@example.com");
+        assert_eq!(
+            cfg.git.signing,
+            Some(SigningConfig {
+                format: SigningFormat::Openpgp,
+                key: Some("3AA5C34371567BD2".into())
+            })
+        );
+    }
+
     #[test]
     fn workspace_config_partial_toml_uses_defaults() {
         let toml_str = r#"
@@ -433,6 +824,7 @@ branch = "develop"
         assert_eq!(cfg.git.branch, "develop");
         assert_eq!(cfg.git.remote, "origin"); // default
         assert_eq!(cfg.git.push_policy, PushPolicy::Disabled); // default
+        assert_eq!(cfg.guard.mode, GuardMode::Warn); // default
     }
 
     #[test]
@@ -459,6 +851,50 @@ branch = "develop"
         assert_eq!(loaded, WorkspaceConfig::default());
     }
 
+    #[test]
+    fn agent_policy_loads_rules_and_is_absent_without_file() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(WorkspaceConfig::load_agent_policy(dir.path()).unwrap(), None);
+
+        let path = agents_config_path(dir.path());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"
+[default]
+read_only = true
+
+[[agent]]
+name = "claude-*"
+paths = ["docs/**"]
+max_edits_per_minute = 10
+"#,
+        )
+        .unwrap();
+        let policy = WorkspaceConfig::load_agent_policy(dir.path()).unwrap().unwrap();
+        assert!(policy.default.as_ref().is_some_and(|rule| rule.read_only));
+        assert_eq!(policy.agents.len(), 1);
+        assert_eq!(policy.agents[0].paths, vec!["docs/**"]);
+        assert_eq!(policy.agents[0].max_edits_per_minute, Some(10));
+
+        std::fs::write(&path, "[[agent]]\nname = \"x\"\nwrite_anything = true\n").unwrap();
+        assert!(WorkspaceConfig::load_agent_policy(dir.path()).is_err());
+    }
+
+    #[test]
+    fn guard_mode_defaults_without_file_and_errors_on_a_bad_file() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(WorkspaceConfig::load_guard_mode(dir.path()).unwrap(), GuardMode::Warn);
+
+        let path = workspace_config_path(dir.path());
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "[guard]\nmode = \"redact\"\n").unwrap();
+        assert_eq!(WorkspaceConfig::load_guard_mode(dir.path()).unwrap(), GuardMode::Redact);
+
+        std::fs::write(&path, "[guard]\nmode = \"off\"\n").unwrap();
+        assert!(WorkspaceConfig::load_guard_mode(dir.path()).is_err());
+    }
+
     // ── Path helpers ───────────────────────────────────────────────
 
     #[test]
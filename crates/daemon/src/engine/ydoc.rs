@@ -1,15 +1,22 @@
 // Y.Doc wrapper using yrs (y-crdt Rust bindings).
 // Provides a higher-level API for Scriptum's CRDT operations.
 
-use anyhow::{Context, Result};
-use scriptum_common::crdt::origin::OriginTag;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context, Result};
+use scriptum_common::crdt::origin::{AuthorType, OriginTag};
+use uuid::Uuid;
 use yrs::updates::decoder::Decode;
 use yrs::updates::encoder::Encode;
 use yrs::{
-    Doc, GetString, MapRef, ReadTxn, StateVector, Subscription, Text, TextRef, Transact,
-    TransactionMut, Update, UpdateEvent,
+    undo, Doc, GetString, MapRef, ReadTxn, StateVector, Subscription, Text, TextRef, Transact,
+    TransactionMut, UndoManager, Update, UpdateEvent,
 };
 
+/// Shared text holding a document's markdown; the scope of per-author undo.
+const CONTENT_TEXT: &str = "content";
+
 /// A single observed Yjs update with CRDT-level origin attribution (if present and decodable).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ObservedDocUpdate {
@@ -37,18 +44,24 @@ impl ObservedDocUpdate {
 /// Wrapper around a Yjs document for Scriptum.
 pub struct YDoc {
     doc: Doc,
+    /// Identifies this in-memory instance. A doc evicted and loaded again
+    /// gets a new one, and starts with empty undo stacks.
+    instance_id: Uuid,
+    /// One undo manager per agent, tracking only transactions tagged with that
+    /// agent's origins. Lives as long as the loaded doc.
+    author_undo: Mutex<HashMap<String, UndoManager>>,
 }
 
 impl YDoc {
     /// Create a new empty document.
     pub fn new() -> Self {
-        Self { doc: Doc::new() }
+        Self::from_doc(Doc::new())
     }
 
     /// Create a document with a specific client ID (for deterministic testing).
     pub fn with_client_id(client_id: u64) -> Self {
         let options = yrs::Options { client_id, ..Default::default() };
-        Self { doc: Doc::with_options(options) }
+        Self::from_doc(Doc::with_options(options))
     }
 
     /// Load a document from a binary state (full snapshot).
@@ -56,7 +69,16 @@ impl YDoc {
         let doc = Doc::new();
         let update = Update::decode_v1(data).context("failed to decode Yjs state")?;
         doc.transact_mut().apply_update(update).context("failed to apply Yjs state update")?;
-        Ok(Self { doc })
+        Ok(Self::from_doc(doc))
+    }
+
+    fn from_doc(doc: Doc) -> Self {
+        Self { doc, instance_id: Uuid::new_v4(), author_undo: Mutex::new(HashMap::new()) }
+    }
+
+    /// Identifier of this in-memory instance of the document.
+    pub fn instance_id(&self) -> Uuid {
+        self.instance_id
     }
 
     /// Apply an incremental binary update to the document.
@@ -66,6 +88,72 @@ impl YDoc {
         Ok(())
     }
 
+    /// Apply an incremental update inside a transaction tagged with `origin_tag`.
+    ///
+    /// Agent-authored updates are captured on that agent's undo stack so they
+    /// can later be reverted with [`YDoc::undo_author`].
+    pub fn apply_update_with_origin(&self, data: &[u8], origin_tag: &OriginTag) -> Result<()> {
+        let update = Update::decode_v1(data).context("failed to decode Yjs update")?;
+        let origin_bytes = origin_tag.to_bytes().context("failed to encode origin tag")?;
+        self.track_author_origin(origin_tag, &origin_bytes)?;
+        self.doc
+            .transact_mut_with(origin_bytes.as_slice())
+            .apply_update(update)
+            .context("failed to apply Yjs update")?;
+        Ok(())
+    }
+
+    /// Revert up to `steps` of `author_id`'s most recent tracked changes,
+    /// leaving interleaved edits by other authors intact.
+    ///
+    /// Returns the number of steps reverted and the resulting update to
+    /// persist (empty when nothing changed).
+    pub fn undo_author(&self, author_id: &str, steps: usize) -> Result<(usize, Vec<u8>)> {
+        self.step_author(author_id, steps, UndoManager::undo_blocking)
+    }
+
+    /// Re-apply up to `steps` changes previously reverted by [`YDoc::undo_author`].
+    pub fn redo_author(&self, author_id: &str, steps: usize) -> Result<(usize, Vec<u8>)> {
+        self.step_author(author_id, steps, UndoManager::redo_blocking)
+    }
+
+    fn step_author(
+        &self,
+        author_id: &str,
+        steps: usize,
+        step: fn(&mut UndoManager) -> bool,
+    ) -> Result<(usize, Vec<u8>)> {
+        let mut managers = self.author_undo.lock().map_err(|_| anyhow!("undo lock poisoned"))?;
+        let Some(manager) = managers.get_mut(author_id) else {
+            return Ok((0, Vec::new()));
+        };
+
+        let before = self.doc.transact().state_vector();
+        let mut applied = 0;
+        while applied < steps && step(manager) {
+            applied += 1;
+        }
+        if applied == 0 {
+            return Ok((0, Vec::new()));
+        }
+        Ok((applied, self.doc.transact().encode_diff_v1(&before)))
+    }
+
+    fn track_author_origin(&self, origin_tag: &OriginTag, origin_bytes: &[u8]) -> Result<()> {
+        if origin_tag.author_type != AuthorType::Agent {
+            return Ok(());
+        }
+        let mut managers = self.author_undo.lock().map_err(|_| anyhow!("undo lock poisoned"))?;
+        let manager = managers.entry(origin_tag.author_id.clone()).or_insert_with(|| {
+            let text = self.doc.get_or_insert_text(CONTENT_TEXT);
+            // No capture window: every tracked transaction is its own undo step.
+            let options = undo::Options { capture_timeout_millis: 0, ..Default::default() };
+            UndoManager::with_scope_and_options(&self.doc, &text, options)
+        });
+        manager.include_origin(origin_bytes);
+        Ok(())
+    }
+
     /// Encode the full document state as a binary blob.
     pub fn encode_state(&self) -> Vec<u8> {
         self.doc.transact().encode_state_as_update_v1(&StateVector::default())
@@ -146,6 +234,7 @@ impl YDoc {
         origin_tag: &OriginTag,
     ) -> Result<()> {
         let origin_bytes = origin_tag.to_bytes().context("failed to encode origin tag")?;
+        self.track_author_origin(origin_tag, &origin_bytes)?;
         let text = self.doc.get_or_insert_text(name);
         let mut txn = self.doc.transact_mut_with(origin_bytes.as_slice());
         text.remove_range(&mut txn, index, length);
@@ -497,6 +586,40 @@ mod tests {
         assert!(!updates[0].update.is_empty());
     }
 
+    #[test]
+    fn undo_author_reverts_only_that_agents_changes() {
+        let doc = YDoc::new();
+        doc.insert_text(TEST_TEXT_KEY, 0, "intro\n");
+        let agent = |author_id: &str| OriginTag {
+            author_id: author_id.to_string(),
+            author_type: AuthorType::Agent,
+            timestamp: Utc::now(),
+        };
+
+        let staged = YDoc::from_state(&doc.encode_state()).expect("state should load");
+        staged.insert_text(TEST_TEXT_KEY, 6, "agent line\n");
+        doc.apply_update_with_origin(&staged.encode_state(), &agent("claude-1"))
+            .expect("agent update should apply");
+        doc.replace_text_with_origin(TEST_TEXT_KEY, 0, 0, "# ", &agent("cursor-1"))
+            .expect("other agent edit should apply");
+        doc.insert_text(TEST_TEXT_KEY, doc.text_len(TEST_TEXT_KEY), "human\n");
+        assert_eq!(doc.get_text_string(TEST_TEXT_KEY), "# intro\nagent line\nhuman\n");
+
+        let (undone, update) = doc.undo_author("claude-1", 5).expect("undo should run");
+        assert_eq!(undone, 1);
+        assert!(!update.is_empty());
+        assert_eq!(doc.get_text_string(TEST_TEXT_KEY), "# intro\nhuman\n");
+
+        let replica = YDoc::from_state(&staged.encode_state()).expect("state should load");
+        replica.apply_update(&doc.encode_state()).expect("replica should sync");
+        assert_eq!(replica.get_text_string(TEST_TEXT_KEY), "# intro\nhuman\n");
+
+        let (redone, _) = doc.redo_author("claude-1", 1).expect("redo should run");
+        assert_eq!(redone, 1);
+        assert_eq!(doc.get_text_string(TEST_TEXT_KEY), "# intro\nagent line\nhuman\n");
+        assert_eq!(doc.undo_author("nobody", 1).expect("undo should run").0, 0);
+    }
+
     #[test]
     fn randomized_convergence_property_smoke() {
         for seed in [7_u64, 42, 99, 2026, 65_537] {
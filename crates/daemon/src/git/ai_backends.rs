@@ -0,0 +1,416 @@
+// Commit message backends besides Anthropic.
+//
+// `OpenAiCommitClient` speaks the OpenAI chat completions API, which vLLM,
+// Ollama and llama.cpp servers also serve, so diffs can stay on a local
+// model. `CommandCommitClient` hands the prompt to a local program instead.
+// `commit_client_from_config` picks one from the `[ai]` backend.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+
+use super::commit::{AiCommitClient, AiCommitError, AnthropicCommitClient};
+use crate::config::{AiBackend, AiConfig};
+
+const OPENAI_API_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_MAX_TOKENS: usize = 200;
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+/// Environment variable that carries the system prompt to a command backend.
+pub const COMMAND_SYSTEM_PROMPT_ENV: &str = "SCRIPTUM_COMMIT_SYSTEM_PROMPT";
+
+/// The commit client `config.backend` selects.
+pub fn commit_client_from_config(config: &AiConfig) -> Arc<dyn AiCommitClient> {
+    match config.backend {
+        AiBackend::Anthropic => Arc::new(AnthropicCommitClient::from_ai_config(config)),
+        AiBackend::OpenAi => Arc::new(OpenAiCommitClient::from_ai_config(config)),
+        AiBackend::Command => Arc::new(CommandCommitClient::from_ai_config(config)),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OpenAiCommitClient {
+    http: reqwest::Client,
+    /// Full chat completions endpoint.
+    api_url: String,
+    api_key: Option<String>,
+    model: Option<String>,
+    max_tokens: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest {
+    model: String,
+    max_tokens: usize,
+    messages: Vec<ChatMessage>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage {
+    role: &'static str,
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    #[serde(default)]
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatChoiceMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoiceMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+impl OpenAiCommitClient {
+    /// Local servers usually need no key; `OPENAI_API_KEY` wins over
+    /// `[ai].api_key` when set.
+    pub fn from_ai_config(config: &AiConfig) -> Self {
+        let base_url = config
+            .base_url
+            .as_deref()
+            .and_then(trimmed_non_empty)
+            .unwrap_or_else(|| OPENAI_API_URL.to_string());
+        let api_key = std::env::var("OPENAI_API_KEY")
+            .ok()
+            .as_deref()
+            .and_then(trimmed_non_empty)
+            .or_else(|| config.api_key.as_deref().and_then(trimmed_non_empty));
+
+        Self {
+            http: reqwest::Client::new(),
+            api_url: format!("{}/chat/completions", base_url.trim_end_matches('/')),
+            api_key,
+            model: config.model.as_deref().and_then(trimmed_non_empty),
+            max_tokens: DEFAULT_MAX_TOKENS,
+        }
+    }
+}
+
+impl AiCommitClient for OpenAiCommitClient {
+    fn generate(
+        &self,
+        system: &str,
+        user_prompt: &str,
+    ) -> Pin<Box<dyn Future<Output = Result<String, AiCommitError>> + Send>> {
+        let http = self.http.clone();
+        let api_url = self.api_url.clone();
+        let api_key = self.api_key.clone();
+        let model = self.model.clone();
+        let max_tokens = self.max_tokens;
+        let messages = vec![
+            ChatMessage { role: "system", content: system.to_string() },
+            ChatMessage { role: "user", content: user_prompt.to_string() },
+        ];
+
+        Box::pin(async move {
+            let model = model.ok_or_else(|| {
+                AiCommitError::ClientError(
+                    "the openai backend needs [ai].model or [git.ai].model".to_string(),
+                )
+            })?;
+
+            let mut request =
+                http.post(&api_url).json(&ChatCompletionRequest { model, max_tokens, messages });
+            if let Some(api_key) = api_key {
+                request = request.bearer_auth(api_key);
+            }
+            let response = request.send().await.map_err(|error| {
+                AiCommitError::ClientError(format!("failed to call {api_url}: {error}"))
+            })?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                let body: String = body.trim().chars().take(240).collect();
+                return Err(AiCommitError::ClientError(format!(
+                    "chat completions API returned status {status}: {body}"
+                )));
+            }
+
+            let payload: ChatCompletionResponse = response.json().await.map_err(|error| {
+                AiCommitError::ClientError(format!(
+                    "failed to decode chat completions response: {error}"
+                ))
+            })?;
+            payload
+                .choices
+                .into_iter()
+                .find_map(|choice| choice.message.content.as_deref().and_then(trimmed_non_empty))
+                .ok_or(AiCommitError::EmptyResponse)
+        })
+    }
+
+    fn is_configured(&self) -> bool {
+        self.model.is_some()
+    }
+}
+
+/// Runs `[ai].command` with the prompt on stdin and the system prompt in
+/// `SCRIPTUM_COMMIT_SYSTEM_PROMPT`; its stdout is the message.
+#[derive(Debug, Clone)]
+pub struct CommandCommitClient {
+    argv: Vec<String>,
+    timeout: Duration,
+}
+
+impl CommandCommitClient {
+    pub fn new(argv: Vec<String>) -> Self {
+        Self { argv, timeout: COMMAND_TIMEOUT }
+    }
+
+    pub fn from_ai_config(config: &AiConfig) -> Self {
+        Self::new(config.command.clone())
+    }
+}
+
+impl AiCommitClient for CommandCommitClient {
+    fn generate(
+        &self,
+        system: &str,
+        user_prompt: &str,
+    ) -> Pin<Box<dyn Future<Output = Result<String, AiCommitError>> + Send>> {
+        let argv = self.argv.clone();
+        let timeout = self.timeout;
+        let system = system.to_string();
+        let user_prompt = user_prompt.to_string();
+
+        Box::pin(async move {
+            let (program, args) = argv.split_first().ok_or_else(|| {
+                AiCommitError::ClientError("the command backend needs [ai].command".to_string())
+            })?;
+            let mut child = tokio::process::Command::new(program)
+                .args(args)
+                .env(COMMAND_SYSTEM_PROMPT_ENV, &system)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .kill_on_drop(true)
+                .spawn()
+                .map_err(|error| {
+                    AiCommitError::ClientError(format!("failed to run `{program}`: {error}"))
+                })?;
+
+            // Write from a task so a large prompt can't deadlock against
+            // a child that fills its stdout first.
+            let mut stdin = child.stdin.take().expect("stdin is piped");
+            let writer = tokio::spawn(async move {
+                let _ = stdin.write_all(user_prompt.as_bytes()).await;
+            });
+            let output = tokio::time::timeout(timeout, child.wait_with_output())
+                .await
+                .map_err(|_| {
+                    AiCommitError::ClientError(format!(
+                        "`{program}` did not finish within {}s",
+                        timeout.as_secs()
+                    ))
+                })?
+                .map_err(|error| {
+                    AiCommitError::ClientError(format!("failed to run `{program}`: {error}"))
+                })?;
+            let _ = writer.await;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(AiCommitError::ClientError(format!(
+                    "`{program}` exited with {}: {}",
+                    output.status,
+                    stderr.trim()
+                )));
+            }
+            trimmed_non_empty(&String::from_utf8_lossy(&output.stdout))
+                .ok_or(AiCommitError::EmptyResponse)
+        })
+    }
+
+    fn is_configured(&self) -> bool {
+        !self.argv.is_empty()
+    }
+}
+
+fn trimmed_non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use axum::{
+        http::{HeaderMap, StatusCode},
+        routing::post,
+        Json, Router,
+    };
+    use serde_json::{json, Value};
+    use tokio::sync::oneshot;
+
+    use super::*;
+    use crate::git::commit::{
+        build_prompt, generate_commit_message_with_fallback, RedactionPolicy, SYSTEM_PROMPT,
+    };
+    use crate::git::triggers::{ChangeType, ChangedFile};
+
+    /// Serve `reply` from `/v1/chat/completions`, handing back the first
+    /// request's auth header and body.
+    async fn stub_server(
+        status: StatusCode,
+        reply: Value,
+    ) -> (String, oneshot::Receiver<(Option<String>, Value)>, tokio::task::JoinHandle<()>) {
+        let (tx, rx) = oneshot::channel();
+        let sender = Arc::new(Mutex::new(Some(tx)));
+        let app = Router::new().route(
+            "/v1/chat/completions",
+            post(move |headers: HeaderMap, Json(body): Json<Value>| {
+                let sender = Arc::clone(&sender);
+                let reply = reply.clone();
+                async move {
+                    let auth = headers
+                        .get("authorization")
+                        .and_then(|value| value.to_str().ok())
+                        .map(str::to_string);
+                    if let Some(tx) = sender.lock().unwrap().take() {
+                        let _ = tx.send((auth, body));
+                    }
+                    (status, Json(reply))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("stub server should run");
+        });
+        (format!("http://{address}/v1/"), rx, server)
+    }
+
+    fn openai_config(base_url: &str, api_key: Option<&str>) -> AiConfig {
+        AiConfig {
+            backend: AiBackend::OpenAi,
+            base_url: Some(base_url.to_string()),
+            api_key: api_key.map(str::to_string),
+            model: Some("llama3.1".to_string()),
+            ..AiConfig::default()
+        }
+    }
+
+    fn files() -> Vec<ChangedFile> {
+        vec![ChangedFile {
+            path: "docs/rfcs/001.md".into(),
+            doc_id: None,
+            change_type: ChangeType::Modified,
+        }]
+    }
+
+    #[tokio::test]
+    async fn openai_client_posts_chat_completion_to_local_server() {
+        let (base_url, captured, server) = stub_server(
+            StatusCode::OK,
+            json!({ "choices": [{ "message": { "role": "assistant", "content": " docs: RFC 1 \n" } }] }),
+        )
+        .await;
+        let mut client = OpenAiCommitClient::from_ai_config(&openai_config(&base_url, None));
+        client.api_key = Some("local-key".to_string());
+        assert!(client.is_configured());
+
+        let prompt = build_prompt("+new line", &files(), RedactionPolicy::Full);
+        let message = client.generate(SYSTEM_PROMPT, &prompt).await.expect("generate");
+        assert_eq!(message, "docs: RFC 1");
+
+        let (auth, body) = captured.await.unwrap();
+        assert_eq!(auth.as_deref(), Some("Bearer local-key"));
+        assert_eq!(body["model"], "llama3.1");
+        assert_eq!(body["max_tokens"], DEFAULT_MAX_TOKENS);
+        assert_eq!(body["messages"][0], json!({ "role": "system", "content": SYSTEM_PROMPT }));
+        assert_eq!(body["messages"][1]["role"], "user");
+        assert!(body["messages"][1]["content"].as_str().unwrap().contains("docs/rfcs/001.md"));
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn openai_client_errors_fall_back_to_path_message() {
+        let (base_url, _captured, server) =
+            stub_server(StatusCode::SERVICE_UNAVAILABLE, json!({ "error": "model loading" })).await;
+        let client = OpenAiCommitClient::from_ai_config(&openai_config(&base_url, None));
+
+        let error = client.generate(SYSTEM_PROMPT, "prompt").await.unwrap_err();
+        assert!(
+            matches!(&error, AiCommitError::ClientError(message) if message.contains("503")),
+            "{error}"
+        );
+        let message =
+            generate_commit_message_with_fallback(&client, "+x", &files(), RedactionPolicy::Full)
+                .await;
+        assert_eq!(message, "Update 1 file(s): docs/rfcs/001.md");
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn openai_client_needs_a_model() {
+        let config = AiConfig { model: None, ..openai_config("http://127.0.0.1:1/v1", None) };
+        let client = OpenAiCommitClient::from_ai_config(&config);
+        assert!(!client.is_configured());
+        assert!(client.generate(SYSTEM_PROMPT, "prompt").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn command_client_pipes_prompt_and_reads_stdout() {
+        let client = CommandCommitClient::new(vec![
+            "sh".into(),
+            "-c".into(),
+            format!(
+                "grep -q 'docs/rfcs/001.md' && test -n \"${COMMAND_SYSTEM_PROMPT_ENV}\" \
+                 && echo 'docs: update RFC 1'"
+            ),
+        ]);
+        let prompt = build_prompt("+new line", &files(), RedactionPolicy::Redacted);
+
+        let message = client.generate(SYSTEM_PROMPT, &prompt).await.expect("generate");
+        assert_eq!(message, "docs: update RFC 1");
+    }
+
+    #[tokio::test]
+    async fn command_client_reports_failures() {
+        let failing = CommandCommitClient::new(vec![
+            "sh".into(),
+            "-c".into(),
+            "cat >/dev/null; echo 'model missing' >&2; exit 3".into(),
+        ]);
+        let error = failing.generate(SYSTEM_PROMPT, "prompt").await.unwrap_err();
+        assert!(error.to_string().contains("model missing"), "{error}");
+
+        let silent = CommandCommitClient::new(vec!["true".into()]);
+        assert_eq!(
+            silent.generate(SYSTEM_PROMPT, "prompt").await.unwrap_err(),
+            AiCommitError::EmptyResponse
+        );
+
+        let mut slow = CommandCommitClient::new(vec!["sleep".into(), "5".into()]);
+        slow.timeout = Duration::from_millis(50);
+        assert!(slow.generate(SYSTEM_PROMPT, "").await.unwrap_err().to_string().contains("within"));
+
+        assert!(!CommandCommitClient::new(Vec::new()).is_configured());
+    }
+
+    #[test]
+    fn factory_picks_the_configured_backend() {
+        let command = AiConfig {
+            backend: AiBackend::Command,
+            command: vec!["commit-msg".into()],
+            ..AiConfig::default()
+        };
+        assert!(commit_client_from_config(&command).is_configured());
+        let openai = AiConfig { model: None, ..openai_config("http://localhost:8000/v1", None) };
+        assert!(!commit_client_from_config(&openai).is_configured());
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Attribution captured for a single change event.
 ///
@@ -76,6 +76,55 @@ pub fn with_coauthor_trailers(base_message: &str, updates: &[UpdateAttribution])
     append_coauthor_trailers(base_message, &coauthors)
 }
 
+/// Lines each author changed in one document since its last commit.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocAuthorship {
+    /// Normalized email to the author and their changed-line count.
+    weights: BTreeMap<String, (CoAuthor, usize)>,
+}
+
+impl DocAuthorship {
+    /// Credit `lines` changed lines to the update's agent, else its user.
+    pub fn record(&mut self, update: &UpdateAttribution, lines: usize) {
+        let Some(author) = coauthor_for_agent(update).or_else(|| coauthor_for_user(update)) else {
+            return;
+        };
+        self.weights.entry(author.email.clone()).or_insert((author, 0)).1 += lines;
+    }
+
+    /// Whoever changed the most lines; ties go to the lowest email.
+    pub fn primary_author(&self) -> Option<&CoAuthor> {
+        self.weights
+            .values()
+            .fold(None, |best: Option<&(CoAuthor, usize)>, entry| match best {
+                Some(best) if best.1 >= entry.1 => Some(best),
+                _ => Some(entry),
+            })
+            .map(|(author, _)| author)
+    }
+
+    /// Total lines changed in the document.
+    pub fn changed_lines(&self) -> usize {
+        self.weights.values().map(|(_, lines)| lines).sum()
+    }
+}
+
+/// Lines added plus lines removed between two versions, ignoring moves.
+pub fn changed_line_count(before: &str, after: &str) -> usize {
+    let mut remaining: HashMap<&str, usize> = HashMap::new();
+    for line in before.lines() {
+        *remaining.entry(line).or_default() += 1;
+    }
+    let mut added = 0;
+    for line in after.lines() {
+        match remaining.get_mut(line) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => added += 1,
+        }
+    }
+    added + remaining.values().sum::<usize>()
+}
+
 fn coauthor_for_user(update: &UpdateAttribution) -> Option<CoAuthor> {
     let user_id = normalize(update.user_id.as_deref())?;
     let name = normalize(update.user_name.as_deref()).unwrap_or(user_id).to_string();
@@ -130,6 +179,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn primary_author_changed_the_most_lines() {
+        let mut authorship = DocAuthorship::default();
+        authorship.record(&UpdateAttribution::for_user("user-1", "Gary", None), 3);
+        authorship.record(&UpdateAttribution::for_agent("claude-1"), 2);
+        authorship.record(&UpdateAttribution::for_agent("claude-1"), 4);
+        authorship.record(&UpdateAttribution::default(), 100);
+
+        assert_eq!(authorship.changed_lines(), 9);
+        assert_eq!(
+            authorship.primary_author(),
+            Some(&CoAuthor { name: "claude-1".into(), email: "agent:claude-1@scriptum".into() })
+        );
+        assert_eq!(DocAuthorship::default().primary_author(), None);
+    }
+
+    #[test]
+    fn changed_line_count_counts_additions_and_removals() {
+        assert_eq!(changed_line_count("a\nb\nc\n", "a\nb\nc\n"), 0);
+        assert_eq!(changed_line_count("a\nb\n", "a\nB\nc\n"), 3);
+        assert_eq!(changed_line_count("", "one\ntwo\n"), 2);
+        assert_eq!(changed_line_count("x\nx\n", "x\n"), 1);
+    }
+
     #[test]
     fn appends_coauthor_trailers_in_order() {
         let message = append_coauthor_trailers(
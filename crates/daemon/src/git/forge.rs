@@ -0,0 +1,474 @@
+// Forge clients for the `pull_request` git strategy.
+//
+// Opens a pull request from a session branch, or updates the open one, so
+// each agent session becomes one reviewable PR. GitHub, GitLab and Gitea
+// have close enough REST APIs that `RestForgeClient` speaks all three.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+
+use reqwest::Method;
+use serde_json::{json, Value};
+
+use crate::config::{ForgeConfig, ForgeKind};
+use crate::security::{self, SecretSlot};
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
+const USER_AGENT: &str = "scriptum-daemon";
+
+/// The pull request to open, or to update when one is already open from
+/// `head` into `base`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequestSpec {
+    pub head: String,
+    pub base: String,
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PullRequest {
+    /// PR number on GitHub and Gitea, merge request iid on GitLab.
+    pub number: u64,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForgeError {
+    NotConfigured(String),
+    Request(String),
+    Api { status: u16, body: String },
+    InvalidResponse(String),
+}
+
+impl Display for ForgeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ForgeError::NotConfigured(message) => write!(f, "forge not configured: {message}"),
+            ForgeError::Request(message) => write!(f, "forge request failed: {message}"),
+            ForgeError::Api { status, body } => {
+                write!(f, "forge API returned status {status}: {}", body.trim())
+            }
+            ForgeError::InvalidResponse(message) => {
+                write!(f, "unexpected forge response: {message}")
+            }
+        }
+    }
+}
+
+impl Error for ForgeError {}
+
+/// Opens or updates pull requests. Tests inject a fake.
+pub trait ForgeClient: Send + Sync {
+    fn open_or_update(
+        &self,
+        spec: PullRequestSpec,
+    ) -> Pin<Box<dyn Future<Output = Result<PullRequest, ForgeError>> + Send>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct RestForgeClient {
+    http: reqwest::Client,
+    kind: ForgeKind,
+    api_url: String,
+    repo: String,
+    token: Option<String>,
+}
+
+impl RestForgeClient {
+    pub fn new(
+        kind: ForgeKind,
+        api_url: impl Into<String>,
+        repo: impl Into<String>,
+        token: Option<String>,
+    ) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            kind,
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            repo: repo.into(),
+            token,
+        }
+    }
+
+    /// Build from `[git.forge]`. The token comes from `token_env` (default
+    /// `GITHUB_TOKEN`, `GITLAB_TOKEN` or `GITEA_TOKEN`), else from the
+    /// stored git credentials.
+    pub fn from_config(config: &ForgeConfig) -> Result<Self, ForgeError> {
+        let api_url = match (config.kind, config.api_url.as_deref()) {
+            (_, Some(api_url)) => api_url.to_string(),
+            (ForgeKind::Github, None) => GITHUB_API_URL.to_string(),
+            (ForgeKind::Gitlab, None) => GITLAB_API_URL.to_string(),
+            (ForgeKind::Gitea, None) => {
+                return Err(ForgeError::NotConfigured("gitea requires `api_url`".to_string()))
+            }
+        };
+        let token_env = config.token_env.as_deref().unwrap_or(match config.kind {
+            ForgeKind::Github => "GITHUB_TOKEN",
+            ForgeKind::Gitlab => "GITLAB_TOKEN",
+            ForgeKind::Gitea => "GITEA_TOKEN",
+        });
+        let token = std::env::var(token_env)
+            .ok()
+            .filter(|token| !token.trim().is_empty())
+            .or_else(|| security::get_secret(SecretSlot::GitCredentials).ok().flatten());
+        Ok(Self::new(config.kind, api_url, config.repo.clone(), token))
+    }
+
+    async fn open_or_update_pr(&self, spec: &PullRequestSpec) -> Result<PullRequest, ForgeError> {
+        let token = self.token.as_deref().ok_or_else(|| {
+            ForgeError::NotConfigured("no API token (set token_env or git credentials)".into())
+        })?;
+
+        let (method, url, body) = match self.find_open(token, spec).await? {
+            Some(number) => {
+                let method = match self.kind {
+                    ForgeKind::Gitlab => Method::PUT,
+                    ForgeKind::Github | ForgeKind::Gitea => Method::PATCH,
+                };
+                let body = match self.kind {
+                    ForgeKind::Gitlab => json!({ "title": spec.title, "description": spec.body }),
+                    ForgeKind::Github | ForgeKind::Gitea => {
+                        json!({ "title": spec.title, "body": spec.body })
+                    }
+                };
+                (method, format!("{}/{number}", self.pulls_url()), body)
+            }
+            None => {
+                let body = match self.kind {
+                    ForgeKind::Gitlab => json!({
+                        "source_branch": spec.head,
+                        "target_branch": spec.base,
+                        "title": spec.title,
+                        "description": spec.body,
+                    }),
+                    ForgeKind::Github | ForgeKind::Gitea => json!({
+                        "head": spec.head,
+                        "base": spec.base,
+                        "title": spec.title,
+                        "body": spec.body,
+                    }),
+                };
+                (Method::POST, self.pulls_url(), body)
+            }
+        };
+
+        let response = self.send(self.request(method, &url, token).json(&body)).await?;
+        self.parse_pull_request(&response)
+    }
+
+    /// Number of the open pull request from `spec.head` into `spec.base`.
+    async fn find_open(
+        &self,
+        token: &str,
+        spec: &PullRequestSpec,
+    ) -> Result<Option<u64>, ForgeError> {
+        let query: Vec<(&str, String)> = match self.kind {
+            ForgeKind::Github => {
+                let owner = self.repo.split('/').next().unwrap_or_default();
+                vec![
+                    ("state", "open".to_string()),
+                    ("head", format!("{owner}:{}", spec.head)),
+                    ("base", spec.base.clone()),
+                ]
+            }
+            ForgeKind::Gitlab => vec![
+                ("state", "opened".to_string()),
+                ("source_branch", spec.head.clone()),
+                ("target_branch", spec.base.clone()),
+            ],
+            // Gitea can't filter the list by branch; match below.
+            ForgeKind::Gitea => vec![("state", "open".to_string())],
+        };
+        let listed =
+            self.send(self.request(Method::GET, &self.pulls_url(), token).query(&query)).await?;
+        let items = listed.as_array().ok_or_else(|| {
+            ForgeError::InvalidResponse("expected a list of pull requests".into())
+        })?;
+
+        let open = items.iter().find(|item| match self.kind {
+            ForgeKind::Gitea => {
+                item["head"]["ref"].as_str() == Some(spec.head.as_str())
+                    && item["base"]["ref"].as_str() == Some(spec.base.as_str())
+            }
+            ForgeKind::Github | ForgeKind::Gitlab => true,
+        });
+        open.map(|item| self.parse_pull_request(item).map(|pr| pr.number)).transpose()
+    }
+
+    fn pulls_url(&self) -> String {
+        match self.kind {
+            ForgeKind::Gitlab => {
+                let project: String =
+                    url::form_urlencoded::byte_serialize(self.repo.as_bytes()).collect();
+                format!("{}/projects/{project}/merge_requests", self.api_url)
+            }
+            ForgeKind::Github | ForgeKind::Gitea => {
+                format!("{}/repos/{}/pulls", self.api_url, self.repo)
+            }
+        }
+    }
+
+    fn request(&self, method: Method, url: &str, token: &str) -> reqwest::RequestBuilder {
+        let request =
+            self.http.request(method, url).header(reqwest::header::USER_AGENT, USER_AGENT);
+        match self.kind {
+            ForgeKind::Github => request
+                .bearer_auth(token)
+                .header(reqwest::header::ACCEPT, "application/vnd.github+json"),
+            ForgeKind::Gitlab => request.header("PRIVATE-TOKEN", token),
+            ForgeKind::Gitea => {
+                request.header(reqwest::header::AUTHORIZATION, format!("token {token}"))
+            }
+        }
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value, ForgeError> {
+        let response =
+            request.send().await.map_err(|error| ForgeError::Request(error.to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ForgeError::Api { status: status.as_u16(), body });
+        }
+        response.json().await.map_err(|error| ForgeError::InvalidResponse(error.to_string()))
+    }
+
+    fn parse_pull_request(&self, value: &Value) -> Result<PullRequest, ForgeError> {
+        let (number_key, url_key) = match self.kind {
+            ForgeKind::Gitlab => ("iid", "web_url"),
+            ForgeKind::Github | ForgeKind::Gitea => ("number", "html_url"),
+        };
+        let number = value[number_key]
+            .as_u64()
+            .ok_or_else(|| ForgeError::InvalidResponse(format!("missing `{number_key}`")))?;
+        let url = value[url_key]
+            .as_str()
+            .ok_or_else(|| ForgeError::InvalidResponse(format!("missing `{url_key}`")))?;
+        Ok(PullRequest { number, url: url.to_string() })
+    }
+}
+
+impl ForgeClient for RestForgeClient {
+    fn open_or_update(
+        &self,
+        spec: PullRequestSpec,
+    ) -> Pin<Box<dyn Future<Output = Result<PullRequest, ForgeError>> + Send>> {
+        let client = self.clone();
+        Box::pin(async move { client.open_or_update_pr(&spec).await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::extract::{Path, Query, State};
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::{get, patch};
+    use axum::{Json, Router};
+
+    use super::*;
+
+    /// In-memory forge serving the GitHub/Gitea and GitLab pull request
+    /// endpoints the client uses.
+    #[derive(Clone, Default)]
+    struct FakeForge {
+        pulls: Arc<Mutex<Vec<Value>>>,
+        auth: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl FakeForge {
+        fn record_auth(&self, headers: &HeaderMap) {
+            let auth = ["authorization", "private-token"]
+                .iter()
+                .find_map(|name| headers.get(*name).and_then(|value| value.to_str().ok()))
+                .unwrap_or_default();
+            self.auth.lock().unwrap().push(auth.to_string());
+        }
+
+        fn create(&self, head: &str, base: &str, title: &str, body: &str) -> Value {
+            let mut pulls = self.pulls.lock().unwrap();
+            let number = pulls.len() as u64 + 1;
+            let pull = json!({
+                "number": number,
+                "iid": number,
+                "html_url": format!("https://forge.test/pulls/{number}"),
+                "web_url": format!("https://forge.test/merge_requests/{number}"),
+                "head": { "ref": head },
+                "base": { "ref": base },
+                "source_branch": head,
+                "target_branch": base,
+                "title": title,
+                "body": body,
+            });
+            pulls.push(pull.clone());
+            pull
+        }
+
+        fn update(&self, number: u64, title: &Value, body: &Value) -> Option<Value> {
+            let mut pulls = self.pulls.lock().unwrap();
+            let pull = pulls.iter_mut().find(|pull| pull["number"] == number)?;
+            pull["title"] = title.clone();
+            pull["body"] = body.clone();
+            Some(pull.clone())
+        }
+    }
+
+    async fn list_pulls(
+        State(forge): State<FakeForge>,
+        headers: HeaderMap,
+        Query(query): Query<std::collections::HashMap<String, String>>,
+    ) -> Json<Value> {
+        forge.record_auth(&headers);
+        let pulls = forge.pulls.lock().unwrap();
+        let head = query
+            .get("head")
+            .map(|head| head.split_once(':').map_or(head.as_str(), |(_, branch)| branch))
+            .or(query.get("source_branch").map(String::as_str));
+        let listed = pulls
+            .iter()
+            .filter(|pull| head.is_none_or(|head| pull["head"]["ref"] == head))
+            .cloned()
+            .collect::<Vec<_>>();
+        Json(json!(listed))
+    }
+
+    async fn create_pull(
+        State(forge): State<FakeForge>,
+        headers: HeaderMap,
+        Json(request): Json<Value>,
+    ) -> (StatusCode, Json<Value>) {
+        forge.record_auth(&headers);
+        let field = |github: &str, gitlab: &str| {
+            request[github].as_str().or(request[gitlab].as_str()).unwrap_or_default().to_string()
+        };
+        let pull = forge.create(
+            &field("head", "source_branch"),
+            &field("base", "target_branch"),
+            &field("title", "title"),
+            &field("body", "description"),
+        );
+        (StatusCode::CREATED, Json(pull))
+    }
+
+    async fn update_pull(
+        State(forge): State<FakeForge>,
+        headers: HeaderMap,
+        Path((_, _, number)): Path<(String, String, u64)>,
+        Json(request): Json<Value>,
+    ) -> Result<Json<Value>, StatusCode> {
+        forge.record_auth(&headers);
+        forge
+            .update(number, &request["title"], &request["body"])
+            .map(Json)
+            .ok_or(StatusCode::NOT_FOUND)
+    }
+
+    async fn update_merge_request(
+        State(forge): State<FakeForge>,
+        headers: HeaderMap,
+        Path((_, iid)): Path<(String, u64)>,
+        Json(request): Json<Value>,
+    ) -> Result<Json<Value>, StatusCode> {
+        forge.record_auth(&headers);
+        forge
+            .update(iid, &request["title"], &request["description"])
+            .map(Json)
+            .ok_or(StatusCode::NOT_FOUND)
+    }
+
+    async fn serve_fake_forge() -> (String, FakeForge) {
+        let forge = FakeForge::default();
+        let app = Router::new()
+            .route("/repos/{owner}/{name}/pulls", get(list_pulls).post(create_pull))
+            .route("/repos/{owner}/{name}/pulls/{number}", patch(update_pull))
+            .route("/projects/{project}/merge_requests", get(list_pulls).post(create_pull))
+            .route(
+                "/projects/{project}/merge_requests/{iid}",
+                axum::routing::put(update_merge_request),
+            )
+            .with_state(forge.clone());
+        let listener =
+            tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("listener should bind");
+        let address = listener.local_addr().expect("listener should expose address");
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.expect("fake forge should run");
+        });
+        (format!("http://{address}"), forge)
+    }
+
+    fn spec(title: &str) -> PullRequestSpec {
+        PullRequestSpec {
+            head: "scriptum/claude/2026-01-01".to_string(),
+            base: "main".to_string(),
+            title: title.to_string(),
+            body: "- docs: update intro".to_string(),
+        }
+    }
+
+    async fn opens_then_updates(kind: ForgeKind, expected_auth: &str, expected_url: &str) {
+        let (api_url, forge) = serve_fake_forge().await;
+        let client =
+            RestForgeClient::new(kind, api_url, "acme/handbook", Some("tok-123".to_string()));
+
+        let opened = client.open_or_update(spec("Scriptum: claude")).await.expect("PR should open");
+        assert_eq!(opened, PullRequest { number: 1, url: expected_url.to_string() });
+
+        let updated =
+            client.open_or_update(spec("Scriptum: claude (2)")).await.expect("PR should update");
+        assert_eq!(updated.number, 1);
+        let pulls = forge.pulls.lock().unwrap();
+        assert_eq!(pulls.len(), 1, "the open PR is updated, not duplicated");
+        assert_eq!(pulls[0]["title"], "Scriptum: claude (2)");
+        assert_eq!(pulls[0]["base"]["ref"], "main");
+        assert!(forge.auth.lock().unwrap().iter().all(|auth| auth == expected_auth));
+    }
+
+    #[tokio::test]
+    async fn github_client_opens_then_updates_pull_request() {
+        opens_then_updates(ForgeKind::Github, "Bearer tok-123", "https://forge.test/pulls/1").await;
+    }
+
+    #[tokio::test]
+    async fn gitlab_client_opens_then_updates_merge_request() {
+        opens_then_updates(ForgeKind::Gitlab, "tok-123", "https://forge.test/merge_requests/1")
+            .await;
+    }
+
+    #[tokio::test]
+    async fn gitea_client_matches_open_pull_request_by_branches() {
+        opens_then_updates(ForgeKind::Gitea, "token tok-123", "https://forge.test/pulls/1").await;
+    }
+
+    #[tokio::test]
+    async fn client_without_token_or_api_url_is_not_configured() {
+        let client = RestForgeClient::new(ForgeKind::Github, "http://127.0.0.1:9", "a/b", None);
+        let error = client.open_or_update(spec("x")).await.expect_err("token is required");
+        assert!(matches!(error, ForgeError::NotConfigured(_)));
+
+        let config = ForgeConfig {
+            kind: ForgeKind::Gitea,
+            repo: "a/b".to_string(),
+            api_url: None,
+            token_env: None,
+        };
+        assert!(matches!(RestForgeClient::from_config(&config), Err(ForgeError::NotConfigured(_))));
+    }
+
+    #[tokio::test]
+    async fn api_errors_carry_status_and_body() {
+        let (api_url, _forge) = serve_fake_forge().await;
+        let client = RestForgeClient::new(
+            ForgeKind::Github,
+            format!("{api_url}/missing"),
+            "acme/handbook",
+            Some("tok".to_string()),
+        );
+        let error = client.open_or_update(spec("x")).await.expect_err("unknown route fails");
+        assert!(matches!(error, ForgeError::Api { status: 404, .. }));
+    }
+}
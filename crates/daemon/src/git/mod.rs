@@ -1,7 +1,13 @@
-// Git sync: worker, leader election, AI commit messages, attribution.
+// Git sync: CLI and native workers, leader election, AI commit messages,
+// attribution, forges, selective sync, notes.
 
+pub mod ai_backends;
 pub mod attribution;
 pub mod commit;
+pub mod forge;
 pub mod leader;
+pub mod native;
+pub mod notes;
+pub mod selective;
 pub mod triggers;
 pub mod worker;
@@ -0,0 +1,878 @@
+// In-process git backend on libgit2, for machines without the `git` CLI.
+//
+// `NativeGitWorker` mirrors `GitWorker` operation for operation, so git sync
+// behaves the same whichever `[git] backend` is configured. Signing still
+// runs `ssh-keygen` or `gpg`, as git itself does.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use git2::build::CheckoutBuilder;
+use git2::{
+    Commit, Cred, CredentialType, Delta, Diff, DiffFormat, ErrorCode, FetchOptions, IndexAddOption,
+    IndexEntry, IndexTime, Oid, PushOptions, RemoteCallbacks, Repository, Signature, Sort, Status,
+    StatusOptions, Tree,
+};
+
+use crate::config::SigningFormat;
+use crate::git::notes::NOTES_REF;
+use crate::git::worker::{
+    BlamedLine, CommitAuthor, CommitSigning, GitCommandOutput, GitRepository, GitWorkerError,
+    PathCommit, UpstreamChanges, UpstreamFile,
+};
+
+#[derive(Debug, Clone)]
+pub struct NativeGitWorker {
+    repo_path: PathBuf,
+    committer: Option<CommitAuthor>,
+    signing: Option<CommitSigning>,
+}
+
+impl NativeGitWorker {
+    pub fn new(repo_path: impl Into<PathBuf>) -> Self {
+        Self { repo_path: repo_path.into(), committer: None, signing: None }
+    }
+
+    /// Run `f` on a freshly opened repository; libgit2 handles are not
+    /// `Sync`, so none is kept between calls.
+    fn with_repo<T>(
+        &self,
+        operation: &str,
+        f: impl FnOnce(&Repository) -> Result<T, git2::Error>,
+    ) -> Result<T, GitWorkerError> {
+        Repository::open(&self.repo_path).and_then(|repo| f(&repo)).map_err(|error| {
+            GitWorkerError::NativeFailed {
+                operation: operation.to_string(),
+                message: error.message().to_string(),
+            }
+        })
+    }
+
+    fn stage(&self, operation: &str, paths: &[String]) -> Result<GitCommandOutput, GitWorkerError> {
+        if paths.is_empty() {
+            return Err(GitWorkerError::EmptyAddPaths);
+        }
+
+        self.with_repo(operation, |repo| {
+            // libgit2 pathspecs have no `.`; it means the whole tree.
+            let specs: Vec<&str> =
+                paths.iter().map(|path| if path == "." { "*" } else { path.as_str() }).collect();
+            let mut index = repo.index()?;
+            index.add_all(specs.iter(), IndexAddOption::DEFAULT, None)?;
+            index.update_all(specs.iter(), None)?;
+            index.write()?;
+            Ok(output(String::new()))
+        })
+    }
+
+    fn committer_signature(&self, repo: &Repository) -> Result<Signature<'static>, git2::Error> {
+        match &self.committer {
+            Some(committer) => Signature::now(&committer.name, &committer.email),
+            None => repo.signature(),
+        }
+    }
+
+    /// Write a commit of `tree` without moving any ref, signed when
+    /// signing is configured.
+    fn create_commit(
+        &self,
+        repo: &Repository,
+        message: &str,
+        author: Option<&CommitAuthor>,
+        tree: &Tree<'_>,
+        parents: &[&Commit<'_>],
+    ) -> Result<Oid, git2::Error> {
+        let committer = self.committer_signature(repo)?;
+        let author = match author {
+            Some(author) => Signature::now(&author.name, &author.email)?,
+            None => repo.signature().or_else(|error| match &self.committer {
+                Some(_) => Ok(committer.clone()),
+                None => Err(error),
+            })?,
+        };
+        let message = git2::message_prettify(message, None)?;
+        let Some(signing) = &self.signing else {
+            return repo.commit(None, &author, &committer, &message, tree, parents);
+        };
+
+        let buffer = repo.commit_create_buffer(&author, &committer, &message, tree, parents)?;
+        let payload = buffer
+            .as_str()
+            .ok_or_else(|| git2::Error::from_str("commit buffer is not valid UTF-8"))?;
+        let signature = sign_payload(repo, signing, payload)?;
+        repo.commit_signed(payload, &signature, None)
+    }
+
+    /// The branch tip after committing, and whether a commit was made.
+    fn commit_tree_to_branch(
+        &self,
+        branch: &str,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<(String, bool), GitWorkerError> {
+        self.with_repo("commit-tree", |repo| {
+            let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+            let branch_ref = format!("refs/heads/{branch}");
+            let existing = match repo.find_reference(&branch_ref) {
+                Ok(reference) => Some(reference.peel_to_commit()?),
+                Err(error) if error.code() == ErrorCode::NotFound => None,
+                Err(error) => return Err(error),
+            };
+            let parent = match &existing {
+                Some(tip) => tip.clone(),
+                None => repo.head()?.peel_to_commit()?,
+            };
+            if existing.is_some() && parent.tree_id() == tree.id() {
+                return Ok((parent.id().to_string(), false));
+            }
+
+            let commit = self.create_commit(repo, message, author, &tree, &[&parent])?;
+            let reflog = format!("commit: {}", subject(message));
+            // Matching the old tip guards against a concurrent update; a new
+            // branch must not exist yet.
+            match &existing {
+                Some(tip) => {
+                    repo.reference_matching(&branch_ref, commit, true, tip.id(), &reflog)?
+                }
+                None => repo.reference(&branch_ref, commit, false, &reflog)?,
+            };
+            Ok((commit.to_string(), true))
+        })
+    }
+
+    /// Push `src` to `dst` on `remote`, a remote name or URL. libgit2
+    /// refuses non-fast-forward updates itself.
+    fn push_refspec(
+        &self,
+        operation: &str,
+        remote: &str,
+        src: &str,
+        dst: &str,
+    ) -> Result<GitCommandOutput, GitWorkerError> {
+        self.with_repo(operation, |repo| {
+            let mut remote = match repo.find_remote(remote) {
+                Ok(remote) => remote,
+                Err(error) if error.code() == ErrorCode::NotFound => {
+                    repo.remote_anonymous(remote)?
+                }
+                Err(error) => return Err(error),
+            };
+            let mut rejected = Vec::new();
+            let mut callbacks = remote_callbacks(repo)?;
+            callbacks.push_update_reference(|refname, status| {
+                if let Some(status) = status {
+                    rejected.push(format!("! [remote rejected] {refname} ({status})"));
+                }
+                Ok(())
+            });
+            let mut options = PushOptions::new();
+            options.remote_callbacks(callbacks);
+            remote.push(&[format!("{src}:{dst}")], Some(&mut options))?;
+            drop(options);
+
+            if !rejected.is_empty() {
+                return Err(git2::Error::from_str(&rejected.join("\n")));
+            }
+            Ok(output(String::new()))
+        })
+    }
+}
+
+impl GitRepository for NativeGitWorker {
+    fn repo_path(&self) -> &Path {
+        &self.repo_path
+    }
+
+    fn set_commit_identity(
+        &mut self,
+        committer: Option<CommitAuthor>,
+        signing: Option<CommitSigning>,
+    ) {
+        self.committer = committer;
+        self.signing = signing;
+    }
+
+    fn status(&self) -> Result<GitCommandOutput, GitWorkerError> {
+        self.with_repo("status", |repo| {
+            let mut stdout = String::new();
+            for entry in repo.statuses(Some(&mut status_options()))?.iter() {
+                if let Some(path) = entry.path() {
+                    stdout.push_str(&format!("{} {path}\n", short_status(entry.status())));
+                }
+            }
+            Ok(output(stdout))
+        })
+    }
+
+    fn add(&self, paths: &[String]) -> Result<GitCommandOutput, GitWorkerError> {
+        self.stage("add", paths)
+    }
+
+    fn add_all(&self, paths: &[String]) -> Result<GitCommandOutput, GitWorkerError> {
+        self.stage("add -A", paths)
+    }
+
+    fn changed_paths(&self) -> Result<Vec<String>, GitWorkerError> {
+        // Without rename detection a rename lists as a deletion and an
+        // addition, so both paths still appear.
+        self.with_repo("status", |repo| {
+            Ok(repo
+                .statuses(Some(&mut status_options()))?
+                .iter()
+                .filter_map(|entry| entry.path().map(str::to_string))
+                .collect())
+        })
+    }
+
+    fn commit_as(
+        &self,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<GitCommandOutput, GitWorkerError> {
+        self.with_repo("commit", |repo| {
+            let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+            let parent = match repo.head() {
+                Ok(head) => Some(head.peel_to_commit()?),
+                Err(error) if error.code() == ErrorCode::UnbornBranch => None,
+                Err(error) => return Err(error),
+            };
+            if parent.as_ref().is_some_and(|parent| parent.tree_id() == tree.id()) {
+                return Err(git2::Error::from_str("nothing to commit, working tree clean"));
+            }
+
+            let parents: Vec<&Commit<'_>> = parent.iter().collect();
+            let commit = self.create_commit(repo, message, author, &tree, &parents)?;
+            let reflog = format!("commit: {}", subject(message));
+            let head_ref = repo.find_reference("HEAD")?.symbolic_target().map(str::to_string);
+            let branch = match &head_ref {
+                Some(name) => {
+                    match &parent {
+                        Some(parent) => {
+                            repo.reference_matching(name, commit, true, parent.id(), &reflog)?
+                        }
+                        None => repo.reference(name, commit, false, &reflog)?,
+                    };
+                    name.strip_prefix("refs/heads/").unwrap_or(name)
+                }
+                None => {
+                    repo.set_head_detached(commit)?;
+                    "detached HEAD"
+                }
+            };
+            Ok(output(format!("[{branch} {commit}] {}\n", subject(message))))
+        })
+    }
+
+    fn commit_to_branch_as(
+        &self,
+        branch: &str,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<String, GitWorkerError> {
+        Ok(self.commit_tree_to_branch(branch, message, author)?.0)
+    }
+
+    fn commit_new_to_branch(
+        &self,
+        branch: &str,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<Option<String>, GitWorkerError> {
+        let (tip, created) = self.commit_tree_to_branch(branch, message, author)?;
+        Ok(created.then_some(tip))
+    }
+
+    fn diff_cached(&self) -> Result<GitCommandOutput, GitWorkerError> {
+        self.with_repo("diff --cached", |repo| {
+            let mut stdout = String::new();
+            staged_diff(repo)?.print(DiffFormat::Patch, |_, _, line| {
+                if matches!(line.origin(), '+' | '-' | ' ') {
+                    stdout.push(line.origin());
+                }
+                stdout.push_str(&String::from_utf8_lossy(line.content()));
+                true
+            })?;
+            Ok(output(stdout))
+        })
+    }
+
+    fn diff_cached_name_status(&self) -> Result<GitCommandOutput, GitWorkerError> {
+        self.with_repo("diff --cached --name-status", |repo| {
+            let mut stdout = String::new();
+            for delta in staged_diff(repo)?.deltas() {
+                let code = match delta.status() {
+                    Delta::Added => 'A',
+                    Delta::Deleted => 'D',
+                    Delta::Renamed => 'R',
+                    Delta::Copied => 'C',
+                    Delta::Typechange => 'T',
+                    _ => 'M',
+                };
+                if let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) {
+                    stdout.push_str(&format!("{code}\t{}\n", path.display()));
+                }
+            }
+            Ok(output(stdout))
+        })
+    }
+
+    fn push(&self) -> Result<GitCommandOutput, GitWorkerError> {
+        let (remote, src, dst) = self.with_repo("push", |repo| {
+            let head = repo.head()?;
+            let branch = head.name().unwrap_or_default().to_string();
+            let upstream = repo
+                .branch_upstream_remote(&branch)
+                .and_then(|remote| Ok((remote, repo.branch_upstream_merge(&branch)?)));
+            let Ok((remote, merge)) = upstream else {
+                return Err(git2::Error::from_str(&format!(
+                    "the current branch {} has no upstream branch",
+                    head.shorthand().unwrap_or("HEAD")
+                )));
+            };
+            Ok((
+                remote.as_str().unwrap_or_default().to_string(),
+                branch,
+                merge.as_str().unwrap_or_default().to_string(),
+            ))
+        })?;
+        self.push_refspec("push", &remote, &src, &dst)
+    }
+
+    fn push_branch(&self, remote: &str, branch: &str) -> Result<GitCommandOutput, GitWorkerError> {
+        let branch_ref = format!("refs/heads/{branch}");
+        self.push_refspec("push", remote, &branch_ref, &branch_ref)
+    }
+
+    fn log_subjects(&self, range: &str) -> Result<Vec<String>, GitWorkerError> {
+        self.with_repo("log", |repo| {
+            let mut walk = repo.revwalk()?;
+            walk.set_sorting(Sort::TIME)?;
+            if range.contains("..") {
+                walk.push_range(range)?;
+            } else {
+                walk.push(repo.revparse_single(range)?.peel_to_commit()?.id())?;
+            }
+            walk.map(|oid| Ok(repo.find_commit(oid?)?.summary().unwrap_or_default().to_string()))
+                .collect()
+        })
+    }
+
+    fn add_note(&self, rev: &str, note: &str) -> Result<GitCommandOutput, GitWorkerError> {
+        self.with_repo("notes add", |repo| {
+            let target = repo.revparse_single(rev)?.id();
+            let signature = self.committer_signature(repo)?;
+            let note = git2::message_prettify(note, None)?;
+            repo.note(&signature, &signature, Some(NOTES_REF), target, &note, true)?;
+            Ok(output(String::new()))
+        })
+    }
+
+    fn note(&self, rev: &str) -> Result<Option<String>, GitWorkerError> {
+        self.with_repo("notes show", |repo| {
+            let target = repo.revparse_single(rev)?.id();
+            match repo.find_note(Some(NOTES_REF), target) {
+                Ok(note) => Ok(Some(note.message().unwrap_or_default().to_string())),
+                Err(error) if error.code() == ErrorCode::NotFound => Ok(None),
+                Err(error) => Err(error),
+            }
+        })
+    }
+
+    fn push_notes(&self, remote: &str) -> Result<GitCommandOutput, GitWorkerError> {
+        self.push_refspec("push", remote, NOTES_REF, NOTES_REF)
+    }
+
+    fn blame(&self, rev: &str, path: &str) -> Result<Vec<BlamedLine>, GitWorkerError> {
+        self.with_repo("blame", |repo| {
+            let commit = repo.revparse_single(rev)?.peel_to_commit()?;
+            let mut options = git2::BlameOptions::new();
+            options.newest_commit(commit.id());
+            let blame = repo.blame_file(Path::new(path), Some(&mut options))?;
+            let content = blob_text(repo, &commit.tree()?, path)?;
+
+            let mut lines = Vec::new();
+            for (index, content) in content.lines().enumerate() {
+                let Some(hunk) = blame.get_line(index + 1) else {
+                    continue;
+                };
+                let author = hunk.final_signature();
+                lines.push(BlamedLine {
+                    line_number: index as u32 + 1,
+                    commit: hunk.final_commit_id().to_string(),
+                    author: author.name().unwrap_or_default().to_string(),
+                    author_time: author.when().seconds(),
+                    content: content.to_string(),
+                });
+            }
+            Ok(lines)
+        })
+    }
+
+    fn commits_touching(&self, path: &str, since: i64) -> Result<Vec<PathCommit>, GitWorkerError> {
+        self.with_repo("log", |repo| {
+            let mut walk = repo.revwalk()?;
+            walk.set_sorting(Sort::TIME)?;
+            walk.push_head()?;
+            let mut commits = Vec::new();
+            for oid in walk {
+                let commit = repo.find_commit(oid?)?;
+                let commit_time = commit.time().seconds();
+                // Like `--since`, stop at the first commit that is too old.
+                if commit_time <= since {
+                    break;
+                }
+                if touches_path(&commit, path)? {
+                    commits.push(PathCommit {
+                        commit: commit.id().to_string(),
+                        author: commit.author().name().unwrap_or_default().to_string(),
+                        commit_time,
+                        subject: commit.summary().unwrap_or_default().to_string(),
+                    });
+                }
+            }
+            Ok(commits)
+        })
+    }
+
+    fn rev_parse(&self, rev: &str) -> Result<String, GitWorkerError> {
+        self.with_repo("rev-parse", |repo| Ok(repo.revparse_single(rev)?.id().to_string()))
+    }
+
+    fn upstream_changes(&self) -> Result<Option<UpstreamChanges>, GitWorkerError> {
+        self.with_repo("fetch", |repo| {
+            fetch(repo)?;
+            let remote = repo.revparse_single("@{u}")?.peel_to_commit()?;
+            let head = repo.head()?.peel_to_commit()?;
+            let base = repo.merge_base(head.id(), remote.id())?;
+            if base == remote.id() {
+                return Ok(None);
+            }
+
+            let base_tree = repo.find_commit(base)?.tree()?;
+            let remote_tree = remote.tree()?;
+            let diff = repo.diff_tree_to_tree(Some(&base_tree), Some(&remote_tree), None)?;
+            let mut files = Vec::new();
+            for delta in diff.deltas() {
+                let Some(path) = delta.new_file().path().and_then(Path::to_str) else {
+                    continue;
+                };
+                if !path.ends_with(".md") {
+                    continue;
+                }
+                files.push(UpstreamFile {
+                    path: path.to_string(),
+                    base: match delta.status() {
+                        Delta::Added => None,
+                        _ => Some(blob_text(repo, &base_tree, path)?),
+                    },
+                    remote: match delta.status() {
+                        Delta::Deleted => None,
+                        _ => Some(blob_text(repo, &remote_tree, path)?),
+                    },
+                    author: last_author(repo, base, remote.id(), path)?,
+                });
+            }
+
+            Ok(Some(UpstreamChanges {
+                base: base.to_string(),
+                remote: remote.id().to_string(),
+                files,
+            }))
+        })
+    }
+
+    fn merge_upstream(
+        &self,
+        remote: &str,
+        resolved: &[(String, String)],
+    ) -> Result<GitCommandOutput, GitWorkerError> {
+        // The merge happens in memory, so conflicts leave the working tree
+        // and HEAD untouched, as `git merge --abort` would.
+        let merged = self.with_repo("merge", |repo| {
+            let head = repo.head()?;
+            let ours = head.peel_to_commit()?;
+            let theirs = repo.revparse_single(remote)?.peel_to_commit()?;
+            let mut index = repo.merge_commits(&ours, &theirs, None)?;
+            for (path, content) in resolved {
+                if index.get_path(Path::new(path), 0).is_none() {
+                    index.conflict_remove(Path::new(path))?;
+                }
+                index.add(&blob_entry(repo, path, content)?)?;
+            }
+            if index.has_conflicts() {
+                let mut paths = Vec::new();
+                for conflict in index.conflicts()? {
+                    let conflict = conflict?;
+                    if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+                        paths.push(String::from_utf8_lossy(&entry.path).into_owned());
+                    }
+                }
+                return Ok(Err(paths));
+            }
+
+            let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+            let message = format!("Merge commit '{remote}'");
+            let commit = self.create_commit(repo, &message, None, &tree, &[&ours, &theirs])?;
+            repo.checkout_tree(tree.as_object(), Some(CheckoutBuilder::new().safe()))?;
+            let branch = head.name().unwrap_or("HEAD").to_string();
+            repo.reference_matching(&branch, commit, true, ours.id(), &message)?;
+            Ok(Ok(output(format!("Merge made by libgit2: {commit}\n"))))
+        })?;
+        merged.map_err(|paths| GitWorkerError::MergeConflicts { paths })
+    }
+}
+
+fn output(stdout: String) -> GitCommandOutput {
+    GitCommandOutput { stdout, stderr: String::new() }
+}
+
+fn subject(message: &str) -> &str {
+    message.lines().next().unwrap_or_default()
+}
+
+fn status_options() -> StatusOptions {
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true).exclude_submodules(true);
+    options
+}
+
+/// The two-letter `git status --short` code.
+fn short_status(status: Status) -> String {
+    if status.contains(Status::CONFLICTED) {
+        return "UU".to_string();
+    }
+    if status == Status::WT_NEW {
+        return "??".to_string();
+    }
+    let index = if status.contains(Status::INDEX_NEW) {
+        'A'
+    } else if status.contains(Status::INDEX_DELETED) {
+        'D'
+    } else if status.contains(Status::INDEX_RENAMED) {
+        'R'
+    } else if status.contains(Status::INDEX_TYPECHANGE) {
+        'T'
+    } else if status.contains(Status::INDEX_MODIFIED) {
+        'M'
+    } else {
+        ' '
+    };
+    let worktree = if status.contains(Status::WT_NEW) {
+        '?'
+    } else if status.contains(Status::WT_DELETED) {
+        'D'
+    } else if status.contains(Status::WT_TYPECHANGE) {
+        'T'
+    } else if status.contains(Status::WT_MODIFIED) {
+        'M'
+    } else {
+        ' '
+    };
+    format!("{index}{worktree}")
+}
+
+/// The index against `HEAD`, or against nothing before the first commit.
+fn staged_diff(repo: &Repository) -> Result<Diff<'_>, git2::Error> {
+    let head = match repo.head() {
+        Ok(head) => Some(head.peel_to_tree()?),
+        Err(error) if error.code() == ErrorCode::UnbornBranch => None,
+        Err(error) => return Err(error),
+    };
+    repo.diff_tree_to_index(head.as_ref(), None, None)
+}
+
+fn blob_text(repo: &Repository, tree: &Tree<'_>, path: &str) -> Result<String, git2::Error> {
+    let blob = tree.get_path(Path::new(path))?.to_object(repo)?.peel_to_blob()?;
+    Ok(String::from_utf8_lossy(blob.content()).into_owned())
+}
+
+fn blob_entry(repo: &Repository, path: &str, content: &str) -> Result<IndexEntry, git2::Error> {
+    Ok(IndexEntry {
+        ctime: IndexTime::new(0, 0),
+        mtime: IndexTime::new(0, 0),
+        dev: 0,
+        ino: 0,
+        mode: 0o100644,
+        uid: 0,
+        gid: 0,
+        file_size: content.len() as u32,
+        id: repo.blob(content.as_bytes())?,
+        flags: 0,
+        flags_extended: 0,
+        path: path.as_bytes().to_vec(),
+    })
+}
+
+/// Author of the newest commit in `base..tip` that changed `path`, as
+/// `git log -1 base..tip -- path` picks it.
+fn last_author(
+    repo: &Repository,
+    base: Oid,
+    tip: Oid,
+    path: &str,
+) -> Result<Option<CommitAuthor>, git2::Error> {
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TIME)?;
+    walk.push(tip)?;
+    walk.hide(base)?;
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        if touches_path(&commit, path)? {
+            let author = commit.author();
+            return Ok(Some(CommitAuthor {
+                name: author.name().unwrap_or_default().to_string(),
+                email: author.email().unwrap_or_default().to_string(),
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// `git fetch`: the current branch's remote, else `origin`.
+/// Whether `commit` changed `path` against every parent, the way `git log
+/// -- path` simplifies history.
+fn touches_path(commit: &Commit<'_>, path: &str) -> Result<bool, git2::Error> {
+    let entry_id = |tree: Tree<'_>| tree.get_path(Path::new(path)).ok().map(|entry| entry.id());
+    let entry = entry_id(commit.tree()?);
+    Ok(if commit.parent_count() == 0 {
+        entry.is_some()
+    } else {
+        commit.parents().all(|parent| parent.tree().ok().and_then(entry_id) != entry)
+    })
+}
+
+fn fetch(repo: &Repository) -> Result<(), git2::Error> {
+    let remote_name = repo
+        .head()
+        .ok()
+        .and_then(|head| head.name().map(str::to_string))
+        .and_then(|branch| repo.branch_upstream_remote(&branch).ok())
+        .and_then(|remote| remote.as_str().map(str::to_string))
+        .unwrap_or_else(|| "origin".to_string());
+    let mut remote = repo.find_remote(&remote_name)?;
+    let mut options = FetchOptions::new();
+    options.remote_callbacks(remote_callbacks(repo)?);
+    remote.fetch::<&str>(&[], Some(&mut options), None)
+}
+
+/// Credentials from ssh-agent, then the default `~/.ssh` keys, then git's
+/// credential helpers. Each is offered once, so a refused login fails
+/// instead of retrying forever.
+fn remote_callbacks<'a>(repo: &Repository) -> Result<RemoteCallbacks<'a>, git2::Error> {
+    let config = repo.config()?;
+    let mut tried_agent = false;
+    let mut tried_helper = false;
+    let mut ssh_keys = default_ssh_keys().into_iter();
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |url, username, allowed| {
+        let user = username.unwrap_or("git");
+        if allowed.contains(CredentialType::USERNAME) {
+            return Cred::username(user);
+        }
+        if allowed.contains(CredentialType::SSH_KEY) {
+            if !tried_agent {
+                tried_agent = true;
+                if let Ok(cred) = Cred::ssh_key_from_agent(user) {
+                    return Ok(cred);
+                }
+            }
+            if let Some(key) = ssh_keys.next() {
+                return Cred::ssh_key(user, None, &key, None);
+            }
+        }
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) && !tried_helper {
+            tried_helper = true;
+            return Cred::credential_helper(&config, url, username);
+        }
+        Err(git2::Error::from_str(&format!("no accepted credentials for {url}")))
+    });
+    Ok(callbacks)
+}
+
+fn default_ssh_keys() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    ["id_ed25519", "id_ecdsa", "id_rsa"]
+        .iter()
+        .map(|name| home.join(".ssh").join(name))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Sign a commit buffer the way git does: `ssh-keygen -Y sign` or
+/// `gpg -bsau`, with the key from config, else `user.signingkey`.
+fn sign_payload(
+    repo: &Repository,
+    signing: &CommitSigning,
+    payload: &str,
+) -> Result<String, git2::Error> {
+    let key = match &signing.key {
+        Some(key) => Some(key.clone()),
+        None => repo.config()?.get_string("user.signingkey").ok(),
+    };
+    match signing.format {
+        SigningFormat::Ssh => {
+            let key = key.ok_or_else(|| {
+                git2::Error::from_str("ssh signing needs [git.signing] key or user.signingkey")
+            })?;
+            let key_file = SshKeyFile::new(&key)?;
+            let key_path = key_file.path.to_string_lossy().into_owned();
+            run_signer("ssh-keygen", &["-Y", "sign", "-n", "git", "-f", &key_path], payload)
+        }
+        SigningFormat::Openpgp => {
+            let mut args = vec!["--status-fd=2", "-bsa"];
+            if let Some(key) = &key {
+                args.extend(["-u", key.as_str()]);
+            }
+            run_signer("gpg", &args, payload)
+        }
+    }
+}
+
+fn run_signer(program: &str, args: &[&str], payload: &str) -> Result<String, git2::Error> {
+    let spawn_failed =
+        |error: std::io::Error| git2::Error::from_str(&format!("failed to run {program}: {error}"));
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(spawn_failed)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(payload.as_bytes()).map_err(spawn_failed)?;
+    }
+    let result = child.wait_with_output().map_err(spawn_failed)?;
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        return Err(git2::Error::from_str(&format!("{program} failed to sign: {}", stderr.trim())));
+    }
+    String::from_utf8(result.stdout)
+        .map_err(|_| git2::Error::from_str(&format!("{program} wrote a non-UTF-8 signature")))
+}
+
+/// An SSH signing key as a file. Like git, a `key::` or `ssh-` literal is
+/// written to a temporary file, removed on drop.
+struct SshKeyFile {
+    path: PathBuf,
+    temporary: bool,
+}
+
+impl SshKeyFile {
+    fn new(key: &str) -> Result<Self, git2::Error> {
+        let literal = key.strip_prefix("key::").or_else(|| key.starts_with("ssh-").then_some(key));
+        if let Some(literal) = literal {
+            let path =
+                std::env::temp_dir().join(format!("scriptum-signing-{}", uuid::Uuid::new_v4()));
+            std::fs::write(&path, format!("{literal}\n")).map_err(|error| {
+                git2::Error::from_str(&format!("failed to write signing key: {error}"))
+            })?;
+            return Ok(Self { path, temporary: true });
+        }
+        let path = match key.strip_prefix("~/").zip(dirs::home_dir()) {
+            Some((rest, home)) => home.join(rest),
+            None => PathBuf::from(key),
+        };
+        Ok(Self { path, temporary: false })
+    }
+}
+
+impl Drop for SshKeyFile {
+    fn drop(&mut self) {
+        if self.temporary {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn init_repo(temp: &TempDir) -> (Repository, NativeGitWorker) {
+        let repo = Repository::init(temp.path()).expect("repo should init");
+        let mut config = repo.config().expect("config");
+        config.set_str("user.name", "Scriptum Bot").expect("user.name");
+        config.set_str("user.email", "bot@example.test").expect("user.email");
+        (repo, NativeGitWorker::new(temp.path()))
+    }
+
+    fn write(temp: &TempDir, path: &str, content: &str) {
+        let path = temp.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn stages_and_commits_with_trailers_and_diff_stats() {
+        let temp = TempDir::new().unwrap();
+        let (repo, worker) = init_repo(&temp);
+        write(&temp, "docs/readme.md", "# Readme\n");
+        write(&temp, "notes.md", "old\n");
+        worker.add(&[".".to_string()]).expect("add");
+        let first = worker.commit_as("docs: seed", None).expect("root commit");
+        assert!(first.stdout.starts_with("[master "), "{}", first.stdout);
+
+        write(&temp, "docs/readme.md", "# Readme\n\nMore.\n");
+        std::fs::remove_file(temp.path().join("notes.md")).unwrap();
+        write(&temp, "docs/new.md", "new\n");
+        let status = worker.status().expect("status").stdout;
+        assert!(status.contains(" M docs/readme.md"), "{status}");
+        assert!(status.contains("?? docs/new.md"), "{status}");
+        assert!(status.contains(" D notes.md"), "{status}");
+        assert_eq!(worker.changed_paths().expect("changed").len(), 3);
+
+        worker.add_all(&[".".to_string()]).expect("add -A");
+        assert_eq!(
+            worker.diff_cached_name_status().expect("name-status").stdout,
+            "A\tdocs/new.md\nM\tdocs/readme.md\nD\tnotes.md\n"
+        );
+        assert!(worker.diff_cached().expect("diff").stdout.contains("+More.\n"));
+
+        let agent = CommitAuthor { name: "claude".into(), email: "agent:claude@scriptum".into() };
+        let message = "docs: expand\n\nCo-authored-by: cursor <agent:cursor@scriptum>";
+        worker.commit_as(message, Some(&agent)).expect("commit");
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some(format!("{message}\n").as_str()));
+        assert_eq!(head.author().name(), Some("claude"));
+        assert_eq!(head.committer().name(), Some("Scriptum Bot"));
+        assert!(worker.status().expect("status").stdout.is_empty());
+
+        let error = worker.commit_as("docs: nothing", None).expect_err("nothing staged");
+        assert!(error.to_string().contains("nothing to commit"), "{error}");
+    }
+
+    #[test]
+    fn notes_and_blame_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let (_repo, worker) = init_repo(&temp);
+        write(&temp, "doc.md", "one\ntwo\n");
+        worker.add(&["doc.md".to_string()]).expect("add");
+        worker.commit_as("docs: one", None).expect("commit");
+        let first = worker.rev_parse("HEAD").expect("rev-parse");
+        write(&temp, "doc.md", "one\nthree\n");
+        worker.add(&["doc.md".to_string()]).expect("add");
+        worker.commit_as("docs: three", None).expect("commit");
+        let second = worker.rev_parse("HEAD").expect("rev-parse");
+
+        assert_eq!(worker.note("HEAD").expect("note"), None);
+        worker.add_note(&second, "{\"version\":1}").expect("add note");
+        assert_eq!(worker.note("HEAD").expect("note").as_deref(), Some("{\"version\":1}\n"));
+
+        let blamed = worker.blame("HEAD", "doc.md").expect("blame");
+        assert_eq!(blamed.len(), 2);
+        assert_eq!(
+            (blamed[0].commit.as_str(), blamed[0].content.as_str()),
+            (first.as_str(), "one")
+        );
+        assert_eq!(blamed[1].commit, second);
+        assert_eq!(blamed[1].author, "Scriptum Bot");
+        assert_eq!(
+            worker.log_subjects("HEAD").expect("log"),
+            vec!["docs: three".to_string(), "docs: one".to_string()]
+        );
+    }
+}
@@ -0,0 +1,300 @@
+// Git notes: Scriptum edit metadata attached to each auto-commit.
+//
+// Every commit a git job makes gets a JSON note under `refs/notes/scriptum`
+// with the trigger that caused it, the sections it touched with who touched
+// them, and the edit summaries. `git.blame` reads the notes back, so
+// attribution outlives the CRDT history.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use scriptum_common::section::parser::parse_sections;
+use scriptum_common::types::EditorType;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::git::triggers::{CommitContext, TriggerEvent};
+use crate::section::{diff_sections, SectionChange};
+
+pub const NOTES_REF: &str = "refs/notes/scriptum";
+pub const NOTE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScriptumNote {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<NoteTrigger>,
+    #[serde(default)]
+    pub sections: Vec<NoteSection>,
+    #[serde(default)]
+    pub edits: Vec<NoteEdit>,
+}
+
+/// What caused the commit, from its `CommitContext`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NoteTrigger {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_path: Option<String>,
+    /// Heading of the section whose lease was released.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section_heading: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    /// Checkpoint message or batch summary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub agents_involved: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed_files: Vec<String>,
+}
+
+impl NoteTrigger {
+    pub fn from_context(context: &CommitContext) -> Self {
+        let mut trigger = Self {
+            kind: context.trigger.kind().to_string(),
+            agents_involved: context.agents_involved.clone(),
+            changed_files: context.changed_files.iter().map(|file| file.path.clone()).collect(),
+            ..Self::default()
+        };
+        match &context.trigger {
+            TriggerEvent::LeaseReleased { agent, doc_path, section_heading } => {
+                trigger.agent = Some(agent.clone());
+                trigger.doc_path = Some(doc_path.clone());
+                trigger.section_heading = Some(section_heading.clone());
+            }
+            TriggerEvent::CommentResolved { agent, doc_path, thread_id } => {
+                trigger.agent = Some(agent.clone());
+                trigger.doc_path = Some(doc_path.clone());
+                trigger.thread_id = Some(thread_id.clone());
+            }
+            TriggerEvent::ExplicitCheckpoint { agent, message } => {
+                trigger.agent = Some(agent.clone());
+                trigger.summary = message.clone();
+            }
+            TriggerEvent::BatchApplied { agent, summary, .. } => {
+                trigger.agent = Some(agent.clone());
+                trigger.summary = Some(summary.clone());
+            }
+            TriggerEvent::IdleFallback => {}
+        }
+        trigger
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NoteAuthor {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub author_type: EditorType,
+}
+
+/// A section the commit touched and everyone who edited it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NoteSection {
+    pub doc_id: String,
+    pub path: String,
+    pub section_id: String,
+    pub heading: String,
+    pub authors: Vec<NoteAuthor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NoteEdit {
+    pub doc_id: String,
+    pub path: String,
+    pub author: NoteAuthor,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub section_ids: Vec<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Who a blamed line is credited to, from its commit's note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteAttribution<'a> {
+    pub author: &'a NoteAuthor,
+    pub summary: Option<&'a str>,
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl ScriptumNote {
+    pub fn parse(raw: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(raw.trim())
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("git notes serialize")
+    }
+
+    /// Credit for a line of `path` in `section_id`: the last edit of that
+    /// section, else the section's first author, else the last edit of the
+    /// doc.
+    pub fn attribution(&self, path: &str, section_id: Option<&str>) -> Option<NoteAttribution<'_>> {
+        let doc_edits = || self.edits.iter().rev().filter(|edit| edit.path == path);
+        if let Some(section_id) = section_id {
+            let section_edit = doc_edits()
+                .find(|edit| edit.section_ids.iter().any(|id| id.as_str() == section_id));
+            if let Some(edit) = section_edit {
+                return Some(edit.attribution());
+            }
+            let author = self
+                .sections
+                .iter()
+                .find(|section| section.path == path && section.section_id == section_id)
+                .and_then(|section| section.authors.first());
+            if let Some(author) = author {
+                return Some(NoteAttribution { author, summary: None, timestamp: None });
+            }
+        }
+        doc_edits().next().map(NoteEdit::attribution)
+    }
+}
+
+impl NoteEdit {
+    fn attribution(&self) -> NoteAttribution<'_> {
+        NoteAttribution {
+            author: &self.author,
+            summary: self.summary.as_deref(),
+            timestamp: Some(self.timestamp),
+        }
+    }
+}
+
+/// Edits since the last commit, folded into that commit's note.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NoteDraft {
+    /// `(doc_id, section_id)` to the touched section.
+    sections: BTreeMap<(String, String), NoteSection>,
+    edits: Vec<NoteEdit>,
+}
+
+impl NoteDraft {
+    /// Record one snapshot of `doc_id` going from `before` to `after`.
+    pub fn record_edit(
+        &mut self,
+        doc_id: Uuid,
+        path: &str,
+        before: &str,
+        after: &str,
+        author: NoteAuthor,
+        summary: Option<&str>,
+        timestamp: DateTime<Utc>,
+    ) {
+        let diff = diff_sections(&parse_sections(before), before, &parse_sections(after), after);
+        let doc_id = doc_id.to_string();
+        let mut section_ids = Vec::new();
+        for change in diff.changes {
+            let section = match change {
+                SectionChange::Added(section)
+                | SectionChange::Removed(section)
+                | SectionChange::Modified { new: section, .. } => section,
+            };
+            let entry =
+                self.sections.entry((doc_id.clone(), section.id.clone())).or_insert_with(|| {
+                    NoteSection {
+                        doc_id: doc_id.clone(),
+                        path: path.to_string(),
+                        section_id: section.id.clone(),
+                        heading: section.heading.clone(),
+                        authors: Vec::new(),
+                    }
+                });
+            if !entry.authors.contains(&author) {
+                entry.authors.push(author.clone());
+            }
+            section_ids.push(section.id);
+        }
+        self.edits.push(NoteEdit {
+            doc_id,
+            path: path.to_string(),
+            author,
+            summary: summary.map(str::to_string),
+            section_ids,
+            timestamp,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    pub fn into_note(self, trigger: Option<NoteTrigger>) -> ScriptumNote {
+        ScriptumNote {
+            version: NOTE_VERSION,
+            trigger,
+            sections: self.sections.into_values().collect(),
+            edits: self.edits,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::triggers::{ChangeType, ChangedFile};
+
+    fn agent(id: &str) -> NoteAuthor {
+        NoteAuthor { id: id.to_string(), author_type: EditorType::Agent }
+    }
+
+    #[test]
+    fn draft_collects_sections_authors_and_summaries() {
+        let doc_id = Uuid::new_v4();
+        let ts = "2026-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let v0 = "# Spec\n\n## Auth\nold\n\n## Storage\nsqlite\n";
+        let v1 = "# Spec\n\n## Auth\nnew\n\n## Storage\nsqlite\n";
+        let v2 = "# Spec\n\n## Auth\nnewer\n\n## Storage\npostgres\n";
+
+        let mut draft = NoteDraft::default();
+        draft.record_edit(doc_id, "docs/spec.md", v0, v1, agent("claude"), Some("auth"), ts);
+        let human = NoteAuthor { id: "local-user".into(), author_type: EditorType::Human };
+        draft.record_edit(doc_id, "docs/spec.md", v1, v2, human.clone(), None, ts);
+        let note = draft.into_note(None);
+
+        let auth = note.sections.iter().find(|s| s.heading == "Auth").expect("auth section");
+        assert_eq!(auth.authors, vec![agent("claude"), human.clone()]);
+        let storage = note.sections.iter().find(|s| s.heading == "Storage").expect("storage");
+        assert_eq!(storage.authors, vec![human.clone()]);
+        assert_eq!(note.edits.len(), 2);
+        assert_eq!(note.edits[0].summary.as_deref(), Some("auth"));
+
+        let attribution = note.attribution("docs/spec.md", Some(&storage.section_id)).unwrap();
+        assert_eq!(attribution.author, &human);
+        assert_eq!(ScriptumNote::parse(&note.to_json()).unwrap(), note);
+    }
+
+    #[test]
+    fn trigger_carries_lease_context() {
+        let context = CommitContext {
+            trigger: TriggerEvent::LeaseReleased {
+                agent: "claude".into(),
+                doc_path: "docs/spec.md".into(),
+                section_heading: "Auth".into(),
+            },
+            changed_files: vec![ChangedFile {
+                path: "docs/spec.md".into(),
+                doc_id: None,
+                change_type: ChangeType::Modified,
+            }],
+            agents_involved: vec!["claude".into()],
+        };
+        let json = serde_json::to_value(NoteTrigger::from_context(&context)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "lease_released",
+                "agent": "claude",
+                "doc_path": "docs/spec.md",
+                "section_heading": "Auth",
+                "agents_involved": ["claude"],
+                "changed_files": ["docs/spec.md"],
+            })
+        );
+    }
+}
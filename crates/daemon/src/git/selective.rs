@@ -0,0 +1,148 @@
+// Selective git sync: which workspace paths get staged.
+//
+// `[git] include` / `exclude` globs apply to every path; a per-document
+// override beats both. Excluded docs still sync through the relay; they are
+// only kept out of git.
+
+use std::collections::HashMap;
+
+use scriptum_common::path::glob_match;
+use serde::{Deserialize, Serialize};
+
+use crate::config::GitConfig;
+
+/// Per-document choice stored in `documents_local.git_sync`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitSyncOverride {
+    Include,
+    Exclude,
+}
+
+impl GitSyncOverride {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Include => "include",
+            Self::Exclude => "exclude",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "include" => Some(Self::Include),
+            "exclude" => Some(Self::Exclude),
+            _ => None,
+        }
+    }
+}
+
+/// Why a path is kept out of git.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum ExclusionReason {
+    /// The document's own override says `exclude`.
+    DocOverride,
+    ExcludeGlob {
+        pattern: String,
+    },
+    /// `include` globs are set and none matches.
+    NotIncluded,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitSyncFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    /// Workspace-relative doc path to its override.
+    overrides: HashMap<String, GitSyncOverride>,
+}
+
+impl GitSyncFilter {
+    pub fn new(include: Vec<String>, exclude: Vec<String>) -> Self {
+        Self { include, exclude, overrides: HashMap::new() }
+    }
+
+    pub fn from_config(config: &GitConfig) -> Self {
+        Self::new(config.include.clone(), config.exclude.clone())
+    }
+
+    pub fn set_overrides(&mut self, overrides: HashMap<String, GitSyncOverride>) {
+        self.overrides = overrides;
+    }
+
+    /// True when every path is staged, so `git add .` is enough.
+    pub fn is_unrestricted(&self) -> bool {
+        self.include.is_empty()
+            && self.exclude.is_empty()
+            && !self.overrides.values().any(|value| *value == GitSyncOverride::Exclude)
+    }
+
+    /// None when `path` is staged, else why it isn't.
+    pub fn exclusion(&self, path: &str) -> Option<ExclusionReason> {
+        match self.overrides.get(path) {
+            Some(GitSyncOverride::Include) => return None,
+            Some(GitSyncOverride::Exclude) => return Some(ExclusionReason::DocOverride),
+            None => {}
+        }
+        if let Some(pattern) = self.exclude.iter().find(|pattern| glob_match(pattern, path)) {
+            return Some(ExclusionReason::ExcludeGlob { pattern: pattern.clone() });
+        }
+        if !self.include.is_empty() && !self.include.iter().any(|pattern| glob_match(pattern, path))
+        {
+            return Some(ExclusionReason::NotIncluded);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exclude_globs_beat_include_globs_and_overrides_beat_both() {
+        let mut filter = GitSyncFilter::new(
+            vec!["docs/**".to_string(), "README.md".to_string()],
+            vec!["docs/meetings/**".to_string()],
+        );
+        assert_eq!(filter.exclusion("docs/rfcs/001.md"), None);
+        assert_eq!(filter.exclusion("README.md"), None);
+        assert_eq!(
+            filter.exclusion("docs/meetings/2026-01-01.md"),
+            Some(ExclusionReason::ExcludeGlob { pattern: "docs/meetings/**".to_string() })
+        );
+        assert_eq!(filter.exclusion("notes/todo.md"), Some(ExclusionReason::NotIncluded));
+        assert!(!filter.is_unrestricted());
+
+        filter.set_overrides(HashMap::from([
+            ("docs/meetings/public.md".to_string(), GitSyncOverride::Include),
+            ("docs/rfcs/draft.md".to_string(), GitSyncOverride::Exclude),
+        ]));
+        assert_eq!(filter.exclusion("docs/meetings/public.md"), None);
+        assert_eq!(filter.exclusion("docs/rfcs/draft.md"), Some(ExclusionReason::DocOverride));
+    }
+
+    #[test]
+    fn empty_filter_stages_everything() {
+        let mut filter = GitSyncFilter::default();
+        assert!(filter.is_unrestricted());
+        assert_eq!(filter.exclusion("anything/at/all.md"), None);
+
+        filter.set_overrides(HashMap::from([("a.md".to_string(), GitSyncOverride::Include)]));
+        assert!(filter.is_unrestricted(), "include overrides restrict nothing");
+        filter.set_overrides(HashMap::from([("a.md".to_string(), GitSyncOverride::Exclude)]));
+        assert!(!filter.is_unrestricted());
+    }
+
+    #[test]
+    fn reasons_serialize_with_a_tag() {
+        assert_eq!(
+            serde_json::to_value(ExclusionReason::ExcludeGlob { pattern: "x/**".into() }).unwrap(),
+            serde_json::json!({ "reason": "exclude_glob", "pattern": "x/**" })
+        );
+        assert_eq!(
+            serde_json::to_value(ExclusionReason::NotIncluded).unwrap(),
+            serde_json::json!({ "reason": "not_included" })
+        );
+    }
+}
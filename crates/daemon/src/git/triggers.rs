@@ -20,6 +20,8 @@ pub enum TriggerEvent {
     CommentResolved { agent: String, doc_path: String, thread_id: String },
     /// Explicit checkpoint requested (via `scriptum checkpoint` CLI).
     ExplicitCheckpoint { agent: String, message: Option<String> },
+    /// A `doc.batch` landed as one logical change across several docs.
+    BatchApplied { agent: String, summary: String, doc_count: usize },
     /// Fallback trigger when there are pending changes and no semantic trigger fired.
     IdleFallback,
 }
@@ -31,6 +33,7 @@ impl TriggerEvent {
             TriggerEvent::LeaseReleased { agent, .. } => agent,
             TriggerEvent::CommentResolved { agent, .. } => agent,
             TriggerEvent::ExplicitCheckpoint { agent, .. } => agent,
+            TriggerEvent::BatchApplied { agent, .. } => agent,
             TriggerEvent::IdleFallback => "scriptum",
         }
     }
@@ -41,6 +44,7 @@ impl TriggerEvent {
             TriggerEvent::LeaseReleased { .. } => "lease_released",
             TriggerEvent::CommentResolved { .. } => "comment_resolved",
             TriggerEvent::ExplicitCheckpoint { .. } => "checkpoint",
+            TriggerEvent::BatchApplied { .. } => "batch_applied",
             TriggerEvent::IdleFallback => "idle_fallback",
         }
     }
@@ -101,6 +105,13 @@ impl CommitContext {
                      Checkpoint by {agent}.{file_summary}"
                 )
             }
+            TriggerEvent::BatchApplied { agent, summary, doc_count } => {
+                let file_summary = self.file_summary();
+                format!(
+                    "docs: {summary}\n\n\
+                     Batch of {doc_count} document change(s) by {agent}.{file_summary}"
+                )
+            }
             TriggerEvent::IdleFallback => {
                 let file_summary = self.file_summary();
                 format!(
@@ -446,6 +457,24 @@ mod tests {
         assert!(msg.starts_with("chore: manual checkpoint"));
     }
 
+    #[test]
+    fn commit_message_for_batch_applied() {
+        let ctx = CommitContext {
+            trigger: TriggerEvent::BatchApplied {
+                agent: "claude".into(),
+                summary: "rename Widget to Gadget".into(),
+                doc_count: 3,
+            },
+            changed_files: vec![],
+            agents_involved: vec!["claude".into()],
+        };
+
+        let msg = ctx.generate_message();
+        assert!(msg.starts_with("docs: rename Widget to Gadget"));
+        assert!(msg.contains("Batch of 3 document change(s) by claude."));
+        assert_eq!(ctx.trigger.kind(), "batch_applied");
+    }
+
     #[test]
     fn commit_message_for_idle_fallback() {
         let ctx = CommitContext {
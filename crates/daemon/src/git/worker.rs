@@ -1,8 +1,13 @@
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::config::{GitBackend, SigningFormat};
+use crate::git::native::NativeGitWorker;
+use crate::git::notes::NOTES_REF;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GitCommandOutput {
     pub stdout: String,
@@ -12,8 +17,29 @@ pub struct GitCommandOutput {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum GitWorkerError {
     EmptyAddPaths,
-    SpawnFailed { command: String, message: String },
-    CommandFailed { command: String, code: Option<i32>, stderr: String },
+    SpawnFailed {
+        command: String,
+        message: String,
+    },
+    CommandFailed {
+        command: String,
+        code: Option<i32>,
+        stderr: String,
+    },
+    WriteFailed {
+        path: String,
+        message: String,
+    },
+    /// An upstream merge left conflicts in files the daemon does not manage.
+    MergeConflicts {
+        paths: Vec<String>,
+    },
+    /// The in-process backend failed; `operation` names the git command
+    /// it stands in for.
+    NativeFailed {
+        operation: String,
+        message: String,
+    },
 }
 
 impl Display for GitWorkerError {
@@ -26,12 +52,77 @@ impl Display for GitWorkerError {
             GitWorkerError::CommandFailed { command, code, stderr } => {
                 write!(f, "`{command}` failed with code {:?}: {}", code, stderr.trim())
             }
+            GitWorkerError::WriteFailed { path, message } => {
+                write!(f, "failed to write merged `{path}`: {message}")
+            }
+            GitWorkerError::MergeConflicts { paths } => {
+                write!(f, "upstream merge left conflicts in {}", paths.join(", "))
+            }
+            GitWorkerError::NativeFailed { operation, message } => {
+                write!(f, "git {operation} failed: {message}")
+            }
         }
     }
 }
 
 impl Error for GitWorkerError {}
 
+/// Markdown files the upstream branch changed since the last sync.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamChanges {
+    /// The last-synced commit: the merge base of `HEAD` and upstream.
+    pub base: String,
+    pub remote: String,
+    pub files: Vec<UpstreamFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamFile {
+    pub path: String,
+    /// Content at the last-synced commit; None when added upstream.
+    pub base: Option<String>,
+    /// Content upstream; None when deleted upstream.
+    pub remote: Option<String>,
+    /// Author of the newest upstream commit touching the file.
+    pub author: Option<CommitAuthor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitAuthor {
+    pub name: String,
+    pub email: String,
+}
+
+/// Key that signs daemon commits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitSigning {
+    pub format: SigningFormat,
+    /// `user.signingkey`; None leaves git's own setting in place.
+    pub key: Option<String>,
+}
+
+/// One line of `git blame --porcelain` output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlamedLine {
+    /// 1-based line number in the blamed revision.
+    pub line_number: u32,
+    pub commit: String,
+    pub author: String,
+    /// Author time in seconds since the epoch.
+    pub author_time: i64,
+    pub content: String,
+}
+
+/// A commit that touched a path, from `git log -- <path>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathCommit {
+    pub commit: String,
+    pub author: String,
+    /// Committer time in seconds since the epoch.
+    pub commit_time: i64,
+    pub subject: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CommandResult {
     pub success: bool,
@@ -73,17 +164,30 @@ impl CommandExecutor for ProcessCommandExecutor {
 pub struct GitWorker<E = ProcessCommandExecutor> {
     repo_path: PathBuf,
     executor: E,
+    committer: Option<CommitAuthor>,
+    signing: Option<CommitSigning>,
 }
 
 impl GitWorker<ProcessCommandExecutor> {
     pub fn new(repo_path: impl Into<PathBuf>) -> Self {
-        Self { repo_path: repo_path.into(), executor: ProcessCommandExecutor }
+        Self::with_executor(repo_path, ProcessCommandExecutor)
     }
 }
 
 impl<E: CommandExecutor> GitWorker<E> {
     pub fn with_executor(repo_path: impl Into<PathBuf>, executor: E) -> Self {
-        Self { repo_path: repo_path.into(), executor }
+        Self { repo_path: repo_path.into(), executor, committer: None, signing: None }
+    }
+
+    /// Record `committer` on, and sign with `signing`, every commit this
+    /// worker makes. None keeps git's own configuration.
+    pub fn set_commit_identity(
+        &mut self,
+        committer: Option<CommitAuthor>,
+        signing: Option<CommitSigning>,
+    ) {
+        self.committer = committer;
+        self.signing = signing;
     }
 
     pub fn status(&self) -> Result<GitCommandOutput, GitWorkerError> {
@@ -100,8 +204,118 @@ impl<E: CommandExecutor> GitWorker<E> {
         self.run(args)
     }
 
+    /// Stage additions, edits and deletions of exactly `paths`.
+    pub fn add_all<S: AsRef<str>>(&self, paths: &[S]) -> Result<GitCommandOutput, GitWorkerError> {
+        if paths.is_empty() {
+            return Err(GitWorkerError::EmptyAddPaths);
+        }
+
+        let mut args = vec!["add".to_string(), "-A".to_string(), "--".to_string()];
+        args.extend(paths.iter().map(|path| path.as_ref().to_string()));
+        self.run(args)
+    }
+
+    /// Paths with staged, unstaged or untracked changes. A rename lists
+    /// both its old and new path.
+    pub fn changed_paths(&self) -> Result<Vec<String>, GitWorkerError> {
+        let output = self.run(vec![
+            "status".to_string(),
+            "--porcelain".to_string(),
+            "-z".to_string(),
+            "--untracked-files=all".to_string(),
+        ])?;
+        let mut paths = Vec::new();
+        let mut entries = output.stdout.split('\0').filter(|entry| !entry.is_empty());
+        while let Some(entry) = entries.next() {
+            let (Some(code), Some(path)) = (entry.get(..2), entry.get(3..)) else {
+                continue;
+            };
+            paths.push(path.to_string());
+            if code.contains('R') || code.contains('C') {
+                paths.extend(entries.next().map(str::to_string));
+            }
+        }
+        Ok(paths)
+    }
+
     pub fn commit(&self, message: &str) -> Result<GitCommandOutput, GitWorkerError> {
-        self.run(vec!["commit".to_string(), "-m".to_string(), message.to_string()])
+        self.commit_as(message, None)
+    }
+
+    /// Commit what is staged, crediting `author` when set.
+    pub fn commit_as(
+        &self,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<GitCommandOutput, GitWorkerError> {
+        let mut args = self.commit_command("commit", author);
+        args.extend(["-m".to_string(), message.to_string()]);
+        self.run(args)
+    }
+
+    /// Commit the staged tree to `branch` without checking it out, so
+    /// `HEAD` and the working tree stay put. A new branch starts at `HEAD`.
+    /// Returns the branch tip, unchanged when the tree matches it.
+    pub fn commit_to_branch(&self, branch: &str, message: &str) -> Result<String, GitWorkerError> {
+        self.commit_to_branch_as(branch, message, None)
+    }
+
+    /// `commit_to_branch`, crediting `author` when set.
+    pub fn commit_to_branch_as(
+        &self,
+        branch: &str,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<String, GitWorkerError> {
+        Ok(self.commit_tree_to_branch(branch, message, author)?.0)
+    }
+
+    /// `commit_to_branch_as`, but None when the tree matches the branch tip
+    /// and no commit was made.
+    pub fn commit_new_to_branch(
+        &self,
+        branch: &str,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<Option<String>, GitWorkerError> {
+        let (tip, created) = self.commit_tree_to_branch(branch, message, author)?;
+        Ok(created.then_some(tip))
+    }
+
+    /// The branch tip after committing, and whether a commit was made.
+    fn commit_tree_to_branch(
+        &self,
+        branch: &str,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<(String, bool), GitWorkerError> {
+        let tree = self.run(vec!["write-tree".to_string()])?.stdout.trim().to_string();
+        let branch_ref = format!("refs/heads/{branch}");
+        let existing = match self.rev_parse(&format!("{branch_ref}^{{commit}}")) {
+            Ok(tip) => Some(tip),
+            Err(GitWorkerError::CommandFailed { .. }) => None,
+            Err(error) => return Err(error),
+        };
+        let parent = match &existing {
+            Some(tip) => tip.clone(),
+            None => self.rev_parse("HEAD")?,
+        };
+        if existing.is_some() && self.rev_parse(&format!("{parent}^{{tree}}"))? == tree {
+            return Ok((parent, false));
+        }
+
+        let mut args = self.commit_command("commit-tree", author);
+        args.extend([tree, "-p".to_string(), parent, "-m".to_string(), message.to_string()]);
+        let commit = self.run(args)?.stdout.trim().to_string();
+        // The old value guards against a concurrent update; empty means
+        // the branch must not exist yet.
+        self.run(vec![
+            "update-ref".to_string(),
+            branch_ref,
+            commit.clone(),
+            existing.unwrap_or_default(),
+        ])?;
+        Ok((commit, true))
     }
 
     pub fn diff_cached(&self) -> Result<GitCommandOutput, GitWorkerError> {
@@ -116,10 +330,254 @@ impl<E: CommandExecutor> GitWorker<E> {
         self.run(vec!["push".to_string()])
     }
 
+    pub fn push_branch(
+        &self,
+        remote: &str,
+        branch: &str,
+    ) -> Result<GitCommandOutput, GitWorkerError> {
+        self.run(vec![
+            "push".to_string(),
+            remote.to_string(),
+            format!("refs/heads/{branch}:refs/heads/{branch}"),
+        ])
+    }
+
+    /// Subjects of the commits in `range`, newest first.
+    pub fn log_subjects(&self, range: &str) -> Result<Vec<String>, GitWorkerError> {
+        let output =
+            self.run(vec!["log".to_string(), "--format=%s".to_string(), range.to_string()])?;
+        Ok(output.stdout.lines().map(str::to_string).collect())
+    }
+
+    /// Attach `note` to `rev` under `refs/notes/scriptum`, replacing any
+    /// earlier note.
+    pub fn add_note(&self, rev: &str, note: &str) -> Result<GitCommandOutput, GitWorkerError> {
+        let mut args = Vec::new();
+        // The notes commit needs an identity too.
+        if let Some(committer) = &self.committer {
+            args.extend([
+                "-c".to_string(),
+                format!("user.name={}", committer.name),
+                "-c".to_string(),
+                format!("user.email={}", committer.email),
+            ]);
+        }
+        args.extend(
+            ["notes", "--ref", NOTES_REF, "add", "-f", "-m", note, rev].map(str::to_string),
+        );
+        self.run(args)
+    }
+
+    /// The Scriptum note on `rev`, or None when it has none.
+    pub fn note(&self, rev: &str) -> Result<Option<String>, GitWorkerError> {
+        let args = ["notes", "--ref", NOTES_REF, "show", rev].map(str::to_string).to_vec();
+        match self.run(args) {
+            Ok(output) => Ok(Some(output.stdout)),
+            Err(GitWorkerError::CommandFailed { stderr, .. })
+                if stderr.contains("no note found") =>
+            {
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn push_notes(&self, remote: &str) -> Result<GitCommandOutput, GitWorkerError> {
+        self.run(vec!["push".to_string(), remote.to_string(), format!("{NOTES_REF}:{NOTES_REF}")])
+    }
+
+    /// The commit that last changed each line of `path` as of `rev`.
+    pub fn blame(&self, rev: &str, path: &str) -> Result<Vec<BlamedLine>, GitWorkerError> {
+        let output =
+            self.run(["blame", "--porcelain", rev, "--", path].map(str::to_string).to_vec())?;
+        Ok(parse_blame_porcelain(&output.stdout))
+    }
+
+    /// Commits reachable from `HEAD` that touched `path` and were committed
+    /// after `since` (seconds since the epoch), newest first.
+    pub fn commits_touching(
+        &self,
+        path: &str,
+        since: i64,
+    ) -> Result<Vec<PathCommit>, GitWorkerError> {
+        let output = self.run(vec![
+            "log".to_string(),
+            format!("--since=@{since}"),
+            "--format=%H%x00%an%x00%ct%x00%s".to_string(),
+            "--".to_string(),
+            path.to_string(),
+        ])?;
+        Ok(parse_path_log(&output.stdout)
+            .into_iter()
+            .filter(|commit| commit.commit_time > since)
+            .collect())
+    }
+
     pub fn pull_rebase(&self) -> Result<GitCommandOutput, GitWorkerError> {
         self.run(vec!["pull".to_string(), "--rebase".to_string()])
     }
 
+    pub fn repo_path(&self) -> &Path {
+        &self.repo_path
+    }
+
+    pub fn fetch(&self) -> Result<GitCommandOutput, GitWorkerError> {
+        self.run(vec!["fetch".to_string()])
+    }
+
+    pub fn rev_parse(&self, rev: &str) -> Result<String, GitWorkerError> {
+        let output = self.run(vec!["rev-parse".to_string(), rev.to_string()])?;
+        Ok(output.stdout.trim().to_string())
+    }
+
+    pub fn merge_base(&self, a: &str, b: &str) -> Result<String, GitWorkerError> {
+        let output = self.run(vec!["merge-base".to_string(), a.to_string(), b.to_string()])?;
+        Ok(output.stdout.trim().to_string())
+    }
+
+    /// Markdown files changed on the upstream branch since the merge base
+    /// with `HEAD`, after fetching. None when upstream has nothing new.
+    pub fn upstream_changes(&self) -> Result<Option<UpstreamChanges>, GitWorkerError> {
+        self.fetch()?;
+        let remote = self.rev_parse("@{u}")?;
+        let base = self.merge_base("HEAD", &remote)?;
+        if base == remote {
+            return Ok(None);
+        }
+
+        let name_status = self.run(vec![
+            "diff".to_string(),
+            "--name-status".to_string(),
+            "--no-renames".to_string(),
+            base.clone(),
+            remote.clone(),
+        ])?;
+        let mut files = Vec::new();
+        for line in name_status.stdout.lines() {
+            let mut parts = line.split('\t');
+            let (Some(status), Some(path)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            if !path.ends_with(".md") {
+                continue;
+            }
+            let status = status.chars().next();
+            files.push(UpstreamFile {
+                path: path.to_string(),
+                base: match status {
+                    Some('A') => None,
+                    _ => Some(self.show_file(&base, path)?),
+                },
+                remote: match status {
+                    Some('D') => None,
+                    _ => Some(self.show_file(&remote, path)?),
+                },
+                author: self.last_author(&format!("{base}..{remote}"), path)?,
+            });
+        }
+
+        Ok(Some(UpstreamChanges { base, remote, files }))
+    }
+
+    /// Merge `remote` into `HEAD`, writing `resolved` (path, content) pairs
+    /// over whatever git merged for those files, then commit.
+    pub fn merge_upstream(
+        &self,
+        remote: &str,
+        resolved: &[(String, String)],
+    ) -> Result<GitCommandOutput, GitWorkerError> {
+        let merge = self.run(vec![
+            "merge".to_string(),
+            "--no-ff".to_string(),
+            "--no-commit".to_string(),
+            remote.to_string(),
+        ]);
+        // Exit code 1 means the merge stopped on conflicts; files we resolve
+        // are overwritten below and anything left is checked afterwards.
+        match merge {
+            Err(GitWorkerError::CommandFailed { code: Some(1), .. }) | Ok(_) => {}
+            Err(error) => return Err(error),
+        }
+
+        for (path, content) in resolved {
+            std::fs::write(self.repo_path.join(path), content.as_bytes()).map_err(|error| {
+                GitWorkerError::WriteFailed { path: path.clone(), message: error.to_string() }
+            })?;
+        }
+        if !resolved.is_empty() {
+            self.add(&resolved.iter().map(|(path, _)| path.as_str()).collect::<Vec<_>>())?;
+        }
+
+        let unmerged = self.run(vec![
+            "diff".to_string(),
+            "--name-only".to_string(),
+            "--diff-filter=U".to_string(),
+        ])?;
+        let paths: Vec<String> = unmerged.stdout.lines().map(str::to_string).collect();
+        if !paths.is_empty() {
+            let _ = self.run(vec!["merge".to_string(), "--abort".to_string()]);
+            return Err(GitWorkerError::MergeConflicts { paths });
+        }
+
+        let mut args = self.commit_command("commit", None);
+        args.push("--no-edit".to_string());
+        self.run(args)
+    }
+
+    fn show_file(&self, rev: &str, path: &str) -> Result<String, GitWorkerError> {
+        Ok(self.run(vec!["show".to_string(), format!("{rev}:{path}")])?.stdout)
+    }
+
+    fn last_author(&self, range: &str, path: &str) -> Result<Option<CommitAuthor>, GitWorkerError> {
+        let output = self.run(vec![
+            "log".to_string(),
+            "-1".to_string(),
+            "--format=%an%x00%ae".to_string(),
+            range.to_string(),
+            "--".to_string(),
+            path.to_string(),
+        ])?;
+        Ok(output
+            .stdout
+            .trim_end()
+            .split_once('\0')
+            .map(|(name, email)| CommitAuthor { name: name.to_string(), email: email.to_string() }))
+    }
+
+    /// `subcommand` with `-c` overrides for the committer, `author` and
+    /// signing key, plus `-S` when signing.
+    fn commit_command(&self, subcommand: &str, author: Option<&CommitAuthor>) -> Vec<String> {
+        let mut config = Vec::new();
+        if let Some(committer) = &self.committer {
+            config.push(format!("committer.name={}", committer.name));
+            config.push(format!("committer.email={}", committer.email));
+        }
+        if let Some(author) = author {
+            config.push(format!("author.name={}", author.name));
+            config.push(format!("author.email={}", author.email));
+        }
+        if let Some(signing) = &self.signing {
+            let format = match signing.format {
+                SigningFormat::Ssh => "ssh",
+                SigningFormat::Openpgp => "openpgp",
+            };
+            config.push(format!("gpg.format={format}"));
+            if let Some(key) = &signing.key {
+                config.push(format!("user.signingkey={key}"));
+            }
+        }
+
+        let mut args = Vec::new();
+        for entry in config {
+            args.extend(["-c".to_string(), entry]);
+        }
+        args.push(subcommand.to_string());
+        if self.signing.is_some() {
+            args.push("-S".to_string());
+        }
+        args
+    }
+
     fn run(&self, args: Vec<String>) -> Result<GitCommandOutput, GitWorkerError> {
         let command = format!("git {}", args.join(" "));
         let result = self.executor.execute("git", &args, &self.repo_path).map_err(|error| {
@@ -136,6 +594,249 @@ impl<E: CommandExecutor> GitWorker<E> {
     }
 }
 
+/// Repository operations git sync runs, over either backend.
+pub trait GitRepository: Send + Sync {
+    fn repo_path(&self) -> &Path;
+    /// See [`GitWorker::set_commit_identity`].
+    fn set_commit_identity(
+        &mut self,
+        committer: Option<CommitAuthor>,
+        signing: Option<CommitSigning>,
+    );
+    /// `git status --short` output.
+    fn status(&self) -> Result<GitCommandOutput, GitWorkerError>;
+    fn add(&self, paths: &[String]) -> Result<GitCommandOutput, GitWorkerError>;
+    fn add_all(&self, paths: &[String]) -> Result<GitCommandOutput, GitWorkerError>;
+    fn changed_paths(&self) -> Result<Vec<String>, GitWorkerError>;
+    /// Commit what is staged to `HEAD`; stdout reads like `git commit`.
+    fn commit_as(
+        &self,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<GitCommandOutput, GitWorkerError>;
+    fn commit_to_branch_as(
+        &self,
+        branch: &str,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<String, GitWorkerError>;
+    fn commit_new_to_branch(
+        &self,
+        branch: &str,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<Option<String>, GitWorkerError>;
+    fn diff_cached(&self) -> Result<GitCommandOutput, GitWorkerError>;
+    fn diff_cached_name_status(&self) -> Result<GitCommandOutput, GitWorkerError>;
+    fn push(&self) -> Result<GitCommandOutput, GitWorkerError>;
+    fn push_branch(&self, remote: &str, branch: &str) -> Result<GitCommandOutput, GitWorkerError>;
+    fn log_subjects(&self, range: &str) -> Result<Vec<String>, GitWorkerError>;
+    fn add_note(&self, rev: &str, note: &str) -> Result<GitCommandOutput, GitWorkerError>;
+    fn note(&self, rev: &str) -> Result<Option<String>, GitWorkerError>;
+    fn push_notes(&self, remote: &str) -> Result<GitCommandOutput, GitWorkerError>;
+    fn blame(&self, rev: &str, path: &str) -> Result<Vec<BlamedLine>, GitWorkerError>;
+    fn commits_touching(&self, path: &str, since: i64) -> Result<Vec<PathCommit>, GitWorkerError>;
+    fn rev_parse(&self, rev: &str) -> Result<String, GitWorkerError>;
+    fn upstream_changes(&self) -> Result<Option<UpstreamChanges>, GitWorkerError>;
+    fn merge_upstream(
+        &self,
+        remote: &str,
+        resolved: &[(String, String)],
+    ) -> Result<GitCommandOutput, GitWorkerError>;
+}
+
+/// The repository at `repo_path` behind `backend`.
+pub fn open_repository(
+    backend: GitBackend,
+    repo_path: impl Into<PathBuf>,
+) -> Box<dyn GitRepository> {
+    match backend {
+        GitBackend::Cli => Box::new(GitWorker::new(repo_path)),
+        GitBackend::Native => Box::new(NativeGitWorker::new(repo_path)),
+    }
+}
+
+impl<E: CommandExecutor> GitRepository for GitWorker<E> {
+    fn repo_path(&self) -> &Path {
+        GitWorker::repo_path(self)
+    }
+
+    fn set_commit_identity(
+        &mut self,
+        committer: Option<CommitAuthor>,
+        signing: Option<CommitSigning>,
+    ) {
+        GitWorker::set_commit_identity(self, committer, signing);
+    }
+
+    fn status(&self) -> Result<GitCommandOutput, GitWorkerError> {
+        GitWorker::status(self)
+    }
+
+    fn add(&self, paths: &[String]) -> Result<GitCommandOutput, GitWorkerError> {
+        GitWorker::add(self, paths)
+    }
+
+    fn add_all(&self, paths: &[String]) -> Result<GitCommandOutput, GitWorkerError> {
+        GitWorker::add_all(self, paths)
+    }
+
+    fn changed_paths(&self) -> Result<Vec<String>, GitWorkerError> {
+        GitWorker::changed_paths(self)
+    }
+
+    fn commit_as(
+        &self,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<GitCommandOutput, GitWorkerError> {
+        GitWorker::commit_as(self, message, author)
+    }
+
+    fn commit_to_branch_as(
+        &self,
+        branch: &str,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<String, GitWorkerError> {
+        GitWorker::commit_to_branch_as(self, branch, message, author)
+    }
+
+    fn commit_new_to_branch(
+        &self,
+        branch: &str,
+        message: &str,
+        author: Option<&CommitAuthor>,
+    ) -> Result<Option<String>, GitWorkerError> {
+        GitWorker::commit_new_to_branch(self, branch, message, author)
+    }
+
+    fn diff_cached(&self) -> Result<GitCommandOutput, GitWorkerError> {
+        GitWorker::diff_cached(self)
+    }
+
+    fn diff_cached_name_status(&self) -> Result<GitCommandOutput, GitWorkerError> {
+        GitWorker::diff_cached_name_status(self)
+    }
+
+    fn push(&self) -> Result<GitCommandOutput, GitWorkerError> {
+        GitWorker::push(self)
+    }
+
+    fn push_branch(&self, remote: &str, branch: &str) -> Result<GitCommandOutput, GitWorkerError> {
+        GitWorker::push_branch(self, remote, branch)
+    }
+
+    fn log_subjects(&self, range: &str) -> Result<Vec<String>, GitWorkerError> {
+        GitWorker::log_subjects(self, range)
+    }
+
+    fn add_note(&self, rev: &str, note: &str) -> Result<GitCommandOutput, GitWorkerError> {
+        GitWorker::add_note(self, rev, note)
+    }
+
+    fn note(&self, rev: &str) -> Result<Option<String>, GitWorkerError> {
+        GitWorker::note(self, rev)
+    }
+
+    fn push_notes(&self, remote: &str) -> Result<GitCommandOutput, GitWorkerError> {
+        GitWorker::push_notes(self, remote)
+    }
+
+    fn blame(&self, rev: &str, path: &str) -> Result<Vec<BlamedLine>, GitWorkerError> {
+        GitWorker::blame(self, rev, path)
+    }
+
+    fn commits_touching(&self, path: &str, since: i64) -> Result<Vec<PathCommit>, GitWorkerError> {
+        GitWorker::commits_touching(self, path, since)
+    }
+
+    fn rev_parse(&self, rev: &str) -> Result<String, GitWorkerError> {
+        GitWorker::rev_parse(self, rev)
+    }
+
+    fn upstream_changes(&self) -> Result<Option<UpstreamChanges>, GitWorkerError> {
+        GitWorker::upstream_changes(self)
+    }
+
+    fn merge_upstream(
+        &self,
+        remote: &str,
+        resolved: &[(String, String)],
+    ) -> Result<GitCommandOutput, GitWorkerError> {
+        GitWorker::merge_upstream(self, remote, resolved)
+    }
+}
+
+/// The commit id in `git commit` output: `[main 1a2b3c4] subject`.
+pub fn committed_sha(output: &GitCommandOutput) -> Option<String> {
+    let summary = output.stdout.lines().next()?.strip_prefix('[')?;
+    let (head, _) = summary.split_once(']')?;
+    head.split_whitespace().last().map(str::to_string)
+}
+
+fn parse_path_log(output: &str) -> Vec<PathCommit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\0');
+            let commit = fields.next()?.to_string();
+            let author = fields.next()?.to_string();
+            let commit_time = fields.next()?.parse().ok()?;
+            let subject = fields.next().unwrap_or_default().to_string();
+            Some(PathCommit { commit, author, commit_time, subject })
+        })
+        .collect()
+}
+
+fn parse_blame_porcelain(output: &str) -> Vec<BlamedLine> {
+    // Author fields appear only on a commit's first line.
+    let mut authors: HashMap<String, (String, i64)> = HashMap::new();
+    let mut lines = Vec::new();
+    let mut current: Option<(String, u32)> = None;
+    for line in output.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            if let Some((commit, line_number)) = current.take() {
+                let (author, author_time) = authors.get(&commit).cloned().unwrap_or_default();
+                lines.push(BlamedLine {
+                    line_number,
+                    commit,
+                    author,
+                    author_time,
+                    content: content.to_string(),
+                });
+            }
+            continue;
+        }
+        match &current {
+            None => {
+                let mut fields = line.split(' ');
+                let (Some(commit), Some(_), Some(final_line)) =
+                    (fields.next(), fields.next(), fields.next())
+                else {
+                    continue;
+                };
+                let Ok(line_number) = final_line.parse() else {
+                    continue;
+                };
+                authors.entry(commit.to_string()).or_default();
+                current = Some((commit.to_string(), line_number));
+            }
+            Some((commit, _)) => {
+                let Some(entry) = authors.get_mut(commit) else {
+                    continue;
+                };
+                if let Some(author) = line.strip_prefix("author ") {
+                    entry.0 = author.to_string();
+                } else if let Some(time) = line.strip_prefix("author-time ") {
+                    entry.1 = time.parse().unwrap_or_default();
+                }
+            }
+        }
+    }
+    lines
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -258,6 +959,239 @@ mod tests {
         assert_eq!(calls[0].args, vec!["commit", "-m", "docs: update readme section"]);
     }
 
+    #[test]
+    fn commits_carry_committer_author_and_signing_overrides() {
+        let mock = MockExecutor::new(vec![ok("")]);
+        let mut worker = GitWorker::with_executor("/tmp/repo", mock.clone());
+        worker.set_commit_identity(
+            Some(CommitAuthor { name: "Scriptum".into(), email: "bot@example.com".into() }),
+            Some(CommitSigning {
+                format: SigningFormat::Ssh,
+                key: Some("/keys/signing.pub".into()),
+            }),
+        );
+        let ada = CommitAuthor { name: "Ada".into(), email: "ada@example.com".into() };
+
+        worker.commit_as("docs: update", Some(&ada)).expect("commit should succeed");
+        let calls = mock.calls();
+        assert_eq!(
+            calls[0].args,
+            vec![
+                "-c",
+                "committer.name=Scriptum",
+                "-c",
+                "committer.email=bot@example.com",
+                "-c",
+                "author.name=Ada",
+                "-c",
+                "author.email=ada@example.com",
+                "-c",
+                "gpg.format=ssh",
+                "-c",
+                "user.signingkey=/keys/signing.pub",
+                "commit",
+                "-S",
+                "-m",
+                "docs: update",
+            ]
+        );
+    }
+
+    #[test]
+    fn unsigned_commit_without_identity_is_plain() {
+        let mock = MockExecutor::new(vec![ok("")]);
+        let mut worker = GitWorker::with_executor("/tmp/repo", mock.clone());
+        worker.set_commit_identity(
+            None,
+            Some(CommitSigning { format: SigningFormat::Openpgp, key: None }),
+        );
+
+        worker.commit("docs: update").expect("commit should succeed");
+        assert_eq!(
+            mock.calls()[0].args,
+            vec!["-c", "gpg.format=openpgp", "commit", "-S", "-m", "docs: update"]
+        );
+    }
+
+    #[test]
+    fn parses_blame_porcelain_with_repeated_commits() {
+        let porcelain = "\
+aaaa 1 1 2
+author Ada
+author-mail <ada@example.com>
+author-time 1767225600
+filename docs/spec.md
+\t# Spec
+aaaa 2 2
+\t
+bbbb 3 3 1
+author Scriptum Bot
+author-time 1767312000
+previous aaaa docs/spec.md
+filename docs/spec.md
+\tnew line
+";
+        let lines = parse_blame_porcelain(porcelain);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            BlamedLine {
+                line_number: 2,
+                commit: "aaaa".into(),
+                author: "Ada".into(),
+                author_time: 1767225600,
+                content: String::new(),
+            }
+        );
+        assert_eq!(lines[2].author, "Scriptum Bot");
+        assert_eq!(lines[2].content, "new line");
+    }
+
+    #[test]
+    fn commits_touching_keeps_commits_after_since() {
+        let mock = MockExecutor::new(vec![ok(
+            "bbbb\0Ada\x001767312000\0auth: rotate keys\naaaa\0Bob\x001767225600\0auth: init\n",
+        )]);
+        let worker = GitWorker::with_executor("/tmp/repo", mock.clone());
+
+        let commits =
+            worker.commits_touching("src/auth.rs", 1767225600).expect("log should succeed");
+        assert_eq!(
+            commits,
+            vec![PathCommit {
+                commit: "bbbb".into(),
+                author: "Ada".into(),
+                commit_time: 1767312000,
+                subject: "auth: rotate keys".into(),
+            }]
+        );
+        assert_eq!(
+            mock.calls()[0].args,
+            vec![
+                "log",
+                "--since=@1767225600",
+                "--format=%H%x00%an%x00%ct%x00%s",
+                "--",
+                "src/auth.rs",
+            ]
+        );
+    }
+
+    #[test]
+    fn committed_sha_reads_commit_summary() {
+        let output =
+            |stdout: &str| GitCommandOutput { stdout: stdout.into(), stderr: String::new() };
+        assert_eq!(committed_sha(&output("[main 1a2b3c4] docs: x\n")).as_deref(), Some("1a2b3c4"));
+        assert_eq!(
+            committed_sha(&output("[main (root-commit) 9f8e7d6] init\n")).as_deref(),
+            Some("9f8e7d6")
+        );
+        assert_eq!(committed_sha(&output("nothing to commit\n")), None);
+    }
+
+    #[test]
+    fn missing_note_is_none() {
+        let mock = MockExecutor::new(vec![Ok(CommandResult {
+            success: false,
+            code: Some(1),
+            stdout: String::new(),
+            stderr: "error: no note found for object 1a2b3c4.\n".into(),
+        })]);
+        let worker = GitWorker::with_executor("/tmp/repo", mock.clone());
+        assert_eq!(worker.note("1a2b3c4").expect("show should succeed"), None);
+        assert_eq!(
+            mock.calls()[0].args,
+            vec!["notes", "--ref", "refs/notes/scriptum", "show", "1a2b3c4"]
+        );
+    }
+
+    fn ok(stdout: &str) -> Result<CommandResult, std::io::Error> {
+        Ok(CommandResult {
+            success: true,
+            code: Some(0),
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+        })
+    }
+
+    #[test]
+    fn upstream_changes_reads_base_and_remote_markdown() {
+        let mock = MockExecutor::new(vec![
+            ok(""),
+            ok("remote-sha\n"),
+            ok("base-sha\n"),
+            ok("M\tdocs/a.md\nA\tdocs/new.md\nM\tsrc/lib.rs\n"),
+            ok("old a\n"),
+            ok("new a\n"),
+            ok("Ada\0ada@example.com\n"),
+            ok("fresh\n"),
+            ok("Bob\0bob@example.com\n"),
+        ]);
+        let worker = GitWorker::with_executor("/tmp/repo", mock.clone());
+
+        let changes = worker.upstream_changes().expect("upstream changes").expect("changes");
+        assert_eq!(changes.base, "base-sha");
+        assert_eq!(changes.remote, "remote-sha");
+        assert_eq!(
+            changes.files,
+            vec![
+                UpstreamFile {
+                    path: "docs/a.md".to_string(),
+                    base: Some("old a\n".to_string()),
+                    remote: Some("new a\n".to_string()),
+                    author: Some(CommitAuthor {
+                        name: "Ada".to_string(),
+                        email: "ada@example.com".to_string(),
+                    }),
+                },
+                UpstreamFile {
+                    path: "docs/new.md".to_string(),
+                    base: None,
+                    remote: Some("fresh\n".to_string()),
+                    author: Some(CommitAuthor {
+                        name: "Bob".to_string(),
+                        email: "bob@example.com".to_string(),
+                    }),
+                },
+            ]
+        );
+
+        let calls = mock.calls();
+        assert_eq!(calls[0].args, vec!["fetch"]);
+        assert_eq!(calls[1].args, vec!["rev-parse", "@{u}"]);
+        assert_eq!(calls[2].args, vec!["merge-base", "HEAD", "remote-sha"]);
+        assert_eq!(calls[4].args, vec!["show", "base-sha:docs/a.md"]);
+        assert_eq!(
+            calls[6].args,
+            vec!["log", "-1", "--format=%an%x00%ae", "base-sha..remote-sha", "--", "docs/a.md"]
+        );
+    }
+
+    #[test]
+    fn changed_paths_lists_untracked_files_and_both_sides_of_renames() {
+        let mock = MockExecutor::new(vec![ok(
+            " M docs/a.md\0R  docs/new name.md\0docs/old.md\0?? notes/todo.md\0",
+        )]);
+        let worker = GitWorker::with_executor("/tmp/repo", mock.clone());
+
+        assert_eq!(
+            worker.changed_paths().expect("changed paths"),
+            vec!["docs/a.md", "docs/new name.md", "docs/old.md", "notes/todo.md"]
+        );
+        assert_eq!(
+            mock.calls()[0].args,
+            vec!["status", "--porcelain", "-z", "--untracked-files=all"]
+        );
+    }
+
+    #[test]
+    fn upstream_changes_is_none_when_upstream_is_merged() {
+        let mock = MockExecutor::new(vec![ok(""), ok("same\n"), ok("same\n")]);
+        let worker = GitWorker::with_executor("/tmp/repo", mock);
+
+        assert_eq!(worker.upstream_changes().expect("upstream changes"), None);
+    }
+
     #[test]
     fn diff_commands_use_cached_flags() {
         let mock = MockExecutor::new(vec![
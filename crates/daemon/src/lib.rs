@@ -4,6 +4,7 @@ pub mod agent;
 pub mod config;
 pub mod engine;
 pub mod git;
+pub mod guard;
 pub mod history;
 pub mod outbox;
 pub mod relay;
@@ -1,6 +1,8 @@
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::{
     atomic::{AtomicU64, Ordering},
-    Arc,
+    Arc, Mutex as StdMutex,
 };
 
 use anyhow::{Context, Result};
@@ -17,15 +19,37 @@ use tokio::{
     net::TcpListener,
     sync::{broadcast, Mutex},
 };
+use uuid::Uuid;
 use yrs::encoding::read::Cursor;
 use yrs::sync::{Awareness, DefaultProtocol, Message, MessageReader, Protocol, SyncMessage};
 use yrs::updates::decoder::{Decode, DecoderV1};
 use yrs::updates::encoder::Encode;
-use yrs::{Doc, GetString, ReadTxn, Transact, Update};
+use yrs::{Doc, GetString, ReadTxn, StateVector, Text, Transact, Update};
 
+use crate::config::GuardMode;
 use crate::engine::awareness::AwarenessProtocolWrapper;
+use crate::guard::{self, GuardAction, GuardReport};
 
 const UPDATE_BUFFER_SIZE: usize = 256;
+/// Broadcast sender id that no client has, so every client receives it.
+const SERVER_SENDER_ID: u64 = 0;
+
+/// Workspace guard policy for rooms named `<workspace_id>:<doc_id>`.
+pub trait YjsGuardPolicy: Send + Sync {
+    /// The workspace's secret guard mode for websocket updates.
+    fn room_guard_mode(
+        &self,
+        workspace_id: Uuid,
+    ) -> Pin<Box<dyn Future<Output = GuardMode> + Send + '_>>;
+
+    /// Record a guard outcome for an update to a workspace doc.
+    fn record_room_report<'a>(
+        &'a self,
+        workspace_id: Uuid,
+        doc_id: Uuid,
+        report: &'a GuardReport,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+}
 
 #[derive(Clone)]
 pub struct YjsWsState {
@@ -36,20 +60,50 @@ struct YjsWsStateInner {
     awareness: Mutex<Awareness>,
     updates_tx: broadcast::Sender<(u64, Vec<u8>)>,
     next_client_id: AtomicU64,
+    guard_mode: GuardMode,
+    guard_policy: Option<Arc<dyn YjsGuardPolicy>>,
+    guard_reports: StdMutex<Vec<GuardReport>>,
 }
 
 impl YjsWsState {
     pub fn new(doc: Doc) -> Self {
+        Self::with_guard_mode(doc, GuardMode::default())
+    }
+
+    /// A state whose incoming updates pass the secret guard in `mode`.
+    pub fn with_guard_mode(doc: Doc, guard_mode: GuardMode) -> Self {
+        Self::build(doc, guard_mode, None)
+    }
+
+    /// A state that checks updates to workspace rooms against `policy`.
+    /// Updates to other rooms use the default guard mode.
+    pub fn with_guard_policy(doc: Doc, policy: Arc<dyn YjsGuardPolicy>) -> Self {
+        Self::build(doc, GuardMode::default(), Some(policy))
+    }
+
+    fn build(
+        doc: Doc,
+        guard_mode: GuardMode,
+        guard_policy: Option<Arc<dyn YjsGuardPolicy>>,
+    ) -> Self {
         let (updates_tx, _) = broadcast::channel(UPDATE_BUFFER_SIZE);
         Self {
             inner: Arc::new(YjsWsStateInner {
                 awareness: Mutex::new(Awareness::new(doc)),
                 updates_tx,
                 next_client_id: AtomicU64::new(1),
+                guard_mode,
+                guard_policy,
+                guard_reports: StdMutex::new(Vec::new()),
             }),
         }
     }
 
+    /// Guard reports for updates that introduced secrets or PII, oldest first.
+    pub fn guard_reports(&self) -> Vec<GuardReport> {
+        self.inner.guard_reports.lock().expect("guard reports lock poisoned").clone()
+    }
+
     pub fn router(self) -> Router {
         Router::new()
             .route("/yjs", get(yjs_ws_route))
@@ -75,18 +129,24 @@ pub async fn serve(listener: TcpListener, state: YjsWsState) -> Result<()> {
 }
 
 async fn yjs_ws_route(ws: WebSocketUpgrade, State(state): State<YjsWsState>) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, None))
 }
 
 async fn yjs_ws_room_route(
     ws: WebSocketUpgrade,
-    Path(_room): Path<String>,
+    Path(room): Path<String>,
     State(state): State<YjsWsState>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    ws.on_upgrade(move |socket| handle_socket(socket, state, parse_room(&room)))
+}
+
+/// `<workspace_id>:<doc_id>` room names; None for any other room.
+fn parse_room(room: &str) -> Option<(Uuid, Uuid)> {
+    let (workspace_id, doc_id) = room.split_once(':')?;
+    Some((Uuid::parse_str(workspace_id).ok()?, Uuid::parse_str(doc_id).ok()?))
 }
 
-async fn handle_socket(mut socket: WebSocket, state: YjsWsState) {
+async fn handle_socket(mut socket: WebSocket, state: YjsWsState, room: Option<(Uuid, Uuid)>) {
     let client_id = state.inner.next_client_id.fetch_add(1, Ordering::Relaxed);
     let mut updates_rx = state.inner.updates_tx.subscribe();
 
@@ -99,7 +159,7 @@ async fn handle_socket(mut socket: WebSocket, state: YjsWsState) {
 
                 match message {
                     WsMessage::Binary(payload) => {
-                        if let Err(error) = process_incoming_binary(client_id, payload.as_ref(), &state, &mut socket).await {
+                        if let Err(error) = process_incoming_binary(client_id, payload.as_ref(), &state, room, &mut socket).await {
                             tracing::warn!(?error, "failed to process yjs websocket frame");
                             break;
                         }
@@ -133,12 +193,17 @@ async fn process_incoming_binary(
     client_id: u64,
     payload: &[u8],
     state: &YjsWsState,
+    room: Option<(Uuid, Uuid)>,
     socket: &mut WebSocket,
 ) -> Result<()> {
     let protocol = DefaultProtocol;
+    let guard_mode = state.guard_mode(room).await;
+    let mut guard_reports = Vec::new();
     let awareness_protocol = AwarenessProtocolWrapper::new();
     let mut responses = Vec::new();
     let mut broadcast_updates = Vec::new();
+    // Redaction edits the daemon makes itself; every client, sender included, gets them.
+    let mut server_updates = Vec::new();
 
     {
         let awareness = state.inner.awareness.lock().await;
@@ -160,6 +225,13 @@ async fn process_incoming_binary(
                     responses.push(Message::Sync(SyncMessage::SyncStep1(server_sv)).encode_v1());
                 }
                 Message::Sync(SyncMessage::SyncStep2(update)) => {
+                    let report = match guard_update(&awareness, &update, guard_mode)? {
+                        Some(report) if report.action == GuardAction::Blocked => {
+                            guard_reports.push(report);
+                            continue;
+                        }
+                        report => report,
+                    };
                     let decoded = Update::decode_v1(&update)
                         .context("failed to decode sync step 2 update")?;
                     protocol
@@ -168,14 +240,29 @@ async fn process_incoming_binary(
 
                     // Step-2 carries client updates during handshake; fan it out as regular updates.
                     broadcast_updates.push(Message::Sync(SyncMessage::Update(update)).encode_v1());
+                    if let Some(report) = report {
+                        server_updates.extend(redact_applied(&awareness, &report));
+                        guard_reports.push(report);
+                    }
                 }
                 Message::Sync(SyncMessage::Update(update)) => {
+                    let report = match guard_update(&awareness, &update, guard_mode)? {
+                        Some(report) if report.action == GuardAction::Blocked => {
+                            guard_reports.push(report);
+                            continue;
+                        }
+                        report => report,
+                    };
                     let decoded = Update::decode_v1(&update)
                         .context("failed to decode incremental update")?;
                     protocol
                         .handle_update(&awareness, decoded)
                         .context("failed to process incremental update")?;
                     broadcast_updates.push(Message::Sync(SyncMessage::Update(update)).encode_v1());
+                    if let Some(report) = report {
+                        server_updates.extend(redact_applied(&awareness, &report));
+                        guard_reports.push(report);
+                    }
                 }
                 message @ (Message::AwarenessQuery | Message::Awareness(_)) => {
                     let dispatch = awareness_protocol
@@ -207,6 +294,10 @@ async fn process_incoming_binary(
         }
     }
 
+    for report in guard_reports {
+        state.record_guard_report(room, report).await;
+    }
+
     for response in responses {
         socket
             .send(WsMessage::Binary(response.into()))
@@ -217,6 +308,77 @@ async fn process_incoming_binary(
     for update in broadcast_updates {
         let _ = state.inner.updates_tx.send((client_id, update));
     }
+    for update in server_updates {
+        let _ = state.inner.updates_tx.send((SERVER_SENDER_ID, update));
+    }
 
     Ok(())
 }
+
+impl YjsWsState {
+    async fn guard_mode(&self, room: Option<(Uuid, Uuid)>) -> GuardMode {
+        match (&self.inner.guard_policy, room) {
+            (Some(policy), Some((workspace_id, _))) => policy.room_guard_mode(workspace_id).await,
+            _ => self.inner.guard_mode,
+        }
+    }
+
+    async fn record_guard_report(&self, room: Option<(Uuid, Uuid)>, report: GuardReport) {
+        if let (Some(policy), Some((workspace_id, doc_id))) = (&self.inner.guard_policy, room) {
+            policy.record_room_report(workspace_id, doc_id, &report).await;
+        } else {
+            tracing::warn!(
+                findings = report.findings.len(),
+                action = report.action.as_str(),
+                "yjs update introduces secrets or PII"
+            );
+        }
+        self.inner.guard_reports.lock().expect("guard reports lock poisoned").push(report);
+    }
+}
+
+/// Secrets and PII `update` would introduce into the `content` text,
+/// checked on a fork so nothing is applied yet.
+fn guard_update(
+    awareness: &Awareness,
+    update: &[u8],
+    mode: GuardMode,
+) -> Result<Option<GuardReport>> {
+    let (before, state) = {
+        let txn = awareness.doc().transact();
+        let before = txn.get_text("content").map(|text| text.get_string(&txn)).unwrap_or_default();
+        (before, txn.encode_state_as_update_v1(&StateVector::default()))
+    };
+
+    let fork = Doc::new();
+    {
+        let mut txn = fork.transact_mut();
+        txn.apply_update(Update::decode_v1(&state).context("failed to decode doc state")?)
+            .context("failed to fork doc state")?;
+        txn.apply_update(Update::decode_v1(update).context("failed to decode guarded update")?)
+            .context("failed to apply update to forked doc")?;
+    }
+    let after = {
+        let txn = fork.transact();
+        txn.get_text("content").map(|text| text.get_string(&txn)).unwrap_or_default()
+    };
+
+    let findings = guard::new_findings(&before, &after);
+    Ok((!findings.is_empty()).then(|| GuardReport { action: mode.action(), findings }))
+}
+
+/// Redact a just-applied update's findings in the live doc. Returns the
+/// redaction as a sync update message, or None unless `report` redacts.
+fn redact_applied(awareness: &Awareness, report: &GuardReport) -> Option<Vec<u8>> {
+    if report.action != GuardAction::Redacted {
+        return None;
+    }
+    let doc = awareness.doc();
+    let text = doc.get_or_insert_text("content");
+    let mut txn = doc.transact_mut();
+    for finding in report.findings.iter().rev() {
+        text.remove_range(&mut txn, finding.start as u32, (finding.end - finding.start) as u32);
+        text.insert(&mut txn, finding.start as u32, &finding.replacement);
+    }
+    Some(Message::Sync(SyncMessage::Update(txn.encode_update_v1())).encode_v1())
+}
@@ -1,4 +1,5 @@
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{anyhow, Context, Result};
@@ -9,6 +10,7 @@ use tokio::net::UnixStream;
 use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 use tracing::{info, warn};
+use yrs::Doc;
 
 use crate::rpc::methods::RpcServerState;
 use crate::rpc::unix::serve_unix_until_shutdown;
@@ -56,7 +58,6 @@ pub async fn run_standalone() -> Result<()> {
 async fn run_standalone_with_paths(paths: DaemonPaths) -> Result<()> {
     let listener = bind_socket(&paths.socket_path).await?;
     write_pid_file(&paths.pid_path)?;
-    let yjs_ws_task = start_local_yjs_ws_server().await?;
 
     let (shutdown_tx, shutdown_rx) = broadcast::channel(4);
     let state = RpcServerState::default()
@@ -65,6 +66,7 @@ async fn run_standalone_with_paths(paths: DaemonPaths) -> Result<()> {
         .with_shutdown_notifier(shutdown_tx.clone());
     recover_state_from_crdt_store(&state, &paths.base_dir).await?;
     recover_registered_workspaces(&state).await;
+    let yjs_ws_task = start_local_yjs_ws_server(&state).await?;
     let ctrl_c_tx = shutdown_tx.clone();
     tokio::spawn(async move {
         let _ = tokio::signal::ctrl_c().await;
@@ -84,7 +86,6 @@ async fn start_embedded_with_paths(paths: DaemonPaths) -> Result<EmbeddedDaemonH
 
     let listener = bind_socket(&paths.socket_path).await?;
     write_pid_file(&paths.pid_path)?;
-    let yjs_ws_task = start_local_yjs_ws_server().await?;
 
     let (shutdown_tx, shutdown_rx) = broadcast::channel(4);
     let state = RpcServerState::default()
@@ -93,6 +94,7 @@ async fn start_embedded_with_paths(paths: DaemonPaths) -> Result<EmbeddedDaemonH
         .with_shutdown_notifier(shutdown_tx.clone());
     recover_state_from_crdt_store(&state, &paths.base_dir).await?;
     recover_registered_workspaces(&state).await;
+    let yjs_ws_task = start_local_yjs_ws_server(&state).await?;
     let socket_path = paths.socket_path.clone();
     let pid_path = paths.pid_path.clone();
     let task = tokio::spawn(async move {
@@ -205,14 +207,15 @@ async fn recover_registered_workspaces(state: &RpcServerState) {
     }
 }
 
-async fn start_local_yjs_ws_server() -> Result<JoinHandle<()>> {
+async fn start_local_yjs_ws_server(state: &RpcServerState) -> Result<JoinHandle<()>> {
     let listener = TcpListener::bind(LOCAL_YJS_WS_ADDR).await.with_context(|| {
         format!("failed to bind local yjs websocket server at `{LOCAL_YJS_WS_ADDR}`")
     })?;
     info!(address = LOCAL_YJS_WS_ADDR, "local yjs websocket server started");
 
+    let ws_state = YjsWsState::with_guard_policy(Doc::new(), Arc::new(state.clone()));
     Ok(tokio::spawn(async move {
-        if let Err(error) = yjs_ws::serve(listener, YjsWsState::default()).await {
+        if let Err(error) = yjs_ws::serve(listener, ws_state).await {
             warn!(?error, "local yjs websocket server terminated unexpectedly");
         }
     }))
@@ -0,0 +1,168 @@
+// Code reference extraction + SQLite persistence.
+//
+// Sections that cite source files (see scriptum_common::backlink::code_ref)
+// are indexed next to backlinks so staleness checks can find which files
+// each section depends on without re-parsing every document.
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use scriptum_common::backlink::{parse_code_refs, resolve_code_ref_path};
+use scriptum_common::section::parser::parse_sections;
+
+use crate::section::overlap::find_section_for_line;
+
+/// A code reference resolved to a repo-relative path and its section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedCodeRef {
+    pub source_doc_id: String,
+    pub section_id: String,
+    /// Repo-relative path of the referenced file.
+    pub path: String,
+    pub line: Option<u32>,
+}
+
+/// Extract the code references in `content`, the markdown of the document
+/// at `doc_path`. References outside any section, or that escape the repo
+/// root, are skipped.
+pub fn extract_code_refs(
+    source_doc_id: &str,
+    doc_path: &str,
+    content: &str,
+) -> Vec<IndexedCodeRef> {
+    let sections = parse_sections(content);
+    parse_code_refs(content)
+        .into_iter()
+        .filter_map(|code_ref| {
+            let line = content[..code_ref.start_offset].matches('\n').count() as u32 + 1;
+            let section = find_section_for_line(&sections, line)?;
+            Some(IndexedCodeRef {
+                source_doc_id: source_doc_id.to_string(),
+                section_id: section.id.clone(),
+                path: resolve_code_ref_path(doc_path, &code_ref)?,
+                line: code_ref.line,
+            })
+        })
+        .collect()
+}
+
+/// SQLite-backed code reference persistence.
+pub struct CodeRefStore<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> CodeRefStore<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Ensure code reference schema exists in the current database.
+    pub fn ensure_schema(&self) -> Result<()> {
+        self.conn
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS code_refs (
+                    source_doc_id    TEXT NOT NULL,
+                    section_id       TEXT NOT NULL,
+                    path             TEXT NOT NULL,
+                    line             INTEGER
+                );
+                CREATE INDEX IF NOT EXISTS code_refs_source_idx
+                    ON code_refs (source_doc_id);",
+            )
+            .context("failed to ensure code_refs schema")?;
+        Ok(())
+    }
+
+    /// Replace all code references for a source document.
+    pub fn replace_for_source(&self, source_doc_id: &str, refs: &[IndexedCodeRef]) -> Result<()> {
+        let tx = self
+            .conn
+            .unchecked_transaction()
+            .context("failed to start code_refs replacement transaction")?;
+
+        tx.execute("DELETE FROM code_refs WHERE source_doc_id = ?1", params![source_doc_id])
+            .context("failed to clear code refs for source doc")?;
+
+        for code_ref in refs {
+            tx.execute(
+                "INSERT INTO code_refs (source_doc_id, section_id, path, line)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![code_ref.source_doc_id, code_ref.section_id, code_ref.path, code_ref.line],
+            )
+            .context("failed to insert code ref")?;
+        }
+
+        tx.commit().context("failed to commit code_refs replacement transaction")?;
+        Ok(())
+    }
+
+    /// Code references from a source document, in insertion order.
+    pub fn for_source(&self, source_doc_id: &str) -> Result<Vec<IndexedCodeRef>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT source_doc_id, section_id, path, line
+                 FROM code_refs
+                 WHERE source_doc_id = ?1
+                 ORDER BY rowid",
+            )
+            .context("failed to prepare code refs query")?;
+
+        let rows = stmt
+            .query_map(params![source_doc_id], |row| {
+                Ok(IndexedCodeRef {
+                    source_doc_id: row.get(0)?,
+                    section_id: row.get(1)?,
+                    path: row.get(2)?,
+                    line: row.get(3)?,
+                })
+            })
+            .context("failed to query code refs")?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>().context("failed to collect code refs")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+
+    use super::{extract_code_refs, CodeRefStore};
+
+    #[test]
+    fn extracts_refs_with_their_sections() {
+        let content =
+            "# Spec\n\nIntro cites src/lib.rs:1.\n\n## Auth\n\nSee [login](../src/auth.rs#L12).\n";
+        let refs = extract_code_refs("doc-1", "docs/spec.md", content);
+
+        let summary: Vec<_> =
+            refs.iter().map(|r| (r.section_id.as_str(), r.path.as_str(), r.line)).collect();
+        assert_eq!(
+            summary,
+            vec![("spec", "src/lib.rs", Some(1)), ("spec/auth", "src/auth.rs", Some(12))]
+        );
+    }
+
+    #[test]
+    fn skips_refs_outside_sections() {
+        assert!(extract_code_refs("doc-1", "notes.md", "src/lib.rs:1\n").is_empty());
+    }
+
+    #[test]
+    fn stores_and_replaces_code_refs_in_sqlite_table() {
+        let conn = Connection::open_in_memory().expect("in-memory sqlite should open");
+        let store = CodeRefStore::new(&conn);
+        store.ensure_schema().expect("code_refs schema should be ensured");
+
+        let first = extract_code_refs("doc-1", "spec.md", "# A\n\nsrc/a.rs:3 [[src/b.rs]]\n");
+        store.replace_for_source("doc-1", &first).expect("initial code refs should store");
+        assert_eq!(store.for_source("doc-1").expect("code refs should query"), first);
+
+        let second = extract_code_refs("doc-1", "spec.md", "# A\n\nsrc/c.rs:1\n");
+        store.replace_for_source("doc-1", &second).expect("replacement code refs should store");
+
+        let stored = store.for_source("doc-1").expect("code refs should query");
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].path, "src/c.rs");
+        assert!(store.for_source("doc-2").expect("code refs should query").is_empty());
+    }
+}
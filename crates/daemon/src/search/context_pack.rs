@@ -0,0 +1,223 @@
+// Context packs: rank sections from many documents against a query and pack
+// the best of them into a token budget, each with a `path#section-id` citation.
+//
+// Candidates come from search hits, explicit seeds, backlinks into those
+// docs and the parent sections of everything picked. Expansion candidates
+// inherit a decayed score from the section that pulled them in.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+/// Score multiplier for a parent section pulled in by one of its children.
+pub const PARENT_DECAY: f64 = 0.5;
+/// Score multiplier for a section that links to a primary document.
+pub const BACKLINK_DECAY: f64 = 0.5;
+
+/// Why a section made it into the candidate set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackReason {
+    Seed,
+    Search,
+    Backlink,
+    Parent,
+}
+
+/// A section (or a whole doc without headings) that may go into a pack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackCandidate {
+    pub doc_id: String,
+    pub path: String,
+    pub section_id: Option<String>,
+    pub heading: Option<String>,
+    /// Position of the section in its doc; breaks score ties in doc order.
+    pub order: usize,
+    pub content: String,
+    pub score: f64,
+    pub reason: PackReason,
+}
+
+/// A candidate that fit into the budget.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PackedSection {
+    pub citation: String,
+    pub doc_id: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub section_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<String>,
+    pub reason: PackReason,
+    pub score: f64,
+    pub tokens: usize,
+    pub content_md: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackOutcome {
+    pub items: Vec<PackedSection>,
+    pub tokens_used: usize,
+    /// Candidates that did not fit the budget.
+    pub omitted: usize,
+}
+
+/// `path#section-id`, or just the path for a doc without sections.
+pub fn citation(path: &str, section_id: Option<&str>) -> String {
+    match section_id {
+        Some(section_id) => format!("{path}#{section_id}"),
+        None => path.to_string(),
+    }
+}
+
+/// Lowercased alphanumeric words of a query, deduplicated in order.
+pub fn query_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for term in query.split(|c: char| !c.is_alphanumeric()).filter(|term| !term.is_empty()) {
+        let term = term.to_lowercase();
+        if !terms.contains(&term) {
+            terms.push(term);
+        }
+    }
+    terms
+}
+
+/// Fraction of `terms` that occur in `text`, case-insensitively.
+pub fn term_score(text: &str, terms: &[String]) -> f64 {
+    if terms.is_empty() {
+        return 0.0;
+    }
+    let text = text.to_lowercase();
+    let matched = terms.iter().filter(|term| text.contains(term.as_str())).count();
+    matched as f64 / terms.len() as f64
+}
+
+/// Keep the best-scoring candidate per doc section.
+pub fn dedupe(candidates: Vec<PackCandidate>) -> Vec<PackCandidate> {
+    let mut best: HashMap<(String, Option<String>), PackCandidate> = HashMap::new();
+    for candidate in candidates {
+        let key = (candidate.doc_id.clone(), candidate.section_id.clone());
+        match best.get(&key) {
+            Some(existing) if existing.score >= candidate.score => {}
+            _ => {
+                best.insert(key, candidate);
+            }
+        }
+    }
+    best.into_values().collect()
+}
+
+/// Greedily pack the highest-scoring candidates that fit `token_budget`.
+///
+/// A candidate too large for the remaining budget is skipped rather than
+/// ending the pack, so smaller lower-ranked sections can still fill it.
+pub fn pack<F>(
+    candidates: Vec<PackCandidate>,
+    token_budget: usize,
+    token_counter: &F,
+) -> Result<PackOutcome, String>
+where
+    F: Fn(&str) -> Result<usize, String>,
+{
+    let mut candidates = dedupe(candidates);
+    candidates.sort_by(|left, right| {
+        right
+            .score
+            .total_cmp(&left.score)
+            .then_with(|| left.path.cmp(&right.path))
+            .then_with(|| left.order.cmp(&right.order))
+    });
+
+    let mut items = Vec::new();
+    let mut tokens_used = 0;
+    let mut omitted = 0;
+    for candidate in candidates {
+        let tokens = token_counter(&candidate.content)?;
+        if tokens_used + tokens > token_budget {
+            omitted += 1;
+            continue;
+        }
+        tokens_used += tokens;
+        items.push(PackedSection {
+            citation: citation(&candidate.path, candidate.section_id.as_deref()),
+            doc_id: candidate.doc_id,
+            path: candidate.path,
+            section_id: candidate.section_id,
+            heading: candidate.heading,
+            reason: candidate.reason,
+            score: candidate.score,
+            tokens,
+            content_md: candidate.content,
+        });
+    }
+    Ok(PackOutcome { items, tokens_used, omitted })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(doc: &str, section: &str, score: f64, content: &str) -> PackCandidate {
+        PackCandidate {
+            doc_id: doc.to_string(),
+            path: format!("docs/{doc}.md"),
+            section_id: Some(section.to_string()),
+            heading: Some(format!("## {section}")),
+            order: 0,
+            content: content.to_string(),
+            score,
+            reason: PackReason::Search,
+        }
+    }
+
+    fn word_count(text: &str) -> Result<usize, String> {
+        Ok(text.split_whitespace().count())
+    }
+
+    #[test]
+    fn query_terms_are_lowercased_and_deduplicated() {
+        assert_eq!(query_terms("Auth flow, auth FLOW!"), vec!["auth", "flow"]);
+        assert!(query_terms("  -- ").is_empty());
+    }
+
+    #[test]
+    fn term_score_is_fraction_of_matched_terms() {
+        let terms = query_terms("auth flow");
+        assert_eq!(term_score("The AUTH handshake", &terms), 0.5);
+        assert_eq!(term_score("auth flow", &terms), 1.0);
+        assert_eq!(term_score("unrelated", &terms), 0.0);
+    }
+
+    #[test]
+    fn dedupe_keeps_highest_score_per_section() {
+        let mut parent = candidate("a", "root", 0.25, "root");
+        parent.reason = PackReason::Parent;
+        let direct = candidate("a", "root", 0.75, "root");
+        let kept = dedupe(vec![parent, direct.clone()]);
+        assert_eq!(kept, vec![direct]);
+    }
+
+    #[test]
+    fn pack_skips_oversized_candidates_and_cites_sections() {
+        let outcome = pack(
+            vec![
+                candidate("a", "root/auth", 0.9, "one two three"),
+                candidate("b", "root/big", 0.8, "one two three four five six"),
+                candidate("c", "root/small", 0.1, "one"),
+            ],
+            5,
+            &word_count,
+        )
+        .expect("pack should succeed");
+
+        let citations = outcome.items.iter().map(|item| item.citation.as_str()).collect::<Vec<_>>();
+        assert_eq!(citations, vec!["docs/a.md#root/auth", "docs/c.md#root/small"]);
+        assert_eq!(outcome.tokens_used, 4);
+        assert_eq!(outcome.omitted, 1);
+    }
+
+    #[test]
+    fn citation_without_section_is_the_path() {
+        assert_eq!(citation("notes.md", None), "notes.md");
+    }
+}
@@ -1,9 +1,12 @@
 // Full-text search: FTS5 index behind abstraction layer.
 
 pub mod backlinks;
+pub mod code_refs;
+pub mod context_pack;
 pub mod fts;
 pub mod indexer;
 
 pub use backlinks::{resolve_wiki_links, BacklinkStore, LinkableDocument, ResolvedBacklink};
+pub use code_refs::{extract_code_refs, CodeRefStore, IndexedCodeRef};
 pub use fts::{Fts5Index, IndexEntry, SearchHit, SearchIndex};
 pub use indexer::{extract_title, IndexUpdater};
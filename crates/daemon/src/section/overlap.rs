@@ -84,7 +84,7 @@ pub fn detect_overlaps(
 /// Sections are matched by their [start_line, end_line) range. When
 /// multiple sections contain the line (e.g., a heading and its parent),
 /// the deepest (highest level number) is returned.
-fn find_section_for_line(sections: &[Section], line: u32) -> Option<&Section> {
+pub(crate) fn find_section_for_line(sections: &[Section], line: u32) -> Option<&Section> {
     sections.iter().filter(|s| line >= s.start_line && line < s.end_line).max_by_key(|s| s.level)
 }
 
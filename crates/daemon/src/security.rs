@@ -24,6 +24,8 @@ pub enum SecretSlot {
     ApiKey,
     GitCredentials,
     RelayToken,
+    /// `user.signingkey` for signed daemon commits.
+    CommitSigningKey,
 }
 
 impl SecretSlot {
@@ -32,6 +34,7 @@ impl SecretSlot {
             Self::ApiKey => "api_key",
             Self::GitCredentials => "git_credentials",
             Self::RelayToken => "relay_token",
+            Self::CommitSigningKey => "commit_signing_key",
         }
     }
 }
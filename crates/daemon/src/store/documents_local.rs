@@ -1,6 +1,7 @@
 // documents_local table access: create, update, read, list.
 //
-// Tracks local file projection state for each document in a workspace.
+// Tracks local file projection state for each document in a workspace, and
+// each document's selective git sync override.
 
 use anyhow::{Context, Result};
 use rusqlite::{params, Connection};
@@ -74,6 +75,14 @@ impl DocumentsLocalStore {
         Ok(changed > 0)
     }
 
+    /// Delete a local document row by `doc_id`.
+    pub fn delete(conn: &Connection, doc_id: &str) -> Result<bool> {
+        let changed = conn
+            .execute("DELETE FROM documents_local WHERE doc_id = ?1", params![doc_id])
+            .context("failed to delete documents_local row")?;
+        Ok(changed > 0)
+    }
+
     /// Fetch a local document row by `doc_id`.
     pub fn get_by_doc_id(conn: &Connection, doc_id: &str) -> Result<Option<LocalDocumentRecord>> {
         let mut stmt = conn
@@ -119,6 +128,49 @@ impl DocumentsLocalStore {
         rows.collect::<std::result::Result<Vec<_>, _>>()
             .context("failed to collect documents_local rows")
     }
+
+    /// Set or clear a document's git sync override (`include` or
+    /// `exclude`). A document without a row yet gets one with empty
+    /// projection state.
+    pub fn set_git_sync(
+        conn: &Connection,
+        doc_id: &str,
+        workspace_id: &str,
+        abs_path: &str,
+        git_sync: Option<&str>,
+    ) -> Result<()> {
+        conn.execute(
+            "INSERT INTO documents_local \
+             (doc_id, workspace_id, abs_path, line_ending_style, last_fs_mtime_ns, \
+              last_content_hash, projection_rev, git_sync) \
+             VALUES (?1, ?2, ?3, 'lf', 0, '', 0, ?4) \
+             ON CONFLICT(doc_id) DO UPDATE SET git_sync = excluded.git_sync",
+            params![doc_id, workspace_id, abs_path, git_sync],
+        )
+        .context("failed to set documents_local git sync override")?;
+        Ok(())
+    }
+
+    /// `(doc_id, override)` for each document of a workspace that has one.
+    pub fn git_sync_overrides(
+        conn: &Connection,
+        workspace_id: &str,
+    ) -> Result<Vec<(String, String)>> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT doc_id, git_sync FROM documents_local \
+                 WHERE workspace_id = ?1 AND git_sync IS NOT NULL \
+                 ORDER BY doc_id ASC",
+            )
+            .context("failed to prepare documents_local git sync query")?;
+
+        let rows = stmt
+            .query_map(params![workspace_id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .context("failed to query documents_local git sync overrides")?;
+
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .context("failed to collect documents_local git sync overrides")
+    }
 }
 
 fn row_to_record(row: &rusqlite::Row<'_>) -> rusqlite::Result<LocalDocumentRecord> {
@@ -239,6 +291,26 @@ mod tests {
         cleanup(&path);
     }
 
+    #[test]
+    fn delete_removes_row_by_doc_id() {
+        let (db, path) = setup();
+        DocumentsLocalStore::insert(db.connection(), &rec("doc-1", "ws-1", "/repo/a.md", 1))
+            .expect("seed insert should succeed");
+
+        assert!(
+            DocumentsLocalStore::delete(db.connection(), "doc-1").expect("delete should succeed")
+        );
+        assert!(
+            !DocumentsLocalStore::delete(db.connection(), "doc-1").expect("delete should succeed")
+        );
+        assert!(DocumentsLocalStore::get_by_doc_id(db.connection(), "doc-1")
+            .expect("query should succeed")
+            .is_none());
+
+        drop(db);
+        cleanup(&path);
+    }
+
     #[test]
     fn get_missing_row_returns_none() {
         let (db, path) = setup();
@@ -250,6 +322,46 @@ mod tests {
         cleanup(&path);
     }
 
+    #[test]
+    fn git_sync_overrides_upsert_and_survive_projection_updates() {
+        let (db, path) = setup();
+        let conn = db.connection();
+        DocumentsLocalStore::insert(conn, &rec("doc-a", "ws-1", "/repo/docs/a.md", 1)).unwrap();
+        DocumentsLocalStore::set_git_sync(
+            conn,
+            "doc-a",
+            "ws-1",
+            "/repo/docs/a.md",
+            Some("exclude"),
+        )
+        .unwrap();
+        DocumentsLocalStore::set_git_sync(
+            conn,
+            "doc-b",
+            "ws-1",
+            "/repo/docs/b.md",
+            Some("include"),
+        )
+        .unwrap();
+        DocumentsLocalStore::update(conn, &rec("doc-a", "ws-1", "/repo/docs/a.md", 2)).unwrap();
+
+        assert_eq!(
+            DocumentsLocalStore::git_sync_overrides(conn, "ws-1").unwrap(),
+            vec![
+                ("doc-a".to_string(), "exclude".to_string()),
+                ("doc-b".to_string(), "include".to_string())
+            ]
+        );
+        let created = DocumentsLocalStore::get_by_doc_id(conn, "doc-b").unwrap().unwrap();
+        assert_eq!(created.abs_path, "/repo/docs/b.md");
+
+        DocumentsLocalStore::set_git_sync(conn, "doc-a", "ws-1", "/repo/docs/a.md", None).unwrap();
+        assert_eq!(DocumentsLocalStore::git_sync_overrides(conn, "ws-1").unwrap().len(), 1);
+
+        drop(db);
+        cleanup(&path);
+    }
+
     #[test]
     fn list_by_workspace_returns_only_matching_rows() {
         let (db, path) = setup();
@@ -0,0 +1,145 @@
+// doc_findings table access: secrets and PII the guard found in incoming edits.
+//
+// Rows keep the finding kind, line and masked preview, never the value.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+
+/// A row in the `doc_findings` table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocFindingRecord {
+    pub id: i64,
+    pub workspace_id: String,
+    /// None when the guard blocked the creation of a new doc.
+    pub doc_id: Option<String>,
+    pub path: String,
+    pub kind: String,
+    pub line: u32,
+    pub preview: String,
+    /// Where the edit came from: `rpc`, `watcher` or `yjs_ws`.
+    pub source: String,
+    /// `warned`, `blocked` or `redacted`.
+    pub action: String,
+    pub agent_id: Option<String>,
+    pub ts: DateTime<Utc>,
+}
+
+/// Insert and query operations for `doc_findings`.
+pub struct FindingStore;
+
+impl FindingStore {
+    /// Insert a finding; `record.id` is ignored. Returns the new row id.
+    pub fn record(conn: &Connection, record: &DocFindingRecord) -> Result<i64> {
+        conn.execute(
+            "INSERT INTO doc_findings \
+             (workspace_id, doc_id, path, kind, line, preview, source, action, agent_id, ts) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                record.workspace_id,
+                record.doc_id,
+                record.path,
+                record.kind,
+                record.line,
+                record.preview,
+                record.source,
+                record.action,
+                record.agent_id,
+                record.ts.to_rfc3339(),
+            ],
+        )
+        .context("failed to insert doc finding")?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// List a workspace's findings, optionally for one doc, newest first.
+    pub fn list(
+        conn: &Connection,
+        workspace_id: &str,
+        doc_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<DocFindingRecord>> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, workspace_id, doc_id, path, kind, line, preview, source, action, \
+                        agent_id, ts \
+                 FROM doc_findings \
+                 WHERE workspace_id = ?1 AND (?2 IS NULL OR doc_id = ?2) \
+                 ORDER BY ts DESC, id DESC LIMIT ?3",
+            )
+            .context("failed to prepare doc findings query")?;
+
+        let rows = stmt
+            .query_map(params![workspace_id, doc_id, limit as i64], row_to_record)
+            .context("failed to query doc findings")?;
+
+        rows.collect::<std::result::Result<Vec<_>, _>>().context("failed to collect doc findings")
+    }
+}
+
+fn row_to_record(row: &rusqlite::Row<'_>) -> rusqlite::Result<DocFindingRecord> {
+    let ts_raw: String = row.get(10)?;
+    let ts = ts_raw.parse::<DateTime<Utc>>().map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(10, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+
+    Ok(DocFindingRecord {
+        id: row.get(0)?,
+        workspace_id: row.get(1)?,
+        doc_id: row.get(2)?,
+        path: row.get(3)?,
+        kind: row.get(4)?,
+        line: row.get(5)?,
+        preview: row.get(6)?,
+        source: row.get(7)?,
+        action: row.get(8)?,
+        agent_id: row.get(9)?,
+        ts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::meta_db::MetaDb;
+
+    fn finding(doc_id: &str, kind: &str, ts: DateTime<Utc>) -> DocFindingRecord {
+        DocFindingRecord {
+            id: 0,
+            workspace_id: "ws-1".into(),
+            doc_id: Some(doc_id.into()),
+            path: format!("docs/{doc_id}.md"),
+            kind: kind.into(),
+            line: 3,
+            preview: "AKIA…".into(),
+            source: "rpc".into(),
+            action: "warned".into(),
+            agent_id: Some("claude-1".into()),
+            ts,
+        }
+    }
+
+    #[test]
+    fn findings_are_listed_newest_first_and_filtered_by_doc() {
+        let db = MetaDb::open(":memory:").expect("meta db should open");
+        let conn = db.connection();
+        let now = Utc::now();
+        FindingStore::record(conn, &finding("doc-a", "aws_access_key", now)).unwrap();
+        FindingStore::record(conn, &finding("doc-b", "jwt", now)).unwrap();
+        FindingStore::record(
+            conn,
+            &finding("doc-a", "github_token", now + chrono::Duration::seconds(1)),
+        )
+        .unwrap();
+
+        let all = FindingStore::list(conn, "ws-1", None, 10).unwrap();
+        assert_eq!(
+            all.iter().map(|record| record.kind.as_str()).collect::<Vec<_>>(),
+            vec!["github_token", "jwt", "aws_access_key"]
+        );
+        let doc_a = FindingStore::list(conn, "ws-1", Some("doc-a"), 10).unwrap();
+        assert_eq!(doc_a.len(), 2);
+        assert_eq!(doc_a[1].agent_id.as_deref(), Some("claude-1"));
+        assert!(FindingStore::list(conn, "ws-2", None, 10).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,298 @@
+// git_sync_jobs table access: the per-workspace queue of git commit/push jobs.
+//
+// Jobs run oldest first. A job left `running` or `retrying` by a crashed
+// daemon is picked up again; `committed` makes that resume with the push.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use scriptum_common::types::GitJobState;
+
+/// A row in the `git_sync_jobs` table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitJobRecord {
+    pub job_id: String,
+    pub workspace_id: String,
+    pub state: GitJobState,
+    /// `commit` or `commit_and_push`.
+    pub action: String,
+    pub message: String,
+    pub trigger_type: Option<String>,
+    /// Session branch the job commits to; None commits to the checked-out
+    /// branch.
+    pub branch: Option<String>,
+    /// The commit step is done; only the push is left.
+    pub committed: bool,
+    pub attempt_count: u32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// Exit code of the failed git command, or `spawn_failed`.
+    pub last_error_code: Option<String>,
+    pub last_error_message: Option<String>,
+    pub last_error_stderr: Option<String>,
+    /// Pull request opened or updated for `branch`.
+    pub pull_request_url: Option<String>,
+    /// JSON `NoteTrigger` for the commit's git note, when a trigger fired.
+    pub trigger_context: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl GitJobRecord {
+    pub fn queued(
+        job_id: String,
+        workspace_id: String,
+        action: &str,
+        message: String,
+        trigger_type: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            job_id,
+            workspace_id,
+            state: GitJobState::Queued,
+            action: action.to_string(),
+            message,
+            trigger_type,
+            branch: None,
+            committed: false,
+            attempt_count: 0,
+            next_attempt_at: None,
+            last_error_code: None,
+            last_error_message: None,
+            last_error_stderr: None,
+            pull_request_url: None,
+            trigger_context: None,
+            created_at: now,
+            updated_at: now,
+            finished_at: None,
+        }
+    }
+}
+
+const SELECT_COLUMNS: &str = "job_id, workspace_id, state, action, message, trigger_type, \
+     committed, attempt_count, next_attempt_at, last_error_code, last_error_message, \
+     last_error_stderr, created_at, updated_at, finished_at, branch, pull_request_url, \
+     trigger_context";
+
+/// Queue operations for `git_sync_jobs`.
+pub struct GitJobStore;
+
+impl GitJobStore {
+    pub fn insert(conn: &Connection, record: &GitJobRecord) -> Result<()> {
+        conn.execute(
+            "INSERT INTO git_sync_jobs \
+             (job_id, workspace_id, state, action, message, trigger_type, committed, \
+              attempt_count, next_attempt_at, last_error_code, last_error_message, \
+              last_error_stderr, created_at, updated_at, finished_at, branch, \
+              pull_request_url, trigger_context) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, \
+                     ?18)",
+            params![
+                record.job_id,
+                record.workspace_id,
+                record.state.as_str(),
+                record.action,
+                record.message,
+                record.trigger_type,
+                record.committed,
+                record.attempt_count,
+                record.next_attempt_at.map(|ts| ts.to_rfc3339()),
+                record.last_error_code,
+                record.last_error_message,
+                record.last_error_stderr,
+                record.created_at.to_rfc3339(),
+                record.updated_at.to_rfc3339(),
+                record.finished_at.map(|ts| ts.to_rfc3339()),
+                record.branch,
+                record.pull_request_url,
+                record.trigger_context,
+            ],
+        )
+        .context("failed to insert git sync job")?;
+        Ok(())
+    }
+
+    /// Write back a job's progress: state, attempts, errors and timestamps.
+    pub fn update(conn: &Connection, record: &GitJobRecord) -> Result<()> {
+        conn.execute(
+            "UPDATE git_sync_jobs SET state = ?2, committed = ?3, attempt_count = ?4, \
+             next_attempt_at = ?5, last_error_code = ?6, last_error_message = ?7, \
+             last_error_stderr = ?8, updated_at = ?9, finished_at = ?10, \
+             pull_request_url = ?11 \
+             WHERE job_id = ?1",
+            params![
+                record.job_id,
+                record.state.as_str(),
+                record.committed,
+                record.attempt_count,
+                record.next_attempt_at.map(|ts| ts.to_rfc3339()),
+                record.last_error_code,
+                record.last_error_message,
+                record.last_error_stderr,
+                record.updated_at.to_rfc3339(),
+                record.finished_at.map(|ts| ts.to_rfc3339()),
+                record.pull_request_url,
+            ],
+        )
+        .context("failed to update git sync job")?;
+        Ok(())
+    }
+
+    pub fn get(conn: &Connection, job_id: &str) -> Result<Option<GitJobRecord>> {
+        conn.query_row(
+            &format!("SELECT {SELECT_COLUMNS} FROM git_sync_jobs WHERE job_id = ?1"),
+            params![job_id],
+            row_to_record,
+        )
+        .optional()
+        .context("failed to query git sync job")
+    }
+
+    /// A workspace's jobs, optionally in one state, newest first.
+    pub fn list(
+        conn: &Connection,
+        workspace_id: &str,
+        state: Option<GitJobState>,
+        limit: usize,
+    ) -> Result<Vec<GitJobRecord>> {
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {SELECT_COLUMNS} FROM git_sync_jobs \
+                 WHERE workspace_id = ?1 AND (?2 IS NULL OR state = ?2) \
+                 ORDER BY created_at DESC, rowid DESC LIMIT ?3"
+            ))
+            .context("failed to prepare git sync jobs query")?;
+
+        let rows = stmt
+            .query_map(
+                params![workspace_id, state.map(GitJobState::as_str), limit as i64],
+                row_to_record,
+            )
+            .context("failed to query git sync jobs")?;
+
+        rows.collect::<std::result::Result<Vec<_>, _>>().context("failed to collect git sync jobs")
+    }
+
+    /// The oldest job of a workspace that has not finished yet.
+    pub fn next_pending(conn: &Connection, workspace_id: &str) -> Result<Option<GitJobRecord>> {
+        conn.query_row(
+            &format!(
+                "SELECT {SELECT_COLUMNS} FROM git_sync_jobs \
+                 WHERE workspace_id = ?1 AND state IN ('queued', 'running', 'retrying') \
+                 ORDER BY created_at ASC, rowid ASC LIMIT 1"
+            ),
+            params![workspace_id],
+            row_to_record,
+        )
+        .optional()
+        .context("failed to query pending git sync job")
+    }
+}
+
+fn parse_ts(row: &rusqlite::Row<'_>, index: usize) -> rusqlite::Result<DateTime<Utc>> {
+    let raw: String = row.get(index)?;
+    raw.parse::<DateTime<Utc>>().map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+fn parse_optional_ts(
+    row: &rusqlite::Row<'_>,
+    index: usize,
+) -> rusqlite::Result<Option<DateTime<Utc>>> {
+    match row.get::<_, Option<String>>(index)? {
+        Some(_) => parse_ts(row, index).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn row_to_record(row: &rusqlite::Row<'_>) -> rusqlite::Result<GitJobRecord> {
+    let state_raw: String = row.get(2)?;
+    let state = GitJobState::parse(&state_raw).ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(
+            2,
+            rusqlite::types::Type::Text,
+            format!("unknown git job state `{state_raw}`").into(),
+        )
+    })?;
+
+    Ok(GitJobRecord {
+        job_id: row.get(0)?,
+        workspace_id: row.get(1)?,
+        state,
+        action: row.get(3)?,
+        message: row.get(4)?,
+        trigger_type: row.get(5)?,
+        branch: row.get(15)?,
+        committed: row.get(6)?,
+        attempt_count: row.get(7)?,
+        next_attempt_at: parse_optional_ts(row, 8)?,
+        last_error_code: row.get(9)?,
+        last_error_message: row.get(10)?,
+        last_error_stderr: row.get(11)?,
+        pull_request_url: row.get(16)?,
+        trigger_context: row.get(17)?,
+        created_at: parse_ts(row, 12)?,
+        updated_at: parse_ts(row, 13)?,
+        finished_at: parse_optional_ts(row, 14)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::meta_db::MetaDb;
+
+    fn job(job_id: &str, workspace_id: &str, created_at: DateTime<Utc>) -> GitJobRecord {
+        GitJobRecord::queued(
+            job_id.into(),
+            workspace_id.into(),
+            "commit_and_push",
+            "docs: update".into(),
+            Some("checkpoint".into()),
+            created_at,
+        )
+    }
+
+    #[test]
+    fn jobs_round_trip_and_pending_runs_oldest_first() {
+        let db = MetaDb::open(":memory:").expect("meta db should open");
+        let conn = db.connection();
+        let now = Utc::now();
+        GitJobStore::insert(conn, &job("job-a", "ws-1", now)).unwrap();
+        GitJobStore::insert(conn, &job("job-b", "ws-1", now + chrono::Duration::seconds(1)))
+            .unwrap();
+        GitJobStore::insert(conn, &job("job-c", "ws-2", now)).unwrap();
+
+        let mut first = GitJobStore::next_pending(conn, "ws-1").unwrap().expect("pending job");
+        assert_eq!(first.job_id, "job-a");
+        first.state = GitJobState::Failed;
+        first.committed = true;
+        first.attempt_count = 3;
+        first.last_error_code = Some("1".into());
+        first.last_error_stderr = Some("rejected: non-fast-forward".into());
+        first.finished_at = Some(now);
+        first.pull_request_url = Some("https://forge.example/pr/1".into());
+        GitJobStore::update(conn, &first).unwrap();
+
+        assert_eq!(GitJobStore::get(conn, "job-a").unwrap(), Some(first));
+        assert_eq!(GitJobStore::next_pending(conn, "ws-1").unwrap().unwrap().job_id, "job-b");
+        assert!(GitJobStore::get(conn, "missing").unwrap().is_none());
+
+        let all = GitJobStore::list(conn, "ws-1", None, 10).unwrap();
+        assert_eq!(
+            all.iter().map(|record| record.job_id.as_str()).collect::<Vec<_>>(),
+            vec!["job-b", "job-a"]
+        );
+        let mut branched = job("job-d", "ws-3", now);
+        branched.branch = Some("scriptum/claude/2026-01-01".into());
+        branched.trigger_context = Some(r#"{"type":"lease_released"}"#.into());
+        GitJobStore::insert(conn, &branched).unwrap();
+        assert_eq!(GitJobStore::get(conn, "job-d").unwrap(), Some(branched));
+
+        let failed = GitJobStore::list(conn, "ws-1", Some(GitJobState::Failed), 10).unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].trigger_type.as_deref(), Some("checkpoint"));
+    }
+}
@@ -94,7 +94,166 @@ CREATE INDEX agent_leases_lookup_idx
     ON agent_leases (workspace_id, doc_id, section_id);
 "#;
 
-const MIGRATIONS: &[(i64, &str)] = &[(1, MIGRATION_V1_SQL), (2, MIGRATION_V2_SQL)];
+const MIGRATION_V3_SQL: &str = r#"
+ALTER TABLE agent_recent_edits ADD COLUMN summary TEXT NULL;
+"#;
+
+const MIGRATION_V4_SQL: &str = r#"
+CREATE TABLE agent_policy_violations (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    workspace_id    TEXT NOT NULL,
+    doc_id          TEXT NULL,
+    agent_id        TEXT NOT NULL,
+    rule            TEXT NOT NULL,
+    reason          TEXT NOT NULL,
+    ts              TEXT NOT NULL
+);
+
+CREATE INDEX agent_policy_violations_agent_idx
+    ON agent_policy_violations (agent_id, ts);
+"#;
+
+const MIGRATION_V5_SQL: &str = r#"
+CREATE TABLE doc_findings (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    workspace_id    TEXT NOT NULL,
+    doc_id          TEXT NULL,
+    path            TEXT NOT NULL,
+    kind            TEXT NOT NULL,
+    line            INTEGER NOT NULL,
+    preview         TEXT NOT NULL,
+    source          TEXT NOT NULL,
+    action          TEXT NOT NULL,
+    agent_id        TEXT NULL,
+    ts              TEXT NOT NULL
+);
+
+CREATE INDEX doc_findings_workspace_idx
+    ON doc_findings (workspace_id, ts);
+"#;
+
+const MIGRATION_V6_SQL: &str = r#"
+CREATE TABLE agent_notes (
+    workspace_id    TEXT NOT NULL,
+    agent_id        TEXT NOT NULL,
+    key             TEXT NOT NULL,
+    value           TEXT NOT NULL,
+    doc_id          TEXT NULL,
+    section_id      TEXT NULL,
+    updated_at      TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, agent_id, key)
+);
+
+CREATE TABLE agent_journal (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    workspace_id    TEXT NOT NULL,
+    agent_id        TEXT NOT NULL,
+    entry           TEXT NOT NULL,
+    doc_id          TEXT NULL,
+    section_id      TEXT NULL,
+    ts              TEXT NOT NULL
+);
+
+CREATE INDEX agent_journal_agent_idx
+    ON agent_journal (workspace_id, agent_id, ts);
+"#;
+
+// SQLite cannot alter a CHECK constraint, so the lease table is rebuilt to
+// admit `planned` soft leases.
+const MIGRATION_V7_SQL: &str = r#"
+CREATE TABLE agent_leases_v7 (
+    workspace_id    TEXT NOT NULL,
+    doc_id          TEXT NOT NULL,
+    section_id      TEXT NOT NULL,
+    agent_id        TEXT NOT NULL,
+    ttl_sec         INTEGER NOT NULL,
+    mode            TEXT NOT NULL CHECK (mode IN ('exclusive', 'shared', 'planned')),
+    note            TEXT NULL,
+    expires_at      TEXT NOT NULL,
+    PRIMARY KEY (workspace_id, doc_id, section_id, agent_id)
+);
+
+INSERT INTO agent_leases_v7
+    SELECT workspace_id, doc_id, section_id, agent_id, ttl_sec, mode, note, expires_at
+    FROM agent_leases;
+
+DROP TABLE agent_leases;
+ALTER TABLE agent_leases_v7 RENAME TO agent_leases;
+
+CREATE INDEX agent_leases_expires_idx
+    ON agent_leases (expires_at);
+
+CREATE INDEX agent_leases_lookup_idx
+    ON agent_leases (workspace_id, doc_id, section_id);
+"#;
+
+// `git_sync_jobs` becomes a work queue: each row carries its action and
+// enough progress to resume after a restart.
+const MIGRATION_V8_SQL: &str = r#"
+ALTER TABLE git_sync_jobs ADD COLUMN action TEXT NOT NULL DEFAULT 'commit';
+ALTER TABLE git_sync_jobs ADD COLUMN message TEXT NOT NULL DEFAULT '';
+ALTER TABLE git_sync_jobs ADD COLUMN trigger_type TEXT NULL;
+ALTER TABLE git_sync_jobs ADD COLUMN committed INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE git_sync_jobs ADD COLUMN last_error_stderr TEXT NULL;
+ALTER TABLE git_sync_jobs ADD COLUMN finished_at TEXT NULL;
+
+CREATE INDEX git_sync_jobs_workspace_idx
+    ON git_sync_jobs (workspace_id, created_at);
+"#;
+
+const MIGRATION_V9_SQL: &str = r#"
+CREATE TABLE reconciliation_items (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    workspace_id    TEXT NOT NULL,
+    doc_id          TEXT NOT NULL,
+    path            TEXT NOT NULL,
+    section_id      TEXT NULL,
+    start_line      INTEGER NOT NULL,
+    base_content    TEXT NOT NULL,
+    local_content   TEXT NOT NULL,
+    remote_content  TEXT NOT NULL,
+    remote_author   TEXT NULL,
+    remote_commit   TEXT NOT NULL,
+    created_at      TEXT NOT NULL,
+    resolution      TEXT NULL,
+    resolved_at     TEXT NULL,
+    UNIQUE (doc_id, remote_commit, start_line)
+);
+
+CREATE INDEX reconciliation_items_workspace_idx
+    ON reconciliation_items (workspace_id, created_at);
+"#;
+
+// Branch strategies: a job may commit to a session branch and open a PR.
+const MIGRATION_V10_SQL: &str = r#"
+ALTER TABLE git_sync_jobs ADD COLUMN branch TEXT NULL;
+ALTER TABLE git_sync_jobs ADD COLUMN pull_request_url TEXT NULL;
+"#;
+
+// Per-document selective git sync: `include`, `exclude` or NULL.
+const MIGRATION_V11_SQL: &str = r#"
+ALTER TABLE documents_local ADD COLUMN git_sync TEXT NULL;
+"#;
+
+// Git notes: the trigger context a queued commit's note records.
+const MIGRATION_V12_SQL: &str = r#"
+ALTER TABLE git_sync_jobs ADD COLUMN trigger_context TEXT NULL;
+"#;
+
+const MIGRATIONS: &[(i64, &str)] = &[
+    (1, MIGRATION_V1_SQL),
+    (2, MIGRATION_V2_SQL),
+    (3, MIGRATION_V3_SQL),
+    (4, MIGRATION_V4_SQL),
+    (5, MIGRATION_V5_SQL),
+    (6, MIGRATION_V6_SQL),
+    (7, MIGRATION_V7_SQL),
+    (8, MIGRATION_V8_SQL),
+    (9, MIGRATION_V9_SQL),
+    (10, MIGRATION_V10_SQL),
+    (11, MIGRATION_V11_SQL),
+    (12, MIGRATION_V12_SQL),
+];
 
 #[derive(Debug)]
 pub struct MetaDb {
@@ -226,9 +385,14 @@ mod tests {
         "agent_sessions",
         "agent_recent_edits",
         "agent_leases",
+        "agent_policy_violations",
+        "agent_notes",
+        "agent_journal",
+        "doc_findings",
         "git_sync_config",
         "git_sync_jobs",
         "outbox_updates",
+        "reconciliation_items",
     ];
 
     #[test]
@@ -249,7 +413,7 @@ mod tests {
             assert_eq!(exists, 1, "expected `{table}` table to exist");
         }
 
-        assert_eq!(db.schema_version().expect("schema version should be readable"), 2);
+        assert_eq!(db.schema_version().expect("schema version should be readable"), 12);
 
         drop(db);
         cleanup_sqlite_files(&db_path);
@@ -260,7 +424,7 @@ mod tests {
         let db_path = unique_temp_db_path("meta-db-idempotent");
         {
             let first = MetaDb::open(&db_path).expect("first open should succeed");
-            assert_eq!(first.schema_version().expect("schema version should be readable"), 2);
+            assert_eq!(first.schema_version().expect("schema version should be readable"), 12);
         }
 
         let second = MetaDb::open(&db_path).expect("second open should succeed");
@@ -268,19 +432,19 @@ mod tests {
             .connection()
             .query_row("SELECT COUNT(*) FROM schema_migrations", [], |row| row.get(0))
             .expect("schema migration count query should succeed");
-        assert_eq!(migration_rows, 2);
+        assert_eq!(migration_rows, 12);
 
         drop(second);
         cleanup_sqlite_files(&db_path);
     }
 
     #[test]
-    fn existing_v1_schema_is_migrated_to_v2() {
+    fn existing_v1_schema_is_migrated_to_latest() {
         let db_path = unique_temp_db_path("meta-db-upgrade-v1-v2");
         seed_v1_schema(&db_path);
 
-        let db = MetaDb::open(&db_path).expect("meta db should upgrade from v1 to v2");
-        assert_eq!(db.schema_version().expect("schema version should be readable"), 2);
+        let db = MetaDb::open(&db_path).expect("meta db should upgrade from v1");
+        assert_eq!(db.schema_version().expect("schema version should be readable"), 12);
 
         let lease_table_exists: i64 = db
             .connection()
@@ -296,7 +460,7 @@ mod tests {
             .connection()
             .query_row("SELECT COUNT(*) FROM schema_migrations", [], |row| row.get(0))
             .expect("schema migration count query should succeed");
-        assert_eq!(migration_rows, 2);
+        assert_eq!(migration_rows, 12);
 
         drop(db);
         cleanup_sqlite_files(&db_path);
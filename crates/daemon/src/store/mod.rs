@@ -1,7 +1,10 @@
 // Persistence: WAL, snapshots, SQLite meta.db.
 
 pub mod documents_local;
+pub mod findings;
+pub mod git_jobs;
 pub mod meta_db;
+pub mod reconciliation;
 pub mod recovery;
 pub mod snapshot;
 pub mod wal;
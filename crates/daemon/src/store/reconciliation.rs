@@ -0,0 +1,203 @@
+// reconciliation_items table access: regions an upstream git change and a
+// local edit both rewrote.
+//
+// The CRDT keeps the local version; an item holds both versions until
+// someone picks one.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// A row in the `reconciliation_items` table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconciliationRecord {
+    pub id: i64,
+    pub workspace_id: String,
+    pub doc_id: String,
+    pub path: String,
+    pub section_id: Option<String>,
+    /// 1-based line where the local version starts in the merged doc.
+    pub start_line: u32,
+    pub base_content: String,
+    pub local_content: String,
+    pub remote_content: String,
+    /// `Name <email>` of the upstream commit author.
+    pub remote_author: Option<String>,
+    pub remote_commit: String,
+    pub created_at: DateTime<Utc>,
+    /// `local`, `remote` or `both` once resolved.
+    pub resolution: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+const SELECT_COLUMNS: &str = "id, workspace_id, doc_id, path, section_id, start_line, \
+     base_content, local_content, remote_content, remote_author, remote_commit, created_at, \
+     resolution, resolved_at";
+
+/// Insert, query and resolve operations for `reconciliation_items`.
+pub struct ReconciliationStore;
+
+impl ReconciliationStore {
+    /// Insert an item; `record.id` is ignored. Returns None when the same
+    /// conflict from the same upstream commit is already recorded.
+    pub fn record(conn: &Connection, record: &ReconciliationRecord) -> Result<Option<i64>> {
+        let inserted = conn
+            .execute(
+                "INSERT OR IGNORE INTO reconciliation_items \
+                 (workspace_id, doc_id, path, section_id, start_line, base_content, \
+                  local_content, remote_content, remote_author, remote_commit, created_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    record.workspace_id,
+                    record.doc_id,
+                    record.path,
+                    record.section_id,
+                    record.start_line,
+                    record.base_content,
+                    record.local_content,
+                    record.remote_content,
+                    record.remote_author,
+                    record.remote_commit,
+                    record.created_at.to_rfc3339(),
+                ],
+            )
+            .context("failed to insert reconciliation item")?;
+        Ok((inserted > 0).then(|| conn.last_insert_rowid()))
+    }
+
+    pub fn get(conn: &Connection, id: i64) -> Result<Option<ReconciliationRecord>> {
+        conn.query_row(
+            &format!("SELECT {SELECT_COLUMNS} FROM reconciliation_items WHERE id = ?1"),
+            params![id],
+            row_to_record,
+        )
+        .optional()
+        .context("failed to query reconciliation item")
+    }
+
+    /// List a workspace's items, optionally for one doc, newest first.
+    pub fn list(
+        conn: &Connection,
+        workspace_id: &str,
+        doc_id: Option<&str>,
+        include_resolved: bool,
+        limit: usize,
+    ) -> Result<Vec<ReconciliationRecord>> {
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {SELECT_COLUMNS} FROM reconciliation_items \
+                 WHERE workspace_id = ?1 AND (?2 IS NULL OR doc_id = ?2) \
+                   AND (?3 OR resolved_at IS NULL) \
+                 ORDER BY created_at DESC, id DESC LIMIT ?4"
+            ))
+            .context("failed to prepare reconciliation items query")?;
+
+        let rows = stmt
+            .query_map(params![workspace_id, doc_id, include_resolved, limit as i64], row_to_record)
+            .context("failed to query reconciliation items")?;
+
+        rows.collect::<std::result::Result<Vec<_>, _>>()
+            .context("failed to collect reconciliation items")
+    }
+
+    /// Mark an open item resolved. Returns false if it was already resolved.
+    pub fn resolve(
+        conn: &Connection,
+        id: i64,
+        resolution: &str,
+        resolved_at: DateTime<Utc>,
+    ) -> Result<bool> {
+        let updated = conn
+            .execute(
+                "UPDATE reconciliation_items SET resolution = ?2, resolved_at = ?3 \
+                 WHERE id = ?1 AND resolved_at IS NULL",
+                params![id, resolution, resolved_at.to_rfc3339()],
+            )
+            .context("failed to resolve reconciliation item")?;
+        Ok(updated > 0)
+    }
+}
+
+fn parse_ts(row: &rusqlite::Row<'_>, index: usize) -> rusqlite::Result<DateTime<Utc>> {
+    let raw: String = row.get(index)?;
+    raw.parse::<DateTime<Utc>>().map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))
+    })
+}
+
+fn row_to_record(row: &rusqlite::Row<'_>) -> rusqlite::Result<ReconciliationRecord> {
+    Ok(ReconciliationRecord {
+        id: row.get(0)?,
+        workspace_id: row.get(1)?,
+        doc_id: row.get(2)?,
+        path: row.get(3)?,
+        section_id: row.get(4)?,
+        start_line: row.get(5)?,
+        base_content: row.get(6)?,
+        local_content: row.get(7)?,
+        remote_content: row.get(8)?,
+        remote_author: row.get(9)?,
+        remote_commit: row.get(10)?,
+        created_at: parse_ts(row, 11)?,
+        resolution: row.get(12)?,
+        resolved_at: match row.get::<_, Option<String>>(13)? {
+            Some(_) => Some(parse_ts(row, 13)?),
+            None => None,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::meta_db::MetaDb;
+
+    fn item(doc_id: &str, start_line: u32, created_at: DateTime<Utc>) -> ReconciliationRecord {
+        ReconciliationRecord {
+            id: 0,
+            workspace_id: "ws-1".into(),
+            doc_id: doc_id.into(),
+            path: format!("docs/{doc_id}.md"),
+            section_id: Some("root/intro".into()),
+            start_line,
+            base_content: "alpha\n".into(),
+            local_content: "alpha local\n".into(),
+            remote_content: "alpha remote\n".into(),
+            remote_author: Some("Ada <ada@example.com>".into()),
+            remote_commit: "abc123".into(),
+            created_at,
+            resolution: None,
+            resolved_at: None,
+        }
+    }
+
+    #[test]
+    fn items_dedupe_list_and_resolve() {
+        let db = MetaDb::open(":memory:").expect("meta db should open");
+        let conn = db.connection();
+        let now = Utc::now();
+        let first = ReconciliationStore::record(conn, &item("doc-a", 3, now)).unwrap();
+        assert!(first.is_some());
+        assert_eq!(ReconciliationStore::record(conn, &item("doc-a", 3, now)).unwrap(), None);
+        ReconciliationStore::record(conn, &item("doc-b", 7, now + chrono::Duration::seconds(1)))
+            .unwrap();
+
+        let open = ReconciliationStore::list(conn, "ws-1", None, false, 10).unwrap();
+        assert_eq!(
+            open.iter().map(|record| record.doc_id.as_str()).collect::<Vec<_>>(),
+            vec!["doc-b", "doc-a"]
+        );
+
+        let id = first.unwrap();
+        assert!(ReconciliationStore::resolve(conn, id, "remote", now).unwrap());
+        assert!(!ReconciliationStore::resolve(conn, id, "local", now).unwrap());
+        let resolved = ReconciliationStore::get(conn, id).unwrap().expect("item");
+        assert_eq!(resolved.resolution.as_deref(), Some("remote"));
+
+        assert_eq!(ReconciliationStore::list(conn, "ws-1", None, false, 10).unwrap().len(), 1);
+        assert_eq!(
+            ReconciliationStore::list(conn, "ws-1", Some("doc-a"), true, 10).unwrap().len(),
+            1
+        );
+    }
+}
@@ -170,6 +170,18 @@ impl WalStore {
         })
     }
 
+    /// Byte offset where the next frame will be appended.
+    pub fn end_offset(&self) -> Result<u64> {
+        let metadata = fs::metadata(&self.path)
+            .with_context(|| format!("failed to stat wal file `{}`", self.path.display()))?;
+        Ok(metadata.len())
+    }
+
+    /// Drop every frame appended at or after `offset` (from `end_offset`).
+    pub fn truncate_to(&self, offset: u64) -> Result<()> {
+        truncate_wal(&self.path, offset)
+    }
+
     pub fn path(&self) -> &Path {
         &self.path
     }
@@ -237,6 +249,25 @@ mod tests {
         assert_eq!(updates, vec![b"u1".to_vec(), b"u2".to_vec()]);
     }
 
+    #[test]
+    fn truncate_to_drops_frames_appended_after_offset() {
+        let tmp = tempdir().expect("tempdir should be created");
+        let wal = WalStore::open(tmp.path().join("doc.wal")).expect("wal should open");
+
+        wal.append_update(b"u1").expect("frame 1 should append");
+        let offset = wal.end_offset().expect("wal end offset should be readable");
+        wal.append_update(b"u2").expect("frame 2 should append");
+        wal.truncate_to(offset).expect("wal should truncate");
+
+        let mut updates = Vec::new();
+        wal.replay(|payload| {
+            updates.push(payload.to_vec());
+            Ok(())
+        })
+        .expect("wal replay should succeed");
+        assert_eq!(updates, vec![b"u1".to_vec()]);
+    }
+
     #[test]
     fn replay_truncates_corrupted_tail() {
         let tmp = tempdir().expect("tempdir should be created");
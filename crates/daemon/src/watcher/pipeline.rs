@@ -3,7 +3,9 @@
 // Connects the watcher stages into a single async pipeline that converts
 // external file edits into CRDT updates.
 
+use std::future::Future;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::Mutex as StdMutex;
 use std::time::Duration;
@@ -17,8 +19,10 @@ use uuid::Uuid;
 use scriptum_common::backlink::parse_wiki_links;
 use scriptum_common::diff::patch;
 
+use crate::config::GuardMode;
 use crate::engine::doc_manager::DocManager;
 use crate::engine::ydoc::YDoc;
+use crate::guard::{self, GuardAction, GuardReport};
 
 use super::debounce::{DebounceConfig, Debouncer};
 use super::hash;
@@ -53,11 +57,33 @@ impl WatcherPauseController {
     }
 }
 
-/// How we resolve an absolute file path to a document identity.
+/// How long the watcher ignores a file after rewriting it with redactions.
+const REDACTION_WRITE_PAUSE: Duration = Duration::from_millis(500);
+
+/// How we resolve an absolute file path to a document identity, and the
+/// workspace policy its edits are checked against.
 pub trait PathResolver: Send + Sync {
     /// Map a file path to a (workspace_id, doc_id) pair.
     /// Returns None if the path is not within any tracked workspace.
-    fn resolve(&self, path: &Path) -> Option<(Uuid, Uuid)>;
+    fn resolve<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Pin<Box<dyn Future<Output = Option<(Uuid, Uuid)>> + Send + 'a>>;
+
+    /// The workspace's secret guard mode for local file edits.
+    fn guard_mode(
+        &self,
+        workspace_id: Uuid,
+    ) -> Pin<Box<dyn Future<Output = GuardMode> + Send + '_>>;
+
+    /// Record a guard outcome for a local file edit: warned, redacted or blocked.
+    fn record_guard_report<'a>(
+        &'a self,
+        workspace_id: Uuid,
+        doc_id: Uuid,
+        path: &'a Path,
+        report: &'a GuardReport,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
 }
 
 /// Trait for persisting content hashes (abstracts SQLite for testing).
@@ -78,7 +104,12 @@ pub enum PipelineEvent {
         path: PathBuf,
         content_hash: String,
         patch_op_count: usize,
+        /// Secrets or PII the change introduced, warned about or redacted.
+        guard: Option<GuardReport>,
     },
+    /// A file change was not applied because it introduces secrets or PII
+    /// and the workspace guard blocks such edits.
+    DocBlocked { workspace_id: Uuid, doc_id: Uuid, path: PathBuf, report: GuardReport },
     /// A document was removed (file deleted).
     DocRemoved { workspace_id: Uuid, doc_id: Uuid, path: PathBuf },
     /// An error occurred processing a file event.
@@ -209,6 +240,7 @@ async fn process_event_with_pause(
 
     let (workspace_id, doc_id) = resolver
         .resolve(&event.path)
+        .await
         .ok_or_else(|| anyhow!("path not in any workspace: {}", event.path.display()))?;
 
     let doc_id_str = doc_id.to_string();
@@ -238,12 +270,56 @@ async fn process_event_with_pause(
             let doc: Arc<YDoc> = mgr.subscribe_or_create(doc_id);
             let current_text = doc.get_text_string("content");
 
+            // Secret guard — a blocked change never reaches the CRDT and keeps
+            // its old hash, so the next save is checked again.
+            let findings = guard::new_findings(&current_text, &content);
+            let report = if findings.is_empty() {
+                None
+            } else {
+                Some(GuardReport {
+                    action: resolver.guard_mode(workspace_id).await.action(),
+                    findings,
+                })
+            };
+            let (content, new_hash) = match &report {
+                Some(report) if report.action == GuardAction::Blocked => {
+                    warn!(path = %event.path.display(), "blocked file change with secrets or PII");
+                    drop(mgr);
+                    resolver.record_guard_report(workspace_id, doc_id, &event.path, report).await;
+                    return Ok(Some(PipelineEvent::DocBlocked {
+                        workspace_id,
+                        doc_id,
+                        path: event.path.clone(),
+                        report: report.clone(),
+                    }));
+                }
+                Some(report) if report.action == GuardAction::Redacted => {
+                    let redacted = guard::redact_findings(&content, &report.findings);
+                    let redacted_hash = hash::sha256_hex(redacted.as_bytes());
+                    (redacted, redacted_hash)
+                }
+                _ => (content, new_hash),
+            };
+
             // Diff and apply.
             let ytext = doc.get_or_insert_text("content");
             let ops = patch::apply_text_diff_to_ytext(doc.inner(), &ytext, &current_text, &content);
             let op_count = ops.len();
             drop(mgr); // Release lock before I/O.
 
+            if let Some(report) = &report {
+                resolver.record_guard_report(workspace_id, doc_id, &event.path, report).await;
+            }
+
+            if report.as_ref().is_some_and(|report| report.action == GuardAction::Redacted) {
+                if let Some(controller) = pause_controller {
+                    controller.pause_path_for(&event.path, REDACTION_WRITE_PAUSE);
+                }
+                std::fs::write(&event.path, content.as_bytes()).with_context(|| {
+                    format!("failed to write redacted markdown to {}", event.path.display())
+                })?;
+            }
+
             // Update stored hash.
             let _ = hash_store.set_hash(&doc_id_str, &new_hash);
 
@@ -253,6 +329,7 @@ async fn process_event_with_pause(
                 path: event.path.clone(),
                 content_hash: new_hash,
                 patch_op_count: op_count,
+                guard: report,
             }))
         }
     }
@@ -334,11 +411,18 @@ mod tests {
     struct MockResolver {
         workspace_id: Uuid,
         mappings: HashMap<PathBuf, Uuid>,
+        guard_mode: GuardMode,
+        reports: StdMutex<Vec<GuardReport>>,
     }
 
     impl MockResolver {
         fn new(workspace_id: Uuid) -> Self {
-            Self { workspace_id, mappings: HashMap::new() }
+            Self {
+                workspace_id,
+                mappings: HashMap::new(),
+                guard_mode: GuardMode::default(),
+                reports: StdMutex::new(Vec::new()),
+            }
         }
 
         fn add(&mut self, path: &str, doc_id: Uuid) {
@@ -347,8 +431,31 @@ mod tests {
     }
 
     impl PathResolver for MockResolver {
-        fn resolve(&self, path: &Path) -> Option<(Uuid, Uuid)> {
-            self.mappings.get(path).map(|doc_id| (self.workspace_id, *doc_id))
+        fn resolve<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> Pin<Box<dyn Future<Output = Option<(Uuid, Uuid)>> + Send + 'a>> {
+            let resolved = self.mappings.get(path).map(|doc_id| (self.workspace_id, *doc_id));
+            Box::pin(async move { resolved })
+        }
+
+        fn guard_mode(
+            &self,
+            _workspace_id: Uuid,
+        ) -> Pin<Box<dyn Future<Output = GuardMode> + Send + '_>> {
+            let mode = self.guard_mode;
+            Box::pin(async move { mode })
+        }
+
+        fn record_guard_report<'a>(
+            &'a self,
+            _workspace_id: Uuid,
+            _doc_id: Uuid,
+            _path: &'a Path,
+            report: &'a GuardReport,
+        ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+            self.reports.lock().unwrap().push(report.clone());
+            Box::pin(async {})
         }
     }
 
@@ -616,6 +723,50 @@ mod tests {
         assert!(result.is_none(), "paused path should suppress watcher feedback event");
     }
 
+    #[tokio::test]
+    async fn guard_blocks_or_redacts_file_changes_with_secrets() {
+        let ws_id = Uuid::new_v4();
+        let doc_id = Uuid::new_v4();
+        let tmp = tempfile::TempDir::new().unwrap();
+        let file_path = tmp.path().join("test.md");
+        let hash_store = Arc::new(MockHashStore::default());
+        let doc_mgr = Arc::new(Mutex::new(DocManager::new(64 * 1024 * 1024)));
+        let event = RawFsEvent { kind: FsEventKind::Modify, path: file_path.clone() };
+        let secret = "# Keys\nAKIAABCDEFGHIJKLMNOP\n";
+        std::fs::write(&file_path, secret).unwrap();
+
+        let mut resolver = MockResolver::new(ws_id);
+        resolver.add(file_path.to_str().unwrap(), doc_id);
+        resolver.guard_mode = GuardMode::Block;
+        let result = process_event(&event, &doc_mgr, &resolver, hash_store.as_ref()).await.unwrap();
+        match result {
+            Some(PipelineEvent::DocBlocked { report, .. }) => {
+                assert_eq!(report.findings[0].kind, "aws_access_key");
+            }
+            other => panic!("expected DocBlocked, got {other:?}"),
+        }
+        let doc = doc_mgr.lock().await.subscribe_or_create(doc_id);
+        assert_eq!(doc.get_text_string("content"), "");
+        assert!(hash_store.get_hash(&doc_id.to_string()).unwrap().is_none());
+
+        resolver.guard_mode = GuardMode::Redact;
+        let result = process_event(&event, &doc_mgr, &resolver, hash_store.as_ref()).await.unwrap();
+        match result {
+            Some(PipelineEvent::DocUpdated { guard: Some(report), .. }) => {
+                assert_eq!(report.action, GuardAction::Redacted);
+            }
+            other => panic!("expected redacted DocUpdated, got {other:?}"),
+        }
+        assert_eq!(doc.get_text_string("content"), "# Keys\n[REDACTED]\n");
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "# Keys\n[REDACTED]\n");
+        let rerun = process_event(&event, &doc_mgr, &resolver, hash_store.as_ref()).await.unwrap();
+        assert!(rerun.is_none(), "rewritten file should hash as already applied");
+
+        let recorded: Vec<_> =
+            resolver.reports.lock().unwrap().iter().map(|report| report.action).collect();
+        assert_eq!(recorded, vec![GuardAction::Blocked, GuardAction::Redacted]);
+    }
+
     // ── Pipeline integration test ──────────────────────────────────
 
     #[tokio::test]
@@ -13,6 +13,7 @@ use axum::{
     Router,
 };
 use scriptum_common::protocol::jsonrpc::{Request as RpcRequest, RequestId};
+use scriptum_daemon::config::{GitBackend, SigningFormat};
 use scriptum_daemon::git::attribution::{with_coauthor_trailers, UpdateAttribution};
 use scriptum_daemon::git::commit::{
     generate_ai_commit_message, AiCommitClient, AiCommitError, RedactionPolicy,
@@ -20,11 +21,14 @@ use scriptum_daemon::git::commit::{
 use scriptum_daemon::git::triggers::{
     ChangeType, ChangedFile, TriggerCollector, TriggerConfig, TriggerEvent,
 };
-use scriptum_daemon::git::worker::{GitWorker, ProcessCommandExecutor};
+use scriptum_daemon::git::worker::{
+    open_repository, CommitAuthor, CommitSigning, ProcessCommandExecutor,
+};
 use scriptum_daemon::rpc::methods::{dispatch_request, GitState, RpcServerState};
 use serde_json::json;
 use tempfile::TempDir;
 use tokio::sync::oneshot;
+use uuid::Uuid;
 
 const ANTHROPIC_API_VERSION: &str = "2023-06-01";
 const ANTHROPIC_MODEL: &str = "claude-haiku-4-5-20250929";
@@ -178,6 +182,15 @@ impl AiCommitClient for CountingAiClient {
 
 #[tokio::test]
 async fn git_worker_e2e_commit_with_ai_message_and_coauthors_and_push() {
+    commit_with_ai_message_and_coauthors_and_push(GitBackend::Cli).await;
+}
+
+#[tokio::test]
+async fn native_git_e2e_commit_with_ai_message_and_coauthors_and_push() {
+    commit_with_ai_message_and_coauthors_and_push(GitBackend::Native).await;
+}
+
+async fn commit_with_ai_message_and_coauthors_and_push(backend: GitBackend) {
     let temp = TempDir::new().expect("tempdir should be created");
     let remote_path = temp.path().join("remote.git");
     let repo_path = temp.path().join("repo");
@@ -240,9 +253,9 @@ async fn git_worker_e2e_commit_with_ai_message_and_coauthors_and_push() {
         context.agents_involved.iter().cloned().map(UpdateAttribution::for_agent).collect();
     let final_message = with_coauthor_trailers(&ai_message, &attributions);
 
-    let worker = GitWorker::new(&repo_path);
-    worker.add(&["README.md"]).expect("git add should succeed");
-    worker.commit(&final_message).expect("git commit should succeed");
+    let worker = open_repository(backend, &repo_path);
+    worker.add(&["README.md".to_string()]).expect("git add should succeed");
+    worker.commit_as(&final_message, None).expect("git commit should succeed");
     worker.push().expect("git push should succeed");
 
     let commit_message = run_git_capture(&repo_path, &["log", "-1", "--pretty=%B"]);
@@ -269,8 +282,232 @@ async fn git_worker_e2e_commit_with_ai_message_and_coauthors_and_push() {
     assert_eq!(local_head.trim(), remote_head.trim(), "remote should receive pushed commit");
 }
 
+#[test]
+fn git_worker_e2e_merges_upstream_with_resolved_markdown() {
+    merges_upstream_with_resolved_markdown(GitBackend::Cli);
+}
+
+#[test]
+fn native_git_e2e_merges_upstream_with_resolved_markdown() {
+    merges_upstream_with_resolved_markdown(GitBackend::Native);
+}
+
+fn merges_upstream_with_resolved_markdown(backend: GitBackend) {
+    let temp = TempDir::new().expect("tempdir should be created");
+    let remote_path = temp.path().join("remote.git");
+    let remote = remote_path.to_str().expect("utf8 remote path");
+    let repo_path = setup_repo_for_sync(&temp);
+    std::fs::write(repo_path.join("notes.txt"), "notes\n").expect("notes should be written");
+    run_git(&repo_path, &["add", "."]);
+    run_git(&repo_path, &["commit", "-m", "chore: add notes"]);
+    run_git(temp.path(), &["init", "--bare", "-b", "main", remote]);
+    run_git(&repo_path, &["remote", "add", "origin", remote]);
+    run_git(&repo_path, &["push", "-u", "origin", "main"]);
+    let base = run_git_capture(&repo_path, &["rev-parse", "HEAD"]);
+
+    let teammate_path = temp.path().join("teammate");
+    run_git(temp.path(), &["clone", remote, teammate_path.to_str().expect("utf8 clone path")]);
+    run_git(&teammate_path, &["config", "user.name", "Ada"]);
+    run_git(&teammate_path, &["config", "user.email", "ada@example.test"]);
+    std::fs::write(teammate_path.join("docs/readme.md"), "# Scriptum\n\nRemote content.\n")
+        .expect("remote readme should be written");
+    std::fs::write(teammate_path.join("notes.txt"), "remote notes\n")
+        .expect("remote notes should be written");
+    run_git(&teammate_path, &["commit", "-am", "docs: remote edit"]);
+    run_git(&teammate_path, &["push"]);
+
+    write_repo_edit(&repo_path, "# Scriptum\n\nLocal content.\n");
+    run_git(&repo_path, &["commit", "-am", "docs: local edit"]);
+
+    let worker = open_repository(backend, &repo_path);
+    let upstream = worker.upstream_changes().expect("fetch should succeed").expect("new commits");
+    assert_eq!(upstream.base, base.trim());
+    assert_eq!(upstream.files.len(), 1, "only markdown is merged into docs");
+    let file = &upstream.files[0];
+    assert_eq!(file.path, "docs/readme.md");
+    assert_eq!(file.base.as_deref(), Some("# Scriptum\n\nInitial content.\n"));
+    assert_eq!(file.remote.as_deref(), Some("# Scriptum\n\nRemote content.\n"));
+    assert_eq!(file.author.as_ref().map(|author| author.name.as_str()), Some("Ada"));
+
+    let merged = "# Scriptum\n\nLocal content.\n".to_string();
+    worker
+        .merge_upstream(&upstream.remote, &[("docs/readme.md".to_string(), merged.clone())])
+        .expect("merge should succeed");
+
+    let parents = run_git_capture(&repo_path, &["rev-list", "--parents", "-n", "1", "HEAD"]);
+    assert_eq!(parents.split_whitespace().count(), 3, "HEAD should be a merge commit");
+    assert_eq!(
+        std::fs::read_to_string(repo_path.join("docs/readme.md")).expect("readme should exist"),
+        merged
+    );
+    assert_eq!(
+        std::fs::read_to_string(repo_path.join("notes.txt")).expect("notes should exist"),
+        "remote notes\n"
+    );
+    assert!(run_git_capture(&repo_path, &["status", "--short"]).trim().is_empty());
+    assert_eq!(worker.upstream_changes().expect("fetch should succeed"), None);
+}
+
+#[test]
+fn git_worker_e2e_commits_to_session_branch_without_moving_head() {
+    commits_to_session_branch_without_moving_head(GitBackend::Cli);
+}
+
+#[test]
+fn native_git_e2e_commits_to_session_branch_without_moving_head() {
+    commits_to_session_branch_without_moving_head(GitBackend::Native);
+}
+
+fn commits_to_session_branch_without_moving_head(backend: GitBackend) {
+    let temp = TempDir::new().expect("tempdir should be created");
+    let remote_path = temp.path().join("remote.git");
+    let remote = remote_path.to_str().expect("utf8 remote path");
+    let repo_path = setup_repo_for_sync(&temp);
+    run_git(temp.path(), &["init", "--bare", "-b", "main", remote]);
+    run_git(&repo_path, &["remote", "add", "origin", remote]);
+    let head = run_git_capture(&repo_path, &["rev-parse", "HEAD"]);
+    let branch = "scriptum/claude/2026-01-01";
+    let worker = open_repository(backend, &repo_path);
+
+    write_repo_edit(&repo_path, "# Scriptum\n\nFirst session edit.\n");
+    worker.add(&[".".to_string()]).expect("add should succeed");
+    let first = worker.commit_to_branch_as(branch, "docs: first edit", None).expect("first commit");
+    write_repo_edit(&repo_path, "# Scriptum\n\nSecond session edit.\n");
+    worker.add(&[".".to_string()]).expect("add should succeed");
+    let second =
+        worker.commit_to_branch_as(branch, "docs: second edit", None).expect("second commit");
+    assert_eq!(
+        worker.commit_to_branch_as(branch, "docs: nothing new", None).expect("no-op commit"),
+        second,
+        "an unchanged tree adds no commit"
+    );
+
+    assert_eq!(run_git_capture(&repo_path, &["rev-parse", "HEAD"]), head, "HEAD stays put");
+    assert_eq!(run_git_capture(&repo_path, &["branch", "--show-current"]).trim(), "main");
+    assert_eq!(
+        run_git_capture(&repo_path, &["rev-parse", &format!("{second}^")]).trim(),
+        first,
+        "session commits chain on the branch"
+    );
+    assert_eq!(
+        run_git_capture(&repo_path, &["show", &format!("{branch}:docs/readme.md")]),
+        "# Scriptum\n\nSecond session edit.\n"
+    );
+    assert_eq!(
+        worker.log_subjects(&format!("main..refs/heads/{branch}")).expect("log should succeed"),
+        vec!["docs: second edit", "docs: first edit"]
+    );
+
+    worker.push_branch("origin", branch).expect("push should succeed");
+    assert_eq!(
+        run_git_capture(&remote_path, &["rev-parse", &format!("refs/heads/{branch}")]).trim(),
+        second
+    );
+}
+
+#[test]
+fn git_worker_e2e_lists_commits_touching_a_path_since_a_time() {
+    lists_commits_touching_a_path_since_a_time(GitBackend::Cli);
+}
+
+#[test]
+fn native_git_e2e_lists_commits_touching_a_path_since_a_time() {
+    lists_commits_touching_a_path_since_a_time(GitBackend::Native);
+}
+
+fn lists_commits_touching_a_path_since_a_time(backend: GitBackend) {
+    let temp = TempDir::new().expect("tempdir should be created");
+    let repo_path = setup_repo_for_sync(&temp);
+    std::fs::create_dir_all(repo_path.join("src")).expect("src directory should be created");
+    std::fs::write(repo_path.join("src/auth.rs"), "fn login() {}\n").expect("write auth.rs");
+    commit_at(&repo_path, "auth: init", 1_700_000_000);
+    std::fs::write(repo_path.join("src/auth.rs"), "fn login(key: &str) {}\n")
+        .expect("write auth.rs");
+    commit_at(&repo_path, "auth: rotate keys", 1_700_000_200);
+    write_repo_edit(&repo_path, "# Scriptum\n\nDocs only.\n");
+    commit_at(&repo_path, "docs: readme", 1_700_000_300);
+    let worker = open_repository(backend, &repo_path);
+
+    let commits =
+        worker.commits_touching("src/auth.rs", 1_700_000_100).expect("log should succeed");
+    assert_eq!(commits.len(), 1, "only the later auth commit: {commits:?}");
+    assert_eq!(commits[0].subject, "auth: rotate keys");
+    assert_eq!(commits[0].author, "Scriptum Bot");
+    assert_eq!(commits[0].commit_time, 1_700_000_200);
+    assert_eq!(commits[0].commit, run_git_capture(&repo_path, &["rev-parse", "HEAD~1"]).trim(),);
+    assert!(worker
+        .commits_touching("src/auth.rs", 1_700_000_200)
+        .expect("log should succeed")
+        .is_empty());
+}
+
+#[test]
+fn git_worker_e2e_signs_commits_with_ssh_key_and_committer() {
+    signs_commits_with_ssh_key_and_committer(GitBackend::Cli);
+}
+
+#[test]
+fn native_git_e2e_signs_commits_with_ssh_key_and_committer() {
+    signs_commits_with_ssh_key_and_committer(GitBackend::Native);
+}
+
+fn signs_commits_with_ssh_key_and_committer(backend: GitBackend) {
+    let temp = TempDir::new().expect("tempdir should be created");
+    let key_path = temp.path().join("signing_key");
+    let keygen = Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-C", "signer", "-f"])
+        .arg(&key_path)
+        .output();
+    if !keygen.is_ok_and(|output| output.status.success()) {
+        eprintln!("skipping: ssh-keygen is not available");
+        return;
+    }
+    let public_key_path = temp.path().join("signing_key.pub");
+    let public_key = std::fs::read_to_string(&public_key_path).expect("public key");
+    let allowed_signers = temp.path().join("allowed_signers");
+    std::fs::write(&allowed_signers, format!("signer@example.test {public_key}"))
+        .expect("allowed signers should be written");
+
+    let repo_path = setup_repo_for_sync(&temp);
+    let mut worker = open_repository(backend, &repo_path);
+    worker.set_commit_identity(
+        Some(CommitAuthor { name: "Scriptum Signer".into(), email: "signer@example.test".into() }),
+        Some(CommitSigning {
+            format: SigningFormat::Ssh,
+            key: Some(public_key_path.to_str().expect("utf8 key path").to_string()),
+        }),
+    );
+    let agent = CommitAuthor { name: "cursor-1".into(), email: "agent:cursor-1@scriptum".into() };
+
+    write_repo_edit(&repo_path, "# Scriptum\n\nSigned edit.\n");
+    worker.add(&[".".to_string()]).expect("add should succeed");
+    worker.commit_as("docs: signed edit", Some(&agent)).expect("signed commit");
+    let commit = run_git_capture(&repo_path, &["cat-file", "commit", "HEAD"]);
+    assert!(commit.contains("\nauthor cursor-1 <agent:cursor-1@scriptum>"), "{commit}");
+    assert!(commit.contains("\ncommitter Scriptum Signer <signer@example.test>"), "{commit}");
+    assert!(commit.contains("-----BEGIN SSH SIGNATURE-----"), "{commit}");
+
+    let signers = format!("gpg.ssh.allowedSignersFile={}", allowed_signers.display());
+    run_git(&repo_path, &["-c", &signers, "verify-commit", "HEAD"]);
+
+    write_repo_edit(&repo_path, "# Scriptum\n\nSigned branch edit.\n");
+    worker.add(&[".".to_string()]).expect("add should succeed");
+    let tip =
+        worker.commit_to_branch_as("scriptum/review", "docs: branch edit", None).expect("commit");
+    run_git(&repo_path, &["-c", &signers, "verify-commit", tip.as_str()]);
+}
+
 #[tokio::test]
 async fn git_sync_e2e_generates_ai_message_and_validates_anthropic_request_shape() {
+    generates_ai_message_and_validates_anthropic_request_shape(GitBackend::Cli).await;
+}
+
+#[tokio::test]
+async fn native_git_e2e_generates_ai_message_and_validates_anthropic_request_shape() {
+    generates_ai_message_and_validates_anthropic_request_shape(GitBackend::Native).await;
+}
+
+async fn generates_ai_message_and_validates_anthropic_request_shape(backend: GitBackend) {
     let temp = TempDir::new().expect("tempdir should be created");
     let repo_path = setup_repo_for_sync(&temp);
     write_repo_edit(&repo_path, "# Scriptum\n\nEdited by e2e AI flow.\n");
@@ -287,6 +524,7 @@ async fn git_sync_e2e_generates_ai_message_and_validates_anthropic_request_shape
 
     let ai_client = Arc::new(AnthropicWireMockClient::new(api_url, "sk-ant-local-test"));
     run_git_sync_commit(
+        backend,
         &repo_path,
         ai_client,
         true,
@@ -335,12 +573,22 @@ async fn git_sync_e2e_generates_ai_message_and_validates_anthropic_request_shape
 
 #[tokio::test]
 async fn git_sync_e2e_uses_fallback_message_when_ai_disabled() {
+    uses_fallback_message_when_ai_disabled(GitBackend::Cli).await;
+}
+
+#[tokio::test]
+async fn native_git_e2e_uses_fallback_message_when_ai_disabled() {
+    uses_fallback_message_when_ai_disabled(GitBackend::Native).await;
+}
+
+async fn uses_fallback_message_when_ai_disabled(backend: GitBackend) {
     let temp = TempDir::new().expect("tempdir should be created");
     let repo_path = setup_repo_for_sync(&temp);
     write_repo_edit(&repo_path, "# Scriptum\n\nEdited with AI disabled.\n");
 
     let ai_client = CountingAiClient::default();
     run_git_sync_commit(
+        backend,
         &repo_path,
         Arc::new(ai_client.clone()),
         false,
@@ -361,8 +609,67 @@ async fn git_sync_e2e_uses_fallback_message_when_ai_disabled() {
     );
 }
 
+#[tokio::test]
+async fn git_sync_e2e_notes_commits_and_blames_through_notes() {
+    notes_commits_and_blames_through_notes(GitBackend::Cli).await;
+}
+
+#[tokio::test]
+async fn native_git_e2e_notes_commits_and_blames_through_notes() {
+    notes_commits_and_blames_through_notes(GitBackend::Native).await;
+}
+
+async fn notes_commits_and_blames_through_notes(backend: GitBackend) {
+    let temp = TempDir::new().expect("tempdir should be created");
+    let repo_path = setup_repo_for_sync(&temp);
+    write_repo_edit(&repo_path, "# Scriptum\n\nNoted edit.\n");
+
+    let (state, workspace_id) = run_git_sync_commit(
+        backend,
+        &repo_path,
+        Arc::new(CountingAiClient::default()),
+        false,
+        RedactionPolicy::Disabled,
+        "docs: noted checkpoint",
+    )
+    .await;
+
+    let note: serde_json::Value = serde_json::from_str(&run_git_capture(
+        &repo_path,
+        &["notes", "--ref", "refs/notes/scriptum", "show", "HEAD"],
+    ))
+    .expect("note should be JSON");
+    assert_eq!(note["version"], 1);
+    assert_eq!(note["trigger"]["type"], "checkpoint");
+
+    let request = RpcRequest::new(
+        "git.blame",
+        Some(json!({ "workspace_id": workspace_id, "path": "docs/readme.md" })),
+        RequestId::Number(1003),
+    );
+    let response = dispatch_request(request, &state).await;
+    assert!(response.error.is_none(), "git.blame should succeed: {response:?}");
+    let result = response.result.expect("git.blame result");
+    let head = run_git_capture(&repo_path, &["rev-parse", "HEAD"]);
+    let lines = result["lines"].as_array().expect("blame lines");
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[2]["content"], "Noted edit.");
+    assert_eq!(lines[2]["commit"], head.trim());
+    assert_eq!(lines[2]["agent"], "Scriptum Bot");
+    assert_ne!(lines[0]["commit"], head.trim());
+}
+
 #[tokio::test]
 async fn git_sync_e2e_uses_fallback_message_when_ai_api_fails() {
+    uses_fallback_message_when_ai_api_fails(GitBackend::Cli).await;
+}
+
+#[tokio::test]
+async fn native_git_e2e_uses_fallback_message_when_ai_api_fails() {
+    uses_fallback_message_when_ai_api_fails(GitBackend::Native).await;
+}
+
+async fn uses_fallback_message_when_ai_api_fails(backend: GitBackend) {
     let temp = TempDir::new().expect("tempdir should be created");
     let repo_path = setup_repo_for_sync(&temp);
     write_repo_edit(&repo_path, "# Scriptum\n\nEdited with failing AI API.\n");
@@ -375,6 +682,7 @@ async fn git_sync_e2e_uses_fallback_message_when_ai_api_fails() {
 
     let ai_client = Arc::new(AnthropicWireMockClient::new(api_url, "sk-ant-local-test"));
     run_git_sync_commit(
+        backend,
         &repo_path,
         ai_client,
         true,
@@ -406,23 +714,27 @@ async fn git_sync_e2e_uses_fallback_message_when_ai_api_fails() {
 }
 
 async fn run_git_sync_commit(
+    backend: GitBackend,
     repo_path: &Path,
     ai_client: Arc<dyn AiCommitClient>,
     ai_enabled: bool,
     redaction_policy: RedactionPolicy,
     checkpoint_message: &str,
-) {
+) -> (RpcServerState, Uuid) {
     let git_state = GitState::with_executor_and_ai(
         repo_path.to_path_buf(),
         ProcessCommandExecutor,
         ai_client,
         ai_enabled,
         redaction_policy,
-    );
-    let state = RpcServerState::default().with_git_state(git_state);
+    )
+    .with_backend(backend);
+    let workspace_id = Uuid::new_v4();
+    let state = RpcServerState::default().with_workspace_git_state(workspace_id, git_state);
     let request = RpcRequest::new(
         "git.sync",
         Some(json!({
+            "workspace_id": workspace_id,
             "action": {
                 "commit": {
                     "message": checkpoint_message
@@ -434,6 +746,23 @@ async fn run_git_sync_commit(
 
     let response = dispatch_request(request, &state).await;
     assert!(response.error.is_none(), "git.sync should succeed: {response:?}");
+    let job_id = response.result.expect("git.sync should return a job")["job_id"].clone();
+
+    // The commit runs on the workspace's job queue; wait for it to finish.
+    for _ in 0..500 {
+        let request = RpcRequest::new(
+            "git.job_status",
+            Some(json!({ "workspace_id": workspace_id, "job_id": job_id })),
+            RequestId::Number(1002),
+        );
+        let job = dispatch_request(request, &state).await.result.expect("job status");
+        match job["state"].as_str() {
+            Some("succeeded") => return (state, workspace_id),
+            Some("failed") => panic!("git sync job failed: {job}"),
+            _ => tokio::time::sleep(Duration::from_millis(10)).await,
+        }
+    }
+    panic!("git sync job did not finish");
 }
 
 async fn spawn_mock_anthropic_server(
@@ -506,6 +835,20 @@ fn write_repo_edit(repo_path: &Path, content: &str) {
         .expect("updated readme should be written");
 }
 
+/// Commit everything with author and committer dates at `epoch`.
+fn commit_at(repo_path: &Path, message: &str, epoch: i64) {
+    run_git(repo_path, &["add", "."]);
+    let date = format!("{epoch} +0000");
+    let output = Command::new("git")
+        .args(["commit", "-m", message])
+        .env("GIT_AUTHOR_DATE", &date)
+        .env("GIT_COMMITTER_DATE", &date)
+        .current_dir(repo_path)
+        .output()
+        .expect("git commit should run");
+    assert!(output.status.success(), "git commit failed: {output:?}");
+}
+
 fn latest_commit_message(repo_path: &Path) -> String {
     run_git_capture(repo_path, &["log", "-1", "--pretty=%B"])
 }
@@ -17,8 +17,12 @@ const CONTRACT_METHODS: &[&str] = &[
     "doc.edit",
     "doc.bundle",
     "doc.edit_section",
+    "doc.replace",
+    "doc.batch",
     "doc.sections",
     "doc.diff",
+    "doc.findings",
+    "doc.staleness",
     "doc.search",
     "doc.tree",
     "agent.whoami",
@@ -26,12 +30,26 @@ const CONTRACT_METHODS: &[&str] = &[
     "agent.conflicts",
     "agent.list",
     "agent.claim",
+    "agent.plan",
+    "agent.undo",
+    "agent.redo",
+    "agent.notes.set",
+    "agent.notes.get",
+    "agent.notes.list",
+    "agent.notes.append",
     "workspace.list",
     "workspace.open",
     "workspace.create",
+    "workspace.context_pack",
     "git.status",
     "git.sync",
     "git.configure",
+    "git.job_status",
+    "git.jobs",
+    "git.reconciliations",
+    "git.resolve_reconciliation",
+    "git.set_doc_sync",
+    "git.blame",
 ];
 
 #[tokio::test]
@@ -146,6 +164,21 @@ async fn daemon_methods_accept_documented_param_shapes() {
                 "to_seq": 1
             })),
         ),
+        (
+            "doc.findings",
+            Some(json!({
+                "workspace_id": workspace_id,
+                "doc_id": doc_id,
+                "limit": 10
+            })),
+        ),
+        (
+            "doc.staleness",
+            Some(json!({
+                "workspace_id": CONTRACT_GIT_WORKSPACE_ID,
+                "doc_id": doc_id
+            })),
+        ),
         (
             "doc.search",
             Some(json!({
@@ -213,10 +246,19 @@ async fn daemon_methods_accept_documented_param_shapes() {
                 "root_path": unique_temp_path("scriptum-jsonrpc-contract-workspace-second")
             })),
         ),
-        ("git.status", Some(json!({}))),
+        (
+            "workspace.context_pack",
+            Some(json!({
+                "workspace_id": workspace_id,
+                "query": "contract",
+                "token_budget": 2048
+            })),
+        ),
+        ("git.status", Some(json!({ "workspace_id": CONTRACT_GIT_WORKSPACE_ID }))),
         (
             "git.sync",
             Some(json!({
+                "workspace_id": CONTRACT_GIT_WORKSPACE_ID,
                 "action": {
                     "commit": {
                         "message": "contract check"
@@ -227,9 +269,57 @@ async fn daemon_methods_accept_documented_param_shapes() {
         (
             "git.configure",
             Some(json!({
+                "workspace_id": CONTRACT_GIT_WORKSPACE_ID,
                 "policy": "manual"
             })),
         ),
+        (
+            "git.job_status",
+            Some(json!({
+                "workspace_id": CONTRACT_GIT_WORKSPACE_ID,
+                "job_id": Uuid::new_v4()
+            })),
+        ),
+        (
+            "git.jobs",
+            Some(json!({
+                "workspace_id": CONTRACT_GIT_WORKSPACE_ID,
+                "state": "failed",
+                "limit": 5
+            })),
+        ),
+        (
+            "git.reconciliations",
+            Some(json!({
+                "workspace_id": CONTRACT_GIT_WORKSPACE_ID,
+                "include_resolved": true,
+                "limit": 5
+            })),
+        ),
+        (
+            "git.resolve_reconciliation",
+            Some(json!({
+                "workspace_id": CONTRACT_GIT_WORKSPACE_ID,
+                "id": 1,
+                "resolution": "remote"
+            })),
+        ),
+        (
+            "git.set_doc_sync",
+            Some(json!({
+                "workspace_id": CONTRACT_GIT_WORKSPACE_ID,
+                "doc_id": "00000000-0000-0000-0000-000000000001",
+                "sync": "exclude"
+            })),
+        ),
+        (
+            "git.blame",
+            Some(json!({
+                "workspace_id": CONTRACT_GIT_WORKSPACE_ID,
+                "path": "docs/readme.md",
+                "rev": "HEAD"
+            })),
+        ),
     ];
 
     for (method, params) in cases {
@@ -255,19 +345,38 @@ async fn parameterized_methods_reject_non_object_params() {
         "doc.edit",
         "doc.bundle",
         "doc.edit_section",
+        "doc.replace",
+        "doc.batch",
         "doc.sections",
         "doc.diff",
+        "doc.findings",
+        "doc.staleness",
         "doc.search",
         "doc.tree",
         "agent.status",
         "agent.conflicts",
         "agent.list",
         "agent.claim",
+        "agent.plan",
+        "agent.undo",
+        "agent.redo",
+        "agent.notes.set",
+        "agent.notes.get",
+        "agent.notes.list",
+        "agent.notes.append",
         "workspace.list",
         "workspace.open",
         "workspace.create",
+        "workspace.context_pack",
+        "git.status",
         "git.sync",
         "git.configure",
+        "git.job_status",
+        "git.jobs",
+        "git.reconciliations",
+        "git.resolve_reconciliation",
+        "git.set_doc_sync",
+        "git.blame",
     ];
 
     for method in methods {
@@ -520,10 +629,13 @@ async fn call_raw_json(state: &RpcServerState, payload: serde_json::Value) -> Re
     handle_raw_request(&raw, state).await
 }
 
+const CONTRACT_GIT_WORKSPACE_ID: Uuid = Uuid::from_u128(0x5c71_0000_0000_4000_8000_0000_0000_0001);
+
 fn contract_state_with_git() -> RpcServerState {
     let git_root = unique_temp_path("scriptum-jsonrpc-contract-git");
     fs::create_dir_all(&git_root).expect("git root path should be creatable");
-    RpcServerState::default().with_git_state(GitState::new(git_root))
+    RpcServerState::default()
+        .with_workspace_git_state(CONTRACT_GIT_WORKSPACE_ID, GitState::new(git_root))
 }
 
 fn unique_temp_path(prefix: &str) -> PathBuf {
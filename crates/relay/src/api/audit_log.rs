@@ -0,0 +1,315 @@
+// Owner-only audit log API: filtered listing, NDJSON export and hash-chain
+// verification over `audit_events`.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Extension, Json, Path, Query, State},
+    http::header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    response::{IntoResponse, Response},
+};
+use futures_util::stream;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{
+    types::chrono::{DateTime, Utc},
+    PgPool,
+};
+use uuid::Uuid;
+
+use crate::{
+    audit::{
+        chain::{ChainVerification, ChainVerifier},
+        query::{list_events, AuditCursor, AuditEventFilter, AuditOrder},
+        AuditEvent, AuditEventType, AuditLog,
+    },
+    auth::middleware::AuthenticatedUser,
+};
+
+use super::{
+    encode_cursor, normalize_limit, parse_cursor, try_record_audit_event, ApiError, ApiState,
+};
+
+const EXPORT_PAGE_SIZE: i64 = 500;
+
+#[derive(Debug, Default, Deserialize)]
+pub(super) struct AuditEventsQuery {
+    event_type: Option<String>,
+    actor_user_id: Option<Uuid>,
+    actor_agent_id: Option<String>,
+    entity_type: Option<String>,
+    entity_id: Option<String>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    cursor: Option<String>,
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct AuditEventView {
+    id: Uuid,
+    workspace_id: Option<Uuid>,
+    actor_user_id: Option<Uuid>,
+    actor_agent_id: Option<String>,
+    event_type: String,
+    entity_type: String,
+    entity_id: String,
+    request_id: Option<String>,
+    ip_hash: Option<String>,
+    user_agent_hash: Option<String>,
+    details: Option<Value>,
+    prev_hash: Option<String>,
+    event_hash: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<AuditEvent> for AuditEventView {
+    fn from(event: AuditEvent) -> Self {
+        Self {
+            id: event.id,
+            workspace_id: event.workspace_id,
+            actor_user_id: event.actor_user_id,
+            actor_agent_id: event.actor_agent_id,
+            event_type: event.event_type,
+            entity_type: event.entity_type,
+            entity_id: event.entity_id,
+            request_id: event.request_id,
+            ip_hash: event.ip_hash.as_deref().map(to_hex),
+            user_agent_hash: event.user_agent_hash.as_deref().map(to_hex),
+            details: event.details,
+            prev_hash: event.prev_hash.as_deref().map(to_hex),
+            event_hash: event.event_hash.as_deref().map(to_hex),
+            created_at: event.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct AuditEventsPageEnvelope {
+    items: Vec<AuditEventView>,
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct AuditChainEnvelope {
+    hash_chain_enabled: bool,
+    valid: bool,
+    #[serde(flatten)]
+    verification: ChainVerification,
+}
+
+pub(super) async fn list_audit_events(
+    State(state): State<ApiState>,
+    Path(workspace_id): Path<Uuid>,
+    Query(query): Query<AuditEventsQuery>,
+) -> Result<Json<AuditEventsPageEnvelope>, ApiError> {
+    let filter = parse_filter(&query)?;
+    let cursor = query.cursor.as_deref().map(parse_cursor).transpose()?;
+    let limit = normalize_limit(query.limit);
+
+    let Some(pool) = state.store.postgres_pool() else {
+        return Ok(Json(AuditEventsPageEnvelope { items: Vec::new(), next_cursor: None }));
+    };
+
+    let mut events = list_events(
+        pool,
+        workspace_id,
+        &filter,
+        cursor.map(|cursor| AuditCursor { created_at: cursor.created_at, id: cursor.id }),
+        AuditOrder::NewestFirst,
+        limit as i64 + 1,
+    )
+    .await
+    .map_err(ApiError::internal)?;
+
+    let next_cursor = if events.len() > limit {
+        events.truncate(limit);
+        events.last().map(|event| encode_cursor(event.created_at, event.id))
+    } else {
+        None
+    };
+
+    Ok(Json(AuditEventsPageEnvelope {
+        items: events.into_iter().map(AuditEventView::from).collect(),
+        next_cursor,
+    }))
+}
+
+/// Streams every matching event, oldest first, as newline-delimited JSON.
+pub(super) async fn export_audit_events(
+    State(state): State<ApiState>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(workspace_id): Path<Uuid>,
+    Query(query): Query<AuditEventsQuery>,
+) -> Result<Response, ApiError> {
+    let filter = parse_filter(&query)?;
+    try_record_audit_event(
+        &state,
+        Some(workspace_id),
+        Some(user.user_id),
+        AuditEventType::AdminAction,
+        "workspace",
+        workspace_id.to_string(),
+        Some(serde_json::json!({
+            "action": "export_audit_events",
+            "event_type": query.event_type,
+            "actor_user_id": query.actor_user_id,
+            "actor_agent_id": query.actor_agent_id,
+            "entity_type": query.entity_type,
+            "entity_id": query.entity_id,
+            "since": query.since,
+            "until": query.until,
+        })),
+    )
+    .await;
+
+    let body = match state.store.postgres_pool().cloned() {
+        Some(pool) => Body::from_stream(export_stream(pool, workspace_id, filter)),
+        None => Body::empty(),
+    };
+
+    Ok((
+        [
+            (CONTENT_TYPE, "application/x-ndjson".to_owned()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"audit-events-{workspace_id}.ndjson\""),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Walks the workspace's full log in chain order and reports the first
+/// event whose link or hash does not match.
+pub(super) async fn verify_audit_chain(
+    State(state): State<ApiState>,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<Json<AuditChainEnvelope>, ApiError> {
+    let mut verifier = ChainVerifier::default();
+
+    if let Some(pool) = state.store.postgres_pool() {
+        let filter = AuditEventFilter::default();
+        let mut cursor = None;
+        loop {
+            let page = list_events(
+                pool,
+                workspace_id,
+                &filter,
+                cursor,
+                AuditOrder::OldestFirst,
+                EXPORT_PAGE_SIZE,
+            )
+            .await
+            .map_err(ApiError::internal)?;
+            page.iter().for_each(|event| verifier.push(event));
+            match page.last() {
+                Some(last) if page.len() as i64 == EXPORT_PAGE_SIZE => {
+                    cursor = Some(AuditCursor { created_at: last.created_at, id: last.id });
+                }
+                _ => break,
+            }
+        }
+    }
+
+    let verification = verifier.finish();
+    Ok(Json(AuditChainEnvelope {
+        hash_chain_enabled: state.audit.as_ref().is_some_and(AuditLog::hash_chain_enabled),
+        valid: verification.is_valid(),
+        verification,
+    }))
+}
+
+struct ExportState {
+    pool: PgPool,
+    workspace_id: Uuid,
+    filter: AuditEventFilter,
+    cursor: Option<AuditCursor>,
+    done: bool,
+}
+
+fn export_stream(
+    pool: PgPool,
+    workspace_id: Uuid,
+    filter: AuditEventFilter,
+) -> impl futures_util::Stream<Item = Result<Bytes, std::io::Error>> {
+    let state = ExportState { pool, workspace_id, filter, cursor: None, done: false };
+
+    stream::unfold(state, |mut state| async move {
+        if state.done {
+            return None;
+        }
+        let page = match list_events(
+            &state.pool,
+            state.workspace_id,
+            &state.filter,
+            state.cursor,
+            AuditOrder::OldestFirst,
+            EXPORT_PAGE_SIZE,
+        )
+        .await
+        {
+            Ok(page) => page,
+            Err(error) => {
+                tracing::error!(error = ?error, "audit export failed mid-stream");
+                state.done = true;
+                return Some((Err(std::io::Error::other("audit export failed")), state));
+            }
+        };
+
+        state.done = (page.len() as i64) < EXPORT_PAGE_SIZE;
+        state.cursor =
+            page.last().map(|last| AuditCursor { created_at: last.created_at, id: last.id });
+        if page.is_empty() {
+            return None;
+        }
+        Some((Ok(Bytes::from(encode_ndjson(page))), state))
+    })
+}
+
+fn encode_ndjson(events: Vec<AuditEvent>) -> Vec<u8> {
+    let mut out = Vec::new();
+    for event in events {
+        // Serializing a plain struct of strings, ids and JSON values cannot fail.
+        if serde_json::to_writer(&mut out, &AuditEventView::from(event)).is_ok() {
+            out.push(b'\n');
+        }
+    }
+    out
+}
+
+fn parse_filter(query: &AuditEventsQuery) -> Result<AuditEventFilter, ApiError> {
+    let event_type = query
+        .event_type
+        .as_deref()
+        .map(|value| {
+            AuditEventType::from_db_value(value).ok_or_else(|| {
+                ApiError::bad_request("VALIDATION_ERROR", format!("unknown event_type '{value}'"))
+            })
+        })
+        .transpose()?;
+
+    if let (Some(since), Some(until)) = (query.since, query.until) {
+        if since >= until {
+            return Err(ApiError::bad_request("VALIDATION_ERROR", "since must be before until"));
+        }
+    }
+
+    Ok(AuditEventFilter {
+        event_type,
+        actor_user_id: query.actor_user_id,
+        actor_agent_id: non_empty(query.actor_agent_id.as_deref()),
+        entity_type: non_empty(query.entity_type.as_deref()),
+        entity_id: non_empty(query.entity_id.as_deref()),
+        since: query.since,
+        until: query.until,
+    })
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value.map(str::trim).filter(|value| !value.is_empty()).map(str::to_owned)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
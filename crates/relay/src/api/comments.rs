@@ -28,8 +28,10 @@ use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::{
-    audit::{self, AuditEventType, NewAuditEvent},
+    audit::{AuditEventType, AuditLog, NewAuditEvent},
     auth::{
+        api_tokens::ApiTokenScope,
+        authz::{AuthorizationService, AuthzError, Principal},
         jwt::JwtAccessTokenService,
         middleware::{require_bearer_auth, AuthenticatedUser, WorkspaceRole},
     },
@@ -134,6 +136,8 @@ struct ThreadResponse {
 #[derive(Clone)]
 struct CommentsApiState {
     store: CommentStore,
+    authz: AuthorizationService,
+    audit: Option<AuditLog>,
 }
 
 #[derive(Clone)]
@@ -147,7 +151,6 @@ enum CommentStore {
 struct MemoryCommentStore {
     threads: HashMap<Uuid, MemoryCommentThread>,
     messages: HashMap<Uuid, Vec<MemoryCommentMessage>>,
-    workspace_members: HashMap<(Uuid, Uuid), WorkspaceRole>,
 }
 
 #[derive(Clone)]
@@ -266,6 +269,17 @@ impl CommentsApiError {
     }
 }
 
+impl From<AuthzError> for CommentsApiError {
+    fn from(error: AuthzError) -> Self {
+        match error {
+            AuthzError::NoWorkspaceAccess
+            | AuthzError::InsufficientRole
+            | AuthzError::InsufficientScope => Self::Forbidden,
+            AuthzError::Internal(error) => Self::Internal(error),
+        }
+    }
+}
+
 impl IntoResponse for CommentsApiError {
     fn into_response(self) -> Response {
         match self {
@@ -294,13 +308,30 @@ impl IntoResponse for CommentsApiError {
 
 // ── Router ───────────────────────────────────────────────────────────────────
 
-pub fn router(pool: PgPool, jwt_service: Arc<JwtAccessTokenService>) -> Router {
-    build_router_with_store(CommentStore::Postgres(pool), jwt_service)
+pub fn router(pool: PgPool, audit: AuditLog, jwt_service: Arc<JwtAccessTokenService>) -> Router {
+    build_router_with_state(
+        CommentsApiState {
+            store: CommentStore::Postgres(pool.clone()),
+            authz: AuthorizationService::Postgres(pool),
+            audit: Some(audit),
+        },
+        jwt_service,
+    )
 }
 
-fn build_router_with_store(store: CommentStore, jwt_service: Arc<JwtAccessTokenService>) -> Router {
-    let state = CommentsApiState { store };
+#[cfg(test)]
+fn build_router_with_store(
+    store: CommentStore,
+    authz: AuthorizationService,
+    jwt_service: Arc<JwtAccessTokenService>,
+) -> Router {
+    build_router_with_state(CommentsApiState { store, authz, audit: None }, jwt_service)
+}
 
+fn build_router_with_state(
+    state: CommentsApiState,
+    jwt_service: Arc<JwtAccessTokenService>,
+) -> Router {
     Router::new()
         .route(
             "/v1/workspaces/{ws_id}/documents/{doc_id}/comments",
@@ -321,7 +352,7 @@ async fn list_comments(
     Path((ws_id, doc_id)): Path<(Uuid, Uuid)>,
     Query(query): Query<ListCommentsQuery>,
 ) -> Result<Json<ListCommentsResponse>, CommentsApiError> {
-    require_workspace_role(&state.store, &user, ws_id, WorkspaceRole::Viewer).await?;
+    require_document_role(&state.authz, &user, ws_id, doc_id, WorkspaceRole::Viewer).await?;
 
     let status_filter = parse_status_filter(query.status.as_deref())?;
     let limit = normalize_limit(query.limit);
@@ -342,7 +373,7 @@ async fn create_comment_thread(
     Path((ws_id, doc_id)): Path<(Uuid, Uuid)>,
     ValidatedJson(payload): ValidatedJson<CreateCommentThreadRequest>,
 ) -> Result<(StatusCode, Json<CreateThreadResponse>), CommentsApiError> {
-    require_workspace_role(&state.store, &user, ws_id, WorkspaceRole::Editor).await?;
+    require_document_role(&state.authz, &user, ws_id, doc_id, WorkspaceRole::Editor).await?;
 
     validate_anchor(&payload.anchor)?;
     validate_markdown_body("message", &payload.message)?;
@@ -375,7 +406,7 @@ async fn create_comment_message(
     Path((ws_id, thread_id)): Path<(Uuid, Uuid)>,
     ValidatedJson(payload): ValidatedJson<CreateCommentMessageRequest>,
 ) -> Result<Json<CreateMessageResponse>, CommentsApiError> {
-    require_workspace_role(&state.store, &user, ws_id, WorkspaceRole::Editor).await?;
+    require_thread_role(&state, &user, ws_id, thread_id, WorkspaceRole::Editor).await?;
 
     validate_markdown_body("body_md", &payload.body_md)?;
 
@@ -402,7 +433,7 @@ async fn resolve_comment_thread(
     Path((ws_id, thread_id)): Path<(Uuid, Uuid)>,
     ValidatedJson(payload): ValidatedJson<SetCommentStatusRequest>,
 ) -> Result<Json<ThreadResponse>, CommentsApiError> {
-    require_workspace_role(&state.store, &user, ws_id, WorkspaceRole::Editor).await?;
+    require_thread_role(&state, &user, ws_id, thread_id, WorkspaceRole::Editor).await?;
 
     if payload.if_version < 1 {
         return Err(CommentsApiError::bad_request("if_version must be >= 1"));
@@ -435,7 +466,7 @@ async fn reopen_comment_thread(
     Path((ws_id, thread_id)): Path<(Uuid, Uuid)>,
     ValidatedJson(payload): ValidatedJson<SetCommentStatusRequest>,
 ) -> Result<Json<ThreadResponse>, CommentsApiError> {
-    require_workspace_role(&state.store, &user, ws_id, WorkspaceRole::Editor).await?;
+    require_thread_role(&state, &user, ws_id, thread_id, WorkspaceRole::Editor).await?;
 
     if payload.if_version < 1 {
         return Err(CommentsApiError::bad_request("if_version must be >= 1"));
@@ -552,35 +583,12 @@ impl CommentStore {
         }
     }
 
-    async fn workspace_role_for_user(
-        &self,
-        user_id: Uuid,
-        workspace_id: Uuid,
-    ) -> Result<Option<WorkspaceRole>, CommentsApiError> {
-        match self {
-            Self::Postgres(pool) => workspace_role_for_user_pg(pool, user_id, workspace_id).await,
-            Self::Memory(store) => workspace_role_for_user_mem(store, user_id, workspace_id).await,
-        }
-    }
-
     fn postgres_pool(&self) -> Option<&PgPool> {
         match self {
             Self::Postgres(pool) => Some(pool),
             Self::Memory(_) => None,
         }
     }
-
-    #[cfg(test)]
-    async fn grant_workspace_role_for_tests(
-        &self,
-        workspace_id: Uuid,
-        user_id: Uuid,
-        role: WorkspaceRole,
-    ) {
-        if let Self::Memory(store) = self {
-            store.write().await.workspace_members.insert((workspace_id, user_id), role);
-        }
-    }
 }
 
 // ── Postgres store ───────────────────────────────────────────────────────────
@@ -994,37 +1002,6 @@ async fn set_thread_status_pg(
     )))
 }
 
-async fn workspace_role_for_user_pg(
-    pool: &PgPool,
-    user_id: Uuid,
-    workspace_id: Uuid,
-) -> Result<Option<WorkspaceRole>, CommentsApiError> {
-    let role = sqlx::query_scalar::<_, String>(
-        r#"
-        SELECT role
-        FROM workspace_members
-        WHERE workspace_id = $1
-          AND user_id = $2
-          AND status = 'active'
-        LIMIT 1
-        "#,
-    )
-    .bind(workspace_id)
-    .bind(user_id)
-    .fetch_optional(pool)
-    .await
-    .map_err(map_sqlx_error)?;
-
-    role.map(|value| {
-        WorkspaceRole::from_db_value(&value).ok_or_else(|| {
-            CommentsApiError::internal(anyhow::anyhow!(
-                "invalid workspace role '{value}' in database"
-            ))
-        })
-    })
-    .transpose()
-}
-
 // ── In-memory store ──────────────────────────────────────────────────────────
 
 async fn create_thread_mem(
@@ -1219,15 +1196,6 @@ async fn set_thread_status_mem(
     Ok(mem_thread_to_public(thread))
 }
 
-async fn workspace_role_for_user_mem(
-    store: &RwLock<MemoryCommentStore>,
-    user_id: Uuid,
-    workspace_id: Uuid,
-) -> Result<Option<WorkspaceRole>, CommentsApiError> {
-    let store = store.read().await;
-    Ok(store.workspace_members.get(&(workspace_id, user_id)).copied())
-}
-
 fn mem_thread_to_public(value: &MemoryCommentThread) -> CommentThread {
     CommentThread {
         id: value.id,
@@ -1417,25 +1385,55 @@ fn map_sqlx_error(error: sqlx::Error) -> CommentsApiError {
     CommentsApiError::internal(error.into())
 }
 
-async fn require_workspace_role(
-    store: &CommentStore,
+async fn require_document_role(
+    authz: &AuthorizationService,
     user: &AuthenticatedUser,
     workspace_id: Uuid,
+    doc_id: Uuid,
     required_role: WorkspaceRole,
 ) -> Result<(), CommentsApiError> {
     if user.workspace_id != workspace_id {
         return Err(CommentsApiError::Forbidden);
     }
 
-    let role = store.workspace_role_for_user(user.user_id, workspace_id).await?;
-    let Some(role) = role else {
-        return Err(CommentsApiError::Forbidden);
+    // Comment mutations need the `comment` scope rather than `write`, so
+    // review bots can discuss a document without being able to edit it.
+    let required_scope = match required_role {
+        WorkspaceRole::Viewer => Some(ApiTokenScope::Read),
+        WorkspaceRole::Editor => Some(ApiTokenScope::Comment),
+        WorkspaceRole::Owner => None,
     };
-    if !role.allows(required_role) {
+    authz
+        .require_document_access(
+            workspace_id,
+            doc_id,
+            &Principal::from(user),
+            required_role,
+            required_scope,
+        )
+        .await?;
+    Ok(())
+}
+
+/// Checks workspace membership first so non-members cannot probe thread ids,
+/// then applies the role required on the thread's document.
+async fn require_thread_role(
+    state: &CommentsApiState,
+    user: &AuthenticatedUser,
+    workspace_id: Uuid,
+    thread_id: Uuid,
+    required_role: WorkspaceRole,
+) -> Result<(), CommentsApiError> {
+    if user.workspace_id != workspace_id {
         return Err(CommentsApiError::Forbidden);
     }
 
-    Ok(())
+    state
+        .authz
+        .require_workspace_role(workspace_id, &Principal::from(user), WorkspaceRole::Viewer)
+        .await?;
+    let thread = state.store.get_thread_with_messages(workspace_id, thread_id).await?.thread;
+    require_document_role(&state.authz, user, workspace_id, thread.doc_id, required_role).await
 }
 
 async fn try_record_comment_audit_event(
@@ -1446,7 +1444,7 @@ async fn try_record_comment_audit_event(
     action: &'static str,
     details: serde_json::Value,
 ) {
-    let Some(pool) = state.store.postgres_pool() else {
+    let Some(audit) = &state.audit else {
         return;
     };
 
@@ -1466,7 +1464,7 @@ async fn try_record_comment_audit_event(
         })),
     };
 
-    if let Err(error) = audit::record_event(pool, event).await {
+    if let Err(error) = audit.record(event).await {
         tracing::warn!(error = ?error, "failed to record comment audit event");
     }
 }
@@ -1495,19 +1493,19 @@ mod tests {
         CommentStore::Memory(Arc::new(RwLock::new(MemoryCommentStore::default())))
     }
 
-    fn test_app() -> (Router, CommentStore) {
-        let store = test_store();
-        let app = build_router_with_store(store.clone(), test_jwt_service());
-        (app, store)
+    fn test_app() -> (Router, AuthorizationService) {
+        let authz = AuthorizationService::for_tests();
+        let app = build_router_with_store(test_store(), authz.clone(), test_jwt_service());
+        (app, authz)
     }
 
     async fn grant_workspace_role(
-        store: &CommentStore,
+        authz: &AuthorizationService,
         workspace_id: Uuid,
         user_id: Uuid,
         role: WorkspaceRole,
     ) {
-        store.grant_workspace_role_for_tests(workspace_id, user_id, role).await;
+        authz.grant_for_tests(workspace_id, user_id, role).await;
     }
 
     fn auth_token(jwt: &JwtAccessTokenService, user_id: Uuid, workspace_id: Uuid) -> String {
@@ -1547,13 +1545,13 @@ mod tests {
 
     #[tokio::test]
     async fn create_list_resolve_and_filter_comments() {
-        let (app, store) = test_app();
+        let (app, authz) = test_app();
         let jwt = test_jwt_service();
         let ws_id = Uuid::new_v4();
         let doc_id = Uuid::new_v4();
         let user_id = Uuid::new_v4();
         let token = auth_token(&jwt, user_id, ws_id);
-        grant_workspace_role(&store, ws_id, user_id, WorkspaceRole::Editor).await;
+        grant_workspace_role(&authz, ws_id, user_id, WorkspaceRole::Editor).await;
 
         let create_response = app
             .clone()
@@ -1641,13 +1639,13 @@ mod tests {
 
     #[tokio::test]
     async fn reply_and_reopen_comment_thread() {
-        let (app, store) = test_app();
+        let (app, authz) = test_app();
         let jwt = test_jwt_service();
         let ws_id = Uuid::new_v4();
         let doc_id = Uuid::new_v4();
         let user_id = Uuid::new_v4();
         let token = auth_token(&jwt, user_id, ws_id);
-        grant_workspace_role(&store, ws_id, user_id, WorkspaceRole::Editor).await;
+        grant_workspace_role(&authz, ws_id, user_id, WorkspaceRole::Editor).await;
 
         let create_response = app
             .clone()
@@ -1719,13 +1717,13 @@ mod tests {
 
     #[tokio::test]
     async fn resolve_with_stale_if_version_returns_412() {
-        let (app, store) = test_app();
+        let (app, authz) = test_app();
         let jwt = test_jwt_service();
         let ws_id = Uuid::new_v4();
         let doc_id = Uuid::new_v4();
         let user_id = Uuid::new_v4();
         let token = auth_token(&jwt, user_id, ws_id);
-        grant_workspace_role(&store, ws_id, user_id, WorkspaceRole::Editor).await;
+        grant_workspace_role(&authz, ws_id, user_id, WorkspaceRole::Editor).await;
 
         let create_response = app
             .clone()
@@ -1764,7 +1762,7 @@ mod tests {
 
     #[tokio::test]
     async fn viewer_role_can_list_but_cannot_mutate_comment_threads() {
-        let (app, store) = test_app();
+        let (app, authz) = test_app();
         let jwt = test_jwt_service();
         let ws_id = Uuid::new_v4();
         let doc_id = Uuid::new_v4();
@@ -1772,8 +1770,8 @@ mod tests {
         let viewer_id = Uuid::new_v4();
         let owner_token = auth_token(&jwt, owner_id, ws_id);
         let viewer_token = auth_token(&jwt, viewer_id, ws_id);
-        grant_workspace_role(&store, ws_id, owner_id, WorkspaceRole::Owner).await;
-        grant_workspace_role(&store, ws_id, viewer_id, WorkspaceRole::Viewer).await;
+        grant_workspace_role(&authz, ws_id, owner_id, WorkspaceRole::Owner).await;
+        grant_workspace_role(&authz, ws_id, viewer_id, WorkspaceRole::Viewer).await;
 
         let create_response = app
             .clone()
@@ -1844,13 +1842,13 @@ mod tests {
 
     #[tokio::test]
     async fn status_transitions_enforce_open_and_resolved_guards() {
-        let (app, store) = test_app();
+        let (app, authz) = test_app();
         let jwt = test_jwt_service();
         let ws_id = Uuid::new_v4();
         let doc_id = Uuid::new_v4();
         let user_id = Uuid::new_v4();
         let token = auth_token(&jwt, user_id, ws_id);
-        grant_workspace_role(&store, ws_id, user_id, WorkspaceRole::Editor).await;
+        grant_workspace_role(&authz, ws_id, user_id, WorkspaceRole::Editor).await;
 
         let create_response = app
             .clone()
@@ -1917,6 +1915,77 @@ mod tests {
         assert_eq!(reply_resolved_response.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[tokio::test]
+    async fn acl_override_locks_comment_mutations_on_document() {
+        let (app, authz) = test_app();
+        let jwt = test_jwt_service();
+        let ws_id = Uuid::new_v4();
+        let open_doc_id = Uuid::new_v4();
+        let locked_doc_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let token = auth_token(&jwt, user_id, ws_id);
+        grant_workspace_role(&authz, ws_id, user_id, WorkspaceRole::Editor).await;
+
+        let create_thread = |doc_id: Uuid| {
+            json_request(
+                "POST",
+                &format!("/v1/workspaces/{ws_id}/documents/{doc_id}/comments"),
+                serde_json::json!({
+                    "anchor": { "section_id": "h1:intro" },
+                    "message": "Needs a citation."
+                }),
+                &token,
+            )
+        };
+
+        let open_response =
+            app.clone().oneshot(create_thread(open_doc_id)).await.expect("create should respond");
+        assert_eq!(open_response.status(), StatusCode::CREATED);
+        let thread_id = body_json(open_response).await["thread"]["id"]
+            .as_str()
+            .expect("thread id should be present")
+            .to_string();
+
+        // Lock the thread's document too, then verify replies are rejected.
+        for doc_id in [open_doc_id, locked_doc_id] {
+            authz
+                .override_for_tests(
+                    ws_id,
+                    doc_id,
+                    "user",
+                    &user_id.to_string(),
+                    WorkspaceRole::Viewer,
+                    None,
+                )
+                .await;
+        }
+
+        let locked_response =
+            app.clone().oneshot(create_thread(locked_doc_id)).await.expect("create should respond");
+        assert_eq!(locked_response.status(), StatusCode::FORBIDDEN);
+
+        let reply_response = app
+            .clone()
+            .oneshot(json_request(
+                "POST",
+                &format!("/v1/workspaces/{ws_id}/comments/{thread_id}/messages"),
+                serde_json::json!({ "body_md": "Following up." }),
+                &token,
+            ))
+            .await
+            .expect("reply should respond");
+        assert_eq!(reply_response.status(), StatusCode::FORBIDDEN);
+
+        let list_response = app
+            .oneshot(get_request(
+                &format!("/v1/workspaces/{ws_id}/documents/{open_doc_id}/comments"),
+                &token,
+            ))
+            .await
+            .expect("list should respond");
+        assert_eq!(list_response.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn unauthenticated_request_returns_401() {
         let (app, _) = test_app();
@@ -28,13 +28,15 @@ use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::{
-    audit::{self, AuditEventType, NewAuditEvent},
+    audit::{AuditEventType, AuditLog, NewAuditEvent},
     auth::{
+        authz::{AclOverride, AuthorizationService, AuthzError, NewAclOverride, Principal},
         jwt::JwtAccessTokenService,
         middleware::{require_bearer_auth, AuthenticatedUser, WorkspaceRole},
     },
     error::{current_request_id, ErrorCode, RelayError},
     validation::ValidatedJson,
+    ws::SyncSessionStore,
 };
 
 // ── Types ──────────────────────────────────────────────────────────
@@ -131,45 +133,6 @@ pub struct UpdateDocumentTagsRequest {
     pub tags: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct AclOverride {
-    pub id: Uuid,
-    pub workspace_id: Uuid,
-    pub doc_id: Uuid,
-    pub subject_type: String,
-    pub subject_id: String,
-    pub role: String,
-    pub expires_at: Option<DateTime<Utc>>,
-    pub created_at: DateTime<Utc>,
-}
-
-#[derive(sqlx::FromRow)]
-struct AclOverrideRow {
-    id: Uuid,
-    workspace_id: Uuid,
-    doc_id: Uuid,
-    subject_type: String,
-    subject_id: String,
-    role: String,
-    expires_at: Option<DateTime<Utc>>,
-    created_at: DateTime<Utc>,
-}
-
-impl From<AclOverrideRow> for AclOverride {
-    fn from(row: AclOverrideRow) -> Self {
-        Self {
-            id: row.id,
-            workspace_id: row.workspace_id,
-            doc_id: row.doc_id,
-            subject_type: row.subject_type,
-            subject_id: row.subject_id,
-            role: row.role,
-            expires_at: row.expires_at,
-            created_at: row.created_at,
-        }
-    }
-}
-
 #[derive(Debug, Clone, Deserialize)]
 pub struct CreateAclOverrideRequest {
     pub subject_type: String,
@@ -216,6 +179,11 @@ struct DocumentsPageEnvelope {
 #[derive(Clone)]
 struct DocApiState {
     store: DocumentStore,
+    authz: AuthorizationService,
+    /// Live sync sessions, whose cached document roles go stale when an ACL
+    /// override changes.
+    sessions: Arc<SyncSessionStore>,
+    audit: Option<AuditLog>,
 }
 
 #[derive(Clone)]
@@ -228,8 +196,6 @@ enum DocumentStore {
 #[derive(Default)]
 struct MemoryDocumentStore {
     documents: HashMap<Uuid, MemoryDocument>,
-    acl_overrides: HashMap<Uuid, MemoryAclOverride>,
-    workspace_members: HashMap<(Uuid, Uuid), WorkspaceRole>,
 }
 
 #[derive(Clone)]
@@ -249,18 +215,6 @@ struct MemoryDocument {
     tags: HashSet<String>,
 }
 
-#[derive(Clone)]
-struct MemoryAclOverride {
-    id: Uuid,
-    workspace_id: Uuid,
-    doc_id: Uuid,
-    subject_type: String,
-    subject_id: String,
-    role: String,
-    expires_at: Option<DateTime<Utc>>,
-    created_at: DateTime<Utc>,
-}
-
 // ── Error ──────────────────────────────────────────────────────────
 
 #[derive(Debug)]
@@ -284,6 +238,17 @@ impl DocApiError {
     }
 }
 
+impl From<AuthzError> for DocApiError {
+    fn from(error: AuthzError) -> Self {
+        match error {
+            AuthzError::NoWorkspaceAccess
+            | AuthzError::InsufficientRole
+            | AuthzError::InsufficientScope => Self::Forbidden,
+            AuthzError::Internal(error) => Self::Internal(error),
+        }
+    }
+}
+
 impl IntoResponse for DocApiError {
     fn into_response(self) -> Response {
         match self {
@@ -320,16 +285,34 @@ impl IntoResponse for DocApiError {
 
 // ── Router ─────────────────────────────────────────────────────────
 
-pub fn router(pool: PgPool, jwt_service: Arc<JwtAccessTokenService>) -> Router {
-    build_router_with_store(DocumentStore::Postgres(pool), jwt_service)
+pub fn router(
+    pool: PgPool,
+    audit: AuditLog,
+    sessions: Arc<SyncSessionStore>,
+    jwt_service: Arc<JwtAccessTokenService>,
+) -> Router {
+    build_router_with_state(
+        DocApiState {
+            store: DocumentStore::Postgres(pool.clone()),
+            authz: AuthorizationService::Postgres(pool),
+            sessions,
+            audit: Some(audit),
+        },
+        jwt_service,
+    )
 }
 
+#[cfg(test)]
 fn build_router_with_store(
     store: DocumentStore,
+    authz: AuthorizationService,
+    sessions: Arc<SyncSessionStore>,
     jwt_service: Arc<JwtAccessTokenService>,
 ) -> Router {
-    let state = DocApiState { store };
+    build_router_with_state(DocApiState { store, authz, sessions, audit: None }, jwt_service)
+}
 
+fn build_router_with_state(state: DocApiState, jwt_service: Arc<JwtAccessTokenService>) -> Router {
     Router::new()
         .route("/v1/workspaces/{ws_id}/documents", post(create_document).get(list_documents))
         .route(
@@ -354,8 +337,9 @@ async fn create_document(
     Path(ws_id): Path<Uuid>,
     ValidatedJson(payload): ValidatedJson<CreateDocumentRequest>,
 ) -> Result<(StatusCode, [(&'static str, String); 1], Json<CreateDocumentEnvelope>), DocApiError> {
-    require_workspace_role(&state.store, &user, ws_id, WorkspaceRole::Editor).await?;
+    require_workspace_role(&state.authz, &user, ws_id, WorkspaceRole::Editor).await?;
     validate_path(&payload.path)?;
+    require_token_path(&user, &payload.path)?;
 
     let normalized_tags =
         if payload.tags.is_empty() { None } else { Some(normalize_tag_names(&payload.tags)?) };
@@ -368,7 +352,7 @@ async fn create_document(
 
     try_record_document_audit_event(
         &state,
-        user.user_id,
+        &user,
         ws_id,
         AuditEventType::AdminAction,
         document.id,
@@ -396,7 +380,7 @@ async fn list_documents(
     Path(ws_id): Path<Uuid>,
     Query(query): Query<ListDocumentsQuery>,
 ) -> Result<Json<DocumentsPageEnvelope>, DocApiError> {
-    require_workspace_role(&state.store, &user, ws_id, WorkspaceRole::Viewer).await?;
+    require_workspace_role(&state.authz, &user, ws_id, WorkspaceRole::Viewer).await?;
     let limit = normalize_limit(query.limit);
     let archived = normalize_archived_filter(&query);
     let cursor = match query.cursor {
@@ -404,8 +388,11 @@ async fn list_documents(
         None => None,
     };
 
-    let (items, next_cursor) =
+    let (mut items, next_cursor) =
         state.store.list(ws_id, query.path_prefix.as_deref(), archived, limit, cursor).await?;
+    if let Some(grant) = &user.token {
+        items.retain(|document| grant.allows_path(&document.path));
+    }
 
     Ok(Json(DocumentsPageEnvelope { items, next_cursor }))
 }
@@ -415,7 +402,7 @@ async fn get_document(
     Extension(user): Extension<AuthenticatedUser>,
     Path((ws_id, doc_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<DocumentEnvelope>, DocApiError> {
-    require_document_role(&state.store, &user, ws_id, doc_id, WorkspaceRole::Viewer).await?;
+    require_document_role(&state.authz, &user, ws_id, doc_id, WorkspaceRole::Viewer).await?;
     let document = state.store.get(ws_id, doc_id).await?;
     Ok(Json(DocumentEnvelope { document }))
 }
@@ -427,16 +414,17 @@ async fn update_document(
     headers: HeaderMap,
     ValidatedJson(payload): ValidatedJson<UpdateDocumentRequest>,
 ) -> Result<Json<DocumentEnvelope>, DocApiError> {
-    require_document_role(&state.store, &user, ws_id, doc_id, WorkspaceRole::Editor).await?;
+    require_document_role(&state.authz, &user, ws_id, doc_id, WorkspaceRole::Editor).await?;
     if let Some(path) = payload.path.as_deref() {
         validate_path(path)?;
+        require_token_path(&user, path)?;
     }
 
     let if_match = extract_if_match(&headers)?;
     let document = state.store.update(ws_id, doc_id, if_match, &payload).await?;
     try_record_document_audit_event(
         &state,
-        user.user_id,
+        &user,
         ws_id,
         AuditEventType::AdminAction,
         document.id,
@@ -459,7 +447,7 @@ async fn delete_document(
     headers: HeaderMap,
     Query(query): Query<DeleteDocumentQuery>,
 ) -> Result<StatusCode, DocApiError> {
-    require_document_role(&state.store, &user, ws_id, doc_id, WorkspaceRole::Owner).await?;
+    require_document_role(&state.authz, &user, ws_id, doc_id, WorkspaceRole::Owner).await?;
     let if_match = extract_if_match(&headers)?;
     let current = state.store.get(ws_id, doc_id).await?;
     if !etag_matches(if_match, &current.etag) {
@@ -470,7 +458,7 @@ async fn delete_document(
     state.store.delete(ws_id, doc_id, hard).await?;
     try_record_document_audit_event(
         &state,
-        user.user_id,
+        &user,
         ws_id,
         AuditEventType::Delete,
         doc_id,
@@ -489,7 +477,7 @@ async fn update_document_tags(
     Path((ws_id, doc_id)): Path<(Uuid, Uuid)>,
     ValidatedJson(payload): ValidatedJson<UpdateDocumentTagsRequest>,
 ) -> Result<Json<DocumentEnvelope>, DocApiError> {
-    require_document_role(&state.store, &user, ws_id, doc_id, WorkspaceRole::Editor).await?;
+    require_document_role(&state.authz, &user, ws_id, doc_id, WorkspaceRole::Editor).await?;
     let tags = normalize_tag_names(&payload.tags)?;
     let document = state.store.update_tags(ws_id, doc_id, payload.op, &tags).await?;
     Ok(Json(DocumentEnvelope { document }))
@@ -501,11 +489,26 @@ async fn create_acl_override(
     Path((ws_id, doc_id)): Path<(Uuid, Uuid)>,
     ValidatedJson(payload): ValidatedJson<CreateAclOverrideRequest>,
 ) -> Result<(StatusCode, Json<AclOverrideEnvelope>), DocApiError> {
-    require_document_role(&state.store, &user, ws_id, doc_id, WorkspaceRole::Owner).await?;
+    require_document_role(&state.authz, &user, ws_id, doc_id, WorkspaceRole::Owner).await?;
 
     validate_acl_override_request(&payload)?;
-
-    let acl_override = state.store.create_acl_override(ws_id, doc_id, payload).await?;
+    state.store.get(ws_id, doc_id).await?;
+
+    let acl_override = state
+        .authz
+        .create_acl_override(
+            ws_id,
+            doc_id,
+            NewAclOverride {
+                subject_type: payload.subject_type,
+                subject_id: payload.subject_id,
+                role: payload.role,
+                expires_at: payload.expires_at,
+            },
+        )
+        .await
+        .map_err(DocApiError::internal)?;
+    state.sessions.invalidate_doc_roles(doc_id).await;
     Ok((StatusCode::CREATED, Json(AclOverrideEnvelope { acl_override })))
 }
 
@@ -514,9 +517,17 @@ async fn delete_acl_override(
     Extension(user): Extension<AuthenticatedUser>,
     Path((ws_id, doc_id, override_id)): Path<(Uuid, Uuid, Uuid)>,
 ) -> Result<StatusCode, DocApiError> {
-    require_document_role(&state.store, &user, ws_id, doc_id, WorkspaceRole::Owner).await?;
+    require_document_role(&state.authz, &user, ws_id, doc_id, WorkspaceRole::Owner).await?;
 
-    state.store.delete_acl_override(ws_id, doc_id, override_id).await?;
+    let deleted = state
+        .authz
+        .delete_acl_override(ws_id, doc_id, override_id)
+        .await
+        .map_err(DocApiError::internal)?;
+    if !deleted {
+        return Err(DocApiError::NotFound);
+    }
+    state.sessions.invalidate_doc_roles(doc_id).await;
     Ok(StatusCode::NO_CONTENT)
 }
 
@@ -605,77 +616,6 @@ impl DocumentStore {
             Self::Memory(store) => update_tags_mem(store, workspace_id, doc_id, op, tags).await,
         }
     }
-
-    async fn create_acl_override(
-        &self,
-        workspace_id: Uuid,
-        doc_id: Uuid,
-        payload: CreateAclOverrideRequest,
-    ) -> Result<AclOverride, DocApiError> {
-        match self {
-            Self::Postgres(pool) => {
-                create_acl_override_pg(pool, workspace_id, doc_id, payload).await
-            }
-            Self::Memory(store) => {
-                create_acl_override_mem(store, workspace_id, doc_id, payload).await
-            }
-        }
-    }
-
-    async fn delete_acl_override(
-        &self,
-        workspace_id: Uuid,
-        doc_id: Uuid,
-        override_id: Uuid,
-    ) -> Result<(), DocApiError> {
-        match self {
-            Self::Postgres(pool) => {
-                delete_acl_override_pg(pool, workspace_id, doc_id, override_id).await
-            }
-            Self::Memory(store) => {
-                delete_acl_override_mem(store, workspace_id, doc_id, override_id).await
-            }
-        }
-    }
-
-    async fn workspace_role_for_user(
-        &self,
-        user_id: Uuid,
-        workspace_id: Uuid,
-    ) -> Result<Option<WorkspaceRole>, DocApiError> {
-        match self {
-            Self::Postgres(pool) => workspace_role_for_user_pg(pool, user_id, workspace_id).await,
-            Self::Memory(store) => workspace_role_for_user_mem(store, user_id, workspace_id).await,
-        }
-    }
-
-    async fn acl_override_role_for_user(
-        &self,
-        user_id: Uuid,
-        workspace_id: Uuid,
-        doc_id: Uuid,
-    ) -> Result<Option<WorkspaceRole>, DocApiError> {
-        match self {
-            Self::Postgres(pool) => {
-                acl_override_role_for_user_pg(pool, user_id, workspace_id, doc_id).await
-            }
-            Self::Memory(store) => {
-                acl_override_role_for_user_mem(store, user_id, workspace_id, doc_id).await
-            }
-        }
-    }
-
-    #[cfg(test)]
-    async fn grant_workspace_role_for_tests(
-        &self,
-        workspace_id: Uuid,
-        user_id: Uuid,
-        role: WorkspaceRole,
-    ) {
-        if let Self::Memory(store) = self {
-            store.write().await.workspace_members.insert((workspace_id, user_id), role);
-        }
-    }
 }
 
 // ── Postgres implementations ───────────────────────────────────────
@@ -978,142 +918,6 @@ async fn update_tags_pg(
     Ok(row.into())
 }
 
-async fn create_acl_override_pg(
-    pool: &PgPool,
-    workspace_id: Uuid,
-    doc_id: Uuid,
-    payload: CreateAclOverrideRequest,
-) -> Result<AclOverride, DocApiError> {
-    let doc_exists = sqlx::query_scalar::<_, i64>(
-        r#"
-        SELECT 1
-        FROM documents
-        WHERE id = $1 AND workspace_id = $2 AND deleted_at IS NULL
-        "#,
-    )
-    .bind(doc_id)
-    .bind(workspace_id)
-    .fetch_optional(pool)
-    .await
-    .map_err(map_sqlx_error)?
-    .is_some();
-
-    if !doc_exists {
-        return Err(DocApiError::NotFound);
-    }
-
-    let row = sqlx::query_as::<_, AclOverrideRow>(
-        r#"
-        INSERT INTO acl_overrides (workspace_id, doc_id, subject_type, subject_id, role, expires_at)
-        VALUES ($1, $2, $3, $4, $5, $6)
-        RETURNING id, workspace_id, doc_id, subject_type, subject_id, role, expires_at, created_at
-        "#,
-    )
-    .bind(workspace_id)
-    .bind(doc_id)
-    .bind(payload.subject_type)
-    .bind(payload.subject_id)
-    .bind(payload.role)
-    .bind(payload.expires_at)
-    .fetch_one(pool)
-    .await
-    .map_err(map_sqlx_error)?;
-
-    Ok(row.into())
-}
-
-async fn delete_acl_override_pg(
-    pool: &PgPool,
-    workspace_id: Uuid,
-    doc_id: Uuid,
-    override_id: Uuid,
-) -> Result<(), DocApiError> {
-    let deleted = sqlx::query(
-        r#"
-        DELETE FROM acl_overrides
-        WHERE id = $1 AND workspace_id = $2 AND doc_id = $3
-        "#,
-    )
-    .bind(override_id)
-    .bind(workspace_id)
-    .bind(doc_id)
-    .execute(pool)
-    .await
-    .map_err(map_sqlx_error)?
-    .rows_affected();
-
-    if deleted == 0 {
-        return Err(DocApiError::NotFound);
-    }
-
-    Ok(())
-}
-
-async fn workspace_role_for_user_pg(
-    pool: &PgPool,
-    user_id: Uuid,
-    workspace_id: Uuid,
-) -> Result<Option<WorkspaceRole>, DocApiError> {
-    let role = sqlx::query_scalar::<_, String>(
-        r#"
-        SELECT role
-        FROM workspace_members
-        WHERE workspace_id = $1
-          AND user_id = $2
-          AND status = 'active'
-        LIMIT 1
-        "#,
-    )
-    .bind(workspace_id)
-    .bind(user_id)
-    .fetch_optional(pool)
-    .await
-    .map_err(map_sqlx_error)?;
-
-    role.map(|value| {
-        WorkspaceRole::from_db_value(&value).ok_or_else(|| {
-            DocApiError::internal(anyhow::anyhow!("invalid workspace role '{value}' in database"))
-        })
-    })
-    .transpose()
-}
-
-async fn acl_override_role_for_user_pg(
-    pool: &PgPool,
-    user_id: Uuid,
-    workspace_id: Uuid,
-    doc_id: Uuid,
-) -> Result<Option<WorkspaceRole>, DocApiError> {
-    let role = sqlx::query_scalar::<_, String>(
-        r#"
-        SELECT role
-        FROM acl_overrides
-        WHERE workspace_id = $1
-          AND doc_id = $2
-          AND subject_type = 'user'
-          AND subject_id = $3
-          AND (expires_at IS NULL OR expires_at > now())
-        ORDER BY created_at DESC
-        LIMIT 1
-        "#,
-    )
-    .bind(workspace_id)
-    .bind(doc_id)
-    .bind(user_id.to_string())
-    .fetch_optional(pool)
-    .await
-    .map_err(map_sqlx_error)?;
-
-    role.map(|value| {
-        WorkspaceRole::from_db_value(&value).ok_or_else(|| {
-            DocApiError::internal(anyhow::anyhow!(
-                "invalid ACL override role '{value}' in database"
-            ))
-        })
-    })
-    .transpose()
-}
-
 // ── Memory implementations (for testing) ───────────────────────────
 
 async fn create_mem(
@@ -1126,14 +930,6 @@ async fn create_mem(
     let mut store = store.write().await;
     let path_norm = normalize_doc_path(path);
 
-    if !store
-        .workspace_members
-        .keys()
-        .any(|(existing_workspace_id, _)| *existing_workspace_id == workspace_id)
-    {
-        store.workspace_members.insert((workspace_id, created_by), WorkspaceRole::Owner);
-    }
-
     // Check uniqueness.
     let conflict = store.documents.values().any(|d| {
         d.workspace_id == workspace_id && d.path_norm == path_norm && d.deleted_at.is_none()
@@ -1306,119 +1102,6 @@ async fn update_tags_mem(
     Ok(mem_to_document(doc))
 }
 
-async fn create_acl_override_mem(
-    store: &RwLock<MemoryDocumentStore>,
-    workspace_id: Uuid,
-    doc_id: Uuid,
-    payload: CreateAclOverrideRequest,
-) -> Result<AclOverride, DocApiError> {
-    let mut store = store.write().await;
-    let doc_exists = store.documents.get(&doc_id).is_some_and(|document| {
-        document.workspace_id == workspace_id && document.deleted_at.is_none()
-    });
-    if !doc_exists {
-        return Err(DocApiError::NotFound);
-    }
-
-    let now = Utc::now();
-    let override_id = Uuid::new_v4();
-    let acl_override = MemoryAclOverride {
-        id: override_id,
-        workspace_id,
-        doc_id,
-        subject_type: payload.subject_type,
-        subject_id: payload.subject_id,
-        role: payload.role,
-        expires_at: payload.expires_at,
-        created_at: now,
-    };
-    store.acl_overrides.insert(override_id, acl_override.clone());
-    Ok(AclOverride {
-        id: acl_override.id,
-        workspace_id: acl_override.workspace_id,
-        doc_id: acl_override.doc_id,
-        subject_type: acl_override.subject_type,
-        subject_id: acl_override.subject_id,
-        role: acl_override.role,
-        expires_at: acl_override.expires_at,
-        created_at: acl_override.created_at,
-    })
-}
-
-async fn delete_acl_override_mem(
-    store: &RwLock<MemoryDocumentStore>,
-    workspace_id: Uuid,
-    doc_id: Uuid,
-    override_id: Uuid,
-) -> Result<(), DocApiError> {
-    let mut store = store.write().await;
-    let Some(existing) = store.acl_overrides.get(&override_id) else {
-        return Err(DocApiError::NotFound);
-    };
-    if existing.workspace_id != workspace_id || existing.doc_id != doc_id {
-        return Err(DocApiError::NotFound);
-    }
-
-    store.acl_overrides.remove(&override_id);
-    Ok(())
-}
-
-async fn workspace_role_for_user_mem(
-    store: &RwLock<MemoryDocumentStore>,
-    user_id: Uuid,
-    workspace_id: Uuid,
-) -> Result<Option<WorkspaceRole>, DocApiError> {
-    let store = store.read().await;
-
-    if let Some(role) = store.workspace_members.get(&(workspace_id, user_id)).copied() {
-        return Ok(Some(role));
-    }
-
-    let has_members = store
-        .workspace_members
-        .keys()
-        .any(|(existing_workspace_id, _)| *existing_workspace_id == workspace_id);
-    if !has_members {
-        // Memory tests bootstrap the first caller in a workspace as owner.
-        return Ok(Some(WorkspaceRole::Owner));
-    }
-
-    Ok(None)
-}
-
-async fn acl_override_role_for_user_mem(
-    store: &RwLock<MemoryDocumentStore>,
-    user_id: Uuid,
-    workspace_id: Uuid,
-    doc_id: Uuid,
-) -> Result<Option<WorkspaceRole>, DocApiError> {
-    let store = store.read().await;
-    let user_id = user_id.to_string();
-    let now = Utc::now();
-
-    let role = store
-        .acl_overrides
-        .values()
-        .filter(|override_entry| {
-            override_entry.workspace_id == workspace_id
-                && override_entry.doc_id == doc_id
-                && override_entry.subject_type == "user"
-                && override_entry.subject_id == user_id
-                && override_entry.expires_at.is_none_or(|expires_at| expires_at > now)
-        })
-        .max_by_key(|override_entry| override_entry.created_at)
-        .map(|override_entry| override_entry.role.clone());
-
-    role.map(|value| {
-        WorkspaceRole::from_db_value(&value).ok_or_else(|| {
-            DocApiError::internal(anyhow::anyhow!(
-                "invalid ACL override role '{value}' in memory store"
-            ))
-        })
-    })
-    .transpose()
-}
-
 fn mem_to_document(doc: &MemoryDocument) -> Document {
     Document {
         id: doc.id,
@@ -1436,19 +1119,19 @@ fn mem_to_document(doc: &MemoryDocument) -> Document {
 
 async fn try_record_document_audit_event(
     state: &DocApiState,
-    actor_user_id: Uuid,
+    actor: &AuthenticatedUser,
     workspace_id: Uuid,
     event_type: AuditEventType,
     document_id: Uuid,
     details: serde_json::Value,
 ) {
-    let Some(pool) = state.store.postgres_pool() else {
+    let Some(audit) = &state.audit else {
         return;
     };
     let event = NewAuditEvent {
         workspace_id: Some(workspace_id),
-        actor_user_id: Some(actor_user_id),
-        actor_agent_id: None,
+        actor_user_id: Some(actor.user_id),
+        actor_agent_id: actor.agent_id.clone(),
         event_type,
         entity_type: "document".to_owned(),
         entity_id: document_id.to_string(),
@@ -1458,13 +1141,13 @@ async fn try_record_document_audit_event(
         details: Some(details),
     };
 
-    if let Err(error) = audit::record_event(pool, event).await {
+    if let Err(error) = audit.record(event).await {
         tracing::warn!(error = ?error, "failed to record document audit event");
     }
 }
 
 async fn require_workspace_role(
-    store: &DocumentStore,
+    authz: &AuthorizationService,
     user: &AuthenticatedUser,
     workspace_id: Uuid,
     required_role: WorkspaceRole,
@@ -1473,18 +1156,12 @@ async fn require_workspace_role(
         return Err(DocApiError::Forbidden);
     }
 
-    let role = store.workspace_role_for_user(user.user_id, workspace_id).await?;
-    let Some(role) = role else {
-        return Err(DocApiError::Forbidden);
-    };
-    if !role.allows(required_role) {
-        return Err(DocApiError::Forbidden);
-    }
+    authz.require_workspace_role(workspace_id, &Principal::from(user), required_role).await?;
     Ok(())
 }
 
 async fn require_document_role(
-    store: &DocumentStore,
+    authz: &AuthorizationService,
     user: &AuthenticatedUser,
     workspace_id: Uuid,
     doc_id: Uuid,
@@ -1494,16 +1171,16 @@ async fn require_document_role(
         return Err(DocApiError::Forbidden);
     }
 
-    let workspace_role = store.workspace_role_for_user(user.user_id, workspace_id).await?;
-    let Some(workspace_role) = workspace_role else {
-        return Err(DocApiError::Forbidden);
-    };
+    authz
+        .require_document_role(workspace_id, doc_id, &Principal::from(user), required_role)
+        .await?;
+    Ok(())
+}
 
-    let effective_role = store
-        .acl_override_role_for_user(user.user_id, workspace_id, doc_id)
-        .await?
-        .unwrap_or(workspace_role);
-    if !effective_role.allows(required_role) {
+/// API tokens limited to path prefixes may only create or move documents
+/// inside those prefixes.
+fn require_token_path(user: &AuthenticatedUser, path: &str) -> Result<(), DocApiError> {
+    if user.token.as_ref().is_some_and(|grant| !grant.allows_path(path)) {
         return Err(DocApiError::Forbidden);
     }
     Ok(())
@@ -1673,7 +1350,10 @@ mod tests {
     use tower::ServiceExt;
 
     use super::*;
-    use crate::auth::jwt::JwtAccessTokenService;
+    use crate::auth::{
+        api_tokens::{ApiTokenScope, ApiTokenStore, NewApiToken},
+        jwt::JwtAccessTokenService,
+    };
 
     fn test_jwt_service() -> Arc<JwtAccessTokenService> {
         Arc::new(
@@ -1687,7 +1367,12 @@ mod tests {
     }
 
     fn test_router() -> Router {
-        build_router_with_store(test_store(), test_jwt_service())
+        build_router_with_store(
+            test_store(),
+            AuthorizationService::for_tests_with_bootstrap_owner(),
+            Arc::default(),
+            test_jwt_service(),
+        )
     }
 
     fn auth_token(jwt: &JwtAccessTokenService, user_id: Uuid, workspace_id: Uuid) -> String {
@@ -1793,7 +1478,8 @@ mod tests {
     async fn create_document_rejects_duplicate_path() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store, jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app = build_router_with_store(store, authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let token = auth_token(&jwt, Uuid::new_v4(), ws_id);
@@ -1842,11 +1528,94 @@ mod tests {
         assert!(body["next_cursor"].is_null());
     }
 
+    #[tokio::test]
+    async fn path_scoped_api_token_only_sees_and_creates_documents_under_its_prefix() {
+        let tokens = ApiTokenStore::for_tests();
+        let jwt = Arc::new(
+            JwtAccessTokenService::new("test-secret-that-is-at-least-32-chars-long!!")
+                .expect("jwt service")
+                .with_api_token_store(tokens.clone()),
+        );
+        let app = build_router_with_store(
+            test_store(),
+            AuthorizationService::for_tests_with_bootstrap_owner(),
+            Arc::default(),
+            Arc::clone(&jwt),
+        );
+        let (ws_id, owner_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let owner_token = auth_token(&jwt, owner_id, ws_id);
+        let uri = format!("/v1/workspaces/{ws_id}/documents");
+
+        for path in ["docs/guide.md", "src/notes.md"] {
+            let resp = app
+                .clone()
+                .oneshot(json_request(
+                    "POST",
+                    &uri,
+                    serde_json::json!({ "path": path }),
+                    &owner_token,
+                ))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::CREATED);
+        }
+
+        let (_, api_token) = tokens
+            .create(
+                ws_id,
+                owner_id,
+                NewApiToken {
+                    name: "docs-bot".into(),
+                    agent_id: Some("docs-bot".into()),
+                    scopes: vec![ApiTokenScope::Read, ApiTokenScope::Write],
+                    path_prefixes: vec!["docs/".into()],
+                    expires_at: None,
+                },
+            )
+            .await
+            .expect("api token");
+
+        let resp = app.clone().oneshot(get_request(&uri, &api_token)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = body_json(resp).await;
+        let paths: Vec<&str> = body["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["path"].as_str().unwrap())
+            .collect();
+        assert_eq!(paths, vec!["docs/guide.md"]);
+
+        let resp = app
+            .clone()
+            .oneshot(json_request(
+                "POST",
+                &uri,
+                serde_json::json!({ "path": "src/x.md" }),
+                &api_token,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let resp = app
+            .oneshot(json_request(
+                "POST",
+                &uri,
+                serde_json::json!({ "path": "docs/x.md" }),
+                &api_token,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+
     #[tokio::test]
     async fn get_document_returns_created_doc() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store, jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app = build_router_with_store(store, authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let token = auth_token(&jwt, Uuid::new_v4(), ws_id);
@@ -1899,7 +1668,8 @@ mod tests {
     async fn update_document_with_matching_etag() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store, jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app = build_router_with_store(store, authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let token = auth_token(&jwt, Uuid::new_v4(), ws_id);
@@ -1945,7 +1715,8 @@ mod tests {
     async fn update_document_without_if_match_returns_428() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store, jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app = build_router_with_store(store, authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let token = auth_token(&jwt, Uuid::new_v4(), ws_id);
@@ -1982,7 +1753,8 @@ mod tests {
     async fn delete_document_without_if_match_returns_428() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store, jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app = build_router_with_store(store, authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let token = auth_token(&jwt, Uuid::new_v4(), ws_id);
@@ -2021,7 +1793,8 @@ mod tests {
     async fn update_document_tags_add_and_remove_returns_document() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store, jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app = build_router_with_store(store, authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let token = auth_token(&jwt, Uuid::new_v4(), ws_id);
@@ -2107,10 +1880,29 @@ mod tests {
 
     #[tokio::test]
     async fn create_and_delete_acl_override() {
-        let app = test_router();
+        let sessions = Arc::new(SyncSessionStore::default());
+        let app = build_router_with_store(
+            test_store(),
+            AuthorizationService::for_tests_with_bootstrap_owner(),
+            Arc::clone(&sessions),
+            test_jwt_service(),
+        );
         let jwt = test_jwt_service();
         let ws_id = Uuid::new_v4();
         let token = auth_token(&jwt, Uuid::new_v4(), ws_id);
+        let session_id = Uuid::new_v4();
+        sessions
+            .create_session(
+                session_id,
+                ws_id,
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                Uuid::new_v4().to_string(),
+                Uuid::new_v4().to_string(),
+                chrono::Utc::now() + chrono::Duration::minutes(15),
+                chrono::Utc::now() + chrono::Duration::minutes(10),
+            )
+            .await;
 
         let create_doc_resp = app
             .clone()
@@ -2124,6 +1916,8 @@ mod tests {
             .unwrap();
         let create_doc_body = body_json(create_doc_resp).await;
         let doc_id = create_doc_body["document"]["id"].as_str().unwrap();
+        let doc_uuid = Uuid::parse_str(doc_id).unwrap();
+        sessions.cache_doc_role(session_id, doc_uuid, Some(WorkspaceRole::Editor)).await;
 
         let create_override_resp = app
             .clone()
@@ -2145,6 +1939,12 @@ mod tests {
         assert_eq!(create_override_body["acl_override"]["subject_id"], "cursor");
         assert_eq!(create_override_body["acl_override"]["role"], "editor");
         let override_id = create_override_body["acl_override"]["id"].as_str().unwrap();
+        assert_eq!(
+            sessions.cached_doc_role(session_id, doc_uuid).await,
+            None,
+            "a new override invalidates cached session roles"
+        );
+        sessions.cache_doc_role(session_id, doc_uuid, Some(WorkspaceRole::Editor)).await;
 
         let delete_override_resp = app
             .oneshot(
@@ -2160,6 +1960,11 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(delete_override_resp.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            sessions.cached_doc_role(session_id, doc_uuid).await,
+            None,
+            "a deleted override invalidates cached session roles"
+        );
     }
 
     #[tokio::test]
@@ -2204,7 +2009,13 @@ mod tests {
     #[tokio::test]
     async fn workspace_owner_can_manage_acl_overrides_for_other_document_owner() {
         let store = test_store();
-        let app = build_router_with_store(store.clone(), test_jwt_service());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app = build_router_with_store(
+            store.clone(),
+            authz.clone(),
+            Arc::default(),
+            test_jwt_service(),
+        );
         let jwt = test_jwt_service();
         let ws_id = Uuid::new_v4();
         let owner_id = Uuid::new_v4();
@@ -2224,7 +2035,7 @@ mod tests {
             .await
             .unwrap();
 
-        store.grant_workspace_role_for_tests(ws_id, other_author_id, WorkspaceRole::Editor).await;
+        authz.grant_for_tests(ws_id, other_author_id, WorkspaceRole::Editor).await;
 
         let create_doc_resp = app
             .clone()
@@ -2322,7 +2133,8 @@ mod tests {
     async fn soft_delete_hides_from_list() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store, jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app = build_router_with_store(store, authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let token = auth_token(&jwt, Uuid::new_v4(), ws_id);
@@ -2362,7 +2174,8 @@ mod tests {
     async fn list_documents_respects_include_archived_query_param() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store, jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app = build_router_with_store(store, authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let token = auth_token(&jwt, Uuid::new_v4(), ws_id);
@@ -2452,7 +2265,9 @@ mod tests {
     async fn hard_delete_query_param_performs_hard_delete() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store.clone(), jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app =
+            build_router_with_store(store.clone(), authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let token = auth_token(&jwt, Uuid::new_v4(), ws_id);
@@ -2495,7 +2310,9 @@ mod tests {
     async fn viewer_role_can_read_but_cannot_mutate_documents() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store.clone(), jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app =
+            build_router_with_store(store.clone(), authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let owner_id = Uuid::new_v4();
@@ -2518,7 +2335,7 @@ mod tests {
         let doc_id = create_body["document"]["id"].as_str().unwrap();
         let etag = create_body["document"]["etag"].as_str().unwrap().to_string();
 
-        store.grant_workspace_role_for_tests(ws_id, viewer_id, WorkspaceRole::Viewer).await;
+        authz.grant_for_tests(ws_id, viewer_id, WorkspaceRole::Viewer).await;
 
         let list_resp = app
             .clone()
@@ -2609,7 +2426,9 @@ mod tests {
     async fn acl_override_grants_editor_access_to_workspace_viewer() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store.clone(), jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app =
+            build_router_with_store(store.clone(), authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let owner_id = Uuid::new_v4();
@@ -2631,7 +2450,7 @@ mod tests {
         let create_doc_body = body_json(create_doc_resp).await;
         let doc_id = create_doc_body["document"]["id"].as_str().unwrap();
 
-        store.grant_workspace_role_for_tests(ws_id, viewer_id, WorkspaceRole::Viewer).await;
+        authz.grant_for_tests(ws_id, viewer_id, WorkspaceRole::Viewer).await;
 
         let before_override = app
             .clone()
@@ -2677,7 +2496,9 @@ mod tests {
     async fn acl_override_downgrades_editor_access_to_viewer() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store.clone(), jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app =
+            build_router_with_store(store.clone(), authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let owner_id = Uuid::new_v4();
@@ -2699,7 +2520,7 @@ mod tests {
         let create_doc_body = body_json(create_doc_resp).await;
         let doc_id = create_doc_body["document"]["id"].as_str().unwrap();
 
-        store.grant_workspace_role_for_tests(ws_id, editor_id, WorkspaceRole::Editor).await;
+        authz.grant_for_tests(ws_id, editor_id, WorkspaceRole::Editor).await;
 
         let before_override = app
             .clone()
@@ -2745,7 +2566,9 @@ mod tests {
     async fn editor_role_cannot_manage_acl_overrides_for_other_users_documents() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store.clone(), jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app =
+            build_router_with_store(store.clone(), authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let owner_id = Uuid::new_v4();
@@ -2767,7 +2590,7 @@ mod tests {
         let create_doc_body = body_json(create_doc_resp).await;
         let doc_id = create_doc_body["document"]["id"].as_str().unwrap();
 
-        store.grant_workspace_role_for_tests(ws_id, editor_id, WorkspaceRole::Editor).await;
+        authz.grant_for_tests(ws_id, editor_id, WorkspaceRole::Editor).await;
 
         let create_override_resp = app
             .oneshot(json_request(
@@ -2789,7 +2612,9 @@ mod tests {
     async fn editor_role_cannot_delete_documents() {
         let store = test_store();
         let jwt = test_jwt_service();
-        let app = build_router_with_store(store.clone(), jwt.clone());
+        let authz = AuthorizationService::for_tests_with_bootstrap_owner();
+        let app =
+            build_router_with_store(store.clone(), authz.clone(), Arc::default(), jwt.clone());
 
         let ws_id = Uuid::new_v4();
         let owner_id = Uuid::new_v4();
@@ -2811,7 +2636,7 @@ mod tests {
         let create_doc_body = body_json(create_doc_resp).await;
         let doc_id = create_doc_body["document"]["id"].as_str().unwrap();
 
-        store.grant_workspace_role_for_tests(ws_id, editor_id, WorkspaceRole::Editor).await;
+        authz.grant_for_tests(ws_id, editor_id, WorkspaceRole::Editor).await;
 
         let delete_resp = app
             .oneshot(delete_request(
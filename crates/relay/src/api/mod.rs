@@ -1,8 +1,10 @@
+pub mod audit_log;
 pub mod auth;
 pub mod comments;
 pub mod documents;
 pub mod members;
 pub mod search;
+pub mod tokens;
 pub mod workspaces;
 
 use std::{
@@ -38,8 +40,9 @@ use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use crate::{
-    audit::{self, AuditEventType, NewAuditEvent},
+    audit::{AuditEventType, AuditLog, NewAuditEvent},
     auth::{
+        api_tokens::{ApiTokenScope, ApiTokenStore},
         jwt::JwtAccessTokenService,
         middleware::{require_bearer_auth, AuthenticatedUser, WorkspaceRole},
         oauth::OAuthState,
@@ -48,6 +51,7 @@ use crate::{
     error::{current_request_id, ErrorCode, RelayError},
     idempotency::{self, IdempotencyDbState},
     validation::ValidatedJson,
+    ws::SyncSessionStore,
 };
 
 const DEFAULT_PAGE_SIZE: usize = 50;
@@ -66,6 +70,8 @@ struct RedeemRateEntry {
 struct ApiState {
     store: WorkspaceStore,
     redeem_limiter: Arc<Mutex<HashMap<Vec<u8>, RedeemRateEntry>>>,
+    api_tokens: Option<ApiTokenStore>,
+    audit: Option<AuditLog>,
 }
 
 #[derive(Clone)]
@@ -645,6 +651,8 @@ impl IntoResponse for ApiError {
 pub async fn build_router_from_env(
     jwt_service: Arc<JwtAccessTokenService>,
     oauth_state: OAuthState,
+    session_store: Arc<SyncSessionStore>,
+    audit_hash_chain: bool,
 ) -> Result<Router> {
     let database_url = env::var("SCRIPTUM_RELAY_DATABASE_URL")
         .context("SCRIPTUM_RELAY_DATABASE_URL must be set for workspace API")?;
@@ -656,23 +664,42 @@ pub async fn build_router_from_env(
         .await
         .context("relay PostgreSQL health check failed for workspace API")?;
     let idempotency_state = IdempotencyDbState::new(pool.clone());
+    let audit = AuditLog::new(pool.clone(), audit_hash_chain);
 
-    Ok(build_router_with_store(WorkspaceStore::Postgres(pool.clone()), Arc::clone(&jwt_service))
-        .merge(auth::router(oauth_state))
-        .merge(documents::router(pool.clone(), Arc::clone(&jwt_service)))
-        .merge(comments::router(pool.clone(), Arc::clone(&jwt_service)))
-        .merge(search::router(pool, jwt_service))
-        .layer(middleware::from_fn_with_state(
-            idempotency_state,
-            idempotency::idempotency_db_middleware,
-        )))
+    Ok(build_router_with_audit(
+        WorkspaceStore::Postgres(pool.clone()),
+        Some(audit.clone()),
+        Arc::clone(&jwt_service),
+    )
+    .merge(auth::router(oauth_state.with_pg_pool(pool.clone()).with_audit_log(audit.clone())))
+    .merge(documents::router(pool.clone(), audit.clone(), session_store, Arc::clone(&jwt_service)))
+    .merge(comments::router(pool.clone(), audit, Arc::clone(&jwt_service)))
+    .merge(search::router(pool, jwt_service))
+    .layer(middleware::from_fn_with_state(
+        idempotency_state,
+        idempotency::idempotency_db_middleware,
+    )))
 }
 
+#[cfg(test)]
 fn build_router_with_store(
     store: WorkspaceStore,
     jwt_service: Arc<JwtAccessTokenService>,
 ) -> Router {
-    let state = ApiState { store, redeem_limiter: Arc::new(Mutex::new(HashMap::new())) };
+    build_router_with_audit(store, None, jwt_service)
+}
+
+fn build_router_with_audit(
+    store: WorkspaceStore,
+    audit: Option<AuditLog>,
+    jwt_service: Arc<JwtAccessTokenService>,
+) -> Router {
+    let state = ApiState {
+        store,
+        redeem_limiter: Arc::new(Mutex::new(HashMap::new())),
+        api_tokens: jwt_service.api_tokens().cloned(),
+        audit,
+    };
     let viewer_role_layer =
         middleware::from_fn_with_state(state.clone(), require_workspace_viewer_role);
     let editor_role_layer =
@@ -689,7 +716,9 @@ fn build_router_with_store(
     Router::new()
         .route(
             "/v1/workspaces",
-            post(workspaces::create_workspace).get(workspaces::list_workspaces),
+            post(workspaces::create_workspace)
+                .get(workspaces::list_workspaces)
+                .route_layer(middleware::from_fn(require_session_auth)),
         )
         .route("/v1/workspaces/{id}", get(workspaces::get_workspace).route_layer(viewer_role_layer))
         .route(
@@ -727,7 +756,50 @@ fn build_router_with_store(
                 require_workspace_owner_role,
             )),
         )
-        .route("/v1/invites/{token}/accept", post(members::accept_invite))
+        .route(
+            "/v1/workspaces/{workspace_id}/tokens",
+            post(tokens::create_api_token)
+                .get(tokens::list_api_tokens)
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_workspace_viewer_role,
+                ))
+                .route_layer(middleware::from_fn(require_session_auth)),
+        )
+        .route(
+            "/v1/workspaces/{workspace_id}/tokens/{token_id}",
+            delete(tokens::revoke_api_token)
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    require_workspace_viewer_role,
+                ))
+                .route_layer(middleware::from_fn(require_session_auth)),
+        )
+        .route(
+            "/v1/workspaces/{workspace_id}/audit-events",
+            get(audit_log::list_audit_events).route_layer(middleware::from_fn_with_state(
+                state.clone(),
+                require_workspace_owner_role,
+            )),
+        )
+        .route(
+            "/v1/workspaces/{workspace_id}/audit-events/export",
+            get(audit_log::export_audit_events).route_layer(middleware::from_fn_with_state(
+                state.clone(),
+                require_workspace_owner_role,
+            )),
+        )
+        .route(
+            "/v1/workspaces/{workspace_id}/audit-events/verify",
+            get(audit_log::verify_audit_chain).route_layer(middleware::from_fn_with_state(
+                state.clone(),
+                require_workspace_owner_role,
+            )),
+        )
+        .route(
+            "/v1/invites/{token}/accept",
+            post(members::accept_invite).route_layer(middleware::from_fn(require_session_auth)),
+        )
         .with_state(state.clone())
         .route_layer(middleware::from_fn_with_state(jwt_service, require_bearer_auth))
         .merge(
@@ -965,6 +1037,14 @@ async fn require_workspace_role(
             .into_response();
     }
 
+    // Workspace-level management is read-only for API tokens.
+    if user.token.as_ref().is_some_and(|grant| {
+        required_role != WorkspaceRole::Viewer || !grant.allows_scope(ApiTokenScope::Read)
+    }) {
+        return ApiError::forbidden("AUTH_FORBIDDEN", "api token lacks required scope")
+            .into_response();
+    }
+
     let role = match state.store.workspace_role_for_user(user.user_id, workspace_id).await {
         Ok(Some(role)) => role,
         Ok(None) => {
@@ -982,6 +1062,20 @@ async fn require_workspace_role(
     next.run(request).await
 }
 
+/// Rejects API tokens on routes that act across workspaces or manage
+/// credentials; those need a signed-in user.
+async fn require_session_auth(
+    Extension(user): Extension<AuthenticatedUser>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if user.is_api_token() {
+        return ApiError::forbidden("AUTH_FORBIDDEN", "api tokens cannot access this endpoint")
+            .into_response();
+    }
+    next.run(request).await
+}
+
 async fn extract_workspace_id(request: Request) -> Result<(Request, Uuid), ApiError> {
     let (mut parts, body) = request.into_parts();
     let Path(path_params) = Path::<HashMap<String, String>>::from_request_parts(&mut parts, &())
@@ -1006,7 +1100,7 @@ async fn try_record_audit_event(
     entity_id: impl Into<String>,
     details: Option<serde_json::Value>,
 ) {
-    let Some(pool) = state.store.postgres_pool() else {
+    let Some(audit) = &state.audit else {
         return;
     };
     let event = NewAuditEvent {
@@ -1022,7 +1116,7 @@ async fn try_record_audit_event(
         details,
     };
 
-    if let Err(error) = audit::record_event(pool, event).await {
+    if let Err(error) = audit.record(event).await {
         tracing::warn!(error = ?error, "failed to record relay audit event");
     }
 }
@@ -2971,7 +3065,7 @@ mod tests {
         MemoryWorkspaceStore, RedeemShareLinkResponse, ShareLinkEnvelope, ShareLinksEnvelope,
         WorkspaceEnvelope, WorkspaceStore, WorkspacesPageEnvelope,
     };
-    use crate::auth::jwt::JwtAccessTokenService;
+    use crate::auth::{api_tokens::ApiTokenStore, jwt::JwtAccessTokenService};
     use axum::{
         body::{to_bytes, Body},
         http::{header::AUTHORIZATION, Method, Request, StatusCode},
@@ -3031,6 +3125,208 @@ mod tests {
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
 
+    #[tokio::test]
+    async fn api_tokens_are_minted_by_sessions_and_limited_to_read_access() {
+        let tokens = ApiTokenStore::for_tests();
+        let jwt_service = Arc::new(
+            JwtAccessTokenService::new(TEST_SECRET)
+                .expect("jwt service should initialize")
+                .with_api_token_store(tokens),
+        );
+        let (user_id, workspace_id, now) = (Uuid::new_v4(), Uuid::new_v4(), Utc::now());
+        let store = Arc::new(RwLock::new(MemoryWorkspaceStore::default()));
+        {
+            let mut guard = store.write().await;
+            guard.workspaces.insert(
+                workspace_id,
+                MemoryWorkspace {
+                    id: workspace_id,
+                    slug: "tokens".to_owned(),
+                    name: "Tokens".to_owned(),
+                    created_at: now,
+                    updated_at: now,
+                    deleted_at: None,
+                },
+            );
+            guard.memberships.insert(
+                (workspace_id, user_id),
+                MemoryMembership {
+                    role: "editor".to_owned(),
+                    status: "active".to_owned(),
+                    email: "editor@test.local".to_owned(),
+                    display_name: "Editor".to_owned(),
+                    joined_at: now,
+                },
+            );
+        }
+        let router =
+            build_router_with_store(WorkspaceStore::Memory(store), Arc::clone(&jwt_service));
+        let session = bearer_token(&jwt_service, user_id, workspace_id);
+        let request =
+            |method: Method, uri: String, bearer: &str, body: Option<serde_json::Value>| {
+                let builder = Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header(AUTHORIZATION, format!("Bearer {bearer}"));
+                match body {
+                    Some(body) => builder
+                        .header("content-type", "application/json")
+                        .body(Body::from(body.to_string())),
+                    None => builder.body(Body::empty()),
+                }
+                .expect("request should build")
+            };
+        let tokens_uri = format!("/v1/workspaces/{workspace_id}/tokens");
+
+        let response = router
+            .clone()
+            .oneshot(request(
+                Method::POST,
+                tokens_uri.clone(),
+                &session,
+                Some(json!({ "name": "ci", "agent_id": "ci-bot", "scopes": ["read"] })),
+            ))
+            .await
+            .expect("create should respond");
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let created: serde_json::Value = read_json(response).await;
+        let secret = created["token"].as_str().expect("secret is returned once").to_owned();
+        let token_id = created["api_token"]["id"].as_str().expect("token id").to_owned();
+        assert!(created["api_token"].get("token_hash").is_none());
+
+        let response = router
+            .clone()
+            .oneshot(request(Method::GET, format!("/v1/workspaces/{workspace_id}"), &secret, None))
+            .await
+            .expect("get should respond");
+        assert_eq!(response.status(), StatusCode::OK);
+
+        for (method, uri) in [
+            (Method::GET, tokens_uri.clone()),
+            (Method::GET, "/v1/workspaces".to_owned()),
+            (Method::GET, format!("/v1/workspaces/{workspace_id}/share-links")),
+        ] {
+            let response = router
+                .clone()
+                .oneshot(request(method, uri.clone(), &secret, None))
+                .await
+                .expect("request should respond");
+            assert_eq!(response.status(), StatusCode::FORBIDDEN, "{uri}");
+        }
+
+        let response = router
+            .clone()
+            .oneshot(request(Method::GET, tokens_uri.clone(), &session, None))
+            .await
+            .expect("list should respond");
+        assert_eq!(response.status(), StatusCode::OK);
+        let listed: serde_json::Value = read_json(response).await;
+        assert_eq!(listed["items"].as_array().map(Vec::len), Some(1));
+
+        let response = router
+            .clone()
+            .oneshot(request(Method::DELETE, format!("{tokens_uri}/{token_id}"), &session, None))
+            .await
+            .expect("revoke should respond");
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = router
+            .oneshot(request(Method::GET, format!("/v1/workspaces/{workspace_id}"), &secret, None))
+            .await
+            .expect("get should respond");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn audit_events_are_owner_only_and_validate_filters() {
+        let jwt_service = Arc::new(
+            JwtAccessTokenService::new(TEST_SECRET).expect("jwt service should initialize"),
+        );
+        let (owner_id, editor_id, workspace_id, now) =
+            (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Utc::now());
+        let store = Arc::new(RwLock::new(MemoryWorkspaceStore::default()));
+        {
+            let mut guard = store.write().await;
+            guard.workspaces.insert(
+                workspace_id,
+                MemoryWorkspace {
+                    id: workspace_id,
+                    slug: "audit".to_owned(),
+                    name: "Audit".to_owned(),
+                    created_at: now,
+                    updated_at: now,
+                    deleted_at: None,
+                },
+            );
+            for (user_id, role) in [(owner_id, "owner"), (editor_id, "editor")] {
+                guard.memberships.insert(
+                    (workspace_id, user_id),
+                    MemoryMembership {
+                        role: role.to_owned(),
+                        status: "active".to_owned(),
+                        email: format!("{role}@test.local"),
+                        display_name: role.to_owned(),
+                        joined_at: now,
+                    },
+                );
+            }
+        }
+        let router =
+            build_router_with_store(WorkspaceStore::Memory(store), Arc::clone(&jwt_service));
+        let get = |uri: String, user_id: Uuid| {
+            Request::builder()
+                .uri(uri)
+                .header(
+                    AUTHORIZATION,
+                    format!("Bearer {}", bearer_token(&jwt_service, user_id, workspace_id)),
+                )
+                .body(Body::empty())
+                .expect("request should build")
+        };
+        let base = format!("/v1/workspaces/{workspace_id}/audit-events");
+
+        for uri in [base.clone(), format!("{base}/export"), format!("{base}/verify")] {
+            let response =
+                router.clone().oneshot(get(uri.clone(), editor_id)).await.expect("response");
+            assert_eq!(response.status(), StatusCode::FORBIDDEN, "{uri}");
+        }
+
+        let response = router
+            .clone()
+            .oneshot(get(format!("{base}?event_type=auth&limit=10"), owner_id))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let page: serde_json::Value = read_json(response).await;
+        assert_eq!(page["items"], json!([]));
+        assert!(page["next_cursor"].is_null());
+
+        let response = router
+            .clone()
+            .oneshot(get(format!("{base}?event_type=bogus"), owner_id))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = router
+            .clone()
+            .oneshot(get(format!("{base}/export"), owner_id))
+            .await
+            .expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get("content-type").and_then(|value| value.to_str().ok()),
+            Some("application/x-ndjson")
+        );
+
+        let response =
+            router.oneshot(get(format!("{base}/verify"), owner_id)).await.expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let verification: serde_json::Value = read_json(response).await;
+        assert_eq!(verification["valid"], json!(true));
+        assert_eq!(verification["checked"], json!(0));
+    }
+
     #[tokio::test]
     async fn patch_workspace_requires_owner_role() {
         let jwt_service = Arc::new(
@@ -24,6 +24,7 @@ use uuid::Uuid;
 
 use crate::{
     auth::{
+        authz::{AuthorizationService, AuthzError, Principal},
         jwt::JwtAccessTokenService,
         middleware::{require_bearer_auth, AuthenticatedUser, WorkspaceRole},
     },
@@ -59,6 +60,7 @@ struct SearchResponse {
 #[derive(Clone)]
 struct SearchApiState {
     store: SearchStore,
+    authz: AuthorizationService,
 }
 
 #[derive(Clone)]
@@ -71,7 +73,6 @@ enum SearchStore {
 #[derive(Default)]
 struct MemorySearchStore {
     documents: HashMap<Uuid, MemoryDocument>,
-    workspace_members: HashMap<(Uuid, Uuid), WorkspaceRole>,
 }
 
 #[derive(Clone)]
@@ -110,6 +111,17 @@ impl SearchApiError {
     }
 }
 
+impl From<AuthzError> for SearchApiError {
+    fn from(error: AuthzError) -> Self {
+        match error {
+            AuthzError::NoWorkspaceAccess
+            | AuthzError::InsufficientRole
+            | AuthzError::InsufficientScope => Self::Forbidden,
+            AuthzError::Internal(error) => Self::Internal(error),
+        }
+    }
+}
+
 impl IntoResponse for SearchApiError {
     fn into_response(self) -> Response {
         match self {
@@ -129,11 +141,19 @@ impl IntoResponse for SearchApiError {
 }
 
 pub fn router(pool: PgPool, jwt_service: Arc<JwtAccessTokenService>) -> Router {
-    build_router_with_store(SearchStore::Postgres(pool), jwt_service)
+    build_router_with_store(
+        SearchStore::Postgres(pool.clone()),
+        AuthorizationService::Postgres(pool),
+        jwt_service,
+    )
 }
 
-fn build_router_with_store(store: SearchStore, jwt_service: Arc<JwtAccessTokenService>) -> Router {
-    let state = SearchApiState { store };
+fn build_router_with_store(
+    store: SearchStore,
+    authz: AuthorizationService,
+    jwt_service: Arc<JwtAccessTokenService>,
+) -> Router {
+    let state = SearchApiState { store, authz };
 
     Router::new()
         .route("/v1/workspaces/{id}/search", get(search_documents))
@@ -147,7 +167,7 @@ async fn search_documents(
     Path(workspace_id): Path<Uuid>,
     Query(query): Query<SearchQuery>,
 ) -> Result<Json<SearchResponse>, SearchApiError> {
-    require_workspace_role(&state.store, &user, workspace_id, WorkspaceRole::Viewer).await?;
+    require_workspace_role(&state.authz, &user, workspace_id, WorkspaceRole::Viewer).await?;
 
     let raw_query =
         query.q.ok_or_else(|| SearchApiError::bad_request("missing query parameter: q"))?;
@@ -158,7 +178,19 @@ async fn search_documents(
         None => 0,
     };
 
-    let (items, next_cursor) = state.store.search(workspace_id, q, limit, offset).await?;
+    let (mut items, next_cursor) = state.store.search(workspace_id, q, limit, offset).await?;
+
+    // ACL overrides only grant viewer or editor, so every member can read
+    // every hit; documents outside a path-scoped token's grant have no
+    // effective role and are dropped.
+    let doc_ids = items.iter().map(|item| item.doc_id).collect::<Vec<_>>();
+    let roles = state
+        .authz
+        .effective_roles(workspace_id, &doc_ids, &Principal::from(&user))
+        .await
+        .map_err(SearchApiError::internal)?;
+    items.retain(|item| roles.contains_key(&item.doc_id));
+
     Ok(Json(SearchResponse { items, next_cursor }))
 }
 
@@ -175,17 +207,6 @@ impl SearchStore {
             Self::Memory(store) => search_mem(store, workspace_id, q, limit, offset).await,
         }
     }
-
-    async fn workspace_role_for_user(
-        &self,
-        user_id: Uuid,
-        workspace_id: Uuid,
-    ) -> Result<Option<WorkspaceRole>, SearchApiError> {
-        match self {
-            Self::Postgres(pool) => workspace_role_for_user_pg(pool, user_id, workspace_id).await,
-            Self::Memory(store) => workspace_role_for_user_mem(store, user_id, workspace_id).await,
-        }
-    }
 }
 
 async fn search_pg(
@@ -309,48 +330,8 @@ async fn search_mem(
     Ok((items, next_cursor))
 }
 
-async fn workspace_role_for_user_pg(
-    pool: &PgPool,
-    user_id: Uuid,
-    workspace_id: Uuid,
-) -> Result<Option<WorkspaceRole>, SearchApiError> {
-    let role = sqlx::query_scalar::<_, String>(
-        r#"
-        SELECT role
-        FROM workspace_members
-        WHERE workspace_id = $1
-          AND user_id = $2
-          AND status = 'active'
-        LIMIT 1
-        "#,
-    )
-    .bind(workspace_id)
-    .bind(user_id)
-    .fetch_optional(pool)
-    .await
-    .map_err(map_sqlx_error)?;
-
-    role.map(|value| {
-        WorkspaceRole::from_db_value(&value).ok_or_else(|| {
-            SearchApiError::internal(anyhow::anyhow!(
-                "invalid workspace role '{value}' in database"
-            ))
-        })
-    })
-    .transpose()
-}
-
-async fn workspace_role_for_user_mem(
-    store: &RwLock<MemorySearchStore>,
-    user_id: Uuid,
-    workspace_id: Uuid,
-) -> Result<Option<WorkspaceRole>, SearchApiError> {
-    let store = store.read().await;
-    Ok(store.workspace_members.get(&(workspace_id, user_id)).copied())
-}
-
 async fn require_workspace_role(
-    store: &SearchStore,
+    authz: &AuthorizationService,
     user: &AuthenticatedUser,
     workspace_id: Uuid,
     required_role: WorkspaceRole,
@@ -359,14 +340,7 @@ async fn require_workspace_role(
         return Err(SearchApiError::Forbidden);
     }
 
-    let role = store.workspace_role_for_user(user.user_id, workspace_id).await?;
-    let Some(role) = role else {
-        return Err(SearchApiError::Forbidden);
-    };
-    if !role.allows(required_role) {
-        return Err(SearchApiError::Forbidden);
-    }
-
+    authz.require_workspace_role(workspace_id, &Principal::from(user), required_role).await?;
     Ok(())
 }
 
@@ -439,7 +413,10 @@ mod tests {
     use tower::ServiceExt;
 
     use super::*;
-    use crate::auth::jwt::JwtAccessTokenService;
+    use crate::auth::{
+        api_tokens::{ApiTokenScope, ApiTokenStore, NewApiToken},
+        jwt::JwtAccessTokenService,
+    };
 
     fn test_jwt_service() -> Arc<JwtAccessTokenService> {
         Arc::new(
@@ -452,17 +429,8 @@ mod tests {
         jwt.issue_workspace_token(user_id, workspace_id).expect("token")
     }
 
-    fn grant_workspace_role(
-        store: &mut MemorySearchStore,
-        workspace_id: Uuid,
-        user_id: Uuid,
-        role: WorkspaceRole,
-    ) {
-        store.workspace_members.insert((workspace_id, user_id), role);
-    }
-
-    fn test_router(store: SearchStore) -> Router {
-        build_router_with_store(store, test_jwt_service())
+    fn test_router(store: SearchStore, authz: AuthorizationService) -> Router {
+        build_router_with_store(store, authz, test_jwt_service())
     }
 
     fn get_request(uri: &str, token: &str) -> Request<Body> {
@@ -510,9 +478,10 @@ mod tests {
                 deleted_at: None,
             },
         );
-        grant_workspace_role(&mut mem, ws_id, user_id, WorkspaceRole::Viewer);
+        let authz = AuthorizationService::for_tests();
+        authz.grant_for_tests(ws_id, user_id, WorkspaceRole::Viewer).await;
 
-        let app = test_router(SearchStore::Memory(Arc::new(RwLock::new(mem))));
+        let app = test_router(SearchStore::Memory(Arc::new(RwLock::new(mem))), authz);
         let jwt = test_jwt_service();
         let token = auth_token(&jwt, user_id, ws_id);
 
@@ -542,12 +511,74 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn search_rejects_empty_query() {
+    async fn search_hides_documents_outside_a_path_scoped_token() {
         let ws_id = Uuid::new_v4();
         let user_id = Uuid::new_v4();
+        let authz = AuthorizationService::for_tests();
+        authz.grant_for_tests(ws_id, user_id, WorkspaceRole::Editor).await;
+
         let mut mem = MemorySearchStore::default();
-        grant_workspace_role(&mut mem, ws_id, user_id, WorkspaceRole::Viewer);
-        let app = test_router(SearchStore::Memory(Arc::new(RwLock::new(mem))));
+        let mut doc_ids = Vec::new();
+        for path in ["docs/auth.md", "src/auth.md"] {
+            let doc_id = Uuid::new_v4();
+            authz.document_path_for_tests(doc_id, path).await;
+            mem.documents.insert(
+                doc_id,
+                MemoryDocument {
+                    id: doc_id,
+                    workspace_id: ws_id,
+                    path: path.to_string(),
+                    title: None,
+                    updated_at: Utc::now(),
+                    deleted_at: None,
+                },
+            );
+            doc_ids.push(doc_id);
+        }
+
+        let tokens = ApiTokenStore::for_tests();
+        let jwt = Arc::new(
+            JwtAccessTokenService::new("test-secret-that-is-at-least-32-chars-long!!")
+                .expect("jwt service")
+                .with_api_token_store(tokens.clone()),
+        );
+        let (_, api_token) = tokens
+            .create(
+                ws_id,
+                user_id,
+                NewApiToken {
+                    name: "docs-bot".into(),
+                    agent_id: Some("docs-bot".into()),
+                    scopes: vec![ApiTokenScope::Read],
+                    path_prefixes: vec!["docs/".into()],
+                    expires_at: None,
+                },
+            )
+            .await
+            .expect("api token");
+        let app =
+            build_router_with_store(SearchStore::Memory(Arc::new(RwLock::new(mem))), authz, jwt);
+
+        let resp = app
+            .oneshot(get_request(&format!("/v1/workspaces/{ws_id}/search?q=auth"), &api_token))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = body_json(resp).await;
+        assert_eq!(body["items"].as_array().unwrap().len(), 1);
+        assert_eq!(body["items"][0]["doc_id"], doc_ids[0].to_string());
+    }
+
+    #[tokio::test]
+    async fn search_rejects_empty_query() {
+        let ws_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let authz = AuthorizationService::for_tests();
+        authz.grant_for_tests(ws_id, user_id, WorkspaceRole::Viewer).await;
+        let app = test_router(
+            SearchStore::Memory(Arc::new(RwLock::new(MemorySearchStore::default()))),
+            authz,
+        );
         let jwt = test_jwt_service();
         let token = auth_token(&jwt, user_id, ws_id);
 
@@ -564,9 +595,12 @@ mod tests {
         let ws_id = Uuid::new_v4();
         let other_ws_id = Uuid::new_v4();
         let user_id = Uuid::new_v4();
-        let mut mem = MemorySearchStore::default();
-        grant_workspace_role(&mut mem, ws_id, user_id, WorkspaceRole::Viewer);
-        let app = test_router(SearchStore::Memory(Arc::new(RwLock::new(mem))));
+        let authz = AuthorizationService::for_tests();
+        authz.grant_for_tests(ws_id, user_id, WorkspaceRole::Viewer).await;
+        let app = test_router(
+            SearchStore::Memory(Arc::new(RwLock::new(MemorySearchStore::default()))),
+            authz,
+        );
         let jwt = test_jwt_service();
         let token = auth_token(&jwt, user_id, other_ws_id);
 
@@ -582,8 +616,10 @@ mod tests {
     async fn search_forbids_non_members() {
         let ws_id = Uuid::new_v4();
         let user_id = Uuid::new_v4();
-        let app =
-            test_router(SearchStore::Memory(Arc::new(RwLock::new(MemorySearchStore::default()))));
+        let app = test_router(
+            SearchStore::Memory(Arc::new(RwLock::new(MemorySearchStore::default()))),
+            AuthorizationService::for_tests(),
+        );
         let jwt = test_jwt_service();
         let token = auth_token(&jwt, user_id, ws_id);
 
@@ -597,8 +633,10 @@ mod tests {
 
     #[tokio::test]
     async fn search_requires_authentication() {
-        let app =
-            test_router(SearchStore::Memory(Arc::new(RwLock::new(MemorySearchStore::default()))));
+        let app = test_router(
+            SearchStore::Memory(Arc::new(RwLock::new(MemorySearchStore::default()))),
+            AuthorizationService::for_tests(),
+        );
         let ws_id = Uuid::new_v4();
 
         let response = app
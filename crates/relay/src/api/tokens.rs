@@ -0,0 +1,181 @@
+// Workspace API token management.
+//
+// Tokens are minted and revoked from a human session only; a token can never
+// create or list other tokens. Owners see every token in the workspace, other
+// members only their own.
+
+use axum::{
+    extract::{Extension, Json, Path, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    audit::AuditEventType,
+    auth::{
+        api_tokens::{ApiToken, ApiTokenScope, ApiTokenStore, NewApiToken},
+        middleware::{AuthenticatedUser, WorkspaceRole},
+    },
+    validation::ValidatedJson,
+};
+
+use super::{try_record_audit_event, ApiError, ApiState};
+
+const MAX_TOKEN_NAME_CHARS: usize = 100;
+const MAX_PATH_PREFIXES: usize = 32;
+
+#[derive(Deserialize)]
+pub(super) struct CreateApiTokenRequest {
+    name: String,
+    #[serde(default)]
+    agent_id: Option<String>,
+    scopes: Vec<ApiTokenScope>,
+    #[serde(default)]
+    path_prefixes: Vec<String>,
+    #[serde(default)]
+    expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+pub(super) struct CreatedApiTokenEnvelope {
+    api_token: ApiToken,
+    /// The raw token. It is only ever returned by this response.
+    token: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct ApiTokensEnvelope {
+    items: Vec<ApiToken>,
+}
+
+pub(super) async fn create_api_token(
+    State(state): State<ApiState>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Path(workspace_id): Path<Uuid>,
+    ValidatedJson(payload): ValidatedJson<CreateApiTokenRequest>,
+) -> Result<(StatusCode, Json<CreatedApiTokenEnvelope>), ApiError> {
+    let store = api_token_store(&state)?;
+    let new_token = validate_create_request(payload)?;
+    let audit_details = serde_json::json!({
+        "action": "create_api_token",
+        "name": new_token.name,
+        "agent_id": new_token.agent_id,
+        "scopes": new_token.scopes,
+        "path_prefixes": new_token.path_prefixes,
+        "expires_at": new_token.expires_at,
+    });
+
+    let (api_token, token) =
+        store.create(workspace_id, user.user_id, new_token).await.map_err(ApiError::internal)?;
+    try_record_audit_event(
+        &state,
+        Some(workspace_id),
+        Some(user.user_id),
+        AuditEventType::Auth,
+        "api_token",
+        api_token.id.to_string(),
+        Some(audit_details),
+    )
+    .await;
+
+    Ok((StatusCode::CREATED, Json(CreatedApiTokenEnvelope { api_token, token })))
+}
+
+pub(super) async fn list_api_tokens(
+    State(state): State<ApiState>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Extension(role): Extension<WorkspaceRole>,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<Json<ApiTokensEnvelope>, ApiError> {
+    let store = api_token_store(&state)?;
+    let owner_filter = (role != WorkspaceRole::Owner).then_some(user.user_id);
+    let items = store.list(workspace_id, owner_filter).await.map_err(ApiError::internal)?;
+    Ok(Json(ApiTokensEnvelope { items }))
+}
+
+pub(super) async fn revoke_api_token(
+    State(state): State<ApiState>,
+    Extension(user): Extension<AuthenticatedUser>,
+    Extension(role): Extension<WorkspaceRole>,
+    Path((workspace_id, token_id)): Path<(Uuid, Uuid)>,
+) -> Result<StatusCode, ApiError> {
+    let store = api_token_store(&state)?;
+    let token = store
+        .get(workspace_id, token_id)
+        .await
+        .map_err(ApiError::internal)?
+        .filter(|token| role == WorkspaceRole::Owner || token.user_id == user.user_id)
+        .ok_or_else(|| ApiError::not_found("NOT_FOUND", "api token not found"))?;
+
+    if !store.revoke(workspace_id, token.id).await.map_err(ApiError::internal)? {
+        return Err(ApiError::not_found("NOT_FOUND", "api token not found"));
+    }
+    try_record_audit_event(
+        &state,
+        Some(workspace_id),
+        Some(user.user_id),
+        AuditEventType::Auth,
+        "api_token",
+        token.id.to_string(),
+        Some(serde_json::json!({
+            "action": "revoke_api_token",
+            "name": token.name,
+            "token_user_id": token.user_id,
+        })),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn api_token_store(state: &ApiState) -> Result<&ApiTokenStore, ApiError> {
+    state.api_tokens.as_ref().ok_or_else(|| {
+        ApiError::internal(anyhow::anyhow!("api token store is not configured on this relay"))
+    })
+}
+
+fn validate_create_request(payload: CreateApiTokenRequest) -> Result<NewApiToken, ApiError> {
+    let name = payload.name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_TOKEN_NAME_CHARS {
+        return Err(ApiError::bad_request(
+            "VALIDATION_ERROR",
+            format!("name must be 1-{MAX_TOKEN_NAME_CHARS} characters"),
+        ));
+    }
+
+    let agent_id = payload.agent_id.map(|agent_id| agent_id.trim().to_string());
+    if agent_id.as_deref().is_some_and(str::is_empty) {
+        return Err(ApiError::bad_request("VALIDATION_ERROR", "agent_id must not be empty"));
+    }
+
+    let mut scopes = payload.scopes;
+    scopes.sort_by_key(|scope| scope.as_str());
+    scopes.dedup();
+    if scopes.is_empty() {
+        return Err(ApiError::bad_request("VALIDATION_ERROR", "at least one scope is required"));
+    }
+
+    if payload.path_prefixes.len() > MAX_PATH_PREFIXES {
+        return Err(ApiError::bad_request(
+            "VALIDATION_ERROR",
+            format!("at most {MAX_PATH_PREFIXES} path prefixes are allowed"),
+        ));
+    }
+    if payload.path_prefixes.iter().any(|prefix| prefix.trim().is_empty()) {
+        return Err(ApiError::bad_request("VALIDATION_ERROR", "path prefixes must not be empty"));
+    }
+
+    if payload.expires_at.is_some_and(|expires_at| expires_at <= Utc::now()) {
+        return Err(ApiError::bad_request("VALIDATION_ERROR", "expires_at must be in the future"));
+    }
+
+    Ok(NewApiToken {
+        name,
+        agent_id,
+        scopes,
+        path_prefixes: payload.path_prefixes,
+        expires_at: payload.expires_at,
+    })
+}
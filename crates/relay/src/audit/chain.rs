@@ -0,0 +1,192 @@
+//! Optional tamper-evident hash chain over audit events.
+//!
+//! When enabled, every event stores `prev_hash` (the `event_hash` of the
+//! previous chained event in the same workspace) and its own `event_hash`,
+//! a SHA-256 over `prev_hash` and all persisted fields. Editing, deleting or
+//! reordering a chained row breaks the chain at that point.
+
+use sha2::{Digest, Sha256};
+use sqlx::types::chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::AuditEvent;
+
+/// Advisory-lock key serializing chain appends for one workspace (or for
+/// events without a workspace).
+pub(super) fn chain_lock_key(workspace_id: Option<Uuid>) -> i64 {
+    let mut hasher = Sha256::new();
+    hasher.update(b"scriptum.audit_chain:");
+    if let Some(workspace_id) = workspace_id {
+        hasher.update(workspace_id.as_bytes());
+    }
+    let digest = hasher.finalize();
+    let mut key = [0u8; 8];
+    key.copy_from_slice(&digest[..8]);
+    i64::from_be_bytes(key)
+}
+
+/// Timestamp for the next chained event: now, truncated to the microsecond
+/// precision Postgres stores, and strictly after the previous link so chain
+/// order always matches `(created_at, id)` order.
+pub(super) fn next_chain_timestamp(
+    now: DateTime<Utc>,
+    previous: Option<DateTime<Utc>>,
+) -> DateTime<Utc> {
+    let micros = match previous {
+        Some(previous) => now.timestamp_micros().max(previous.timestamp_micros() + 1),
+        None => now.timestamp_micros(),
+    };
+    DateTime::<Utc>::from_timestamp_micros(micros).unwrap_or(now)
+}
+
+/// SHA-256 over the event's `prev_hash` and persisted fields. `event_hash`
+/// itself is not part of the input.
+pub fn compute_event_hash(event: &AuditEvent) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    let details = event.details.as_ref().map(|details| details.to_string());
+    let created_at = event.created_at.timestamp_micros().to_be_bytes();
+
+    for field in [
+        event.prev_hash.as_deref(),
+        Some(event.id.as_bytes().as_slice()),
+        event.workspace_id.as_ref().map(|id| id.as_bytes().as_slice()),
+        event.actor_user_id.as_ref().map(|id| id.as_bytes().as_slice()),
+        event.actor_agent_id.as_deref().map(str::as_bytes),
+        Some(event.event_type.as_bytes()),
+        Some(event.entity_type.as_bytes()),
+        Some(event.entity_id.as_bytes()),
+        event.request_id.as_deref().map(str::as_bytes),
+        event.ip_hash.as_deref(),
+        event.user_agent_hash.as_deref(),
+        details.as_deref().map(str::as_bytes),
+        Some(created_at.as_slice()),
+    ] {
+        match field {
+            None => hasher.update([0u8]),
+            Some(bytes) => {
+                hasher.update([1u8]);
+                hasher.update((bytes.len() as u64).to_be_bytes());
+                hasher.update(bytes);
+            }
+        }
+    }
+
+    hasher.finalize().to_vec()
+}
+
+/// Result of walking a workspace's events in chain order.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize)]
+pub struct ChainVerification {
+    /// Chained events checked.
+    pub checked: u64,
+    /// Events recorded while chaining was disabled.
+    pub unchained: u64,
+    /// First event whose link or hash does not match, if any.
+    pub first_invalid_event_id: Option<Uuid>,
+}
+
+impl ChainVerification {
+    pub fn is_valid(&self) -> bool {
+        self.first_invalid_event_id.is_none()
+    }
+}
+
+/// Incremental verifier; feed events oldest first.
+#[derive(Debug, Default)]
+pub struct ChainVerifier {
+    last_hash: Option<Vec<u8>>,
+    result: ChainVerification,
+}
+
+impl ChainVerifier {
+    pub fn push(&mut self, event: &AuditEvent) {
+        let Some(event_hash) = event.event_hash.as_ref() else {
+            self.result.unchained += 1;
+            return;
+        };
+        self.result.checked += 1;
+
+        let linked = event.prev_hash == self.last_hash;
+        if self.result.first_invalid_event_id.is_none()
+            && (!linked || compute_event_hash(event) != *event_hash)
+        {
+            self.result.first_invalid_event_id = Some(event.id);
+        }
+        self.last_hash = Some(event_hash.clone());
+    }
+
+    pub fn finish(self) -> ChainVerification {
+        self.result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+    use serde_json::json;
+
+    use super::*;
+
+    fn chain(count: usize) -> Vec<AuditEvent> {
+        let workspace_id = Uuid::new_v4();
+        let start = Utc::now();
+        let mut events: Vec<AuditEvent> = Vec::new();
+        for index in 0..count {
+            let mut event = AuditEvent {
+                id: Uuid::new_v4(),
+                workspace_id: Some(workspace_id),
+                actor_user_id: Some(Uuid::new_v4()),
+                actor_agent_id: None,
+                event_type: "admin_action".to_owned(),
+                entity_type: "document".to_owned(),
+                entity_id: format!("doc-{index}"),
+                request_id: None,
+                ip_hash: None,
+                user_agent_hash: None,
+                details: Some(json!({ "action": "update", "index": index })),
+                prev_hash: events.last().and_then(|previous| previous.event_hash.clone()),
+                event_hash: None,
+                created_at: next_chain_timestamp(
+                    start,
+                    events.last().map(|previous| previous.created_at),
+                ),
+            };
+            event.event_hash = Some(compute_event_hash(&event));
+            events.push(event);
+        }
+        events
+    }
+
+    fn verify(events: &[AuditEvent]) -> ChainVerification {
+        let mut verifier = ChainVerifier::default();
+        events.iter().for_each(|event| verifier.push(event));
+        verifier.finish()
+    }
+
+    #[test]
+    fn intact_chain_verifies() {
+        let result = verify(&chain(4));
+        assert!(result.is_valid());
+        assert_eq!(result.checked, 4);
+    }
+
+    #[test]
+    fn edited_or_deleted_events_break_the_chain() {
+        let mut edited = chain(4);
+        edited[2].details = Some(json!({ "action": "delete" }));
+        assert_eq!(verify(&edited).first_invalid_event_id, Some(edited[2].id));
+
+        let mut deleted = chain(4);
+        deleted.remove(1);
+        assert_eq!(verify(&deleted).first_invalid_event_id, Some(deleted[1].id));
+    }
+
+    #[test]
+    fn chain_timestamps_are_strictly_increasing() {
+        let now = Utc::now();
+        let later = now + Duration::seconds(5);
+        let next = next_chain_timestamp(now, Some(later));
+        assert!(next > later);
+        assert_eq!(next.timestamp_subsec_nanos() % 1_000, 0);
+    }
+}
@@ -1,7 +1,11 @@
 //! Immutable append-only audit event logging.
 //!
-//! This module intentionally only exposes inserts into `audit_events`.
-//! It never stores raw IP addresses or raw user-agent strings.
+//! Events are only ever inserted; `query` provides the read side for the
+//! owner-only audit API and `chain` the optional tamper-evident hash chain.
+//! Raw IP addresses and raw user-agent strings are never stored.
+
+pub mod chain;
+pub mod query;
 
 use std::net::IpAddr;
 
@@ -29,7 +33,18 @@ pub enum AuditEventType {
 }
 
 impl AuditEventType {
-    const fn as_str(self) -> &'static str {
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "auth" => Some(Self::Auth),
+            "permission_change" => Some(Self::PermissionChange),
+            "share_link_operation" => Some(Self::ShareLinkOperation),
+            "delete" => Some(Self::Delete),
+            "admin_action" => Some(Self::AdminAction),
+            _ => None,
+        }
+    }
+
+    pub const fn as_str(self) -> &'static str {
         match self {
             Self::Auth => "auth",
             Self::PermissionChange => "permission_change",
@@ -67,6 +82,8 @@ pub struct AuditEvent {
     pub ip_hash: Option<Vec<u8>>,
     pub user_agent_hash: Option<Vec<u8>>,
     pub details: Option<Value>,
+    pub prev_hash: Option<Vec<u8>>,
+    pub event_hash: Option<Vec<u8>>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -108,9 +125,36 @@ impl PreparedAuditEvent {
     }
 }
 
-pub async fn record_event(pool: &PgPool, event: NewAuditEvent) -> anyhow::Result<AuditEvent> {
-    let prepared = PreparedAuditEvent::from_new(event)?;
+/// Writer for audit events, bound to the relay database and to whether
+/// new events are appended to the tamper-evident hash chain.
+#[derive(Clone)]
+pub struct AuditLog {
+    pool: PgPool,
+    hash_chain: bool,
+}
 
+impl AuditLog {
+    pub fn new(pool: PgPool, hash_chain: bool) -> Self {
+        Self { pool, hash_chain }
+    }
+
+    pub fn hash_chain_enabled(&self) -> bool {
+        self.hash_chain
+    }
+
+    pub async fn record(&self, event: NewAuditEvent) -> anyhow::Result<AuditEvent> {
+        let prepared = PreparedAuditEvent::from_new(event)?;
+        if self.hash_chain {
+            return record_chained_event(&self.pool, prepared).await;
+        }
+        record_plain_event(&self.pool, prepared).await
+    }
+}
+
+async fn record_plain_event(
+    pool: &PgPool,
+    prepared: PreparedAuditEvent,
+) -> anyhow::Result<AuditEvent> {
     sqlx::query_as::<_, AuditEvent>(
         r#"
         INSERT INTO audit_events (
@@ -138,6 +182,8 @@ pub async fn record_event(pool: &PgPool, event: NewAuditEvent) -> anyhow::Result
             ip_hash,
             user_agent_hash,
             details,
+            prev_hash,
+            event_hash,
             created_at
         "#,
     )
@@ -156,6 +202,96 @@ pub async fn record_event(pool: &PgPool, event: NewAuditEvent) -> anyhow::Result
     .context("failed to insert immutable audit event")
 }
 
+/// Appends the event to its workspace's hash chain. Appends are serialized
+/// per workspace with a transaction-scoped advisory lock.
+async fn record_chained_event(
+    pool: &PgPool,
+    prepared: PreparedAuditEvent,
+) -> anyhow::Result<AuditEvent> {
+    let mut tx = pool.begin().await.context("failed to start audit chain transaction")?;
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(chain::chain_lock_key(prepared.workspace_id))
+        .execute(&mut *tx)
+        .await
+        .context("failed to lock audit chain")?;
+
+    let previous = sqlx::query_as::<_, (Vec<u8>, DateTime<Utc>)>(
+        r#"
+        SELECT event_hash, created_at
+        FROM audit_events
+        WHERE workspace_id IS NOT DISTINCT FROM $1
+          AND event_hash IS NOT NULL
+        ORDER BY created_at DESC, id DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(prepared.workspace_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .context("failed to load audit chain head")?;
+
+    let (prev_hash, previous_created_at) = previous.unzip();
+    let mut event = AuditEvent {
+        id: Uuid::new_v4(),
+        workspace_id: prepared.workspace_id,
+        actor_user_id: prepared.actor_user_id,
+        actor_agent_id: prepared.actor_agent_id,
+        event_type: prepared.event_type.to_owned(),
+        entity_type: prepared.entity_type,
+        entity_id: prepared.entity_id,
+        request_id: prepared.request_id,
+        ip_hash: prepared.ip_hash,
+        user_agent_hash: prepared.user_agent_hash,
+        details: prepared.details,
+        prev_hash,
+        event_hash: None,
+        created_at: chain::next_chain_timestamp(Utc::now(), previous_created_at),
+    };
+    event.event_hash = Some(chain::compute_event_hash(&event));
+
+    sqlx::query(
+        r#"
+        INSERT INTO audit_events (
+            id,
+            workspace_id,
+            actor_user_id,
+            actor_agent_id,
+            event_type,
+            entity_type,
+            entity_id,
+            request_id,
+            ip_hash,
+            user_agent_hash,
+            details,
+            prev_hash,
+            event_hash,
+            created_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+        "#,
+    )
+    .bind(event.id)
+    .bind(event.workspace_id)
+    .bind(event.actor_user_id)
+    .bind(&event.actor_agent_id)
+    .bind(&event.event_type)
+    .bind(&event.entity_type)
+    .bind(&event.entity_id)
+    .bind(&event.request_id)
+    .bind(&event.ip_hash)
+    .bind(&event.user_agent_hash)
+    .bind(&event.details)
+    .bind(&event.prev_hash)
+    .bind(&event.event_hash)
+    .bind(event.created_at)
+    .execute(&mut *tx)
+    .await
+    .context("failed to insert chained audit event")?;
+    tx.commit().await.context("failed to commit audit chain append")?;
+
+    Ok(event)
+}
+
 pub fn client_ip_from_headers(headers: &HeaderMap) -> Option<String> {
     forwarded_for_first_hop(headers)
         .or_else(|| header_value(headers, X_REAL_IP_HEADER))
@@ -0,0 +1,104 @@
+//! Read side of the audit log: filtered, keyset-paginated queries.
+
+use anyhow::Context;
+use sqlx::{
+    types::chrono::{DateTime, Utc},
+    PgPool,
+};
+use uuid::Uuid;
+
+use super::{AuditEvent, AuditEventType};
+
+/// Filters for one workspace's audit events. `since` is inclusive, `until`
+/// exclusive.
+#[derive(Debug, Clone, Default)]
+pub struct AuditEventFilter {
+    pub event_type: Option<AuditEventType>,
+    pub actor_user_id: Option<Uuid>,
+    pub actor_agent_id: Option<String>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// Keyset position: the `(created_at, id)` of the last event already returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditOrder {
+    /// Browsing order for the API.
+    NewestFirst,
+    /// Chain order, used by export and verification.
+    OldestFirst,
+}
+
+pub async fn list_events(
+    pool: &PgPool,
+    workspace_id: Uuid,
+    filter: &AuditEventFilter,
+    after: Option<AuditCursor>,
+    order: AuditOrder,
+    limit: i64,
+) -> anyhow::Result<Vec<AuditEvent>> {
+    let sql = match order {
+        AuditOrder::NewestFirst => LIST_NEWEST_FIRST_SQL,
+        AuditOrder::OldestFirst => LIST_OLDEST_FIRST_SQL,
+    };
+
+    sqlx::query_as::<_, AuditEvent>(sql)
+        .bind(workspace_id)
+        .bind(filter.event_type.map(AuditEventType::as_str))
+        .bind(filter.actor_user_id)
+        .bind(filter.actor_agent_id.as_deref())
+        .bind(filter.entity_type.as_deref())
+        .bind(filter.entity_id.as_deref())
+        .bind(filter.since)
+        .bind(filter.until)
+        .bind(after.map(|cursor| cursor.created_at))
+        .bind(after.map(|cursor| cursor.id))
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .context("failed to query audit events")
+}
+
+const LIST_NEWEST_FIRST_SQL: &str = r#"
+    SELECT id, workspace_id, actor_user_id, actor_agent_id, event_type, entity_type,
+           entity_id, request_id, ip_hash, user_agent_hash, details, prev_hash, event_hash,
+           created_at
+    FROM audit_events
+    WHERE workspace_id = $1
+      AND ($2::text IS NULL OR event_type = $2)
+      AND ($3::uuid IS NULL OR actor_user_id = $3)
+      AND ($4::text IS NULL OR actor_agent_id = $4)
+      AND ($5::text IS NULL OR entity_type = $5)
+      AND ($6::text IS NULL OR entity_id = $6)
+      AND ($7::timestamptz IS NULL OR created_at >= $7)
+      AND ($8::timestamptz IS NULL OR created_at < $8)
+      AND ($9::timestamptz IS NULL OR (created_at, id) < ($9, $10))
+    ORDER BY created_at DESC, id DESC
+    LIMIT $11
+"#;
+
+const LIST_OLDEST_FIRST_SQL: &str = r#"
+    SELECT id, workspace_id, actor_user_id, actor_agent_id, event_type, entity_type,
+           entity_id, request_id, ip_hash, user_agent_hash, details, prev_hash, event_hash,
+           created_at
+    FROM audit_events
+    WHERE workspace_id = $1
+      AND ($2::text IS NULL OR event_type = $2)
+      AND ($3::uuid IS NULL OR actor_user_id = $3)
+      AND ($4::text IS NULL OR actor_agent_id = $4)
+      AND ($5::text IS NULL OR entity_type = $5)
+      AND ($6::text IS NULL OR entity_id = $6)
+      AND ($7::timestamptz IS NULL OR created_at >= $7)
+      AND ($8::timestamptz IS NULL OR created_at < $8)
+      AND ($9::timestamptz IS NULL OR (created_at, id) > ($9, $10))
+    ORDER BY created_at ASC, id ASC
+    LIMIT $11
+"#;
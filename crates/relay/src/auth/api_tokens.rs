@@ -0,0 +1,425 @@
+// Scoped API tokens for agents and CI.
+//
+// Tokens are opaque `sct_…` strings bound to a workspace and the user who
+// minted them; only their SHA-256 hash is stored. A token acts as its user
+// (membership and ACL overrides still apply) but is further narrowed by its
+// scopes and optional document path prefixes, and may name an agent id so
+// edits made with it are attributed to that agent.
+
+use std::sync::Arc;
+
+use anyhow::Context;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, PgPool};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::auth::middleware::WorkspaceRole;
+
+pub const API_TOKEN_PREFIX: &str = "sct_";
+const API_TOKEN_BYTES: usize = 32;
+/// Characters of the raw token kept for display (`sct_` plus 6).
+const DISPLAY_PREFIX_LEN: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiTokenScope {
+    Read,
+    Write,
+    Comment,
+}
+
+impl ApiTokenScope {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write",
+            Self::Comment => "comment",
+        }
+    }
+
+    /// Scope a token needs to act with `role`; `None` means tokens may never
+    /// act at that level (owner-only operations require a human session).
+    pub const fn for_role(role: WorkspaceRole) -> Option<Self> {
+        match role {
+            WorkspaceRole::Viewer => Some(Self::Read),
+            WorkspaceRole::Editor => Some(Self::Write),
+            WorkspaceRole::Owner => None,
+        }
+    }
+
+    pub fn from_db_value(value: &str) -> Option<Self> {
+        match value {
+            "read" => Some(Self::Read),
+            "write" => Some(Self::Write),
+            "comment" => Some(Self::Comment),
+            _ => None,
+        }
+    }
+}
+
+/// Restrictions carried by a request authenticated with an API token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiTokenGrant {
+    pub token_id: Uuid,
+    pub workspace_id: Uuid,
+    pub scopes: Vec<ApiTokenScope>,
+    /// Document path prefixes the token may touch; empty means any path.
+    pub path_prefixes: Vec<String>,
+}
+
+impl ApiTokenGrant {
+    pub fn allows_scope(&self, scope: ApiTokenScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+
+    pub fn allows_path(&self, path: &str) -> bool {
+        self.path_prefixes.is_empty()
+            || self.path_prefixes.iter().any(|prefix| path.starts_with(prefix.as_str()))
+    }
+}
+
+/// Caller identity resolved from a valid, unrevoked, unexpired token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedApiToken {
+    pub user_id: Uuid,
+    pub workspace_id: Uuid,
+    pub agent_id: Option<String>,
+    pub grant: ApiTokenGrant,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ApiToken {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub user_id: Uuid,
+    pub agent_id: Option<String>,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub path_prefixes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiToken {
+    fn is_live(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+
+    fn authenticated(&self) -> AuthenticatedApiToken {
+        AuthenticatedApiToken {
+            user_id: self.user_id,
+            workspace_id: self.workspace_id,
+            agent_id: self.agent_id.clone(),
+            grant: ApiTokenGrant {
+                token_id: self.id,
+                workspace_id: self.workspace_id,
+                scopes: self
+                    .scopes
+                    .iter()
+                    .filter_map(|scope| ApiTokenScope::from_db_value(scope))
+                    .collect(),
+                path_prefixes: self.path_prefixes.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewApiToken {
+    pub name: String,
+    pub agent_id: Option<String>,
+    pub scopes: Vec<ApiTokenScope>,
+    pub path_prefixes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct MemoryApiTokenStore {
+    tokens: Vec<(ApiToken, Vec<u8>)>,
+}
+
+#[derive(Clone)]
+pub enum ApiTokenStore {
+    Postgres(PgPool),
+    #[cfg_attr(not(test), allow(dead_code))]
+    Memory(Arc<RwLock<MemoryApiTokenStore>>),
+}
+
+impl ApiTokenStore {
+    #[cfg(test)]
+    pub(crate) fn for_tests() -> Self {
+        Self::Memory(Arc::new(RwLock::new(MemoryApiTokenStore::default())))
+    }
+
+    /// Mint a token; the raw secret is returned once and never stored.
+    pub(crate) async fn create(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+        new_token: NewApiToken,
+    ) -> anyhow::Result<(ApiToken, String)> {
+        let (secret, token_hash) = generate_api_token();
+        let token_prefix = secret[..DISPLAY_PREFIX_LEN].to_string();
+        let scopes: Vec<String> =
+            new_token.scopes.iter().map(|scope| scope.as_str().to_string()).collect();
+
+        let token = match self {
+            Self::Postgres(pool) => sqlx::query_as::<_, ApiToken>(
+                r#"
+                INSERT INTO api_tokens (
+                    workspace_id, user_id, agent_id, name, token_prefix, token_hash,
+                    scopes, path_prefixes, expires_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                RETURNING id, workspace_id, user_id, agent_id, name, token_prefix, scopes,
+                          path_prefixes, expires_at, last_used_at, revoked_at, created_at
+                "#,
+            )
+            .bind(workspace_id)
+            .bind(user_id)
+            .bind(&new_token.agent_id)
+            .bind(&new_token.name)
+            .bind(&token_prefix)
+            .bind(&token_hash)
+            .bind(&scopes)
+            .bind(&new_token.path_prefixes)
+            .bind(new_token.expires_at)
+            .fetch_one(pool)
+            .await
+            .context("failed to insert api token")?,
+            Self::Memory(store) => {
+                let token = ApiToken {
+                    id: Uuid::new_v4(),
+                    workspace_id,
+                    user_id,
+                    agent_id: new_token.agent_id,
+                    name: new_token.name,
+                    token_prefix,
+                    scopes,
+                    path_prefixes: new_token.path_prefixes,
+                    expires_at: new_token.expires_at,
+                    last_used_at: None,
+                    revoked_at: None,
+                    created_at: Utc::now(),
+                };
+                store.write().await.tokens.push((token.clone(), token_hash));
+                token
+            }
+        };
+
+        Ok((token, secret))
+    }
+
+    /// Tokens in the workspace, optionally limited to one user's tokens.
+    pub(crate) async fn list(
+        &self,
+        workspace_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> anyhow::Result<Vec<ApiToken>> {
+        match self {
+            Self::Postgres(pool) => sqlx::query_as::<_, ApiToken>(
+                r#"
+                SELECT id, workspace_id, user_id, agent_id, name, token_prefix, scopes,
+                       path_prefixes, expires_at, last_used_at, revoked_at, created_at
+                FROM api_tokens
+                WHERE workspace_id = $1 AND ($2::uuid IS NULL OR user_id = $2)
+                ORDER BY created_at DESC, id DESC
+                "#,
+            )
+            .bind(workspace_id)
+            .bind(user_id)
+            .fetch_all(pool)
+            .await
+            .context("failed to list api tokens"),
+            Self::Memory(store) => {
+                let mut tokens: Vec<ApiToken> = store
+                    .read()
+                    .await
+                    .tokens
+                    .iter()
+                    .map(|(token, _)| token)
+                    .filter(|token| {
+                        token.workspace_id == workspace_id
+                            && user_id.is_none_or(|user_id| token.user_id == user_id)
+                    })
+                    .cloned()
+                    .collect();
+                tokens.sort_by_key(|token| std::cmp::Reverse(token.created_at));
+                Ok(tokens)
+            }
+        }
+    }
+
+    pub(crate) async fn get(
+        &self,
+        workspace_id: Uuid,
+        token_id: Uuid,
+    ) -> anyhow::Result<Option<ApiToken>> {
+        match self {
+            Self::Postgres(pool) => sqlx::query_as::<_, ApiToken>(
+                r#"
+                SELECT id, workspace_id, user_id, agent_id, name, token_prefix, scopes,
+                       path_prefixes, expires_at, last_used_at, revoked_at, created_at
+                FROM api_tokens
+                WHERE workspace_id = $1 AND id = $2
+                "#,
+            )
+            .bind(workspace_id)
+            .bind(token_id)
+            .fetch_optional(pool)
+            .await
+            .context("failed to load api token"),
+            Self::Memory(store) => Ok(store
+                .read()
+                .await
+                .tokens
+                .iter()
+                .map(|(token, _)| token)
+                .find(|token| token.workspace_id == workspace_id && token.id == token_id)
+                .cloned()),
+        }
+    }
+
+    /// Revoke a token; returns `false` when it does not exist or is already revoked.
+    pub(crate) async fn revoke(&self, workspace_id: Uuid, token_id: Uuid) -> anyhow::Result<bool> {
+        match self {
+            Self::Postgres(pool) => {
+                let result = sqlx::query(
+                    r#"
+                    UPDATE api_tokens
+                    SET revoked_at = now()
+                    WHERE workspace_id = $1 AND id = $2 AND revoked_at IS NULL
+                    "#,
+                )
+                .bind(workspace_id)
+                .bind(token_id)
+                .execute(pool)
+                .await
+                .context("failed to revoke api token")?;
+                Ok(result.rows_affected() > 0)
+            }
+            Self::Memory(store) => {
+                let mut guard = store.write().await;
+                let Some((token, _)) = guard.tokens.iter_mut().find(|(token, _)| {
+                    token.workspace_id == workspace_id
+                        && token.id == token_id
+                        && token.revoked_at.is_none()
+                }) else {
+                    return Ok(false);
+                };
+                token.revoked_at = Some(Utc::now());
+                Ok(true)
+            }
+        }
+    }
+
+    /// Resolve a raw bearer token, recording its last use.
+    pub async fn authenticate(
+        &self,
+        raw_token: &str,
+    ) -> anyhow::Result<Option<AuthenticatedApiToken>> {
+        if !raw_token.starts_with(API_TOKEN_PREFIX) {
+            return Ok(None);
+        }
+        let token_hash = hash_api_token(raw_token);
+
+        match self {
+            Self::Postgres(pool) => Ok(sqlx::query_as::<_, ApiToken>(
+                r#"
+                UPDATE api_tokens
+                SET last_used_at = now()
+                WHERE token_hash = $1
+                  AND revoked_at IS NULL
+                  AND (expires_at IS NULL OR expires_at > now())
+                RETURNING id, workspace_id, user_id, agent_id, name, token_prefix, scopes,
+                          path_prefixes, expires_at, last_used_at, revoked_at, created_at
+                "#,
+            )
+            .bind(token_hash)
+            .fetch_optional(pool)
+            .await
+            .context("failed to authenticate api token")?
+            .map(|token| token.authenticated())),
+            Self::Memory(store) => {
+                let mut guard = store.write().await;
+                let now = Utc::now();
+                Ok(guard
+                    .tokens
+                    .iter_mut()
+                    .find(|(token, hash)| *hash == token_hash && token.is_live(now))
+                    .map(|(token, _)| {
+                        token.last_used_at = Some(now);
+                        token.authenticated()
+                    }))
+            }
+        }
+    }
+}
+
+fn generate_api_token() -> (String, Vec<u8>) {
+    let mut bytes = [0u8; API_TOKEN_BYTES];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let token = format!("{API_TOKEN_PREFIX}{}", URL_SAFE_NO_PAD.encode(bytes));
+    let hash = hash_api_token(&token);
+    (token, hash)
+}
+
+fn hash_api_token(token: &str) -> Vec<u8> {
+    Sha256::digest(token.as_bytes()).to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    use super::{ApiTokenScope, ApiTokenStore, NewApiToken};
+
+    fn new_token(expires_in: Option<Duration>) -> NewApiToken {
+        NewApiToken {
+            name: "ci".into(),
+            agent_id: Some("ci-bot".into()),
+            scopes: vec![ApiTokenScope::Read, ApiTokenScope::Comment],
+            path_prefixes: vec!["docs/".into()],
+            expires_at: expires_in.map(|ttl| Utc::now() + ttl),
+        }
+    }
+
+    #[tokio::test]
+    async fn tokens_authenticate_until_revoked_or_expired() {
+        let store = ApiTokenStore::for_tests();
+        let (workspace_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let (token, secret) =
+            store.create(workspace_id, user_id, new_token(None)).await.expect("create");
+        assert!(secret.starts_with("sct_"));
+        assert!(secret.starts_with(&token.token_prefix));
+
+        let caller = store.authenticate(&secret).await.expect("auth").expect("token is live");
+        assert_eq!(caller.user_id, user_id);
+        assert_eq!(caller.agent_id.as_deref(), Some("ci-bot"));
+        assert!(caller.grant.allows_scope(ApiTokenScope::Comment));
+        assert!(!caller.grant.allows_scope(ApiTokenScope::Write));
+        assert!(caller.grant.allows_path("docs/guide.md"));
+        assert!(!caller.grant.allows_path("src/main.rs"));
+
+        assert!(store.revoke(workspace_id, token.id).await.expect("revoke"));
+        assert!(!store.revoke(workspace_id, token.id).await.expect("second revoke"));
+        assert!(store.authenticate(&secret).await.expect("auth").is_none());
+
+        let (_, expired) = store
+            .create(workspace_id, user_id, new_token(Some(Duration::seconds(-1))))
+            .await
+            .expect("create expired");
+        assert!(store.authenticate(&expired).await.expect("auth").is_none());
+        assert!(store.authenticate("not-a-token").await.expect("auth").is_none());
+    }
+}
@@ -0,0 +1,870 @@
+// Document-level authorization.
+//
+// Every relay entry point (REST documents/comments/search and WebSocket sync)
+// resolves access through `AuthorizationService`, so ACL overrides and their
+// expiry are honoured consistently.
+//
+// Resolution order for a principal on a document:
+//   1. The backing user must be an active member of a live workspace.
+//   2. An unexpired `agent` override for the principal's agent id wins.
+//   3. Otherwise an unexpired `user` override for the user id wins.
+//   4. Otherwise the workspace role applies.
+// Overrides replace the workspace role; the newest override wins on ties.
+//
+// Requests made with an API token are further narrowed: the token must belong
+// to the workspace, carry the scope for the requested action, and (when it
+// lists path prefixes) only sees documents under those prefixes.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::auth::api_tokens::{ApiTokenGrant, ApiTokenScope};
+use crate::auth::middleware::{AuthenticatedUser, WorkspaceRole};
+use crate::db::pool::{check_pool_health, create_pg_pool, PoolConfig};
+
+pub(crate) const SUBJECT_USER: &str = "user";
+pub(crate) const SUBJECT_AGENT: &str = "agent";
+pub(crate) const SUBJECT_SHARE_LINK: &str = "share_link";
+
+/// The actor an access decision is made for.
+///
+/// Agents act on behalf of a user: the user's membership gates workspace
+/// access, while an agent-specific override may narrow or widen its role on a
+/// single document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Principal {
+    pub(crate) user_id: Option<Uuid>,
+    pub(crate) agent_id: Option<String>,
+    /// Present when the caller authenticated with an API token.
+    pub(crate) token: Option<ApiTokenGrant>,
+}
+
+impl Principal {
+    pub(crate) fn user(user_id: Uuid) -> Self {
+        Self { user_id: Some(user_id), agent_id: None, token: None }
+    }
+
+    pub(crate) fn agent(user_id: Option<Uuid>, agent_id: impl Into<String>) -> Self {
+        Self { user_id, agent_id: Some(agent_id.into()), token: None }
+    }
+
+    /// Whether the principal's token (if any) grants `scope` in `workspace_id`.
+    fn token_allows(&self, workspace_id: Uuid, scope: Option<ApiTokenScope>) -> bool {
+        self.token.as_ref().is_none_or(|grant| {
+            grant.workspace_id == workspace_id
+                && scope.is_some_and(|scope| grant.allows_scope(scope))
+        })
+    }
+}
+
+impl From<&AuthenticatedUser> for Principal {
+    fn from(user: &AuthenticatedUser) -> Self {
+        Self {
+            user_id: Some(user.user_id),
+            agent_id: user.agent_id.clone(),
+            token: user.token.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AclOverride {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub doc_id: Uuid,
+    pub subject_type: String,
+    pub subject_id: String,
+    pub role: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AclOverrideRow {
+    id: Uuid,
+    workspace_id: Uuid,
+    doc_id: Uuid,
+    subject_type: String,
+    subject_id: String,
+    role: String,
+    expires_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<AclOverrideRow> for AclOverride {
+    fn from(row: AclOverrideRow) -> Self {
+        Self {
+            id: row.id,
+            workspace_id: row.workspace_id,
+            doc_id: row.doc_id,
+            subject_type: row.subject_type,
+            subject_id: row.subject_id,
+            role: row.role,
+            expires_at: row.expires_at,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct NewAclOverride {
+    pub(crate) subject_type: String,
+    pub(crate) subject_id: String,
+    pub(crate) role: String,
+    pub(crate) expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug)]
+pub(crate) enum AuthzError {
+    /// The principal is not an active member of the workspace.
+    NoWorkspaceAccess,
+    /// The principal's effective role is below the required role.
+    InsufficientRole,
+    /// The principal's API token lacks the scope for the action.
+    InsufficientScope,
+    Internal(anyhow::Error),
+}
+
+impl AuthzError {
+    pub(crate) fn message(&self) -> &'static str {
+        match self {
+            Self::NoWorkspaceAccess => "caller lacks workspace access",
+            Self::InsufficientRole => "caller lacks required role",
+            Self::InsufficientScope => "api token lacks required scope",
+            Self::Internal(_) => "internal error",
+        }
+    }
+}
+
+impl From<anyhow::Error> for AuthzError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::Internal(error)
+    }
+}
+
+#[derive(Clone)]
+pub enum AuthorizationService {
+    Postgres(PgPool),
+    #[cfg_attr(not(test), allow(dead_code))]
+    Memory(Arc<RwLock<MemoryAccessStore>>),
+}
+
+#[derive(Default)]
+pub struct MemoryAccessStore {
+    workspace_members: HashMap<(Uuid, Uuid), WorkspaceRole>,
+    acl_overrides: HashMap<Uuid, AclOverride>,
+    document_paths: HashMap<Uuid, String>,
+    /// Memory tests bootstrap the first caller in a workspace as owner.
+    bootstrap_first_caller_as_owner: bool,
+}
+
+impl AuthorizationService {
+    pub async fn from_env() -> anyhow::Result<Self> {
+        let database_url = env::var("SCRIPTUM_RELAY_DATABASE_URL")
+            .context("SCRIPTUM_RELAY_DATABASE_URL must be set for relay authorization")?;
+        let pool = create_pg_pool(&database_url, PoolConfig::from_env())
+            .await
+            .context("failed to initialize relay PostgreSQL pool for authorization")?;
+        check_pool_health(&pool)
+            .await
+            .context("relay PostgreSQL health check failed for authorization")?;
+
+        Ok(Self::Postgres(pool))
+    }
+
+    /// Role of `user_id` in the workspace, ignoring document overrides.
+    pub(crate) async fn role_for_user(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+    ) -> anyhow::Result<Option<WorkspaceRole>> {
+        match self {
+            Self::Postgres(pool) => workspace_role_pg(pool, workspace_id, user_id).await,
+            Self::Memory(store) => Ok(workspace_role_mem(store, workspace_id, user_id).await),
+        }
+    }
+
+    /// Effective role of `principal` on `doc_id`, or `None` without access.
+    pub(crate) async fn effective_role(
+        &self,
+        workspace_id: Uuid,
+        doc_id: Uuid,
+        principal: &Principal,
+    ) -> anyhow::Result<Option<WorkspaceRole>> {
+        Ok(self.effective_roles(workspace_id, &[doc_id], principal).await?.remove(&doc_id))
+    }
+
+    /// Effective roles of `principal` on each of `doc_ids`. Documents the
+    /// principal cannot access are absent from the returned map.
+    pub(crate) async fn effective_roles(
+        &self,
+        workspace_id: Uuid,
+        doc_ids: &[Uuid],
+        principal: &Principal,
+    ) -> anyhow::Result<HashMap<Uuid, WorkspaceRole>> {
+        let Some(user_id) = principal.user_id else {
+            return Ok(HashMap::new());
+        };
+        if principal.token.as_ref().is_some_and(|grant| grant.workspace_id != workspace_id) {
+            return Ok(HashMap::new());
+        }
+        let Some(workspace_role) = self.role_for_user(workspace_id, user_id).await? else {
+            return Ok(HashMap::new());
+        };
+
+        let overrides = match self {
+            Self::Postgres(pool) => {
+                override_roles_pg(pool, workspace_id, doc_ids, principal).await?
+            }
+            Self::Memory(store) => {
+                override_roles_mem(store, workspace_id, doc_ids, principal).await?
+            }
+        };
+
+        let mut roles: HashMap<Uuid, WorkspaceRole> = doc_ids
+            .iter()
+            .map(|doc_id| (*doc_id, overrides.get(doc_id).copied().unwrap_or(workspace_role)))
+            .collect();
+
+        if let Some(grant) =
+            principal.token.as_ref().filter(|grant| !grant.path_prefixes.is_empty())
+        {
+            let paths = self.document_paths(workspace_id, doc_ids).await?;
+            roles.retain(|doc_id, _| paths.get(doc_id).is_some_and(|path| grant.allows_path(path)));
+        }
+
+        Ok(roles)
+    }
+
+    async fn document_paths(
+        &self,
+        workspace_id: Uuid,
+        doc_ids: &[Uuid],
+    ) -> anyhow::Result<HashMap<Uuid, String>> {
+        match self {
+            Self::Postgres(pool) => document_paths_pg(pool, workspace_id, doc_ids).await,
+            Self::Memory(store) => {
+                let store = store.read().await;
+                Ok(doc_ids
+                    .iter()
+                    .filter_map(|doc_id| {
+                        store.document_paths.get(doc_id).map(|path| (*doc_id, path.clone()))
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    pub(crate) async fn require_workspace_role(
+        &self,
+        workspace_id: Uuid,
+        principal: &Principal,
+        required_role: WorkspaceRole,
+    ) -> Result<WorkspaceRole, AuthzError> {
+        let Some(user_id) = principal.user_id else {
+            return Err(AuthzError::NoWorkspaceAccess);
+        };
+        if principal.token.as_ref().is_some_and(|grant| grant.workspace_id != workspace_id) {
+            return Err(AuthzError::NoWorkspaceAccess);
+        }
+        let Some(role) = self.role_for_user(workspace_id, user_id).await? else {
+            return Err(AuthzError::NoWorkspaceAccess);
+        };
+        if !role.allows(required_role) {
+            return Err(AuthzError::InsufficientRole);
+        }
+        if !principal.token_allows(workspace_id, ApiTokenScope::for_role(required_role)) {
+            return Err(AuthzError::InsufficientScope);
+        }
+        Ok(role)
+    }
+
+    /// Requires `required_role` on the document, with the token scope implied
+    /// by that role.
+    pub(crate) async fn require_document_role(
+        &self,
+        workspace_id: Uuid,
+        doc_id: Uuid,
+        principal: &Principal,
+        required_role: WorkspaceRole,
+    ) -> Result<WorkspaceRole, AuthzError> {
+        self.require_document_access(
+            workspace_id,
+            doc_id,
+            principal,
+            required_role,
+            ApiTokenScope::for_role(required_role),
+        )
+        .await
+    }
+
+    /// Requires `required_role` on the document and, for token callers,
+    /// `required_scope` (e.g. `comment` for comment mutations).
+    pub(crate) async fn require_document_access(
+        &self,
+        workspace_id: Uuid,
+        doc_id: Uuid,
+        principal: &Principal,
+        required_role: WorkspaceRole,
+        required_scope: Option<ApiTokenScope>,
+    ) -> Result<WorkspaceRole, AuthzError> {
+        let role = self.effective_role(workspace_id, doc_id, principal).await?;
+        check_document_access(workspace_id, role, principal, required_role, required_scope)
+    }
+
+    /// `require_document_role` against an effective role resolved earlier.
+    pub(crate) fn check_document_role(
+        workspace_id: Uuid,
+        role: Option<WorkspaceRole>,
+        principal: &Principal,
+        required_role: WorkspaceRole,
+    ) -> Result<WorkspaceRole, AuthzError> {
+        check_document_access(
+            workspace_id,
+            role,
+            principal,
+            required_role,
+            ApiTokenScope::for_role(required_role),
+        )
+    }
+
+    pub(crate) async fn create_acl_override(
+        &self,
+        workspace_id: Uuid,
+        doc_id: Uuid,
+        new_override: NewAclOverride,
+    ) -> anyhow::Result<AclOverride> {
+        match self {
+            Self::Postgres(pool) => {
+                create_acl_override_pg(pool, workspace_id, doc_id, new_override).await
+            }
+            Self::Memory(store) => {
+                Ok(create_acl_override_mem(store, workspace_id, doc_id, new_override).await)
+            }
+        }
+    }
+
+    /// Deletes an override; returns `false` when no matching override exists.
+    pub(crate) async fn delete_acl_override(
+        &self,
+        workspace_id: Uuid,
+        doc_id: Uuid,
+        override_id: Uuid,
+    ) -> anyhow::Result<bool> {
+        match self {
+            Self::Postgres(pool) => {
+                delete_acl_override_pg(pool, workspace_id, doc_id, override_id).await
+            }
+            Self::Memory(store) => {
+                Ok(delete_acl_override_mem(store, workspace_id, doc_id, override_id).await)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn for_tests() -> Self {
+        Self::Memory(Arc::new(RwLock::new(MemoryAccessStore::default())))
+    }
+
+    #[cfg(test)]
+    pub(crate) fn for_tests_with_bootstrap_owner() -> Self {
+        Self::Memory(Arc::new(RwLock::new(MemoryAccessStore {
+            bootstrap_first_caller_as_owner: true,
+            ..MemoryAccessStore::default()
+        })))
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn grant_for_tests(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+        role: WorkspaceRole,
+    ) {
+        if let Self::Memory(store) = self {
+            store.write().await.workspace_members.insert((workspace_id, user_id), role);
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn document_path_for_tests(&self, doc_id: Uuid, path: &str) {
+        if let Self::Memory(store) = self {
+            store.write().await.document_paths.insert(doc_id, path.to_string());
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn override_for_tests(
+        &self,
+        workspace_id: Uuid,
+        doc_id: Uuid,
+        subject_type: &str,
+        subject_id: &str,
+        role: WorkspaceRole,
+        expires_at: Option<DateTime<Utc>>,
+    ) {
+        let role = match role {
+            WorkspaceRole::Viewer => "viewer",
+            WorkspaceRole::Editor | WorkspaceRole::Owner => "editor",
+        };
+        if let Self::Memory(store) = self {
+            create_acl_override_mem(
+                store,
+                workspace_id,
+                doc_id,
+                NewAclOverride {
+                    subject_type: subject_type.to_string(),
+                    subject_id: subject_id.to_string(),
+                    role: role.to_string(),
+                    expires_at,
+                },
+            )
+            .await;
+        }
+    }
+}
+
+fn check_document_access(
+    workspace_id: Uuid,
+    role: Option<WorkspaceRole>,
+    principal: &Principal,
+    required_role: WorkspaceRole,
+    required_scope: Option<ApiTokenScope>,
+) -> Result<WorkspaceRole, AuthzError> {
+    let Some(role) = role else {
+        return Err(AuthzError::NoWorkspaceAccess);
+    };
+    if !role.allows(required_role) {
+        return Err(AuthzError::InsufficientRole);
+    }
+    if !principal.token_allows(workspace_id, required_scope) {
+        return Err(AuthzError::InsufficientScope);
+    }
+    Ok(role)
+}
+
+// ── Postgres ───────────────────────────────────────────────────────
+
+async fn workspace_role_pg(
+    pool: &PgPool,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> anyhow::Result<Option<WorkspaceRole>> {
+    sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT wm.role
+        FROM workspace_members AS wm
+        INNER JOIN workspaces AS w
+            ON w.id = wm.workspace_id
+        WHERE wm.workspace_id = $1
+          AND wm.user_id = $2
+          AND wm.status = 'active'
+          AND w.deleted_at IS NULL
+        "#,
+    )
+    .bind(workspace_id)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await
+    .context("failed to query workspace role")?
+    .map(|role| parse_role(&role, "workspace role"))
+    .transpose()
+}
+
+async fn override_roles_pg(
+    pool: &PgPool,
+    workspace_id: Uuid,
+    doc_ids: &[Uuid],
+    principal: &Principal,
+) -> anyhow::Result<HashMap<Uuid, WorkspaceRole>> {
+    let rows = sqlx::query_as::<_, (Uuid, String)>(
+        r#"
+        SELECT DISTINCT ON (doc_id) doc_id, role
+        FROM acl_overrides
+        WHERE workspace_id = $1
+          AND doc_id = ANY($2)
+          AND (
+              (subject_type = 'user' AND subject_id = $3)
+              OR (subject_type = 'agent' AND subject_id = $4)
+          )
+          AND (expires_at IS NULL OR expires_at > now())
+        ORDER BY doc_id, (subject_type = 'agent') DESC, created_at DESC
+        "#,
+    )
+    .bind(workspace_id)
+    .bind(doc_ids)
+    .bind(principal.user_id.map(|user_id| user_id.to_string()))
+    .bind(principal.agent_id.as_deref())
+    .fetch_all(pool)
+    .await
+    .context("failed to query ACL overrides")?;
+
+    rows.into_iter()
+        .map(|(doc_id, role)| Ok((doc_id, parse_role(&role, "ACL override role")?)))
+        .collect()
+}
+
+async fn document_paths_pg(
+    pool: &PgPool,
+    workspace_id: Uuid,
+    doc_ids: &[Uuid],
+) -> anyhow::Result<HashMap<Uuid, String>> {
+    let rows = sqlx::query_as::<_, (Uuid, String)>(
+        r#"
+        SELECT id, path
+        FROM documents
+        WHERE workspace_id = $1
+          AND id = ANY($2)
+        "#,
+    )
+    .bind(workspace_id)
+    .bind(doc_ids)
+    .fetch_all(pool)
+    .await
+    .context("failed to query document paths")?;
+
+    Ok(rows.into_iter().collect())
+}
+
+async fn create_acl_override_pg(
+    pool: &PgPool,
+    workspace_id: Uuid,
+    doc_id: Uuid,
+    new_override: NewAclOverride,
+) -> anyhow::Result<AclOverride> {
+    let row = sqlx::query_as::<_, AclOverrideRow>(
+        r#"
+        INSERT INTO acl_overrides (workspace_id, doc_id, subject_type, subject_id, role, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id, workspace_id, doc_id, subject_type, subject_id, role, expires_at, created_at
+        "#,
+    )
+    .bind(workspace_id)
+    .bind(doc_id)
+    .bind(new_override.subject_type)
+    .bind(new_override.subject_id)
+    .bind(new_override.role)
+    .bind(new_override.expires_at)
+    .fetch_one(pool)
+    .await
+    .context("failed to insert ACL override")?;
+
+    Ok(row.into())
+}
+
+async fn delete_acl_override_pg(
+    pool: &PgPool,
+    workspace_id: Uuid,
+    doc_id: Uuid,
+    override_id: Uuid,
+) -> anyhow::Result<bool> {
+    let deleted = sqlx::query(
+        r#"
+        DELETE FROM acl_overrides
+        WHERE id = $1 AND workspace_id = $2 AND doc_id = $3
+        "#,
+    )
+    .bind(override_id)
+    .bind(workspace_id)
+    .bind(doc_id)
+    .execute(pool)
+    .await
+    .context("failed to delete ACL override")?
+    .rows_affected();
+
+    Ok(deleted > 0)
+}
+
+// ── Memory (for testing) ───────────────────────────────────────────
+
+async fn workspace_role_mem(
+    store: &RwLock<MemoryAccessStore>,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Option<WorkspaceRole> {
+    let mut store = store.write().await;
+    if let Some(role) = store.workspace_members.get(&(workspace_id, user_id)).copied() {
+        return Some(role);
+    }
+
+    let has_members = store
+        .workspace_members
+        .keys()
+        .any(|(member_workspace_id, _)| *member_workspace_id == workspace_id);
+    if store.bootstrap_first_caller_as_owner && !has_members {
+        store.workspace_members.insert((workspace_id, user_id), WorkspaceRole::Owner);
+        return Some(WorkspaceRole::Owner);
+    }
+
+    None
+}
+
+async fn override_roles_mem(
+    store: &RwLock<MemoryAccessStore>,
+    workspace_id: Uuid,
+    doc_ids: &[Uuid],
+    principal: &Principal,
+) -> anyhow::Result<HashMap<Uuid, WorkspaceRole>> {
+    let store = store.read().await;
+    let now = Utc::now();
+    let user_subject = principal.user_id.map(|user_id| user_id.to_string());
+
+    let mut winners: HashMap<Uuid, &AclOverride> = HashMap::new();
+    for entry in store.acl_overrides.values() {
+        if entry.workspace_id != workspace_id
+            || !doc_ids.contains(&entry.doc_id)
+            || entry.expires_at.is_some_and(|expires_at| expires_at <= now)
+        {
+            continue;
+        }
+        let matches_subject = match entry.subject_type.as_str() {
+            SUBJECT_USER => user_subject.as_deref() == Some(entry.subject_id.as_str()),
+            SUBJECT_AGENT => principal.agent_id.as_deref() == Some(entry.subject_id.as_str()),
+            _ => false,
+        };
+        if !matches_subject {
+            continue;
+        }
+
+        let replace = match winners.get(&entry.doc_id) {
+            None => true,
+            Some(current) => override_precedence(entry) > override_precedence(current),
+        };
+        if replace {
+            winners.insert(entry.doc_id, entry);
+        }
+    }
+
+    winners
+        .into_iter()
+        .map(|(doc_id, entry)| Ok((doc_id, parse_role(&entry.role, "ACL override role")?)))
+        .collect()
+}
+
+fn override_precedence(entry: &AclOverride) -> (bool, DateTime<Utc>) {
+    (entry.subject_type == SUBJECT_AGENT, entry.created_at)
+}
+
+async fn create_acl_override_mem(
+    store: &RwLock<MemoryAccessStore>,
+    workspace_id: Uuid,
+    doc_id: Uuid,
+    new_override: NewAclOverride,
+) -> AclOverride {
+    let acl_override = AclOverride {
+        id: Uuid::new_v4(),
+        workspace_id,
+        doc_id,
+        subject_type: new_override.subject_type,
+        subject_id: new_override.subject_id,
+        role: new_override.role,
+        expires_at: new_override.expires_at,
+        created_at: Utc::now(),
+    };
+    store.write().await.acl_overrides.insert(acl_override.id, acl_override.clone());
+    acl_override
+}
+
+async fn delete_acl_override_mem(
+    store: &RwLock<MemoryAccessStore>,
+    workspace_id: Uuid,
+    doc_id: Uuid,
+    override_id: Uuid,
+) -> bool {
+    let mut store = store.write().await;
+    let matches = store
+        .acl_overrides
+        .get(&override_id)
+        .is_some_and(|existing| existing.workspace_id == workspace_id && existing.doc_id == doc_id);
+    if matches {
+        store.acl_overrides.remove(&override_id);
+    }
+    matches
+}
+
+fn parse_role(value: &str, kind: &str) -> anyhow::Result<WorkspaceRole> {
+    WorkspaceRole::from_db_value(value)
+        .ok_or_else(|| anyhow::anyhow!("invalid {kind} '{value}' in database"))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn non_members_have_no_effective_role_even_with_override() {
+        let authz = AuthorizationService::for_tests();
+        let (ws_id, doc_id, user_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        authz
+            .override_for_tests(
+                ws_id,
+                doc_id,
+                SUBJECT_USER,
+                &user_id.to_string(),
+                WorkspaceRole::Editor,
+                None,
+            )
+            .await;
+
+        let role = authz.effective_role(ws_id, doc_id, &Principal::user(user_id)).await.unwrap();
+        assert_eq!(role, None);
+    }
+
+    #[tokio::test]
+    async fn user_override_replaces_workspace_role_until_it_expires() {
+        let authz = AuthorizationService::for_tests();
+        let (ws_id, locked_doc, expired_doc, other_doc, user_id) =
+            (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        authz.grant_for_tests(ws_id, user_id, WorkspaceRole::Editor).await;
+        authz
+            .override_for_tests(
+                ws_id,
+                locked_doc,
+                SUBJECT_USER,
+                &user_id.to_string(),
+                WorkspaceRole::Viewer,
+                None,
+            )
+            .await;
+        authz
+            .override_for_tests(
+                ws_id,
+                expired_doc,
+                SUBJECT_USER,
+                &user_id.to_string(),
+                WorkspaceRole::Viewer,
+                Some(Utc::now() - Duration::minutes(1)),
+            )
+            .await;
+
+        let roles = authz
+            .effective_roles(
+                ws_id,
+                &[locked_doc, expired_doc, other_doc],
+                &Principal::user(user_id),
+            )
+            .await
+            .unwrap();
+        assert_eq!(roles[&locked_doc], WorkspaceRole::Viewer);
+        assert_eq!(roles[&expired_doc], WorkspaceRole::Editor);
+        assert_eq!(roles[&other_doc], WorkspaceRole::Editor);
+    }
+
+    #[tokio::test]
+    async fn agent_override_takes_precedence_over_user_override() {
+        let authz = AuthorizationService::for_tests();
+        let (ws_id, doc_id, user_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        authz.grant_for_tests(ws_id, user_id, WorkspaceRole::Editor).await;
+        authz
+            .override_for_tests(
+                ws_id,
+                doc_id,
+                SUBJECT_AGENT,
+                "claude-1",
+                WorkspaceRole::Viewer,
+                None,
+            )
+            .await;
+
+        let as_agent = Principal::agent(Some(user_id), "claude-1");
+        let denied = authz
+            .require_document_role(ws_id, doc_id, &as_agent, WorkspaceRole::Editor)
+            .await
+            .unwrap_err();
+        assert!(matches!(denied, AuthzError::InsufficientRole));
+
+        let as_user = authz
+            .require_document_role(ws_id, doc_id, &Principal::user(user_id), WorkspaceRole::Editor)
+            .await
+            .unwrap();
+        assert_eq!(as_user, WorkspaceRole::Editor);
+    }
+
+    #[tokio::test]
+    async fn api_token_is_limited_by_workspace_scope_and_path_prefix() {
+        let authz = AuthorizationService::for_tests();
+        let (ws_id, guide, secret, user_id) =
+            (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        authz.grant_for_tests(ws_id, user_id, WorkspaceRole::Owner).await;
+        authz.document_path_for_tests(guide, "docs/guide.md").await;
+        authz.document_path_for_tests(secret, "private/plan.md").await;
+
+        let mut principal = Principal::agent(Some(user_id), "ci-bot");
+        principal.token = Some(ApiTokenGrant {
+            token_id: Uuid::new_v4(),
+            workspace_id: ws_id,
+            scopes: vec![ApiTokenScope::Read, ApiTokenScope::Comment],
+            path_prefixes: vec!["docs/".into()],
+        });
+
+        let roles = authz.effective_roles(ws_id, &[guide, secret], &principal).await.unwrap();
+        assert_eq!(roles.get(&guide), Some(&WorkspaceRole::Owner));
+        assert!(!roles.contains_key(&secret));
+
+        authz
+            .require_document_role(ws_id, guide, &principal, WorkspaceRole::Viewer)
+            .await
+            .expect("read scope allows viewing");
+        authz
+            .require_document_access(
+                ws_id,
+                guide,
+                &principal,
+                WorkspaceRole::Editor,
+                Some(ApiTokenScope::Comment),
+            )
+            .await
+            .expect("comment scope allows commenting");
+        let error = authz
+            .require_document_role(ws_id, guide, &principal, WorkspaceRole::Editor)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AuthzError::InsufficientScope));
+        let error = authz
+            .require_workspace_role(ws_id, &principal, WorkspaceRole::Owner)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AuthzError::InsufficientScope));
+        let error = authz
+            .require_document_role(ws_id, secret, &principal, WorkspaceRole::Viewer)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AuthzError::NoWorkspaceAccess));
+
+        let error = authz
+            .require_workspace_role(Uuid::new_v4(), &principal, WorkspaceRole::Viewer)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AuthzError::NoWorkspaceAccess));
+    }
+
+    #[tokio::test]
+    async fn agent_without_backing_user_has_no_access() {
+        let authz = AuthorizationService::for_tests();
+        let (ws_id, doc_id) = (Uuid::new_v4(), Uuid::new_v4());
+        authz
+            .override_for_tests(ws_id, doc_id, SUBJECT_AGENT, "ci", WorkspaceRole::Editor, None)
+            .await;
+
+        let error = authz
+            .require_document_role(
+                ws_id,
+                doc_id,
+                &Principal::agent(None, "ci"),
+                WorkspaceRole::Viewer,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, AuthzError::NoWorkspaceAccess));
+    }
+}
@@ -0,0 +1,370 @@
+// External identity linking.
+//
+// Maps `(provider, subject)` pairs from SSO providers onto relay users.
+// Resolution order: an existing link wins; otherwise the identity is linked
+// to the signed-in caller (explicit linking), then to the user owning the
+// same *verified* email, and only then is a new user created.
+
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Context;
+use sqlx::{FromRow, PgPool, Postgres, Transaction};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Identity asserted by an external provider after a successful login.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalIdentity {
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub display_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, FromRow)]
+pub(crate) struct IdentityUser {
+    pub id: Uuid,
+    pub email: String,
+    pub display_name: String,
+}
+
+/// How an external identity was matched to its user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum IdentityResolution {
+    Existing,
+    LinkedToCaller,
+    LinkedByVerifiedEmail,
+    Created,
+}
+
+impl IdentityResolution {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Existing => "existing",
+            Self::LinkedToCaller => "linked_to_caller",
+            Self::LinkedByVerifiedEmail => "linked_by_verified_email",
+            Self::Created => "created",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum IdentityLinkError {
+    /// The identity is already linked to a different user.
+    LinkedToOtherUser,
+    /// An unverified email collides with an existing account.
+    EmailInUse,
+    MissingEmail,
+    UnknownCaller,
+    Internal(anyhow::Error),
+}
+
+impl From<anyhow::Error> for IdentityLinkError {
+    fn from(value: anyhow::Error) -> Self {
+        Self::Internal(value)
+    }
+}
+
+impl From<sqlx::Error> for IdentityLinkError {
+    fn from(value: sqlx::Error) -> Self {
+        Self::Internal(value.into())
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct MemoryIdentityStore {
+    users: HashMap<Uuid, IdentityUser>,
+    identities: HashMap<(String, String), Uuid>,
+}
+
+impl MemoryIdentityStore {
+    fn user_by_email(&self, email: &str) -> Option<IdentityUser> {
+        self.users.values().find(|user| user.email.eq_ignore_ascii_case(email)).cloned()
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum IdentityStore {
+    Postgres(PgPool),
+    Memory(Arc<RwLock<MemoryIdentityStore>>),
+}
+
+impl Default for IdentityStore {
+    fn default() -> Self {
+        Self::Memory(Arc::new(RwLock::new(MemoryIdentityStore::default())))
+    }
+}
+
+impl IdentityStore {
+    pub fn postgres_pool(&self) -> Option<&PgPool> {
+        match self {
+            Self::Postgres(pool) => Some(pool),
+            Self::Memory(_) => None,
+        }
+    }
+
+    /// Resolve (and if needed link or create) the user behind `identity`.
+    ///
+    /// `link_to` is the currently signed-in user, when the login was started
+    /// to attach another provider to an existing account.
+    pub async fn resolve(
+        &self,
+        identity: &ExternalIdentity,
+        link_to: Option<Uuid>,
+    ) -> Result<(IdentityUser, IdentityResolution), IdentityLinkError> {
+        match self {
+            Self::Postgres(pool) => resolve_pg(pool, identity, link_to).await,
+            Self::Memory(store) => resolve_mem(store, identity, link_to).await,
+        }
+    }
+}
+
+async fn resolve_mem(
+    store: &RwLock<MemoryIdentityStore>,
+    identity: &ExternalIdentity,
+    link_to: Option<Uuid>,
+) -> Result<(IdentityUser, IdentityResolution), IdentityLinkError> {
+    let mut guard = store.write().await;
+    let key = (identity.provider.clone(), identity.subject.clone());
+
+    if let Some(user_id) = guard.identities.get(&key).copied() {
+        if link_to.is_some_and(|caller| caller != user_id) {
+            return Err(IdentityLinkError::LinkedToOtherUser);
+        }
+        let user = guard.users.get(&user_id).cloned().context("linked user is missing")?;
+        return Ok((user, IdentityResolution::Existing));
+    }
+
+    let (user, resolution) = if let Some(caller) = link_to {
+        let user = guard.users.get(&caller).cloned().ok_or(IdentityLinkError::UnknownCaller)?;
+        (user, IdentityResolution::LinkedToCaller)
+    } else {
+        let email = identity.email.as_deref().ok_or(IdentityLinkError::MissingEmail)?;
+        match guard.user_by_email(email) {
+            Some(user) if identity.email_verified => {
+                (user, IdentityResolution::LinkedByVerifiedEmail)
+            }
+            Some(_) => return Err(IdentityLinkError::EmailInUse),
+            None => {
+                let user = IdentityUser {
+                    id: Uuid::new_v4(),
+                    email: email.to_string(),
+                    display_name: identity.display_name.clone(),
+                };
+                guard.users.insert(user.id, user.clone());
+                (user, IdentityResolution::Created)
+            }
+        }
+    };
+
+    guard.identities.insert(key, user.id);
+    Ok((user, resolution))
+}
+
+async fn resolve_pg(
+    pool: &PgPool,
+    identity: &ExternalIdentity,
+    link_to: Option<Uuid>,
+) -> Result<(IdentityUser, IdentityResolution), IdentityLinkError> {
+    let mut tx = pool.begin().await?;
+
+    let linked = sqlx::query_as::<_, IdentityUser>(
+        r#"
+        SELECT u.id, u.email::text AS email, u.display_name
+        FROM user_identities i
+        JOIN users u ON u.id = i.user_id
+        WHERE i.provider = $1 AND i.subject = $2
+        FOR UPDATE OF i
+        "#,
+    )
+    .bind(&identity.provider)
+    .bind(&identity.subject)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if let Some(user) = linked {
+        if link_to.is_some_and(|caller| caller != user.id) {
+            return Err(IdentityLinkError::LinkedToOtherUser);
+        }
+        sqlx::query(
+            r#"
+            UPDATE user_identities
+            SET last_login_at = now(), email = COALESCE($3, email)
+            WHERE provider = $1 AND subject = $2
+            "#,
+        )
+        .bind(&identity.provider)
+        .bind(&identity.subject)
+        .bind(&identity.email)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        return Ok((user, IdentityResolution::Existing));
+    }
+
+    let (user, resolution) = if let Some(caller) = link_to {
+        let user =
+            find_user_by_id_pg(&mut tx, caller).await?.ok_or(IdentityLinkError::UnknownCaller)?;
+        (user, IdentityResolution::LinkedToCaller)
+    } else {
+        let email = identity.email.as_deref().ok_or(IdentityLinkError::MissingEmail)?;
+        match find_user_by_email_pg(&mut tx, email).await? {
+            Some(user) if identity.email_verified => {
+                (user, IdentityResolution::LinkedByVerifiedEmail)
+            }
+            Some(_) => return Err(IdentityLinkError::EmailInUse),
+            None => {
+                let user = sqlx::query_as::<_, IdentityUser>(
+                    r#"
+                    INSERT INTO users (email, display_name)
+                    VALUES ($1, $2)
+                    RETURNING id, email::text AS email, display_name
+                    "#,
+                )
+                .bind(email)
+                .bind(&identity.display_name)
+                .fetch_one(&mut *tx)
+                .await?;
+                (user, IdentityResolution::Created)
+            }
+        }
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO user_identities (user_id, provider, subject, email)
+        VALUES ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(user.id)
+    .bind(&identity.provider)
+    .bind(&identity.subject)
+    .bind(&identity.email)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    Ok((user, resolution))
+}
+
+async fn find_user_by_id_pg(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<Option<IdentityUser>, sqlx::Error> {
+    sqlx::query_as::<_, IdentityUser>(
+        "SELECT id, email::text AS email, display_name FROM users WHERE id = $1",
+    )
+    .bind(user_id)
+    .fetch_optional(&mut **tx)
+    .await
+}
+
+async fn find_user_by_email_pg(
+    tx: &mut Transaction<'_, Postgres>,
+    email: &str,
+) -> Result<Option<IdentityUser>, sqlx::Error> {
+    sqlx::query_as::<_, IdentityUser>(
+        "SELECT id, email::text AS email, display_name FROM users WHERE email = $1::citext",
+    )
+    .bind(email)
+    .fetch_optional(&mut **tx)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{ExternalIdentity, IdentityLinkError, IdentityResolution, IdentityStore};
+
+    fn identity(provider: &str, subject: &str, email: &str, verified: bool) -> ExternalIdentity {
+        ExternalIdentity {
+            provider: provider.into(),
+            subject: subject.into(),
+            email: Some(email.into()),
+            email_verified: verified,
+            display_name: "Dana".into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn identities_from_multiple_providers_link_to_the_same_user() {
+        let store = IdentityStore::default();
+        let (okta_user, resolution) = store
+            .resolve(&identity("okta", "00u1", "dana@corp.example", true), None)
+            .await
+            .expect("okta login should create a user");
+        assert_eq!(resolution, IdentityResolution::Created);
+
+        let (keycloak_user, resolution) = store
+            .resolve(&identity("keycloak", "kc-7", "Dana@corp.example", true), None)
+            .await
+            .expect("verified email should link");
+        assert_eq!(resolution, IdentityResolution::LinkedByVerifiedEmail);
+        assert_eq!(keycloak_user.id, okta_user.id);
+
+        let (again, resolution) = store
+            .resolve(&identity("keycloak", "kc-7", "dana@corp.example", true), None)
+            .await
+            .expect("repeat login should resolve");
+        assert_eq!(resolution, IdentityResolution::Existing);
+        assert_eq!(again.id, okta_user.id);
+    }
+
+    #[tokio::test]
+    async fn unverified_email_never_links_to_an_existing_account() {
+        let store = IdentityStore::default();
+        store
+            .resolve(&identity("okta", "00u1", "dana@corp.example", true), None)
+            .await
+            .expect("first login");
+
+        let error = store
+            .resolve(&identity("other", "x", "dana@corp.example", false), None)
+            .await
+            .expect_err("unverified email must not take over the account");
+        assert!(matches!(error, IdentityLinkError::EmailInUse));
+    }
+
+    #[tokio::test]
+    async fn explicit_link_attaches_to_caller_and_rejects_foreign_identity() {
+        let store = IdentityStore::default();
+        let (dana, _) = store
+            .resolve(&identity("okta", "00u1", "dana@corp.example", true), None)
+            .await
+            .expect("first login");
+        let (lee, _) = store
+            .resolve(&identity("okta", "00u2", "lee@corp.example", true), None)
+            .await
+            .expect("second login");
+
+        let (linked, resolution) = store
+            .resolve(
+                &identity("keycloak", "kc-9", "dana.personal@example.com", false),
+                Some(dana.id),
+            )
+            .await
+            .expect("explicit link");
+        assert_eq!(resolution, IdentityResolution::LinkedToCaller);
+        assert_eq!(linked.id, dana.id);
+
+        let error = store
+            .resolve(
+                &identity("keycloak", "kc-9", "dana.personal@example.com", false),
+                Some(lee.id),
+            )
+            .await
+            .expect_err("identity already belongs to dana");
+        assert!(matches!(error, IdentityLinkError::LinkedToOtherUser));
+
+        let error = store
+            .resolve(
+                &identity("keycloak", "kc-10", "ghost@example.com", true),
+                Some(Uuid::new_v4()),
+            )
+            .await
+            .expect_err("unknown caller");
+        assert!(matches!(error, IdentityLinkError::UnknownCaller));
+    }
+}
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
+use crate::auth::api_tokens::ApiTokenStore;
+
 pub const ACCESS_TOKEN_TTL_SECONDS: i64 = 15 * 60;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +27,7 @@ pub struct JwtAccessTokenService {
     encoding_key: EncodingKey,
     decoding_key: DecodingKey,
     validation: Validation,
+    api_tokens: Option<ApiTokenStore>,
 }
 
 impl JwtAccessTokenService {
@@ -42,9 +45,20 @@ impl JwtAccessTokenService {
             encoding_key: EncodingKey::from_secret(secret.as_bytes()),
             decoding_key: DecodingKey::from_secret(secret.as_bytes()),
             validation,
+            api_tokens: None,
         })
     }
 
+    /// Accept `sct_…` API tokens from `store` alongside workspace JWTs.
+    pub fn with_api_token_store(mut self, store: ApiTokenStore) -> Self {
+        self.api_tokens = Some(store);
+        self
+    }
+
+    pub fn api_tokens(&self) -> Option<&ApiTokenStore> {
+        self.api_tokens.as_ref()
+    }
+
     pub fn issue_workspace_token(
         &self,
         user_id: Uuid,
@@ -1,5 +1,8 @@
 use crate::{
-    auth::jwt::{JwtAccessTokenService, WorkspaceAccess},
+    auth::{
+        api_tokens::{ApiTokenGrant, AuthenticatedApiToken, API_TOKEN_PREFIX},
+        jwt::{JwtAccessTokenService, WorkspaceAccess},
+    },
     error::{ErrorCode, RelayError},
 };
 use axum::{
@@ -14,6 +17,20 @@ use std::sync::Arc;
 pub struct AuthenticatedUser {
     pub user_id: uuid::Uuid,
     pub workspace_id: uuid::Uuid,
+    /// Agent the request acts as, set by agent-bound API tokens.
+    pub agent_id: Option<String>,
+    /// Scope restrictions when the caller used an API token instead of a session.
+    pub token: Option<ApiTokenGrant>,
+}
+
+impl AuthenticatedUser {
+    pub fn session(user_id: uuid::Uuid, workspace_id: uuid::Uuid) -> Self {
+        Self { user_id, workspace_id, agent_id: None, token: None }
+    }
+
+    pub fn is_api_token(&self) -> bool {
+        self.token.is_some()
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -53,13 +70,30 @@ pub async fn require_bearer_auth(
         None => return unauthorized_response("missing bearer token"),
     };
 
-    let WorkspaceAccess { user_id, workspace_id } =
+    let user = if token.starts_with(API_TOKEN_PREFIX) {
+        let Some(store) = jwt_service.api_tokens() else {
+            return unauthorized_response("invalid bearer token");
+        };
+        match store.authenticate(token).await {
+            Ok(Some(AuthenticatedApiToken { user_id, workspace_id, agent_id, grant })) => {
+                AuthenticatedUser { user_id, workspace_id, agent_id, token: Some(grant) }
+            }
+            Ok(None) => return unauthorized_response("invalid bearer token"),
+            Err(error) => {
+                tracing::error!(error = ?error, "failed to authenticate api token");
+                return RelayError::from_code(ErrorCode::InternalError).into_response();
+            }
+        }
+    } else {
         match jwt_service.validate_workspace_token(token) {
-            Ok(claims) => claims,
+            Ok(WorkspaceAccess { user_id, workspace_id }) => {
+                AuthenticatedUser::session(user_id, workspace_id)
+            }
             Err(_) => return unauthorized_response("invalid bearer token"),
-        };
+        }
+    };
 
-    request.extensions_mut().insert(AuthenticatedUser { user_id, workspace_id });
+    request.extensions_mut().insert(user);
 
     next.run(request).await
 }
@@ -86,7 +120,10 @@ fn unauthorized_response(message: &'static str) -> Response {
 #[cfg(test)]
 mod tests {
     use super::{require_bearer_auth, AuthenticatedUser};
-    use crate::auth::jwt::JwtAccessTokenService;
+    use crate::auth::{
+        api_tokens::{ApiTokenScope, ApiTokenStore, NewApiToken},
+        jwt::JwtAccessTokenService,
+    };
     use axum::{
         body::Body,
         extract::Extension,
@@ -174,6 +211,60 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn injects_scoped_user_for_valid_api_token() {
+        let store = ApiTokenStore::for_tests();
+        let service = Arc::new(
+            JwtAccessTokenService::new(TEST_SECRET)
+                .expect("service should initialize")
+                .with_api_token_store(store.clone()),
+        );
+        let (workspace_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (token, secret) = store
+            .create(
+                workspace_id,
+                user_id,
+                NewApiToken {
+                    name: "ci".into(),
+                    agent_id: Some("ci-bot".into()),
+                    scopes: vec![ApiTokenScope::Read],
+                    path_prefixes: Vec::new(),
+                    expires_at: None,
+                },
+            )
+            .await
+            .expect("token should be created");
+
+        let app = Router::new()
+            .route(
+                "/protected",
+                get(|Extension(user): Extension<AuthenticatedUser>| async move {
+                    format!(
+                        "{}:{}",
+                        user.agent_id.as_deref().unwrap_or_default(),
+                        user.is_api_token()
+                    )
+                }),
+            )
+            .layer(middleware::from_fn_with_state(service, require_bearer_auth));
+        let request = |bearer: &str| {
+            Request::builder()
+                .uri("/protected")
+                .header(AUTHORIZATION, format!("Bearer {bearer}"))
+                .body(Body::empty())
+                .expect("request should build")
+        };
+
+        let response = app.clone().oneshot(request(&secret)).await.expect("response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.expect("body");
+        assert_eq!(&body[..], b"ci-bot:true");
+
+        store.revoke(workspace_id, token.id).await.expect("revoke");
+        let response = app.oneshot(request(&secret)).await.expect("response");
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
     // ── Contract tests ─────────────────────────────────────────────
 
     fn load_roles_contract() -> serde_json::Value {
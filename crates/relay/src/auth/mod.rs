@@ -1,5 +1,11 @@
-// Authentication (OAuth, JWT, and auth middleware).
+// Authentication (GitHub OAuth, OIDC SSO, password accounts, JWT, scoped API
+// tokens, and auth middleware) and document authorization.
 
+pub mod api_tokens;
+pub mod authz;
+pub mod identity;
 pub mod jwt;
 pub mod middleware;
 pub mod oauth;
+pub mod oidc;
+pub mod password;
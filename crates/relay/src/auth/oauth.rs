@@ -12,13 +12,13 @@ use argon2::{
     Argon2,
 };
 use axum::{
-    extract::{Json, State},
+    extract::{Json, Path, State},
     http::{
         header::{AUTHORIZATION, RETRY_AFTER},
         HeaderMap, HeaderValue, StatusCode,
     },
     response::{IntoResponse, Response},
-    routing::post,
+    routing::{get, post},
     Router,
 };
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
@@ -26,15 +26,24 @@ use chrono::{DateTime, Duration, Utc};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use sqlx::PgPool;
 use tokio::sync::RwLock;
 use url::Url;
 use uuid::Uuid;
 
 use crate::{
-    error::{ErrorCode, RelayError},
+    audit::{AuditEventType, AuditLog, NewAuditEvent},
+    auth::identity::{ExternalIdentity, IdentityLinkError, IdentityResolution, IdentityStore},
+    auth::oidc::{AuthorizationRequest, IdentityProvider},
+    auth::password::{
+        FailedLoginOutcome, LockoutPolicy, PasswordTokenMailer, PasswordTokenPurpose, PasswordUser,
+        PasswordUserStore, UnconfiguredPasswordTokenMailer,
+    },
+    error::{current_request_id, ErrorCode, RelayError},
     validation::ValidatedJson,
 };
 
+const GITHUB_PROVIDER_ID: &str = "github";
 const DEFAULT_GITHUB_AUTH_URL: &str = "https://github.com/login/oauth/authorize";
 const DEFAULT_GITHUB_SCOPE: &str = "read:user user:email";
 const DEFAULT_GITHUB_CLIENT_ID: &str = "scriptum-dev-github-client-id";
@@ -51,6 +60,10 @@ const DEFAULT_REFRESH_TOKEN_TTL_DAYS: i64 = 30;
 const REFRESH_TOKEN_BYTES: usize = 32;
 const MIN_PASSWORD_LEN: usize = 8;
 const MAX_PASSWORD_LEN: usize = 256;
+const DEFAULT_MAX_FAILED_LOGINS: i32 = 5;
+const DEFAULT_LOCKOUT_MINUTES: i64 = 15;
+const EMAIL_VERIFICATION_TOKEN_TTL_HOURS: i64 = 48;
+const PASSWORD_RESET_TOKEN_TTL_MINUTES: i64 = 30;
 
 /// Information returned by GitHub after code exchange.
 #[derive(Debug, Clone)]
@@ -157,61 +170,22 @@ impl RefreshTokenStore {
         }
         count
     }
-}
 
-#[derive(Debug, Clone)]
-struct PasswordUser {
-    id: Uuid,
-    email: String,
-    display_name: String,
-    password_hash: String,
-}
-
-#[derive(Debug, Default)]
-struct PasswordUserStore {
-    users_by_email: RwLock<HashMap<String, PasswordUser>>,
-}
-
-impl PasswordUserStore {
-    async fn create(
-        &self,
-        email: String,
-        display_name: String,
-        password_hash: String,
-    ) -> Result<PasswordUser, RelayError> {
-        let mut guard = self.users_by_email.write().await;
-        if guard.contains_key(&email) {
-            return Err(RelayError::new(
-                ErrorCode::ValidationFailed,
-                "an account with this email already exists",
-            ));
+    /// Revoke every live session for a user (e.g. after a password reset).
+    async fn revoke_user(&self, user_id: Uuid) -> usize {
+        let session_ids = self.users.read().await.get(&user_id).cloned().unwrap_or_default();
+        let mut guard = self.sessions.write().await;
+        let now = Utc::now();
+        let mut count = 0;
+        for session_id in session_ids {
+            if let Some(session) = guard.get_mut(&session_id) {
+                if session.revoked_at.is_none() {
+                    session.revoked_at = Some(now);
+                    count += 1;
+                }
+            }
         }
-
-        let user =
-            PasswordUser { id: Uuid::new_v4(), email: email.clone(), display_name, password_hash };
-        guard.insert(email, user.clone());
-        Ok(user)
-    }
-
-    async fn find_by_email(&self, email: &str) -> Option<PasswordUser> {
-        self.users_by_email.read().await.get(email).cloned()
-    }
-
-    async fn find_by_user_id(&self, user_id: Uuid) -> Option<PasswordUser> {
-        self.users_by_email.read().await.values().find(|user| user.id == user_id).cloned()
-    }
-
-    async fn update_password_hash(
-        &self,
-        user_id: Uuid,
-        new_password_hash: String,
-    ) -> Result<(), RelayError> {
-        let mut guard = self.users_by_email.write().await;
-        let user = guard.values_mut().find(|user| user.id == user_id).ok_or_else(|| {
-            RelayError::new(ErrorCode::AuthInvalidToken, "authenticated user account not found")
-        })?;
-        user.password_hash = new_password_hash;
-        Ok(())
+        count
     }
 }
 
@@ -229,7 +203,13 @@ pub struct OAuthState {
     flow_ttl: Duration,
     github_exchange: Arc<dyn GithubExchange>,
     refresh_store: Arc<RefreshTokenStore>,
-    password_store: Arc<PasswordUserStore>,
+    password_store: PasswordUserStore,
+    identity_store: IdentityStore,
+    audit: Option<AuditLog>,
+    identity_providers: Arc<HashMap<String, Arc<dyn IdentityProvider>>>,
+    password_mailer: Arc<dyn PasswordTokenMailer>,
+    lockout_policy: LockoutPolicy,
+    require_email_verification: bool,
     jwt_secret: String,
 }
 
@@ -336,6 +316,22 @@ impl OAuthState {
                 .filter(|value| *value > 0)
                 .unwrap_or(DEFAULT_RATE_LIMIT_MAX_REQUESTS);
 
+        let max_failed_logins = env::var("SCRIPTUM_RELAY_AUTH_MAX_FAILED_LOGINS")
+            .ok()
+            .and_then(|value| value.parse::<i32>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(DEFAULT_MAX_FAILED_LOGINS);
+
+        let lockout_minutes = env::var("SCRIPTUM_RELAY_AUTH_LOCKOUT_MINUTES")
+            .ok()
+            .and_then(|value| value.parse::<i64>().ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(DEFAULT_LOCKOUT_MINUTES);
+
+        let require_email_verification = env::var("SCRIPTUM_RELAY_AUTH_REQUIRE_EMAIL_VERIFICATION")
+            .ok()
+            .is_some_and(|value| matches!(value.trim(), "1" | "true" | "yes"));
+
         Self {
             flow_store: Arc::new(OAuthFlowStore::default()),
             start_rate_limiter: Arc::new(SlidingWindowRateLimiter::new(
@@ -358,11 +354,48 @@ impl OAuthState {
             flow_ttl: Duration::minutes(flow_ttl_minutes),
             github_exchange: Arc::new(StubGithubExchange),
             refresh_store: Arc::new(RefreshTokenStore::default()),
-            password_store: Arc::new(PasswordUserStore::default()),
+            password_store: PasswordUserStore::default(),
+            identity_store: IdentityStore::default(),
+            audit: None,
+            identity_providers: Arc::new(HashMap::new()),
+            password_mailer: Arc::new(UnconfiguredPasswordTokenMailer),
+            lockout_policy: LockoutPolicy {
+                max_failed_attempts: max_failed_logins,
+                lockout: Duration::minutes(lockout_minutes),
+            },
+            require_email_verification,
             jwt_secret,
         }
     }
 
+    /// Persist password accounts and linked identities in PostgreSQL
+    /// instead of process memory.
+    pub fn with_pg_pool(mut self, pool: PgPool) -> Self {
+        self.password_store = PasswordUserStore::Postgres(pool.clone());
+        self.identity_store = IdentityStore::Postgres(pool);
+        self
+    }
+
+    /// Record password and OIDC logins in the audit log.
+    pub fn with_audit_log(mut self, audit: AuditLog) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Register SSO providers, keyed by their route id.
+    pub fn with_identity_providers(mut self, providers: Vec<Arc<dyn IdentityProvider>>) -> Self {
+        self.identity_providers = Arc::new(
+            providers.into_iter().map(|provider| (provider.id().to_string(), provider)).collect(),
+        );
+        self
+    }
+
+    /// Deliver verification and reset tokens through `mailer`.
+    pub fn with_password_mailer(mut self, mailer: Arc<dyn PasswordTokenMailer>) -> Self {
+        self.password_mailer = mailer;
+        self
+    }
+
     #[cfg(test)]
     fn for_tests(
         flow_store: Arc<OAuthFlowStore>,
@@ -406,7 +439,16 @@ impl OAuthState {
             flow_ttl: Duration::minutes(DEFAULT_FLOW_TTL_MINUTES),
             github_exchange,
             refresh_store: Arc::new(RefreshTokenStore::default()),
-            password_store: Arc::new(PasswordUserStore::default()),
+            password_store: PasswordUserStore::default(),
+            identity_store: IdentityStore::default(),
+            audit: None,
+            identity_providers: Arc::new(HashMap::new()),
+            password_mailer: Arc::new(UnconfiguredPasswordTokenMailer),
+            lockout_policy: LockoutPolicy {
+                max_failed_attempts: DEFAULT_MAX_FAILED_LOGINS,
+                lockout: Duration::minutes(DEFAULT_LOCKOUT_MINUTES),
+            },
+            require_email_verification: false,
             jwt_secret: "scriptum_test_jwt_secret_that_is_definitely_long_enough".to_string(),
         }
     }
@@ -421,9 +463,16 @@ pub fn router(state: OAuthState) -> Router {
     Router::new()
         .route("/v1/auth/oauth/github/start", post(start_github_oauth))
         .route("/v1/auth/oauth/github/callback", post(callback_github_oauth))
+        .route("/v1/auth/oidc/providers", get(list_oidc_providers))
+        .route("/v1/auth/oidc/{provider}/start", post(start_oidc_login))
+        .route("/v1/auth/oidc/{provider}/callback", post(callback_oidc_login))
         .route("/v1/auth/password/register", post(register_password_user))
         .route("/v1/auth/password/login", post(login_password_user))
         .route("/v1/auth/password/change", post(change_password))
+        .route("/v1/auth/password/verify-email", post(verify_email))
+        .route("/v1/auth/password/verify-email/resend", post(resend_email_verification))
+        .route("/v1/auth/password/reset/request", post(request_password_reset))
+        .route("/v1/auth/password/reset/confirm", post(confirm_password_reset))
         .route("/v1/auth/token/refresh", post(handle_token_refresh))
         .route("/v1/auth/logout", post(handle_logout))
         .with_state(state)
@@ -431,9 +480,13 @@ pub fn router(state: OAuthState) -> Router {
 
 #[derive(Debug, Clone)]
 pub struct OAuthFlowRecord {
+    /// Provider the flow was started for (`github` or an OIDC provider id).
+    pub provider: String,
     pub redirect_uri: String,
     pub state: String,
     pub code_challenge: String,
+    /// OIDC nonce bound into the ID token; `None` for GitHub flows.
+    pub nonce: Option<String>,
     pub expires_at: DateTime<Utc>,
 }
 
@@ -509,7 +562,7 @@ impl SlidingWindowRateLimiter {
 }
 
 #[derive(Debug, Deserialize)]
-struct OAuthGithubStartRequest {
+struct OAuthStartRequest {
     redirect_uri: String,
     state: String,
     code_challenge: String,
@@ -517,7 +570,7 @@ struct OAuthGithubStartRequest {
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-struct OAuthGithubStartResponse {
+struct OAuthStartResponse {
     flow_id: Uuid,
     authorization_url: String,
     expires_at: DateTime<Utc>,
@@ -551,8 +604,8 @@ impl IntoResponse for OAuthStartError {
 
 async fn start_github_oauth(
     State(state): State<OAuthState>,
-    ValidatedJson(payload): ValidatedJson<OAuthGithubStartRequest>,
-) -> Result<Json<OAuthGithubStartResponse>, OAuthStartError> {
+    ValidatedJson(payload): ValidatedJson<OAuthStartRequest>,
+) -> Result<Json<OAuthStartResponse>, OAuthStartError> {
     match state.start_rate_limiter.check().await {
         RateLimitDecision::Allowed => {}
         RateLimitDecision::Limited { retry_after_secs } => {
@@ -572,9 +625,11 @@ async fn start_github_oauth(
         .insert(
             flow_id,
             OAuthFlowRecord {
+                provider: GITHUB_PROVIDER_ID.to_string(),
                 redirect_uri: payload.redirect_uri.clone(),
                 state: payload.state.clone(),
                 code_challenge: payload.code_challenge.clone(),
+                nonce: None,
                 expires_at,
             },
         )
@@ -587,7 +642,7 @@ async fn start_github_oauth(
         &payload,
     )?;
 
-    Ok(Json(OAuthGithubStartResponse { flow_id, authorization_url, expires_at }))
+    Ok(Json(OAuthStartResponse { flow_id, authorization_url, expires_at }))
 }
 
 fn validate_redirect_uri(redirect_uri: &str) -> Result<(), OAuthStartError> {
@@ -677,7 +732,7 @@ fn build_authorization_url(
     authorize_base: &str,
     client_id: &str,
     scope: &str,
-    payload: &OAuthGithubStartRequest,
+    payload: &OAuthStartRequest,
 ) -> Result<String, OAuthStartError> {
     let mut url = Url::parse(authorize_base).map_err(|error| {
         tracing::error!(?error, "invalid GitHub authorize URL configuration");
@@ -732,6 +787,22 @@ struct PasswordChangeRequest {
     new_password: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct EmailVerificationRequest {
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PasswordResetRequest {
+    email: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PasswordResetConfirmRequest {
+    token: String,
+    new_password: String,
+}
+
 #[derive(Debug, Serialize)]
 struct AuthSessionResponse {
     access_token: String,
@@ -753,11 +824,11 @@ async fn register_password_user(
 
     validate_password(&payload.password)?;
     let password_hash = hash_password(&payload.password)?;
-    let user =
-        state.password_store.create(email, display_name, password_hash).await.map(|record| {
-            OAuthUser { id: record.id, email: record.email, display_name: record.display_name }
-        })?;
+    let record = state.password_store.create(email, display_name, password_hash).await?;
+    record_password_audit_event(&state, record.id, "register", None).await;
+    send_password_token(&state, &record, PasswordTokenPurpose::EmailVerification).await?;
 
+    let user = OAuthUser { id: record.id, email: record.email, display_name: record.display_name };
     Ok(Json(issue_auth_session(&state, user).await?))
 }
 
@@ -767,14 +838,54 @@ async fn login_password_user(
 ) -> Result<Json<AuthSessionResponse>, RelayError> {
     let email = normalize_email(&payload.email)?;
     let user =
-        state.password_store.find_by_email(&email).await.ok_or_else(|| {
-            RelayError::new(ErrorCode::AuthInvalidToken, "invalid email or password")
-        })?;
+        state.password_store.find_by_email(&email).await.map_err(internal_error)?.ok_or_else(
+            || RelayError::new(ErrorCode::AuthInvalidToken, "invalid email or password"),
+        )?;
+
+    if user.is_locked(Utc::now()) {
+        record_password_audit_event(&state, user.id, "login_rejected_locked", None).await;
+        return Err(account_locked_error());
+    }
 
     if !verify_password(&payload.password, &user.password_hash) {
-        return Err(RelayError::new(ErrorCode::AuthInvalidToken, "invalid email or password"));
+        let outcome = state
+            .password_store
+            .record_failed_login(user.id, state.lockout_policy)
+            .await
+            .map_err(internal_error)?;
+        return Err(match outcome {
+            FailedLoginOutcome::Counted { failed_login_count } => {
+                record_password_audit_event(
+                    &state,
+                    user.id,
+                    "login_failed",
+                    Some(serde_json::json!({ "failed_login_count": failed_login_count })),
+                )
+                .await;
+                RelayError::new(ErrorCode::AuthInvalidToken, "invalid email or password")
+            }
+            FailedLoginOutcome::Locked { locked_until } => {
+                record_password_audit_event(
+                    &state,
+                    user.id,
+                    "account_locked",
+                    Some(serde_json::json!({ "locked_until": locked_until })),
+                )
+                .await;
+                account_locked_error()
+            }
+        });
     }
 
+    if state.require_email_verification && user.email_verified_at.is_none() {
+        return Err(RelayError::new(ErrorCode::AuthForbidden, "email address is not verified"));
+    }
+
+    if user.failed_login_count > 0 {
+        state.password_store.clear_failed_logins(user.id).await.map_err(internal_error)?;
+    }
+    record_password_audit_event(&state, user.id, "login", None).await;
+
     let user = OAuthUser { id: user.id, email: user.email, display_name: user.display_name };
     Ok(Json(issue_auth_session(&state, user).await?))
 }
@@ -785,9 +896,7 @@ async fn change_password(
     ValidatedJson(payload): ValidatedJson<PasswordChangeRequest>,
 ) -> Result<StatusCode, RelayError> {
     let user_id = extract_user_id_from_bearer(&state, &headers)?;
-    let user = state.password_store.find_by_user_id(user_id).await.ok_or_else(|| {
-        RelayError::new(ErrorCode::AuthInvalidToken, "authenticated user account not found")
-    })?;
+    let user = find_authenticated_password_user(&state, user_id).await?;
 
     if !verify_password(&payload.current_password, &user.password_hash) {
         return Err(RelayError::new(ErrorCode::AuthInvalidToken, "current password is invalid"));
@@ -802,10 +911,164 @@ async fn change_password(
     }
 
     let new_hash = hash_password(&payload.new_password)?;
-    state.password_store.update_password_hash(user_id, new_hash).await?;
+    state.password_store.update_password_hash(user_id, new_hash).await.map_err(internal_error)?;
+    record_password_audit_event(&state, user_id, "password_changed", None).await;
     Ok(StatusCode::NO_CONTENT)
 }
 
+async fn verify_email(
+    State(state): State<OAuthState>,
+    ValidatedJson(payload): ValidatedJson<EmailVerificationRequest>,
+) -> Result<StatusCode, RelayError> {
+    let token_hash = Sha256::digest(payload.token.as_bytes()).to_vec();
+    let user_id = state
+        .password_store
+        .consume_token(PasswordTokenPurpose::EmailVerification, &token_hash)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| {
+            RelayError::new(ErrorCode::AuthInvalidToken, "verification token is invalid or expired")
+        })?;
+
+    state.password_store.mark_email_verified(user_id).await.map_err(internal_error)?;
+    record_password_audit_event(&state, user_id, "email_verified", None).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn resend_email_verification(
+    State(state): State<OAuthState>,
+    headers: HeaderMap,
+) -> Result<StatusCode, RelayError> {
+    let user_id = extract_user_id_from_bearer(&state, &headers)?;
+    let user = find_authenticated_password_user(&state, user_id).await?;
+    if user.email_verified_at.is_some() {
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
+    send_password_token(&state, &user, PasswordTokenPurpose::EmailVerification).await?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Always answers 202 so the endpoint cannot be used to enumerate accounts.
+async fn request_password_reset(
+    State(state): State<OAuthState>,
+    ValidatedJson(payload): ValidatedJson<PasswordResetRequest>,
+) -> Result<StatusCode, RelayError> {
+    let email = normalize_email(&payload.email)?;
+    if let Some(user) = state.password_store.find_by_email(&email).await.map_err(internal_error)? {
+        send_password_token(&state, &user, PasswordTokenPurpose::PasswordReset).await?;
+        record_password_audit_event(&state, user.id, "password_reset_requested", None).await;
+    }
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn confirm_password_reset(
+    State(state): State<OAuthState>,
+    ValidatedJson(payload): ValidatedJson<PasswordResetConfirmRequest>,
+) -> Result<StatusCode, RelayError> {
+    validate_password(&payload.new_password)?;
+    let token_hash = Sha256::digest(payload.token.as_bytes()).to_vec();
+    let user_id = state
+        .password_store
+        .consume_token(PasswordTokenPurpose::PasswordReset, &token_hash)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| {
+            RelayError::new(ErrorCode::AuthInvalidToken, "reset token is invalid or expired")
+        })?;
+
+    let new_hash = hash_password(&payload.new_password)?;
+    state.password_store.update_password_hash(user_id, new_hash).await.map_err(internal_error)?;
+    // Completing a reset proves control of the mailbox.
+    state.password_store.mark_email_verified(user_id).await.map_err(internal_error)?;
+    let revoked_sessions = state.refresh_store.revoke_user(user_id).await;
+    record_password_audit_event(
+        &state,
+        user_id,
+        "password_reset",
+        Some(serde_json::json!({ "revoked_sessions": revoked_sessions })),
+    )
+    .await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn find_authenticated_password_user(
+    state: &OAuthState,
+    user_id: Uuid,
+) -> Result<PasswordUser, RelayError> {
+    state.password_store.find_by_user_id(user_id).await.map_err(internal_error)?.ok_or_else(|| {
+        RelayError::new(ErrorCode::AuthInvalidToken, "authenticated user account not found")
+    })
+}
+
+async fn send_password_token(
+    state: &OAuthState,
+    user: &PasswordUser,
+    purpose: PasswordTokenPurpose,
+) -> Result<(), RelayError> {
+    let expires_at = Utc::now()
+        + match purpose {
+            PasswordTokenPurpose::EmailVerification => {
+                Duration::hours(EMAIL_VERIFICATION_TOKEN_TTL_HOURS)
+            }
+            PasswordTokenPurpose::PasswordReset => {
+                Duration::minutes(PASSWORD_RESET_TOKEN_TTL_MINUTES)
+            }
+        };
+    let (token, token_hash) = generate_refresh_token();
+    state
+        .password_store
+        .issue_token(user.id, purpose, token_hash, expires_at)
+        .await
+        .map_err(internal_error)?;
+    state.password_mailer.send_token(purpose, &user.email, &token).await
+}
+
+async fn record_password_audit_event(
+    state: &OAuthState,
+    user_id: Uuid,
+    action: &str,
+    details: Option<serde_json::Value>,
+) {
+    let Some(audit) = &state.audit else {
+        return;
+    };
+    let mut payload = serde_json::json!({ "action": action, "method": "password" });
+    if let (Some(target), Some(serde_json::Value::Object(extra))) =
+        (payload.as_object_mut(), details)
+    {
+        target.extend(extra);
+    }
+    let event = NewAuditEvent {
+        workspace_id: None,
+        actor_user_id: Some(user_id),
+        actor_agent_id: None,
+        event_type: AuditEventType::Auth,
+        entity_type: "user".to_owned(),
+        entity_id: user_id.to_string(),
+        request_id: current_request_id(),
+        ip_address: None,
+        user_agent: None,
+        details: Some(payload),
+    };
+
+    if let Err(error) = audit.record(event).await {
+        tracing::warn!(error = ?error, "failed to record password auth audit event");
+    }
+}
+
+fn account_locked_error() -> RelayError {
+    RelayError::new(
+        ErrorCode::RateLimited,
+        "account is temporarily locked after repeated failed logins",
+    )
+}
+
+fn internal_error(error: anyhow::Error) -> RelayError {
+    tracing::error!(error = ?error, "password account store failed");
+    RelayError::from_code(ErrorCode::InternalError)
+}
+
 async fn issue_auth_session(
     state: &OAuthState,
     user: OAuthUser,
@@ -906,7 +1169,7 @@ fn verify_password(password: &str, password_hash: &str) -> bool {
 // ─── OAuth Callback ────────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
-struct OAuthGithubCallbackRequest {
+struct OAuthCallbackRequest {
     flow_id: Uuid,
     code: String,
     state: String,
@@ -943,7 +1206,7 @@ impl From<RelayError> for OAuthCallbackError {
 
 async fn callback_github_oauth(
     State(state): State<OAuthState>,
-    ValidatedJson(payload): ValidatedJson<OAuthGithubCallbackRequest>,
+    ValidatedJson(payload): ValidatedJson<OAuthCallbackRequest>,
 ) -> Result<Json<AuthSessionResponse>, OAuthCallbackError> {
     match state.callback_rate_limiter.check().await {
         RateLimitDecision::Allowed => {}
@@ -953,10 +1216,12 @@ async fn callback_github_oauth(
     }
 
     // 1. Consume the flow (one-time use)
-    let flow =
-        state.flow_store.take(payload.flow_id).await.ok_or_else(|| {
-            RelayError::new(ErrorCode::AuthCodeInvalid, "unknown or expired flow")
-        })?;
+    let flow = state
+        .flow_store
+        .take(payload.flow_id)
+        .await
+        .filter(|flow| flow.provider == GITHUB_PROVIDER_ID)
+        .ok_or_else(|| RelayError::new(ErrorCode::AuthCodeInvalid, "unknown or expired flow"))?;
 
     // 2. Verify state matches
     if flow.state != payload.state {
@@ -1014,6 +1279,179 @@ fn generate_refresh_token() -> (String, Vec<u8>) {
     (token, hash)
 }
 
+// ─── OIDC ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Serialize)]
+struct OidcProviderSummary {
+    id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct OidcProvidersResponse {
+    providers: Vec<OidcProviderSummary>,
+}
+
+async fn list_oidc_providers(State(state): State<OAuthState>) -> Json<OidcProvidersResponse> {
+    let mut providers: Vec<_> =
+        state.identity_providers.keys().map(|id| OidcProviderSummary { id: id.clone() }).collect();
+    providers.sort_by(|left, right| left.id.cmp(&right.id));
+    Json(OidcProvidersResponse { providers })
+}
+
+fn identity_provider(
+    state: &OAuthState,
+    provider_id: &str,
+) -> Result<Arc<dyn IdentityProvider>, RelayError> {
+    state
+        .identity_providers
+        .get(provider_id)
+        .cloned()
+        .ok_or_else(|| RelayError::new(ErrorCode::NotFound, "unknown identity provider"))
+}
+
+async fn start_oidc_login(
+    State(state): State<OAuthState>,
+    Path(provider_id): Path<String>,
+    ValidatedJson(payload): ValidatedJson<OAuthStartRequest>,
+) -> Result<Json<OAuthStartResponse>, OAuthStartError> {
+    match state.start_rate_limiter.check().await {
+        RateLimitDecision::Allowed => {}
+        RateLimitDecision::Limited { retry_after_secs } => {
+            return Err(OAuthStartError::RateLimited { retry_after_secs });
+        }
+    }
+
+    let provider = identity_provider(&state, &provider_id)?;
+    validate_redirect_uri(&payload.redirect_uri)?;
+    validate_state(&payload.state)?;
+    validate_code_challenge_method(&payload.code_challenge_method)?;
+    validate_code_challenge(&payload.code_challenge)?;
+
+    let (nonce, _) = generate_refresh_token();
+    let authorization_url = provider
+        .authorization_url(AuthorizationRequest {
+            redirect_uri: &payload.redirect_uri,
+            state: &payload.state,
+            code_challenge: &payload.code_challenge,
+            nonce: &nonce,
+        })
+        .await?;
+
+    let flow_id = Uuid::new_v4();
+    let expires_at = Utc::now() + state.flow_ttl;
+    state
+        .flow_store
+        .insert(
+            flow_id,
+            OAuthFlowRecord {
+                provider: provider_id,
+                redirect_uri: payload.redirect_uri,
+                state: payload.state,
+                code_challenge: payload.code_challenge,
+                nonce: Some(nonce),
+                expires_at,
+            },
+        )
+        .await;
+
+    Ok(Json(OAuthStartResponse { flow_id, authorization_url, expires_at }))
+}
+
+/// Completes an OIDC login. With a bearer token the identity is linked to
+/// the signed-in user instead of resolving a user from the ID token.
+async fn callback_oidc_login(
+    State(state): State<OAuthState>,
+    Path(provider_id): Path<String>,
+    headers: HeaderMap,
+    ValidatedJson(payload): ValidatedJson<OAuthCallbackRequest>,
+) -> Result<Json<AuthSessionResponse>, OAuthCallbackError> {
+    match state.callback_rate_limiter.check().await {
+        RateLimitDecision::Allowed => {}
+        RateLimitDecision::Limited { retry_after_secs } => {
+            return Err(OAuthCallbackError::RateLimited { retry_after_secs });
+        }
+    }
+
+    let provider = identity_provider(&state, &provider_id)?;
+    let link_to = match headers.get(AUTHORIZATION) {
+        Some(_) => Some(extract_user_id_from_bearer(&state, &headers)?),
+        None => None,
+    };
+
+    let flow = state
+        .flow_store
+        .take(payload.flow_id)
+        .await
+        .filter(|flow| flow.provider == provider_id)
+        .ok_or_else(|| RelayError::new(ErrorCode::AuthCodeInvalid, "unknown or expired flow"))?;
+    if flow.state != payload.state {
+        return Err(RelayError::from_code(ErrorCode::AuthStateMismatch).into());
+    }
+    verify_pkce_s256(&payload.code_verifier, &flow.code_challenge)?;
+    let nonce = flow.nonce.as_deref().ok_or_else(|| {
+        RelayError::new(ErrorCode::AuthCodeInvalid, "flow was not started for OIDC")
+    })?;
+
+    let identity = provider
+        .authenticate(&payload.code, &payload.code_verifier, &flow.redirect_uri, nonce)
+        .await?;
+    let (user, resolution) =
+        state.identity_store.resolve(&identity, link_to).await.map_err(|error| match error {
+            IdentityLinkError::LinkedToOtherUser => RelayError::new(
+                ErrorCode::AuthForbidden,
+                "identity is already linked to another account",
+            ),
+            IdentityLinkError::EmailInUse => RelayError::new(
+                ErrorCode::AuthForbidden,
+                "an account with this email already exists; sign in and link this provider",
+            ),
+            IdentityLinkError::MissingEmail => RelayError::new(
+                ErrorCode::AuthInvalidToken,
+                "identity provider did not supply an email address",
+            ),
+            IdentityLinkError::UnknownCaller => {
+                RelayError::new(ErrorCode::AuthInvalidToken, "authenticated user account not found")
+            }
+            IdentityLinkError::Internal(error) => internal_error(error),
+        })?;
+
+    record_oidc_audit_event(&state, user.id, &identity, resolution).await;
+    let user = OAuthUser { id: user.id, email: user.email, display_name: user.display_name };
+    Ok(Json(issue_auth_session(&state, user).await?))
+}
+
+async fn record_oidc_audit_event(
+    state: &OAuthState,
+    user_id: Uuid,
+    identity: &ExternalIdentity,
+    resolution: IdentityResolution,
+) {
+    let Some(audit) = &state.audit else {
+        return;
+    };
+    let event = NewAuditEvent {
+        workspace_id: None,
+        actor_user_id: Some(user_id),
+        actor_agent_id: None,
+        event_type: AuditEventType::Auth,
+        entity_type: "user".to_owned(),
+        entity_id: user_id.to_string(),
+        request_id: current_request_id(),
+        ip_address: None,
+        user_agent: None,
+        details: Some(serde_json::json!({
+            "action": "login",
+            "method": "oidc",
+            "provider": identity.provider,
+            "resolution": resolution.as_str(),
+        })),
+    };
+
+    if let Err(error) = audit.record(event).await {
+        tracing::warn!(error = ?error, "failed to record OIDC audit event");
+    }
+}
+
 // ─── Token Refresh ─────────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
@@ -1139,7 +1577,15 @@ mod tests {
     use std::future::Future;
     use std::pin::Pin;
 
-    use crate::{auth::jwt::JwtAccessTokenService, error::RelayError};
+    use crate::{
+        auth::{
+            jwt::JwtAccessTokenService,
+            oidc::{mock_idp, mock_idp::MockIdp, IdentityProvider, OidcProvider},
+            password::{PasswordTokenMailer, PasswordTokenPurpose},
+        },
+        config::OidcProviderConfig,
+        error::RelayError,
+    };
 
     const TEST_JWT_SECRET: &str = "scriptum_test_jwt_secret_that_is_definitely_long_enough";
 
@@ -1366,9 +1812,11 @@ mod tests {
             .insert(
                 flow_id,
                 OAuthFlowRecord {
+                    provider: "github".to_string(),
                     redirect_uri: "https://app.scriptum.dev/callback".to_string(),
                     state: "test-state-abc".to_string(),
                     code_challenge: code_challenge.to_string(),
+                    nonce: None,
                     expires_at: Utc::now() + Duration::minutes(10),
                 },
             )
@@ -1488,6 +1936,170 @@ mod tests {
         assert_eq!(second.status(), StatusCode::UNAUTHORIZED);
     }
 
+    async fn oidc_test_router(idp: &MockIdp) -> axum::Router {
+        let provider = |id: &str| {
+            Arc::new(OidcProvider::new(OidcProviderConfig {
+                id: id.to_string(),
+                issuer: idp.issuer.clone(),
+                client_id: mock_idp::CLIENT_ID.to_string(),
+                client_secret: "mock-secret".to_string(),
+                scopes: "openid email profile".to_string(),
+                email_claim: "email".to_string(),
+                name_claim: "name".to_string(),
+            })) as Arc<dyn IdentityProvider>
+        };
+        let state = OAuthState::for_tests(
+            Arc::new(OAuthFlowStore::default()),
+            100,
+            StdDuration::from_secs(60),
+        )
+        .with_identity_providers(vec![provider("okta"), provider("keycloak")]);
+        router(state)
+    }
+
+    /// Runs start + callback against the mock IdP and returns the response body.
+    async fn oidc_login(
+        app: &axum::Router,
+        idp: &MockIdp,
+        provider: &str,
+        subject: &str,
+        email: &str,
+    ) -> Value {
+        let (code_verifier, code_challenge) = make_pkce_pair();
+        let start = app
+            .clone()
+            .oneshot(start_request_to(
+                &format!("/v1/auth/oidc/{provider}/start"),
+                json!({
+                    "redirect_uri": "https://app.scriptum.dev/callback",
+                    "state": "oidc-state",
+                    "code_challenge": code_challenge,
+                    "code_challenge_method": "S256"
+                }),
+            ))
+            .await
+            .expect("start should return");
+        assert_eq!(start.status(), StatusCode::OK);
+        let start: Value = serde_json::from_slice(
+            &to_bytes(start.into_body(), usize::MAX).await.expect("start body"),
+        )
+        .expect("start json");
+        let authorization_url =
+            Url::parse(start["authorization_url"].as_str().expect("authorization_url"))
+                .expect("authorization url should parse");
+        let nonce = authorization_url
+            .query_pairs()
+            .find(|(key, _)| key == "nonce")
+            .map(|(_, value)| value.into_owned())
+            .expect("nonce should be present");
+        idp.set_claims(json!({
+            "sub": subject,
+            "email": email,
+            "email_verified": true,
+            "name": "Dana SSO",
+            "nonce": nonce,
+        }));
+
+        let callback = app
+            .clone()
+            .oneshot(start_request_to(
+                &format!("/v1/auth/oidc/{provider}/callback"),
+                json!({
+                    "flow_id": start["flow_id"],
+                    "code": mock_idp::CODE,
+                    "state": "oidc-state",
+                    "code_verifier": code_verifier
+                }),
+            ))
+            .await
+            .expect("callback should return");
+        assert_eq!(callback.status(), StatusCode::OK);
+        serde_json::from_slice(&to_bytes(callback.into_body(), usize::MAX).await.expect("body"))
+            .expect("callback json")
+    }
+
+    fn start_request_to(uri: &str, payload: Value) -> Request<Body> {
+        Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(payload.to_string()))
+            .expect("request should build")
+    }
+
+    #[tokio::test]
+    async fn oidc_login_against_mock_idp_links_providers_by_verified_email() {
+        let idp = MockIdp::start().await;
+        let app = oidc_test_router(&idp).await;
+
+        let okta = oidc_login(&app, &idp, "okta", "00u-dana", "dana@corp.example").await;
+        assert!(okta["access_token"].as_str().is_some_and(|token| !token.is_empty()));
+        assert_eq!(okta["user"]["email"], "dana@corp.example");
+        assert_eq!(okta["user"]["display_name"], "Dana SSO");
+
+        let keycloak = oidc_login(&app, &idp, "keycloak", "kc-dana", "dana@corp.example").await;
+        assert_eq!(keycloak["user"]["id"], okta["user"]["id"]);
+
+        let providers = app
+            .oneshot(
+                Request::builder()
+                    .uri("/v1/auth/oidc/providers")
+                    .body(Body::empty())
+                    .expect("request should build"),
+            )
+            .await
+            .expect("providers request should return");
+        let providers: Value = serde_json::from_slice(
+            &to_bytes(providers.into_body(), usize::MAX).await.expect("providers body"),
+        )
+        .expect("providers json");
+        assert_eq!(providers["providers"], json!([{ "id": "keycloak" }, { "id": "okta" }]));
+    }
+
+    #[tokio::test]
+    async fn oidc_routes_reject_unknown_providers_and_foreign_flows() {
+        let idp = MockIdp::start().await;
+        let app = oidc_test_router(&idp).await;
+        let (code_verifier, code_challenge) = make_pkce_pair();
+        let start_payload = json!({
+            "redirect_uri": "https://app.scriptum.dev/callback",
+            "state": "oidc-state",
+            "code_challenge": code_challenge,
+            "code_challenge_method": "S256"
+        });
+
+        let unknown = app
+            .clone()
+            .oneshot(start_request_to("/v1/auth/oidc/unknown/start", start_payload.clone()))
+            .await
+            .expect("start should return");
+        assert_eq!(unknown.status(), StatusCode::NOT_FOUND);
+
+        let start = app
+            .clone()
+            .oneshot(start_request_to("/v1/auth/oidc/okta/start", start_payload))
+            .await
+            .expect("start should return");
+        let start: Value = serde_json::from_slice(
+            &to_bytes(start.into_body(), usize::MAX).await.expect("start body"),
+        )
+        .expect("start json");
+
+        let cross_provider = app
+            .oneshot(start_request_to(
+                "/v1/auth/oidc/keycloak/callback",
+                json!({
+                    "flow_id": start["flow_id"],
+                    "code": mock_idp::CODE,
+                    "state": "oidc-state",
+                    "code_verifier": code_verifier
+                }),
+            ))
+            .await
+            .expect("callback should return");
+        assert_eq!(cross_provider.status(), StatusCode::UNAUTHORIZED);
+    }
+
     #[tokio::test]
     async fn callback_rate_limits_and_sets_retry_after_header() {
         let flow_store = Arc::new(OAuthFlowStore::default());
@@ -1659,6 +2271,174 @@ mod tests {
         assert_eq!(new_login.status(), StatusCode::OK);
     }
 
+    #[derive(Default)]
+    struct CapturingMailer {
+        sent: std::sync::Mutex<Vec<(PasswordTokenPurpose, String, String)>>,
+    }
+
+    impl CapturingMailer {
+        fn last_token(&self, purpose: PasswordTokenPurpose) -> String {
+            self.sent
+                .lock()
+                .expect("mailer lock")
+                .iter()
+                .rev()
+                .find(|(sent_purpose, _, _)| *sent_purpose == purpose)
+                .map(|(_, _, token)| token.clone())
+                .expect("a token should have been sent")
+        }
+    }
+
+    impl PasswordTokenMailer for CapturingMailer {
+        fn send_token(
+            &self,
+            purpose: PasswordTokenPurpose,
+            email: &str,
+            token: &str,
+        ) -> Pin<Box<dyn Future<Output = Result<(), RelayError>> + Send>> {
+            self.sent.lock().expect("mailer lock").push((
+                purpose,
+                email.to_string(),
+                token.to_string(),
+            ));
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    fn test_password_router() -> (axum::Router, Arc<CapturingMailer>) {
+        let mailer = Arc::new(CapturingMailer::default());
+        let state = OAuthState::for_tests(
+            Arc::new(OAuthFlowStore::default()),
+            10,
+            StdDuration::from_secs(60),
+        )
+        .with_password_mailer(mailer.clone());
+        (router(state), mailer)
+    }
+
+    fn json_post(uri: &str, payload: Value) -> Request<Body> {
+        Request::builder()
+            .method(Method::POST)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(payload.to_string()))
+            .expect("request should build")
+    }
+
+    async fn register_password_account(app: &axum::Router, email: &str) {
+        let response = app
+            .clone()
+            .oneshot(password_register_request(json!({
+                "email": email,
+                "display_name": "Password User",
+                "password": "Sup3rSecurePass!"
+            })))
+            .await
+            .expect("register request should return");
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn password_register_sends_single_use_email_verification_token() {
+        let (app, mailer) = test_password_router();
+        register_password_account(&app, "verify@example.com").await;
+        let token = mailer.last_token(PasswordTokenPurpose::EmailVerification);
+
+        let verify = app
+            .clone()
+            .oneshot(json_post("/v1/auth/password/verify-email", json!({ "token": token })))
+            .await
+            .expect("verify request should return");
+        assert_eq!(verify.status(), StatusCode::NO_CONTENT);
+
+        let replay = app
+            .oneshot(json_post("/v1/auth/password/verify-email", json!({ "token": token })))
+            .await
+            .expect("replayed verify request should return");
+        assert_eq!(replay.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn password_reset_flow_replaces_password_and_token_is_single_use() {
+        let (app, mailer) = test_password_router();
+        register_password_account(&app, "reset@example.com").await;
+
+        let unknown = app
+            .clone()
+            .oneshot(json_post(
+                "/v1/auth/password/reset/request",
+                json!({ "email": "nobody@example.com" }),
+            ))
+            .await
+            .expect("reset request should return");
+        assert_eq!(unknown.status(), StatusCode::ACCEPTED);
+
+        let request = app
+            .clone()
+            .oneshot(json_post(
+                "/v1/auth/password/reset/request",
+                json!({ "email": "Reset@Example.com" }),
+            ))
+            .await
+            .expect("reset request should return");
+        assert_eq!(request.status(), StatusCode::ACCEPTED);
+        let token = mailer.last_token(PasswordTokenPurpose::PasswordReset);
+
+        let confirm_payload = json!({ "token": token, "new_password": "R3setSecurePass!" });
+        let confirm = app
+            .clone()
+            .oneshot(json_post("/v1/auth/password/reset/confirm", confirm_payload.clone()))
+            .await
+            .expect("reset confirm should return");
+        assert_eq!(confirm.status(), StatusCode::NO_CONTENT);
+
+        let replay = app
+            .clone()
+            .oneshot(json_post("/v1/auth/password/reset/confirm", confirm_payload))
+            .await
+            .expect("replayed reset confirm should return");
+        assert_eq!(replay.status(), StatusCode::UNAUTHORIZED);
+
+        let login = app
+            .oneshot(password_login_request(json!({
+                "email": "reset@example.com",
+                "password": "R3setSecurePass!"
+            })))
+            .await
+            .expect("login request should return");
+        assert_eq!(login.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn password_login_locks_account_after_repeated_failures() {
+        let (app, _) = test_password_router();
+        register_password_account(&app, "locked@example.com").await;
+
+        let mut statuses = Vec::new();
+        for _ in 0..5 {
+            let response = app
+                .clone()
+                .oneshot(password_login_request(json!({
+                    "email": "locked@example.com",
+                    "password": "wrong-password"
+                })))
+                .await
+                .expect("login request should return");
+            statuses.push(response.status());
+        }
+        assert_eq!(&statuses[..4], &[StatusCode::UNAUTHORIZED; 4]);
+        assert_eq!(statuses[4], StatusCode::TOO_MANY_REQUESTS);
+
+        let correct = app
+            .oneshot(password_login_request(json!({
+                "email": "locked@example.com",
+                "password": "Sup3rSecurePass!"
+            })))
+            .await
+            .expect("login request should return");
+        assert_eq!(correct.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
     #[test]
     fn pkce_verification_works_for_valid_pair() {
         let (verifier, challenge) = make_pkce_pair();
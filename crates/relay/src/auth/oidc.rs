@@ -0,0 +1,518 @@
+// Generic OpenID Connect login.
+//
+// `IdentityProvider` is the provider abstraction used by the `/v1/auth/oidc`
+// routes; `OidcProvider` implements it for any standards-compliant IdP
+// (Okta, Keycloak, ...) using the discovery document, the authorization code
+// flow with PKCE, and ID-token validation against the provider's JWKS.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration as StdDuration, Instant},
+};
+
+use jsonwebtoken::{decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tokio::sync::RwLock;
+use url::Url;
+
+use crate::{
+    auth::identity::ExternalIdentity,
+    config::OidcProviderConfig,
+    error::{ErrorCode, RelayError},
+};
+
+const DISCOVERY_PATH: &str = "/.well-known/openid-configuration";
+const DISCOVERY_TTL: StdDuration = StdDuration::from_secs(60 * 60);
+const HTTP_TIMEOUT: StdDuration = StdDuration::from_secs(10);
+/// Clock skew tolerated when checking `exp`/`nbf`.
+const ID_TOKEN_LEEWAY_SECS: u64 = 60;
+/// Asymmetric algorithms only; HMAC would let anyone holding the client
+/// secret mint ID tokens.
+const ALLOWED_ID_TOKEN_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+/// Parameters for building a provider authorization URL.
+#[derive(Debug, Clone, Copy)]
+pub struct AuthorizationRequest<'a> {
+    pub redirect_uri: &'a str,
+    pub state: &'a str,
+    pub code_challenge: &'a str,
+    pub nonce: &'a str,
+}
+
+/// Login provider abstraction used by the SSO routes.
+/// Boxed futures keep the trait object-safe (same pattern as `GithubExchange`).
+pub trait IdentityProvider: Send + Sync {
+    fn id(&self) -> &str;
+
+    fn authorization_url<'a>(
+        &'a self,
+        request: AuthorizationRequest<'a>,
+    ) -> Pin<Box<dyn Future<Output = Result<String, RelayError>> + Send + 'a>>;
+
+    /// Exchange an authorization code and return the validated identity.
+    fn authenticate<'a>(
+        &'a self,
+        code: &'a str,
+        code_verifier: &'a str,
+        redirect_uri: &'a str,
+        nonce: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<ExternalIdentity, RelayError>> + Send + 'a>>;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct OidcDiscovery {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OidcTokenResponse {
+    id_token: Option<String>,
+}
+
+pub struct OidcProvider {
+    config: OidcProviderConfig,
+    http: reqwest::Client,
+    discovery: RwLock<Option<(OidcDiscovery, Instant)>>,
+    jwks: RwLock<Option<JwkSet>>,
+}
+
+impl OidcProvider {
+    pub fn new(config: OidcProviderConfig) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(HTTP_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self { config, http, discovery: RwLock::new(None), jwks: RwLock::new(None) }
+    }
+
+    pub fn from_configs(configs: &[OidcProviderConfig]) -> Vec<Arc<dyn IdentityProvider>> {
+        configs
+            .iter()
+            .cloned()
+            .map(|config| Arc::new(Self::new(config)) as Arc<dyn IdentityProvider>)
+            .collect()
+    }
+
+    async fn discovery(&self) -> Result<OidcDiscovery, RelayError> {
+        if let Some((discovery, fetched_at)) = self.discovery.read().await.as_ref() {
+            if fetched_at.elapsed() < DISCOVERY_TTL {
+                return Ok(discovery.clone());
+            }
+        }
+
+        let url = format!("{}{DISCOVERY_PATH}", self.config.issuer.trim_end_matches('/'));
+        let discovery: OidcDiscovery = self.get_json(&url).await?;
+        if discovery.issuer.trim_end_matches('/') != self.config.issuer.trim_end_matches('/') {
+            tracing::error!(
+                provider = %self.config.id,
+                advertised = %discovery.issuer,
+                "OIDC discovery issuer does not match configured issuer"
+            );
+            return Err(RelayError::from_code(ErrorCode::InternalError));
+        }
+
+        *self.discovery.write().await = Some((discovery.clone(), Instant::now()));
+        Ok(discovery)
+    }
+
+    /// JWKS lookup with one forced refresh when `kid` is unknown (key rotation).
+    async fn decoding_key(&self, kid: Option<&str>) -> Result<DecodingKey, RelayError> {
+        for refresh in [false, true] {
+            if refresh || self.jwks.read().await.is_none() {
+                let discovery = self.discovery().await?;
+                let jwks: JwkSet = self.get_json(&discovery.jwks_uri).await?;
+                *self.jwks.write().await = Some(jwks);
+            }
+
+            let guard = self.jwks.read().await;
+            let jwk = guard.as_ref().and_then(|jwks| match kid {
+                Some(kid) => jwks.find(kid),
+                None if jwks.keys.len() == 1 => jwks.keys.first(),
+                None => None,
+            });
+            if let Some(jwk) = jwk {
+                return DecodingKey::from_jwk(jwk).map_err(|_| invalid_id_token("unusable JWK"));
+            }
+        }
+        Err(invalid_id_token("ID token signing key is not in the provider JWKS"))
+    }
+
+    async fn exchange_code(
+        &self,
+        code: &str,
+        code_verifier: &str,
+        redirect_uri: &str,
+    ) -> Result<String, RelayError> {
+        let discovery = self.discovery().await?;
+        let response = self
+            .http
+            .post(&discovery.token_endpoint)
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri),
+                ("code_verifier", code_verifier),
+                ("client_id", self.config.client_id.as_str()),
+                ("client_secret", self.config.client_secret.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(|error| self.upstream_error("token request failed", error))?;
+
+        if !response.status().is_success() {
+            return Err(RelayError::new(
+                ErrorCode::AuthCodeInvalid,
+                "identity provider rejected the authorization code",
+            ));
+        }
+
+        let tokens: OidcTokenResponse = response
+            .json()
+            .await
+            .map_err(|error| self.upstream_error("invalid token response", error))?;
+        tokens.id_token.ok_or_else(|| invalid_id_token("token response has no id_token"))
+    }
+
+    async fn validate_id_token(
+        &self,
+        id_token: &str,
+        expected_nonce: &str,
+    ) -> Result<Map<String, Value>, RelayError> {
+        let header = decode_header(id_token).map_err(|_| invalid_id_token("malformed ID token"))?;
+        if !ALLOWED_ID_TOKEN_ALGORITHMS.contains(&header.alg) {
+            return Err(invalid_id_token("ID token uses a disallowed signing algorithm"));
+        }
+
+        let key = self.decoding_key(header.kid.as_deref()).await?;
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[self.config.issuer.as_str()]);
+        validation.set_audience(&[self.config.client_id.as_str()]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+        validation.leeway = ID_TOKEN_LEEWAY_SECS;
+
+        let claims = decode::<Map<String, Value>>(id_token, &key, &validation)
+            .map_err(|_| invalid_id_token("ID token failed validation"))?
+            .claims;
+
+        if claims.get("nonce").and_then(Value::as_str) != Some(expected_nonce) {
+            return Err(invalid_id_token("ID token nonce mismatch"));
+        }
+        Ok(claims)
+    }
+
+    /// Map validated ID-token claims onto an identity using the configured claim names.
+    fn map_claims(&self, claims: &Map<String, Value>) -> Result<ExternalIdentity, RelayError> {
+        let subject = claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .filter(|sub| !sub.is_empty())
+            .ok_or_else(|| invalid_id_token("ID token has no subject"))?;
+        let email = claims
+            .get(&self.config.email_claim)
+            .and_then(Value::as_str)
+            .map(|email| email.trim().to_lowercase())
+            .filter(|email| email.contains('@'));
+        let email_verified = match claims.get("email_verified") {
+            Some(Value::Bool(verified)) => *verified,
+            Some(Value::String(verified)) => verified == "true",
+            _ => false,
+        };
+        let display_name = [self.config.name_claim.as_str(), "preferred_username"]
+            .iter()
+            .find_map(|claim| claims.get(*claim).and_then(Value::as_str))
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .or_else(|| email.clone())
+            .unwrap_or_else(|| subject.to_string());
+
+        Ok(ExternalIdentity {
+            provider: self.config.id.clone(),
+            subject: subject.to_string(),
+            email,
+            email_verified,
+            display_name,
+        })
+    }
+
+    async fn get_json<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T, RelayError> {
+        let response = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|error| self.upstream_error("request failed", error))?;
+        response.json().await.map_err(|error| self.upstream_error("invalid JSON", error))
+    }
+
+    fn upstream_error(&self, context: &str, error: reqwest::Error) -> RelayError {
+        tracing::warn!(provider = %self.config.id, ?error, "OIDC provider {context}");
+        RelayError::new(ErrorCode::InternalError, "identity provider is unavailable")
+    }
+}
+
+impl IdentityProvider for OidcProvider {
+    fn id(&self) -> &str {
+        &self.config.id
+    }
+
+    fn authorization_url<'a>(
+        &'a self,
+        request: AuthorizationRequest<'a>,
+    ) -> Pin<Box<dyn Future<Output = Result<String, RelayError>> + Send + 'a>> {
+        Box::pin(async move {
+            let discovery = self.discovery().await?;
+            let mut url = Url::parse(&discovery.authorization_endpoint).map_err(|error| {
+                tracing::error!(provider = %self.config.id, ?error, "invalid authorization_endpoint");
+                RelayError::from_code(ErrorCode::InternalError)
+            })?;
+            url.query_pairs_mut()
+                .append_pair("client_id", &self.config.client_id)
+                .append_pair("redirect_uri", request.redirect_uri)
+                .append_pair("response_type", "code")
+                .append_pair("scope", &self.config.scopes)
+                .append_pair("state", request.state)
+                .append_pair("nonce", request.nonce)
+                .append_pair("code_challenge", request.code_challenge)
+                .append_pair("code_challenge_method", "S256");
+            Ok(url.to_string())
+        })
+    }
+
+    fn authenticate<'a>(
+        &'a self,
+        code: &'a str,
+        code_verifier: &'a str,
+        redirect_uri: &'a str,
+        nonce: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<ExternalIdentity, RelayError>> + Send + 'a>> {
+        Box::pin(async move {
+            let id_token = self.exchange_code(code, code_verifier, redirect_uri).await?;
+            let claims = self.validate_id_token(&id_token, nonce).await?;
+            self.map_claims(&claims)
+        })
+    }
+}
+
+fn invalid_id_token(message: &'static str) -> RelayError {
+    RelayError::new(ErrorCode::AuthInvalidToken, message)
+}
+
+/// Minimal in-process OpenID provider for tests: discovery, JWKS and a token
+/// endpoint that signs RS256 ID tokens with a fixed test key.
+#[cfg(test)]
+pub(crate) mod mock_idp {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{
+        extract::{Form, State},
+        http::StatusCode,
+        routing::{get, post},
+        Json, Router,
+    };
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+    use serde_json::{json, Map, Value};
+
+    pub const CLIENT_ID: &str = "scriptum-test-client";
+    pub const CODE: &str = "mock-authorization-code";
+    const KID: &str = "mock-key-1";
+    const RSA_PRIVATE_KEY_PEM: &str = include_str!("testdata/oidc_rsa_test_key.pem");
+    const RSA_MODULUS: &str = "tgm1taopx7jk13cayHkk31XvmZWQM70xsgjUDgWS-BUQFHfTHT0VUqTksebU38flI4jdyF5DH8QTDquQkrIpGXaszLn76bGu4pveVx3tLZvdJaw2ofw9UZ0W0akNNiWloEbFSC2dj0MzEtOfTJE6hOpxF7HF6SPhjcfYHQZiErDPQ_rSihBiVEAPlNbr0YOcP3SMbtA1UMR8pZJYk4m0N9E2es0TBcgRWz3oksOjtDKP0HQjnJohd5y6tsBwMlSnbrlxsY7Z7pdC2Bx0ehAjZkYLaBwzC642CVk9zIR_7ZUlxpGu-i8RzPzlzGKOx2YowNFEYn5IZaan5CtVvDVlfQ";
+
+    #[derive(Clone)]
+    pub struct MockIdp {
+        pub issuer: String,
+        claims: Arc<Mutex<Map<String, Value>>>,
+    }
+
+    impl MockIdp {
+        pub async fn start() -> Self {
+            let listener =
+                tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("mock IdP should bind");
+            let issuer = format!("http://{}", listener.local_addr().expect("local addr"));
+            let idp = Self { issuer, claims: Arc::new(Mutex::new(Map::new())) };
+            let app = Router::new()
+                .route("/.well-known/openid-configuration", get(discovery))
+                .route("/jwks", get(jwks))
+                .route("/token", post(token))
+                .with_state(idp.clone());
+            tokio::spawn(async move {
+                axum::serve(listener, app).await.expect("mock IdP should serve");
+            });
+            idp
+        }
+
+        /// Claims (besides iss/aud/exp) placed in the next issued ID token.
+        pub fn set_claims(&self, claims: Value) {
+            let Value::Object(claims) = claims else { panic!("claims must be an object") };
+            *self.claims.lock().expect("claims lock") = claims;
+        }
+
+        pub fn sign(&self, extra: &Map<String, Value>) -> String {
+            let mut claims = Map::new();
+            claims.insert("iss".into(), json!(self.issuer));
+            claims.insert("aud".into(), json!(CLIENT_ID));
+            claims.insert("iat".into(), json!(chrono::Utc::now().timestamp()));
+            claims.insert("exp".into(), json!(chrono::Utc::now().timestamp() + 300));
+            claims.extend(extra.clone());
+            let mut header = Header::new(Algorithm::RS256);
+            header.kid = Some(KID.to_string());
+            let key = EncodingKey::from_rsa_pem(RSA_PRIVATE_KEY_PEM.as_bytes())
+                .expect("test RSA key should parse");
+            encode(&header, &claims, &key).expect("ID token should sign")
+        }
+    }
+
+    async fn discovery(State(idp): State<MockIdp>) -> Json<Value> {
+        Json(json!({
+            "issuer": idp.issuer,
+            "authorization_endpoint": format!("{}/authorize", idp.issuer),
+            "token_endpoint": format!("{}/token", idp.issuer),
+            "jwks_uri": format!("{}/jwks", idp.issuer),
+        }))
+    }
+
+    async fn jwks() -> Json<Value> {
+        Json(json!({
+            "keys": [{
+                "kty": "RSA",
+                "use": "sig",
+                "alg": "RS256",
+                "kid": KID,
+                "n": RSA_MODULUS,
+                "e": "AQAB",
+            }]
+        }))
+    }
+
+    async fn token(
+        State(idp): State<MockIdp>,
+        Form(form): Form<std::collections::HashMap<String, String>>,
+    ) -> Result<Json<Value>, StatusCode> {
+        let valid = form.get("grant_type").map(String::as_str) == Some("authorization_code")
+            && form.get("code").map(String::as_str) == Some(CODE)
+            && form.get("client_id").map(String::as_str) == Some(CLIENT_ID)
+            && form.get("code_verifier").is_some_and(|verifier| !verifier.is_empty());
+        if !valid {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        let claims = idp.claims.lock().expect("claims lock").clone();
+        Ok(Json(json!({
+            "access_token": "mock-access-token",
+            "token_type": "Bearer",
+            "id_token": idp.sign(&claims),
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Map, Value};
+
+    use super::{
+        mock_idp::{MockIdp, CLIENT_ID, CODE},
+        AuthorizationRequest, IdentityProvider, OidcProvider,
+    };
+    use crate::config::OidcProviderConfig;
+
+    fn provider_for(idp: &MockIdp) -> OidcProvider {
+        OidcProvider::new(OidcProviderConfig {
+            id: "keycloak".into(),
+            issuer: idp.issuer.clone(),
+            client_id: CLIENT_ID.into(),
+            client_secret: "secret".into(),
+            scopes: "openid email profile".into(),
+            email_claim: "email".into(),
+            name_claim: "name".into(),
+        })
+    }
+
+    #[tokio::test]
+    async fn authorization_url_comes_from_discovery_and_carries_pkce_and_nonce() {
+        let idp = MockIdp::start().await;
+        let provider = provider_for(&idp);
+        let url = provider
+            .authorization_url(AuthorizationRequest {
+                redirect_uri: "https://app.scriptum.dev/callback",
+                state: "state-1",
+                code_challenge: "challenge",
+                nonce: "nonce-1",
+            })
+            .await
+            .expect("authorization url should build");
+        let url = url::Url::parse(&url).expect("url should parse");
+        assert_eq!(url.path(), "/authorize");
+        let params: std::collections::HashMap<_, _> = url.query_pairs().into_owned().collect();
+        assert_eq!(params["client_id"], CLIENT_ID);
+        assert_eq!(params["nonce"], "nonce-1");
+        assert_eq!(params["code_challenge_method"], "S256");
+        assert_eq!(params["scope"], "openid email profile");
+    }
+
+    #[tokio::test]
+    async fn authenticate_validates_id_token_and_maps_claims() {
+        let idp = MockIdp::start().await;
+        idp.set_claims(json!({
+            "sub": "user-42",
+            "email": "Dana@Corp.Example",
+            "email_verified": true,
+            "preferred_username": "dana",
+            "nonce": "nonce-1",
+        }));
+        let provider = provider_for(&idp);
+
+        let identity = provider
+            .authenticate(CODE, "verifier", "https://app.scriptum.dev/callback", "nonce-1")
+            .await
+            .expect("mock IdP login should succeed");
+        assert_eq!(identity.provider, "keycloak");
+        assert_eq!(identity.subject, "user-42");
+        assert_eq!(identity.email.as_deref(), Some("dana@corp.example"));
+        assert!(identity.email_verified);
+        assert_eq!(identity.display_name, "dana");
+
+        let error = provider
+            .authenticate(CODE, "verifier", "https://app.scriptum.dev/callback", "other-nonce")
+            .await
+            .expect_err("nonce mismatch must be rejected");
+        let status = axum::response::IntoResponse::into_response(error).status();
+        assert_eq!(status, axum::http::StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn id_tokens_for_other_audiences_or_issuers_are_rejected() {
+        let idp = MockIdp::start().await;
+        let provider = provider_for(&idp);
+        let mut claims = Map::new();
+        claims.insert("sub".into(), json!("user-42"));
+        claims.insert("nonce".into(), json!("n"));
+
+        let mut wrong_audience = claims.clone();
+        wrong_audience.insert("aud".into(), json!("someone-else"));
+        let mut wrong_issuer = claims.clone();
+        wrong_issuer.insert("iss".into(), Value::String("https://evil.example".into()));
+
+        for claims in [wrong_audience, wrong_issuer] {
+            let token = idp.sign(&claims);
+            assert!(provider.validate_id_token(&token, "n").await.is_err());
+        }
+        assert!(provider.validate_id_token(&idp.sign(&claims), "n").await.is_ok());
+    }
+}
@@ -0,0 +1,555 @@
+// Password-account persistence.
+//
+// Password users live in the shared `users` table (rows with a non-null
+// `password_hash`). Email verification and password reset use opaque
+// single-use tokens stored only as SHA-256 hashes in
+// `password_auth_tokens`; repeated failed logins lock the account for a
+// configurable window.
+
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+
+use anyhow::Context;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{FromRow, PgPool};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::error::{ErrorCode, RelayError};
+
+/// Purpose of a single-use password-auth token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordTokenPurpose {
+    EmailVerification,
+    PasswordReset,
+}
+
+impl PasswordTokenPurpose {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::EmailVerification => "email_verification",
+            Self::PasswordReset => "password_reset",
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct PasswordUser {
+    pub id: Uuid,
+    pub email: String,
+    pub display_name: String,
+    pub password_hash: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub failed_login_count: i32,
+    pub locked_until: Option<DateTime<Utc>>,
+}
+
+impl PasswordUser {
+    pub fn is_locked(&self, now: DateTime<Utc>) -> bool {
+        self.locked_until.is_some_and(|until| until > now)
+    }
+}
+
+/// Lockout policy applied on failed password logins.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LockoutPolicy {
+    pub max_failed_attempts: i32,
+    pub lockout: Duration,
+}
+
+/// Outcome of recording a failed login attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FailedLoginOutcome {
+    Counted { failed_login_count: i32 },
+    Locked { locked_until: DateTime<Utc> },
+}
+
+#[derive(Debug, Clone)]
+struct MemoryPasswordToken {
+    user_id: Uuid,
+    purpose: PasswordTokenPurpose,
+    token_hash: Vec<u8>,
+    expires_at: DateTime<Utc>,
+    consumed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct MemoryPasswordStore {
+    users: HashMap<Uuid, PasswordUser>,
+    tokens: Vec<MemoryPasswordToken>,
+}
+
+#[derive(Clone)]
+pub(crate) enum PasswordUserStore {
+    Postgres(PgPool),
+    Memory(Arc<RwLock<MemoryPasswordStore>>),
+}
+
+impl Default for PasswordUserStore {
+    fn default() -> Self {
+        Self::Memory(Arc::new(RwLock::new(MemoryPasswordStore::default())))
+    }
+}
+
+impl PasswordUserStore {
+    pub fn postgres_pool(&self) -> Option<&PgPool> {
+        match self {
+            Self::Postgres(pool) => Some(pool),
+            Self::Memory(_) => None,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        email: String,
+        display_name: String,
+        password_hash: String,
+    ) -> Result<PasswordUser, RelayError> {
+        match self {
+            Self::Postgres(pool) => create_pg(pool, email, display_name, password_hash).await,
+            Self::Memory(store) => {
+                let mut guard = store.write().await;
+                if guard.users.values().any(|user| user.email == email) {
+                    return Err(duplicate_email_error());
+                }
+                let user = PasswordUser {
+                    id: Uuid::new_v4(),
+                    email,
+                    display_name,
+                    password_hash,
+                    email_verified_at: None,
+                    failed_login_count: 0,
+                    locked_until: None,
+                };
+                guard.users.insert(user.id, user.clone());
+                Ok(user)
+            }
+        }
+    }
+
+    pub async fn find_by_email(&self, email: &str) -> anyhow::Result<Option<PasswordUser>> {
+        match self {
+            Self::Postgres(pool) => sqlx::query_as::<_, PasswordUser>(
+                r#"
+                SELECT id, email::text AS email, display_name, password_hash,
+                       email_verified_at, failed_login_count, locked_until
+                FROM users
+                WHERE email = $1::citext AND password_hash IS NOT NULL
+                "#,
+            )
+            .bind(email)
+            .fetch_optional(pool)
+            .await
+            .context("failed to look up password user by email"),
+            Self::Memory(store) => {
+                Ok(store.read().await.users.values().find(|user| user.email == email).cloned())
+            }
+        }
+    }
+
+    pub async fn find_by_user_id(&self, user_id: Uuid) -> anyhow::Result<Option<PasswordUser>> {
+        match self {
+            Self::Postgres(pool) => sqlx::query_as::<_, PasswordUser>(
+                r#"
+                SELECT id, email::text AS email, display_name, password_hash,
+                       email_verified_at, failed_login_count, locked_until
+                FROM users
+                WHERE id = $1 AND password_hash IS NOT NULL
+                "#,
+            )
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await
+            .context("failed to look up password user by id"),
+            Self::Memory(store) => Ok(store.read().await.users.get(&user_id).cloned()),
+        }
+    }
+
+    /// Replace the password hash and clear any lockout state.
+    pub async fn update_password_hash(
+        &self,
+        user_id: Uuid,
+        new_password_hash: String,
+    ) -> anyhow::Result<bool> {
+        match self {
+            Self::Postgres(pool) => {
+                let result = sqlx::query(
+                    r#"
+                    UPDATE users
+                    SET password_hash = $2,
+                        failed_login_count = 0,
+                        locked_until = NULL,
+                        updated_at = now()
+                    WHERE id = $1 AND password_hash IS NOT NULL
+                    "#,
+                )
+                .bind(user_id)
+                .bind(new_password_hash)
+                .execute(pool)
+                .await
+                .context("failed to update password hash")?;
+                Ok(result.rows_affected() > 0)
+            }
+            Self::Memory(store) => {
+                let mut guard = store.write().await;
+                let Some(user) = guard.users.get_mut(&user_id) else {
+                    return Ok(false);
+                };
+                user.password_hash = new_password_hash;
+                user.failed_login_count = 0;
+                user.locked_until = None;
+                Ok(true)
+            }
+        }
+    }
+
+    /// Count a failed login, locking the account once the policy limit is hit.
+    ///
+    /// The counter resets when the lock is applied so the user gets a fresh
+    /// set of attempts after the lockout window passes.
+    pub async fn record_failed_login(
+        &self,
+        user_id: Uuid,
+        policy: LockoutPolicy,
+    ) -> anyhow::Result<FailedLoginOutcome> {
+        match self {
+            Self::Postgres(pool) => {
+                let (failed_login_count, locked_until): (i32, Option<DateTime<Utc>>) =
+                    sqlx::query_as(
+                        r#"
+                        UPDATE users
+                        SET failed_login_count = CASE
+                                WHEN failed_login_count + 1 >= $2 THEN 0
+                                ELSE failed_login_count + 1
+                            END,
+                            locked_until = CASE
+                                WHEN failed_login_count + 1 >= $2 THEN $3
+                                ELSE locked_until
+                            END
+                        WHERE id = $1
+                        RETURNING failed_login_count, locked_until
+                        "#,
+                    )
+                    .bind(user_id)
+                    .bind(policy.max_failed_attempts)
+                    .bind(Utc::now() + policy.lockout)
+                    .fetch_one(pool)
+                    .await
+                    .context("failed to record failed login")?;
+                Ok(failed_login_outcome(failed_login_count, locked_until))
+            }
+            Self::Memory(store) => {
+                let mut guard = store.write().await;
+                let user = guard
+                    .users
+                    .get_mut(&user_id)
+                    .context("password user disappeared while recording failed login")?;
+                user.failed_login_count += 1;
+                if user.failed_login_count >= policy.max_failed_attempts {
+                    user.failed_login_count = 0;
+                    user.locked_until = Some(Utc::now() + policy.lockout);
+                }
+                Ok(failed_login_outcome(user.failed_login_count, user.locked_until))
+            }
+        }
+    }
+
+    pub async fn clear_failed_logins(&self, user_id: Uuid) -> anyhow::Result<()> {
+        match self {
+            Self::Postgres(pool) => {
+                sqlx::query(
+                    r#"
+                    UPDATE users
+                    SET failed_login_count = 0, locked_until = NULL
+                    WHERE id = $1 AND (failed_login_count <> 0 OR locked_until IS NOT NULL)
+                    "#,
+                )
+                .bind(user_id)
+                .execute(pool)
+                .await
+                .context("failed to clear failed logins")?;
+            }
+            Self::Memory(store) => {
+                if let Some(user) = store.write().await.users.get_mut(&user_id) {
+                    user.failed_login_count = 0;
+                    user.locked_until = None;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn mark_email_verified(&self, user_id: Uuid) -> anyhow::Result<()> {
+        match self {
+            Self::Postgres(pool) => {
+                sqlx::query(
+                    r#"
+                    UPDATE users
+                    SET email_verified_at = COALESCE(email_verified_at, now()),
+                        updated_at = now()
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(user_id)
+                .execute(pool)
+                .await
+                .context("failed to mark email verified")?;
+            }
+            Self::Memory(store) => {
+                if let Some(user) = store.write().await.users.get_mut(&user_id) {
+                    user.email_verified_at.get_or_insert_with(Utc::now);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Store a new token hash, invalidating older unconsumed tokens of the same purpose.
+    pub async fn issue_token(
+        &self,
+        user_id: Uuid,
+        purpose: PasswordTokenPurpose,
+        token_hash: Vec<u8>,
+        expires_at: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        match self {
+            Self::Postgres(pool) => {
+                let mut tx = pool.begin().await.context("failed to begin token transaction")?;
+                sqlx::query(
+                    r#"
+                    UPDATE password_auth_tokens
+                    SET consumed_at = now()
+                    WHERE user_id = $1 AND purpose = $2 AND consumed_at IS NULL
+                    "#,
+                )
+                .bind(user_id)
+                .bind(purpose.as_str())
+                .execute(&mut *tx)
+                .await
+                .context("failed to invalidate previous password tokens")?;
+                sqlx::query(
+                    r#"
+                    INSERT INTO password_auth_tokens (user_id, purpose, token_hash, expires_at)
+                    VALUES ($1, $2, $3, $4)
+                    "#,
+                )
+                .bind(user_id)
+                .bind(purpose.as_str())
+                .bind(token_hash)
+                .bind(expires_at)
+                .execute(&mut *tx)
+                .await
+                .context("failed to insert password token")?;
+                tx.commit().await.context("failed to commit password token")?;
+            }
+            Self::Memory(store) => {
+                let mut guard = store.write().await;
+                let now = Utc::now();
+                for token in guard.tokens.iter_mut().filter(|token| {
+                    token.user_id == user_id
+                        && token.purpose == purpose
+                        && token.consumed_at.is_none()
+                }) {
+                    token.consumed_at = Some(now);
+                }
+                guard.tokens.push(MemoryPasswordToken {
+                    user_id,
+                    purpose,
+                    token_hash,
+                    expires_at,
+                    consumed_at: None,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Atomically consume a live token, returning the owning user.
+    pub async fn consume_token(
+        &self,
+        purpose: PasswordTokenPurpose,
+        token_hash: &[u8],
+    ) -> anyhow::Result<Option<Uuid>> {
+        match self {
+            Self::Postgres(pool) => sqlx::query_scalar::<_, Uuid>(
+                r#"
+                UPDATE password_auth_tokens
+                SET consumed_at = now()
+                WHERE token_hash = $1
+                  AND purpose = $2
+                  AND consumed_at IS NULL
+                  AND expires_at > now()
+                RETURNING user_id
+                "#,
+            )
+            .bind(token_hash)
+            .bind(purpose.as_str())
+            .fetch_optional(pool)
+            .await
+            .context("failed to consume password token"),
+            Self::Memory(store) => {
+                let mut guard = store.write().await;
+                let now = Utc::now();
+                Ok(guard
+                    .tokens
+                    .iter_mut()
+                    .find(|token| {
+                        token.purpose == purpose
+                            && token.token_hash == token_hash
+                            && token.consumed_at.is_none()
+                            && token.expires_at > now
+                    })
+                    .map(|token| {
+                        token.consumed_at = Some(now);
+                        token.user_id
+                    }))
+            }
+        }
+    }
+}
+
+fn failed_login_outcome(
+    failed_login_count: i32,
+    locked_until: Option<DateTime<Utc>>,
+) -> FailedLoginOutcome {
+    match locked_until {
+        Some(locked_until) if failed_login_count == 0 && locked_until > Utc::now() => {
+            FailedLoginOutcome::Locked { locked_until }
+        }
+        _ => FailedLoginOutcome::Counted { failed_login_count },
+    }
+}
+
+fn duplicate_email_error() -> RelayError {
+    RelayError::new(ErrorCode::ValidationFailed, "an account with this email already exists")
+}
+
+async fn create_pg(
+    pool: &PgPool,
+    email: String,
+    display_name: String,
+    password_hash: String,
+) -> Result<PasswordUser, RelayError> {
+    sqlx::query_as::<_, PasswordUser>(
+        r#"
+        INSERT INTO users (email, display_name, password_hash)
+        VALUES ($1, $2, $3)
+        RETURNING id, email::text AS email, display_name, password_hash,
+                  email_verified_at, failed_login_count, locked_until
+        "#,
+    )
+    .bind(email)
+    .bind(display_name)
+    .bind(password_hash)
+    .fetch_one(pool)
+    .await
+    .map_err(|error| match &error {
+        sqlx::Error::Database(db) if db.is_unique_violation() => duplicate_email_error(),
+        _ => {
+            tracing::error!(error = ?error, "failed to create password user");
+            RelayError::from_code(ErrorCode::InternalError)
+        }
+    })
+}
+
+/// Delivers verification and reset tokens to the account's email address.
+pub trait PasswordTokenMailer: Send + Sync {
+    fn send_token(
+        &self,
+        purpose: PasswordTokenPurpose,
+        email: &str,
+        token: &str,
+    ) -> Pin<Box<dyn Future<Output = Result<(), RelayError>> + Send>>;
+}
+
+/// Mailer used when no delivery transport is configured; tokens are dropped.
+pub struct UnconfiguredPasswordTokenMailer;
+
+impl PasswordTokenMailer for UnconfiguredPasswordTokenMailer {
+    fn send_token(
+        &self,
+        purpose: PasswordTokenPurpose,
+        _email: &str,
+        _token: &str,
+    ) -> Pin<Box<dyn Future<Output = Result<(), RelayError>> + Send>> {
+        tracing::warn!(
+            purpose = purpose.as_str(),
+            "no password token mailer configured; token was not delivered"
+        );
+        Box::pin(async { Ok(()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    use super::{FailedLoginOutcome, LockoutPolicy, PasswordTokenPurpose, PasswordUserStore};
+
+    const POLICY: LockoutPolicy =
+        LockoutPolicy { max_failed_attempts: 3, lockout: Duration::minutes(15) };
+
+    #[tokio::test]
+    async fn failed_logins_lock_after_policy_limit_and_reset_counter() {
+        let store = PasswordUserStore::default();
+        let user = store
+            .create("lock@example.com".into(), "Lock".into(), "hash".into())
+            .await
+            .expect("user should be created");
+
+        for expected in 1..3 {
+            let outcome = store.record_failed_login(user.id, POLICY).await.expect("should count");
+            assert_eq!(outcome, FailedLoginOutcome::Counted { failed_login_count: expected });
+        }
+        let outcome = store.record_failed_login(user.id, POLICY).await.expect("should lock");
+        assert!(matches!(outcome, FailedLoginOutcome::Locked { .. }));
+
+        let stored = store.find_by_user_id(user.id).await.expect("lookup").expect("user");
+        assert!(stored.is_locked(Utc::now()));
+        assert_eq!(stored.failed_login_count, 0);
+
+        store.clear_failed_logins(user.id).await.expect("clear should succeed");
+        let stored = store.find_by_user_id(user.id).await.expect("lookup").expect("user");
+        assert!(!stored.is_locked(Utc::now()));
+    }
+
+    #[tokio::test]
+    async fn tokens_are_single_use_purpose_bound_and_superseded() {
+        let store = PasswordUserStore::default();
+        let user_id = store
+            .create("tokens@example.com".into(), "Tokens".into(), "hash".into())
+            .await
+            .expect("user should be created")
+            .id;
+        let expires_at = Utc::now() + Duration::hours(1);
+
+        store
+            .issue_token(user_id, PasswordTokenPurpose::PasswordReset, vec![1], expires_at)
+            .await
+            .expect("first token");
+        store
+            .issue_token(user_id, PasswordTokenPurpose::PasswordReset, vec![2], expires_at)
+            .await
+            .expect("second token");
+
+        let consume = |hash: Vec<u8>, purpose| {
+            let store = store.clone();
+            async move { store.consume_token(purpose, &hash).await.expect("consume") }
+        };
+        assert_eq!(consume(vec![1], PasswordTokenPurpose::PasswordReset).await, None);
+        assert_eq!(consume(vec![2], PasswordTokenPurpose::EmailVerification).await, None);
+        assert_eq!(consume(vec![2], PasswordTokenPurpose::PasswordReset).await, Some(user_id));
+        assert_eq!(consume(vec![2], PasswordTokenPurpose::PasswordReset).await, None);
+
+        store
+            .issue_token(
+                Uuid::new_v4(),
+                PasswordTokenPurpose::EmailVerification,
+                vec![3],
+                Utc::now() - Duration::seconds(1),
+            )
+            .await
+            .expect("expired token");
+        assert_eq!(consume(vec![3], PasswordTokenPurpose::EmailVerification).await, None);
+    }
+}
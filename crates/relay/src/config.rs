@@ -29,6 +29,27 @@ pub struct RelayConfig {
     pub log_filter: String,
     /// Share link base URL for generating share links.
     pub share_link_base_url: String,
+    /// Generic OpenID Connect providers available for SSO login.
+    pub oidc_providers: Vec<OidcProviderConfig>,
+    /// Chain audit events with SHA-256 hashes so tampering is detectable.
+    pub audit_hash_chain: bool,
+}
+
+/// One OpenID Connect identity provider (Okta, Keycloak, ...).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidcProviderConfig {
+    /// URL-safe provider id used in routes (`/v1/auth/oidc/{id}/...`).
+    pub id: String,
+    /// Issuer URL; discovery is fetched from `{issuer}/.well-known/openid-configuration`.
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    /// Space-separated scopes requested at authorization time.
+    pub scopes: String,
+    /// ID-token claim used as the user's email address.
+    pub email_claim: String,
+    /// ID-token claim used as the user's display name.
+    pub name_claim: String,
 }
 
 impl RelayConfig {
@@ -44,6 +65,14 @@ impl RelayConfig {
     /// | `SCRIPTUM_RELAY_CORS_ORIGINS` | *(none — cors.rs uses dev defaults)* |
     /// | `SCRIPTUM_RELAY_LOG_FILTER` | `info` |
     /// | `SCRIPTUM_RELAY_SHARE_LINK_BASE_URL` | `http://localhost:3000/share` |
+    /// | `SCRIPTUM_RELAY_OIDC_PROVIDERS` | *(none)* — comma-separated provider ids |
+    /// | `SCRIPTUM_RELAY_AUDIT_HASH_CHAIN` | `false` |
+    ///
+    /// Each OIDC provider id `<ID>` (upper-cased, `-` → `_`) reads
+    /// `SCRIPTUM_RELAY_OIDC_<ID>_ISSUER`, `_CLIENT_ID`, `_CLIENT_SECRET`,
+    /// `_SCOPES` (default `openid email profile`), `_EMAIL_CLAIM` (default
+    /// `email`) and `_NAME_CLAIM` (default `name`). Providers without an
+    /// issuer or client id are skipped.
     pub fn from_env() -> Self {
         Self::from_env_fn(|key| std::env::var(key))
     }
@@ -74,6 +103,13 @@ impl RelayConfig {
         let share_link_base_url = env("SCRIPTUM_RELAY_SHARE_LINK_BASE_URL")
             .unwrap_or_else(|_| "http://localhost:3000/share".into());
 
+        let oidc_providers = env("SCRIPTUM_RELAY_OIDC_PROVIDERS")
+            .map(|ids| parse_oidc_providers(&ids, &env))
+            .unwrap_or_default();
+
+        let audit_hash_chain = env("SCRIPTUM_RELAY_AUDIT_HASH_CHAIN")
+            .is_ok_and(|value| matches!(value.trim(), "1" | "true" | "yes"));
+
         Self {
             listen_addr,
             jwt_secret,
@@ -82,6 +118,8 @@ impl RelayConfig {
             cors_origins,
             log_filter,
             share_link_base_url,
+            oidc_providers,
+            audit_hash_chain,
         }
     }
 
@@ -96,6 +134,34 @@ impl RelayConfig {
     }
 }
 
+fn parse_oidc_providers<F>(ids: &str, env: &F) -> Vec<OidcProviderConfig>
+where
+    F: Fn(&str) -> Result<String, std::env::VarError>,
+{
+    ids.split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .filter_map(|id| {
+            let prefix =
+                format!("SCRIPTUM_RELAY_OIDC_{}", id.to_ascii_uppercase().replace('-', "_"));
+            let var = |suffix: &str| {
+                env(&format!("{prefix}_{suffix}")).ok().filter(|value| !value.trim().is_empty())
+            };
+            let issuer = var("ISSUER")?;
+            let client_id = var("CLIENT_ID")?;
+            Some(OidcProviderConfig {
+                id: id.to_ascii_lowercase(),
+                issuer: issuer.trim_end_matches('/').to_string(),
+                client_id,
+                client_secret: var("CLIENT_SECRET").unwrap_or_default(),
+                scopes: var("SCOPES").unwrap_or_else(|| "openid email profile".into()),
+                email_claim: var("EMAIL_CLAIM").unwrap_or_else(|| "email".into()),
+                name_claim: var("NAME_CLAIM").unwrap_or_else(|| "name".into()),
+            })
+        })
+        .collect()
+}
+
 fn validate_ws_base_url(value: &str) -> Result<()> {
     let parsed = Url::parse(value)
         .with_context(|| format!("SCRIPTUM_RELAY_WS_BASE_URL is not a valid URL: `{value}`"))?;
@@ -139,7 +205,9 @@ mod tests {
         assert!(cfg.database_url.is_none());
         assert!(cfg.cors_origins.is_none());
         assert_eq!(cfg.log_filter, "info");
+        assert!(!cfg.audit_hash_chain);
         assert_eq!(cfg.share_link_base_url, "http://localhost:3000/share");
+        assert!(cfg.oidc_providers.is_empty());
     }
 
     #[test]
@@ -217,6 +285,31 @@ mod tests {
         assert_eq!(cfg.share_link_base_url, "https://app.scriptum.dev/s");
     }
 
+    #[test]
+    fn oidc_providers_from_env() {
+        let mut m = HashMap::new();
+        m.insert("SCRIPTUM_RELAY_OIDC_PROVIDERS", "okta, corp-keycloak,missing");
+        m.insert("SCRIPTUM_RELAY_OIDC_OKTA_ISSUER", "https://example.okta.com/");
+        m.insert("SCRIPTUM_RELAY_OIDC_OKTA_CLIENT_ID", "okta-client");
+        m.insert("SCRIPTUM_RELAY_OIDC_OKTA_CLIENT_SECRET", "okta-secret");
+        m.insert("SCRIPTUM_RELAY_OIDC_CORP_KEYCLOAK_ISSUER", "https://sso.corp/realms/eng");
+        m.insert("SCRIPTUM_RELAY_OIDC_CORP_KEYCLOAK_CLIENT_ID", "scriptum");
+        m.insert("SCRIPTUM_RELAY_OIDC_CORP_KEYCLOAK_EMAIL_CLAIM", "upn");
+        m.insert("SCRIPTUM_RELAY_OIDC_MISSING_CLIENT_ID", "no-issuer");
+        let cfg = RelayConfig::from_env_fn(env_from_map(m));
+
+        assert_eq!(cfg.oidc_providers.len(), 2);
+        let okta = &cfg.oidc_providers[0];
+        assert_eq!(okta.id, "okta");
+        assert_eq!(okta.issuer, "https://example.okta.com");
+        assert_eq!(okta.client_secret, "okta-secret");
+        assert_eq!(okta.scopes, "openid email profile");
+        let keycloak = &cfg.oidc_providers[1];
+        assert_eq!(keycloak.id, "corp-keycloak");
+        assert_eq!(keycloak.email_claim, "upn");
+        assert_eq!(keycloak.name_claim, "name");
+    }
+
     #[test]
     fn validate_security_accepts_wss() {
         let mut m = HashMap::new();
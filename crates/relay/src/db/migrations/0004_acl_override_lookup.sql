@@ -0,0 +1,5 @@
+-- Effective-role resolution looks up overrides per (document, subject) on
+-- every authorized request, including each WebSocket yjs_update.
+
+CREATE INDEX idx_acl_overrides_doc_subject
+    ON acl_overrides (workspace_id, doc_id, subject_type, subject_id, created_at DESC);
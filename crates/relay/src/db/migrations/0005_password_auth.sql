@@ -0,0 +1,20 @@
+-- Password accounts: email verification, lockout and single-use tokens
+-- for verification and password reset. Tokens are stored as SHA-256 hashes.
+
+ALTER TABLE users
+    ADD COLUMN email_verified_at  timestamptz NULL,
+    ADD COLUMN failed_login_count integer NOT NULL DEFAULT 0,
+    ADD COLUMN locked_until       timestamptz NULL;
+
+CREATE TABLE password_auth_tokens (
+    id              uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id         uuid NOT NULL REFERENCES users(id),
+    purpose         text NOT NULL CHECK (purpose IN ('email_verification', 'password_reset')),
+    token_hash      bytea UNIQUE NOT NULL,
+    expires_at      timestamptz NOT NULL,
+    consumed_at     timestamptz NULL,
+    created_at      timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_password_auth_tokens_user ON password_auth_tokens (user_id, purpose)
+    WHERE consumed_at IS NULL;
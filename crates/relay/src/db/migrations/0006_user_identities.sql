@@ -0,0 +1,16 @@
+-- External identities (OIDC providers) linked to relay users. One user may
+-- link identities from several providers; each (provider, subject) pair maps
+-- to exactly one user.
+
+CREATE TABLE user_identities (
+    id              uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id         uuid NOT NULL REFERENCES users(id),
+    provider        text NOT NULL,
+    subject         text NOT NULL,
+    email           citext NULL,
+    created_at      timestamptz NOT NULL DEFAULT now(),
+    last_login_at   timestamptz NOT NULL DEFAULT now(),
+    UNIQUE (provider, subject)
+);
+
+CREATE INDEX idx_user_identities_user ON user_identities (user_id);
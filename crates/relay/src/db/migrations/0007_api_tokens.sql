@@ -0,0 +1,24 @@
+-- Scoped API tokens for agents and CI. Only the SHA-256 hash of the raw
+-- `sct_...` token is stored; `token_prefix` keeps a short, non-secret prefix
+-- so users can tell tokens apart.
+
+CREATE TABLE api_tokens (
+    id              uuid PRIMARY KEY DEFAULT gen_random_uuid(),
+    workspace_id    uuid NOT NULL REFERENCES workspaces(id),
+    user_id         uuid NOT NULL REFERENCES users(id),
+    agent_id        text NULL,
+    name            text NOT NULL,
+    token_prefix    text NOT NULL,
+    token_hash      bytea NOT NULL UNIQUE,
+    scopes          text[] NOT NULL CHECK (
+        cardinality(scopes) > 0
+        AND scopes <@ ARRAY['read', 'write', 'comment']::text[]
+    ),
+    path_prefixes   text[] NOT NULL DEFAULT '{}',
+    expires_at      timestamptz NULL,
+    last_used_at    timestamptz NULL,
+    revoked_at      timestamptz NULL,
+    created_at      timestamptz NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_api_tokens_workspace ON api_tokens (workspace_id, created_at DESC);
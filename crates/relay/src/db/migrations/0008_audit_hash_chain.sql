@@ -0,0 +1,14 @@
+-- Optional tamper-evident hash chain over audit events. Rows written while
+-- chaining is disabled leave both columns NULL.
+
+ALTER TABLE audit_events
+    ADD COLUMN prev_hash  bytea NULL,
+    ADD COLUMN event_hash bytea NULL;
+
+-- Chain head lookup and oldest-first export/verification walk.
+CREATE INDEX idx_audit_events_workspace_chain
+    ON audit_events (workspace_id, created_at, id)
+    WHERE event_hash IS NOT NULL;
+
+CREATE INDEX idx_audit_events_workspace_entity
+    ON audit_events (workspace_id, entity_type, entity_id, created_at DESC);
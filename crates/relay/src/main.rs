@@ -51,7 +51,10 @@ use tracing::{error, info, info_span, Instrument};
 use uuid::Uuid;
 use ws::{DocSyncStore, SyncSessionStore};
 
-use crate::auth::{jwt::JwtAccessTokenService, oauth::OAuthState};
+use crate::auth::{
+    api_tokens::ApiTokenStore, authz::AuthorizationService, jwt::JwtAccessTokenService,
+    oauth::OAuthState, oidc::OidcProvider,
+};
 use crate::db::pool::{check_pool_health, create_pg_pool, PoolConfig};
 use crate::error::{
     attach_request_id_header, attach_trace_id_header, default_code_for_status,
@@ -133,8 +136,6 @@ async fn main() -> anyhow::Result<()> {
         );
     }
 
-    let jwt_service =
-        Arc::new(JwtAccessTokenService::new(&cfg.jwt_secret).context("invalid relay JWT secret")?);
     let session_store = Arc::new(SyncSessionStore::default());
     let doc_store = Arc::new(DocSyncStore::default());
     let readiness_database_url = cfg
@@ -159,19 +160,32 @@ async fn main() -> anyhow::Result<()> {
     metrics.set_daemon_recovery_time_ms(recovery_started_at.elapsed().as_millis() as u64);
     readiness_probe.mark_sequencer_recovered();
 
-    let membership_store = ws::WorkspaceMembershipStore::from_env()
+    let jwt_service = Arc::new(
+        JwtAccessTokenService::new(&cfg.jwt_secret)
+            .context("invalid relay JWT secret")?
+            .with_api_token_store(ApiTokenStore::Postgres(readiness_pool.clone())),
+    );
+
+    let authz = AuthorizationService::from_env()
         .await
-        .context("failed to initialize websocket workspace membership store")?;
-    let oauth_state = OAuthState::from_env();
-    let app = build_router(
+        .context("failed to initialize websocket authorization service")?;
+    let oauth_state = OAuthState::from_env()
+        .with_identity_providers(OidcProvider::from_configs(&cfg.oidc_providers));
+    let api_router = api::build_router_from_env(
         Arc::clone(&jwt_service),
+        oauth_state,
+        Arc::clone(&session_store),
+        cfg.audit_hash_chain,
+    )
+    .await
+    .context("failed to build relay workspace API router")?;
+    let app = build_router(
+        jwt_service,
         session_store,
         doc_store,
-        membership_store,
+        authz,
         cfg.ws_base_url.clone(),
-        api::build_router_from_env(jwt_service, oauth_state)
-            .await
-            .context("failed to build relay workspace API router")?,
+        api_router,
         readiness_probe,
         metrics,
     );
@@ -192,7 +206,7 @@ fn build_router(
     jwt_service: Arc<JwtAccessTokenService>,
     session_store: Arc<SyncSessionStore>,
     doc_store: Arc<DocSyncStore>,
-    membership_store: ws::WorkspaceMembershipStore,
+    authz: AuthorizationService,
     ws_base_url: String,
     api_router: Router,
     readiness_probe: Arc<ReadinessProbe>,
@@ -209,7 +223,7 @@ fn build_router(
                 session_store,
                 doc_store,
                 Arc::new(awareness::AwarenessStore::default()),
-                membership_store,
+                authz,
                 ws_base_url,
             ))
             .merge(api_router),
@@ -432,11 +446,11 @@ mod tests {
         workspace_id_from_path, DbCheckFuture, ReadinessProbe, MAX_REQUEST_BODY_BYTES,
     };
     use crate::{
-        auth::jwt::JwtAccessTokenService,
+        auth::{authz::AuthorizationService, jwt::JwtAccessTokenService},
         error::{ErrorCode, REQUEST_ID_HEADER, TRACE_ID_HEADER},
         metrics::RelayMetrics,
         validation::ValidatedJson,
-        ws::{DocSyncStore, SyncSessionStore, WorkspaceMembershipStore},
+        ws::{DocSyncStore, SyncSessionStore},
     };
 
     fn test_router(db_ready: bool, sequencer_ready: bool) -> Router {
@@ -461,7 +475,7 @@ mod tests {
             jwt_service,
             session_store,
             doc_store,
-            WorkspaceMembershipStore::for_tests(),
+            AuthorizationService::for_tests(),
             "ws://localhost:8080".to_string(),
             Router::new(),
             readiness_probe,
@@ -856,7 +870,8 @@ mod tests {
         assert!(rendered.contains("daemon_recovery_time_ms"));
         assert!(rendered.contains("git_sync_jobs_total{state=\"queued\"} 0"));
         assert!(rendered.contains("git_sync_jobs_total{state=\"running\"} 0"));
-        assert!(rendered.contains("git_sync_jobs_total{state=\"completed\"} 0"));
+        assert!(rendered.contains("git_sync_jobs_total{state=\"retrying\"} 0"));
+        assert!(rendered.contains("git_sync_jobs_total{state=\"succeeded\"} 0"));
         assert!(rendered.contains("git_sync_jobs_total{state=\"failed\"} 0"));
         assert!(rendered.contains("sequence_gap_count"));
         assert!(rendered.contains("endpoint=\"/health\""));
@@ -6,6 +6,7 @@ use std::{
     },
 };
 
+use scriptum_common::types::GitJobState;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -30,15 +31,14 @@ pub struct RelayMetrics {
     sync_ack_latency_ms: AtomicU64,
 }
 
-const GIT_SYNC_STATES: [&str; 4] = ["queued", "running", "completed", "failed"];
 const UNKNOWN_WORKSPACE_LABEL: &str = "unknown";
 static GLOBAL_METRICS: OnceLock<Arc<RelayMetrics>> = OnceLock::new();
 
 impl Default for RelayMetrics {
     fn default() -> Self {
         let mut git_sync_jobs_total = HashMap::new();
-        for state in GIT_SYNC_STATES {
-            git_sync_jobs_total.insert(state.to_string(), 0);
+        for state in GitJobState::ALL {
+            git_sync_jobs_total.insert(state.as_str().to_string(), 0);
         }
 
         Self {
@@ -151,7 +151,7 @@ impl RelayMetrics {
     }
 
     pub fn increment_git_sync_jobs_total(&self) {
-        self.increment_git_sync_jobs_total_for_state("completed");
+        self.increment_git_sync_jobs_total_for_state(GitJobState::Succeeded.as_str());
     }
 
     pub fn increment_git_sync_jobs_total_for_state(&self, state: &str) {
@@ -292,7 +292,7 @@ fn normalize_endpoint(path: &str) -> String {
 
 fn normalize_git_sync_state(state: &str) -> String {
     let normalized = state.trim().to_ascii_lowercase();
-    if GIT_SYNC_STATES.contains(&normalized.as_str()) {
+    if GitJobState::parse(&normalized).is_some() {
         normalized
     } else {
         "unknown".to_string()
@@ -449,7 +449,8 @@ mod tests {
         assert!(rendered.contains("daemon_recovery_time_ms 1234"));
         assert!(rendered.contains("git_sync_jobs_total{state=\"queued\"} 1"));
         assert!(rendered.contains("git_sync_jobs_total{state=\"running\"} 0"));
-        assert!(rendered.contains("git_sync_jobs_total{state=\"completed\"} 0"));
+        assert!(rendered.contains("git_sync_jobs_total{state=\"retrying\"} 0"));
+        assert!(rendered.contains("git_sync_jobs_total{state=\"succeeded\"} 0"));
         assert!(rendered.contains("git_sync_jobs_total{state=\"failed\"} 1"));
         assert!(rendered.contains("git_sync_jobs_total{state=\"unknown\"} 1"));
         assert!(rendered.contains("sequence_gap_count 2"));
@@ -1,11 +1,11 @@
 use super::protocol as ws_protocol;
 use super::session::{
     ApplyClientUpdateResult, CreateSyncSessionRequest, CreateSyncSessionResponse, DocSyncStore,
-    SessionTokenValidation, SyncSessionRouterState, SyncSessionStore, WorkspaceMembershipStore,
-    HEARTBEAT_INTERVAL_MS, HEARTBEAT_TIMEOUT_MS, MAX_FRAME_BYTES, RESUME_TOKEN_TTL_MINUTES,
-    SESSION_TOKEN_TTL_MINUTES,
+    SessionTokenValidation, SyncSessionRouterState, SyncSessionStore, HEARTBEAT_INTERVAL_MS,
+    HEARTBEAT_TIMEOUT_MS, MAX_FRAME_BYTES, RESUME_TOKEN_TTL_MINUTES, SESSION_TOKEN_TTL_MINUTES,
 };
 use crate::auth::{
+    authz::{AuthorizationService, AuthzError},
     jwt::JwtAccessTokenService,
     middleware::{require_bearer_auth, AuthenticatedUser, WorkspaceRole},
 };
@@ -40,14 +40,14 @@ pub fn router(
     session_store: Arc<SyncSessionStore>,
     doc_store: Arc<DocSyncStore>,
     awareness_store: Arc<AwarenessStore>,
-    membership_store: WorkspaceMembershipStore,
+    authz: AuthorizationService,
     ws_base_url: String,
 ) -> Router {
     let state = SyncSessionRouterState {
         session_store,
         doc_store,
         awareness_store,
-        membership_store,
+        authz,
         ws_base_url: Arc::<str>::from(ws_base_url),
     };
     let auth_layer = middleware::from_fn_with_state(jwt_service, require_bearer_auth);
@@ -75,7 +75,7 @@ pub async fn create_sync_session(
         return RelayError::new(ErrorCode::AuthForbidden, "workspace mismatch").into_response();
     }
 
-    let role = match state.membership_store.role_for_user(workspace_id, user.user_id).await {
+    let role = match state.authz.role_for_user(workspace_id, user.user_id).await {
         Ok(Some(role)) => role,
         Ok(None) => {
             return RelayError::new(ErrorCode::AuthForbidden, "caller lacks workspace access")
@@ -115,7 +115,8 @@ pub async fn create_sync_session(
             session_expires_at,
             resume_expires_at,
             Some(user.user_id),
-            None,
+            user.agent_id.clone(),
+            user.token.clone(),
         )
         .await;
 
@@ -147,19 +148,12 @@ pub async fn ws_upgrade(
     let session_store = state.session_store.clone();
     let doc_store = state.doc_store.clone();
     let awareness_store = state.awareness_store.clone();
-    let membership_store = state.membership_store.clone();
+    let authz = state.authz.clone();
     let trace_id = trace_id_from_headers_or_generate(&headers);
     ws.max_frame_size(MAX_FRAME_BYTES as usize).on_upgrade(move |socket| async move {
         with_trace_id_scope(
             trace_id,
-            handle_socket(
-                session_store,
-                doc_store,
-                awareness_store,
-                membership_store,
-                session_id,
-                socket,
-            ),
+            handle_socket(session_store, doc_store, awareness_store, authz, session_id, socket),
         )
         .await;
     })
@@ -191,7 +185,7 @@ async fn handle_socket(
     session_store: Arc<SyncSessionStore>,
     doc_store: Arc<DocSyncStore>,
     awareness_store: Arc<AwarenessStore>,
-    membership_store: WorkspaceMembershipStore,
+    authz: AuthorizationService,
     session_id: Uuid,
     mut socket: WebSocket,
 ) {
@@ -368,7 +362,7 @@ async fn handle_socket(
                                 match handle_subscribe_message(
                                     &session_store,
                                     &doc_store,
-                                    &membership_store,
+                                    &authz,
                                     session_id,
                                     doc_id,
                                     last_server_seq,
@@ -416,6 +410,7 @@ async fn handle_socket(
                                 match handle_yjs_update_message(
                                     &session_store,
                                     &doc_store,
+                                    &authz,
                                     session_id,
                                     doc_id,
                                     client_id,
@@ -605,7 +600,7 @@ pub(crate) async fn handle_hello_message(
 pub(crate) async fn handle_subscribe_message(
     session_store: &SyncSessionStore,
     doc_store: &DocSyncStore,
-    membership_store: &WorkspaceMembershipStore,
+    authz: &AuthorizationService,
     session_id: Uuid,
     doc_id: Uuid,
     last_server_seq: Option<i64>,
@@ -630,44 +625,16 @@ pub(crate) async fn handle_subscribe_message(
         });
     };
 
-    if let Some(actor_user_id) = session_store.actor_user_for_session(session_id).await {
-        let role = match membership_store.role_for_user(workspace_id, actor_user_id).await {
-            Ok(Some(role)) => role,
-            Ok(None) => {
-                return Err(WsMessage::Error {
-                    code: ErrorCode::AuthForbidden.as_str().to_string(),
-                    message: "caller lacks workspace access".to_string(),
-                    retryable: false,
-                    doc_id: Some(doc_id),
-                });
-            }
-            Err(error) => {
-                error!(
-                    error = ?error,
-                    session_id = %session_id,
-                    trace_id = current_trace_id().as_deref().unwrap_or(""),
-                    actor_user_id = %actor_user_id,
-                    workspace_id = %workspace_id,
-                    "failed to evaluate websocket subscribe permissions",
-                );
-                return Err(WsMessage::Error {
-                    code: ErrorCode::InternalError.as_str().to_string(),
-                    message: ErrorCode::InternalError.default_message().to_string(),
-                    retryable: true,
-                    doc_id: Some(doc_id),
-                });
-            }
-        };
-
-        if !role.allows(WorkspaceRole::Viewer) {
-            return Err(WsMessage::Error {
-                code: ErrorCode::AuthForbidden.as_str().to_string(),
-                message: "caller lacks required role".to_string(),
-                retryable: false,
-                doc_id: Some(doc_id),
-            });
-        }
-    }
+    authorize_doc_access(
+        authz,
+        session_store,
+        session_id,
+        workspace_id,
+        doc_id,
+        WorkspaceRole::Viewer,
+        false,
+    )
+    .await?;
 
     if !session_store.track_subscription(session_id, doc_id).await {
         return Err(WsMessage::Error {
@@ -681,6 +648,68 @@ pub(crate) async fn handle_subscribe_message(
     Ok(doc_store.build_state_sync_messages(workspace_id, doc_id, last_server_seq).await)
 }
 
+/// Authorizes the session's actor on `doc_id`. Sessions without an actor are
+/// internal (e.g. relay-to-relay) and are not subject to RBAC. The resolved
+/// role is cached on the session; `use_cached_role` reuses it until it is
+/// `DOC_ROLE_TTL_SECONDS` old instead of querying access again.
+async fn authorize_doc_access(
+    authz: &AuthorizationService,
+    session_store: &SyncSessionStore,
+    session_id: Uuid,
+    workspace_id: Uuid,
+    doc_id: Uuid,
+    required_role: WorkspaceRole,
+    use_cached_role: bool,
+) -> Result<(), WsMessage> {
+    let Some(principal) = session_store.principal_for_session(session_id).await else {
+        return Ok(());
+    };
+
+    let cached_role = if use_cached_role {
+        session_store.cached_doc_role(session_id, doc_id).await
+    } else {
+        None
+    };
+    let role = match cached_role {
+        Some(role) => Ok(role),
+        None => {
+            let resolved = authz.effective_role(workspace_id, doc_id, &principal).await;
+            if let Ok(role) = &resolved {
+                session_store.cache_doc_role(session_id, doc_id, *role).await;
+            }
+            resolved.map_err(AuthzError::from)
+        }
+    };
+
+    match role.and_then(|role| {
+        AuthorizationService::check_document_role(workspace_id, role, &principal, required_role)
+    }) {
+        Ok(_) => Ok(()),
+        Err(AuthzError::Internal(error)) => {
+            error!(
+                error = ?error,
+                session_id = %session_id,
+                trace_id = current_trace_id().as_deref().unwrap_or(""),
+                workspace_id = %workspace_id,
+                doc_id = %doc_id,
+                "failed to evaluate websocket document permissions",
+            );
+            Err(WsMessage::Error {
+                code: ErrorCode::InternalError.as_str().to_string(),
+                message: ErrorCode::InternalError.default_message().to_string(),
+                retryable: true,
+                doc_id: Some(doc_id),
+            })
+        }
+        Err(denied) => Err(WsMessage::Error {
+            code: ErrorCode::AuthForbidden.as_str().to_string(),
+            message: denied.message().to_string(),
+            retryable: false,
+            doc_id: Some(doc_id),
+        }),
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct YjsUpdateHandlingResult {
     pub(crate) workspace_id: Uuid,
@@ -692,6 +721,7 @@ pub(crate) struct YjsUpdateHandlingResult {
 pub(crate) async fn handle_yjs_update_message(
     session_store: &SyncSessionStore,
     doc_store: &DocSyncStore,
+    authz: &AuthorizationService,
     session_id: Uuid,
     doc_id: Uuid,
     client_id: Uuid,
@@ -735,6 +765,19 @@ pub(crate) async fn handle_yjs_update_message(
         });
     };
 
+    // Reuses the role resolved at subscribe. Override changes invalidate it;
+    // membership changes apply once it is older than DOC_ROLE_TTL_SECONDS.
+    authorize_doc_access(
+        authz,
+        session_store,
+        session_id,
+        workspace_id,
+        doc_id,
+        WorkspaceRole::Editor,
+        true,
+    )
+    .await?;
+
     let apply_result = doc_store
         .apply_client_update(
             workspace_id,
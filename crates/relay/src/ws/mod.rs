@@ -5,7 +5,7 @@ mod session;
 pub(crate) use handler::router;
 #[cfg(test)]
 pub(crate) use session::CreateSyncSessionResponse;
-pub(crate) use session::{DocSyncStore, SyncSessionStore, WorkspaceMembershipStore};
+pub(crate) use session::{DocSyncStore, SyncSessionStore};
 
 #[cfg(test)]
 pub(crate) use handler::{
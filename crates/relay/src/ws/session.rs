@@ -1,8 +1,10 @@
-use crate::auth::middleware::WorkspaceRole;
+use crate::auth::{
+    api_tokens::ApiTokenGrant,
+    authz::{AuthorizationService, Principal},
+    middleware::WorkspaceRole,
+};
 use crate::awareness::AwarenessStore;
-use crate::db::pool::{check_pool_health, create_pg_pool, PoolConfig};
 use crate::metrics;
-use anyhow::Context;
 use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
 use chrono::{Duration, Utc};
 use scriptum_common::crdt::origin::{AuthorType, OriginTag};
@@ -10,7 +12,6 @@ use scriptum_common::protocol::ws::WsMessage;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
-    env,
     sync::Arc,
 };
 use tokio::sync::{mpsc, RwLock};
@@ -22,92 +23,20 @@ pub(crate) const HEARTBEAT_TIMEOUT_MS: u64 = 10_000;
 pub(crate) const MAX_FRAME_BYTES: u32 = 262_144;
 pub(crate) const SESSION_TOKEN_TTL_MINUTES: i64 = 15;
 pub(crate) const RESUME_TOKEN_TTL_MINUTES: i64 = 10;
+/// How long a session reuses a document role resolved at subscribe time
+/// before re-reading memberships and ACL overrides. ACL override changes made
+/// through the document API invalidate cached roles right away.
+pub(crate) const DOC_ROLE_TTL_SECONDS: i64 = 30;
 
 #[derive(Clone)]
 pub(crate) struct SyncSessionRouterState {
     pub(crate) session_store: Arc<SyncSessionStore>,
     pub(crate) doc_store: Arc<DocSyncStore>,
     pub(crate) awareness_store: Arc<AwarenessStore>,
-    pub(crate) membership_store: WorkspaceMembershipStore,
+    pub(crate) authz: AuthorizationService,
     pub(crate) ws_base_url: Arc<str>,
 }
 
-#[derive(Clone)]
-pub enum WorkspaceMembershipStore {
-    Postgres(sqlx::PgPool),
-    #[cfg_attr(not(test), allow(dead_code))]
-    Memory(Arc<RwLock<HashMap<(Uuid, Uuid), WorkspaceRole>>>),
-}
-
-impl WorkspaceMembershipStore {
-    pub async fn from_env() -> anyhow::Result<Self> {
-        let database_url = env::var("SCRIPTUM_RELAY_DATABASE_URL")
-            .context("SCRIPTUM_RELAY_DATABASE_URL must be set for WebSocket RBAC")?;
-        let pool = create_pg_pool(&database_url, PoolConfig::from_env())
-            .await
-            .context("failed to initialize relay PostgreSQL pool for websocket RBAC")?;
-        check_pool_health(&pool)
-            .await
-            .context("relay PostgreSQL health check failed for websocket RBAC")?;
-
-        Ok(Self::Postgres(pool))
-    }
-
-    pub(crate) async fn role_for_user(
-        &self,
-        workspace_id: Uuid,
-        user_id: Uuid,
-    ) -> anyhow::Result<Option<WorkspaceRole>> {
-        match self {
-            Self::Postgres(pool) => {
-                let role = sqlx::query_scalar::<_, String>(
-                    r#"
-                    SELECT wm.role
-                    FROM workspace_members AS wm
-                    INNER JOIN workspaces AS w
-                        ON w.id = wm.workspace_id
-                    WHERE wm.workspace_id = $1
-                      AND wm.user_id = $2
-                      AND wm.status = 'active'
-                      AND w.deleted_at IS NULL
-                    "#,
-                )
-                .bind(workspace_id)
-                .bind(user_id)
-                .fetch_optional(pool)
-                .await
-                .context("failed to query workspace role for websocket session")?
-                .map(|role| {
-                    WorkspaceRole::from_db_value(&role).ok_or_else(|| {
-                        anyhow::anyhow!("invalid workspace role '{role}' in database")
-                    })
-                })
-                .transpose()?;
-
-                Ok(role)
-            }
-            Self::Memory(store) => Ok(store.read().await.get(&(workspace_id, user_id)).copied()),
-        }
-    }
-
-    #[cfg(test)]
-    pub(crate) fn for_tests() -> Self {
-        Self::Memory(Arc::new(RwLock::new(HashMap::new())))
-    }
-
-    #[cfg(test)]
-    pub(crate) async fn grant_for_tests(
-        &self,
-        workspace_id: Uuid,
-        user_id: Uuid,
-        role: WorkspaceRole,
-    ) {
-        if let Self::Memory(store) = self {
-            store.write().await.insert((workspace_id, user_id), role);
-        }
-    }
-}
-
 #[derive(Debug, Clone, Default)]
 pub struct SyncSessionStore {
     sessions: Arc<RwLock<HashMap<Uuid, SyncSessionRecord>>>,
@@ -154,6 +83,16 @@ pub(crate) struct UpdateAttribution {
     pub(crate) agent_id: Option<String>,
 }
 
+impl UpdateAttribution {
+    /// Principal to authorize, or `None` for sessions without an actor.
+    pub(crate) fn principal(&self) -> Option<Principal> {
+        if self.user_id.is_none() && self.agent_id.is_none() {
+            return None;
+        }
+        Some(Principal { user_id: self.user_id, agent_id: self.agent_id.clone(), token: None })
+    }
+}
+
 #[derive(Debug, Clone)]
 struct SyncSessionRecord {
     workspace_id: Uuid,
@@ -165,9 +104,13 @@ struct SyncSessionRecord {
     resume_expires_at: chrono::DateTime<Utc>,
     active_connections: usize,
     subscriptions: HashSet<Uuid>,
+    /// Effective role per subscribed doc (`None` without access) and when
+    /// it was resolved.
+    doc_roles: HashMap<Uuid, (Option<WorkspaceRole>, chrono::DateTime<Utc>)>,
     outbound: Option<mpsc::UnboundedSender<WsMessage>>,
     actor_user_id: Option<Uuid>,
     actor_agent_id: Option<String>,
+    actor_token: Option<ApiTokenGrant>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -444,6 +387,7 @@ impl SyncSessionStore {
             resume_expires_at,
             None,
             None,
+            None,
         )
         .await;
     }
@@ -461,6 +405,7 @@ impl SyncSessionStore {
         resume_expires_at: chrono::DateTime<Utc>,
         actor_user_id: Option<Uuid>,
         actor_agent_id: Option<String>,
+        actor_token: Option<ApiTokenGrant>,
     ) {
         let mut guard = self.sessions.write().await;
         guard.insert(
@@ -475,9 +420,11 @@ impl SyncSessionStore {
                 resume_expires_at,
                 active_connections: 0,
                 subscriptions: HashSet::new(),
+                doc_roles: HashMap::new(),
                 outbound: None,
                 actor_user_id,
                 actor_agent_id,
+                actor_token,
             },
         );
     }
@@ -539,6 +486,7 @@ impl SyncSessionStore {
             session.active_connections = session.active_connections.saturating_sub(1);
             if session.active_connections == 0 {
                 session.subscriptions.clear();
+                session.doc_roles.clear();
                 session.outbound = None;
             }
         }
@@ -570,6 +518,46 @@ impl SyncSessionStore {
         }
     }
 
+    /// Cache the session's effective role on `doc_id`.
+    pub(crate) async fn cache_doc_role(
+        &self,
+        session_id: Uuid,
+        doc_id: Uuid,
+        role: Option<WorkspaceRole>,
+    ) {
+        if let Some(session) = self.sessions.write().await.get_mut(&session_id) {
+            session.doc_roles.insert(doc_id, (role, Utc::now()));
+        }
+    }
+
+    /// The session's cached role on `doc_id`, unless missing or older than
+    /// `DOC_ROLE_TTL_SECONDS`.
+    pub(crate) async fn cached_doc_role(
+        &self,
+        session_id: Uuid,
+        doc_id: Uuid,
+    ) -> Option<Option<WorkspaceRole>> {
+        let guard = self.sessions.read().await;
+        let (role, resolved_at) = guard.get(&session_id)?.doc_roles.get(&doc_id)?;
+        (Utc::now() - *resolved_at < Duration::seconds(DOC_ROLE_TTL_SECONDS)).then_some(*role)
+    }
+
+    /// Drop every session's cached role on `doc_id`, so the next update
+    /// resolves access again.
+    pub(crate) async fn invalidate_doc_roles(&self, doc_id: Uuid) {
+        for session in self.sessions.write().await.values_mut() {
+            session.doc_roles.remove(&doc_id);
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) async fn expire_doc_roles_for_tests(&self, session_id: Uuid) {
+        if let Some(session) = self.sessions.write().await.get_mut(&session_id) {
+            let expired = Utc::now() - Duration::seconds(DOC_ROLE_TTL_SECONDS);
+            session.doc_roles.values_mut().for_each(|(_, resolved_at)| *resolved_at = expired);
+        }
+    }
+
     pub(crate) async fn session_is_subscribed(&self, session_id: Uuid, doc_id: Uuid) -> bool {
         self.sessions
             .read()
@@ -589,6 +577,20 @@ impl SyncSessionStore {
         })
     }
 
+    /// Principal to authorize the session's requests as, including any API
+    /// token restrictions, or `None` for sessions without an actor.
+    pub(crate) async fn principal_for_session(&self, session_id: Uuid) -> Option<Principal> {
+        let guard = self.sessions.read().await;
+        let session = guard.get(&session_id)?;
+        let mut principal = UpdateAttribution {
+            user_id: session.actor_user_id,
+            agent_id: session.actor_agent_id.clone(),
+        }
+        .principal()?;
+        principal.token = session.actor_token.clone();
+        Some(principal)
+    }
+
     pub(crate) async fn broadcast_to_subscribers(
         &self,
         workspace_id: Uuid,
@@ -658,10 +660,6 @@ impl SyncSessionStore {
         self.sessions.read().await.get(&session_id).map(|session| session.workspace_id)
     }
 
-    pub(crate) async fn actor_user_for_session(&self, session_id: Uuid) -> Option<Uuid> {
-        self.sessions.read().await.get(&session_id).and_then(|session| session.actor_user_id)
-    }
-
     pub(crate) async fn token_for_session(&self, session_id: Uuid) -> Option<String> {
         self.sessions.read().await.get(&session_id).map(|session| session.session_token.clone())
     }
@@ -1,10 +1,12 @@
 use super::{
     handle_awareness_update, handle_hello_message, handle_subscribe_message,
     handle_yjs_update_message, router, CreateSyncSessionResponse, DocSyncStore,
-    SessionTokenValidation, SyncSessionStore, WorkspaceMembershipStore, HEARTBEAT_INTERVAL_MS,
-    HEARTBEAT_TIMEOUT_MS, MAX_FRAME_BYTES,
+    SessionTokenValidation, SyncSessionStore, HEARTBEAT_INTERVAL_MS, HEARTBEAT_TIMEOUT_MS,
+    MAX_FRAME_BYTES,
+};
+use crate::auth::{
+    authz::AuthorizationService, jwt::JwtAccessTokenService, middleware::WorkspaceRole,
 };
-use crate::auth::{jwt::JwtAccessTokenService, middleware::WorkspaceRole};
 use crate::awareness::AwarenessStore;
 use crate::db::{
     migrations::run_migrations,
@@ -290,13 +292,13 @@ async fn create_sync_session_requires_matching_workspace() {
     let jwt_service =
         Arc::new(JwtAccessTokenService::new(TEST_SECRET).expect("jwt service should initialize"));
     let session_store = Arc::new(SyncSessionStore::default());
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let app = router(
         jwt_service.clone(),
         session_store,
         Arc::new(DocSyncStore::default()),
         Arc::new(AwarenessStore::default()),
-        membership_store,
+        authz,
         "ws://localhost:8080".to_string(),
     );
 
@@ -332,7 +334,7 @@ async fn create_sync_session_requires_workspace_membership() {
         Arc::new(SyncSessionStore::default()),
         Arc::new(DocSyncStore::default()),
         Arc::new(AwarenessStore::default()),
-        WorkspaceMembershipStore::for_tests(),
+        AuthorizationService::for_tests(),
         "ws://localhost:8080".to_string(),
     );
     let token = jwt_service
@@ -361,18 +363,18 @@ async fn create_sync_session_returns_expected_contract() {
     let jwt_service =
         Arc::new(JwtAccessTokenService::new(TEST_SECRET).expect("jwt service should initialize"));
     let session_store = Arc::new(SyncSessionStore::default());
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let user_id = Uuid::new_v4();
     let app = router(
         jwt_service.clone(),
         session_store.clone(),
         Arc::new(DocSyncStore::default()),
         Arc::new(AwarenessStore::default()),
-        membership_store.clone(),
+        authz.clone(),
         "ws://localhost:8080".to_string(),
     );
     let workspace_id = Uuid::new_v4();
-    membership_store.grant_for_tests(workspace_id, user_id, WorkspaceRole::Viewer).await;
+    authz.grant_for_tests(workspace_id, user_id, WorkspaceRole::Viewer).await;
     let token = jwt_service
         .issue_workspace_token(user_id, workspace_id)
         .expect("access token should be created");
@@ -417,16 +419,16 @@ async fn websocket_rejects_oversized_frames_with_message_too_big_close() {
     let jwt_service =
         Arc::new(JwtAccessTokenService::new(TEST_SECRET).expect("jwt service should initialize"));
     let session_store = Arc::new(SyncSessionStore::default());
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let workspace_id = Uuid::new_v4();
     let user_id = Uuid::new_v4();
-    membership_store.grant_for_tests(workspace_id, user_id, WorkspaceRole::Editor).await;
+    authz.grant_for_tests(workspace_id, user_id, WorkspaceRole::Editor).await;
     let app = router(
         jwt_service.clone(),
         session_store,
         Arc::new(DocSyncStore::default()),
         Arc::new(AwarenessStore::default()),
-        membership_store,
+        authz,
         format!("ws://{addr}"),
     );
     let access_token = jwt_service
@@ -537,7 +539,7 @@ async fn websocket_integration_ack_and_broadcast_to_other_subscriber() {
         session_store.clone(),
         Arc::new(DocSyncStore::default()),
         Arc::new(AwarenessStore::default()),
-        WorkspaceMembershipStore::Postgres(pool.clone()),
+        AuthorizationService::Postgres(pool.clone()),
         format!("ws://{addr}"),
     );
     let access_token = jwt_service
@@ -745,7 +747,7 @@ async fn run_websocket_load_stress_profile(profile: LoadStressProfile) {
         session_store.clone(),
         Arc::new(DocSyncStore::default()),
         Arc::new(AwarenessStore::default()),
-        WorkspaceMembershipStore::Postgres(pool.clone()),
+        AuthorizationService::Postgres(pool.clone()),
         format!("ws://{addr}"),
     );
     let access_token = jwt_service
@@ -1002,7 +1004,7 @@ async fn run_websocket_reconnect_storm_profile(profile: ReconnectStormProfile) {
         session_store.clone(),
         Arc::new(DocSyncStore::default()),
         Arc::new(AwarenessStore::default()),
-        WorkspaceMembershipStore::Postgres(pool.clone()),
+        AuthorizationService::Postgres(pool.clone()),
         format!("ws://{addr}"),
     );
     let access_token = jwt_service
@@ -1598,7 +1600,7 @@ async fn hello_rejects_expired_session_token() {
 async fn subscribe_tracks_subscription_and_sends_snapshot_and_updates() {
     let session_store = SyncSessionStore::default();
     let doc_store = DocSyncStore::default();
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let session_id = Uuid::new_v4();
     let workspace_id = Uuid::new_v4();
     let doc_id = Uuid::new_v4();
@@ -1638,16 +1640,10 @@ async fn subscribe_tracks_subscription_and_sends_snapshot_and_updates() {
         )
         .await;
 
-    let messages = handle_subscribe_message(
-        &session_store,
-        &doc_store,
-        &membership_store,
-        session_id,
-        doc_id,
-        Some(5),
-    )
-    .await
-    .expect("subscribe should succeed");
+    let messages =
+        handle_subscribe_message(&session_store, &doc_store, &authz, session_id, doc_id, Some(5))
+            .await
+            .expect("subscribe should succeed");
 
     assert_eq!(messages.len(), 3);
     match &messages[0] {
@@ -1682,7 +1678,7 @@ async fn subscribe_tracks_subscription_and_sends_snapshot_and_updates() {
 async fn subscribe_rejects_negative_last_server_seq() {
     let session_store = SyncSessionStore::default();
     let doc_store = DocSyncStore::default();
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let session_id = Uuid::new_v4();
     let workspace_id = Uuid::new_v4();
     let doc_id = Uuid::new_v4();
@@ -1700,16 +1696,10 @@ async fn subscribe_rejects_negative_last_server_seq() {
         )
         .await;
 
-    let error = handle_subscribe_message(
-        &session_store,
-        &doc_store,
-        &membership_store,
-        session_id,
-        doc_id,
-        Some(-1),
-    )
-    .await
-    .expect_err("subscribe should reject negative cursor");
+    let error =
+        handle_subscribe_message(&session_store, &doc_store, &authz, session_id, doc_id, Some(-1))
+            .await
+            .expect_err("subscribe should reject negative cursor");
 
     match error {
         WsMessage::Error { code, doc_id: message_doc_id, .. } => {
@@ -1724,7 +1714,7 @@ async fn subscribe_rejects_negative_last_server_seq() {
 async fn subscribe_requires_workspace_membership_for_authenticated_actor() {
     let session_store = SyncSessionStore::default();
     let doc_store = DocSyncStore::default();
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let session_id = Uuid::new_v4();
     let workspace_id = Uuid::new_v4();
     let doc_id = Uuid::new_v4();
@@ -1742,19 +1732,14 @@ async fn subscribe_requires_workspace_membership_for_authenticated_actor() {
             Utc::now() + Duration::minutes(10),
             Some(actor_user_id),
             None,
+            None,
         )
         .await;
 
-    let error = handle_subscribe_message(
-        &session_store,
-        &doc_store,
-        &membership_store,
-        session_id,
-        doc_id,
-        None,
-    )
-    .await
-    .expect_err("subscribe should fail without membership");
+    let error =
+        handle_subscribe_message(&session_store, &doc_store, &authz, session_id, doc_id, None)
+            .await
+            .expect_err("subscribe should fail without membership");
 
     match error {
         WsMessage::Error { code, doc_id: message_doc_id, .. } => {
@@ -1769,7 +1754,7 @@ async fn subscribe_requires_workspace_membership_for_authenticated_actor() {
 async fn yjs_update_applies_and_returns_ack() {
     let session_store = SyncSessionStore::default();
     let doc_store = DocSyncStore::default();
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let session_id = Uuid::new_v4();
     let workspace_id = Uuid::new_v4();
     let doc_id = Uuid::new_v4();
@@ -1788,20 +1773,14 @@ async fn yjs_update_applies_and_returns_ack() {
             Utc::now() + Duration::minutes(10),
         )
         .await;
-    handle_subscribe_message(
-        &session_store,
-        &doc_store,
-        &membership_store,
-        session_id,
-        doc_id,
-        None,
-    )
-    .await
-    .expect("subscribe should succeed");
+    handle_subscribe_message(&session_store, &doc_store, &authz, session_id, doc_id, None)
+        .await
+        .expect("subscribe should succeed");
 
     let result = handle_yjs_update_message(
         &session_store,
         &doc_store,
+        &authz,
         session_id,
         doc_id,
         client_id,
@@ -1850,7 +1829,7 @@ async fn yjs_update_applies_and_returns_ack() {
 async fn yjs_update_deduplicates_client_update_id() {
     let session_store = SyncSessionStore::default();
     let doc_store = DocSyncStore::default();
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let session_id = Uuid::new_v4();
     let workspace_id = Uuid::new_v4();
     let doc_id = Uuid::new_v4();
@@ -1869,20 +1848,14 @@ async fn yjs_update_deduplicates_client_update_id() {
             Utc::now() + Duration::minutes(10),
         )
         .await;
-    handle_subscribe_message(
-        &session_store,
-        &doc_store,
-        &membership_store,
-        session_id,
-        doc_id,
-        None,
-    )
-    .await
-    .expect("subscribe should succeed");
+    handle_subscribe_message(&session_store, &doc_store, &authz, session_id, doc_id, None)
+        .await
+        .expect("subscribe should succeed");
 
     handle_yjs_update_message(
         &session_store,
         &doc_store,
+        &authz,
         session_id,
         doc_id,
         client_id,
@@ -1896,6 +1869,7 @@ async fn yjs_update_deduplicates_client_update_id() {
     let duplicate = handle_yjs_update_message(
         &session_store,
         &doc_store,
+        &authz,
         session_id,
         doc_id,
         client_id,
@@ -1922,7 +1896,7 @@ async fn yjs_update_deduplicates_client_update_id() {
 async fn yjs_update_rejects_future_base_server_seq() {
     let session_store = SyncSessionStore::default();
     let doc_store = DocSyncStore::default();
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let session_id = Uuid::new_v4();
     let workspace_id = Uuid::new_v4();
     let doc_id = Uuid::new_v4();
@@ -1939,20 +1913,14 @@ async fn yjs_update_rejects_future_base_server_seq() {
             Utc::now() + Duration::minutes(10),
         )
         .await;
-    handle_subscribe_message(
-        &session_store,
-        &doc_store,
-        &membership_store,
-        session_id,
-        doc_id,
-        None,
-    )
-    .await
-    .expect("subscribe should succeed");
+    handle_subscribe_message(&session_store, &doc_store, &authz, session_id, doc_id, None)
+        .await
+        .expect("subscribe should succeed");
 
     let error = handle_yjs_update_message(
         &session_store,
         &doc_store,
+        &authz,
         session_id,
         doc_id,
         Uuid::new_v4(),
@@ -1976,7 +1944,7 @@ async fn yjs_update_rejects_future_base_server_seq() {
 async fn yjs_update_captures_session_attribution_in_update_log() {
     let session_store = SyncSessionStore::default();
     let doc_store = DocSyncStore::default();
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let session_id = Uuid::new_v4();
     let workspace_id = Uuid::new_v4();
     let doc_id = Uuid::new_v4();
@@ -1984,7 +1952,7 @@ async fn yjs_update_captures_session_attribution_in_update_log() {
     let client_update_id = Uuid::new_v4();
     let actor_user_id = Uuid::new_v4();
     let actor_agent_id = "claude-reviewer".to_string();
-    membership_store.grant_for_tests(workspace_id, actor_user_id, WorkspaceRole::Editor).await;
+    authz.grant_for_tests(workspace_id, actor_user_id, WorkspaceRole::Editor).await;
 
     session_store
         .create_session_with_actor(
@@ -1998,18 +1966,12 @@ async fn yjs_update_captures_session_attribution_in_update_log() {
             Utc::now() + Duration::minutes(10),
             Some(actor_user_id),
             Some(actor_agent_id.clone()),
+            None,
         )
         .await;
-    handle_subscribe_message(
-        &session_store,
-        &doc_store,
-        &membership_store,
-        session_id,
-        doc_id,
-        None,
-    )
-    .await
-    .expect("subscribe should succeed");
+    handle_subscribe_message(&session_store, &doc_store, &authz, session_id, doc_id, None)
+        .await
+        .expect("subscribe should succeed");
 
     let mismatched_origin = OriginTag {
         author_id: "spoofed-agent".to_string(),
@@ -2022,6 +1984,7 @@ async fn yjs_update_captures_session_attribution_in_update_log() {
     handle_yjs_update_message(
         &session_store,
         &doc_store,
+        &authz,
         session_id,
         doc_id,
         client_id,
@@ -2040,22 +2003,209 @@ async fn yjs_update_captures_session_attribution_in_update_log() {
     assert_eq!(attribution.agent_id, Some(actor_agent_id));
 }
 
+#[tokio::test]
+async fn yjs_update_is_forbidden_when_acl_override_locks_doc_to_viewer() {
+    let session_store = SyncSessionStore::default();
+    let doc_store = DocSyncStore::default();
+    let authz = AuthorizationService::for_tests();
+    let session_id = Uuid::new_v4();
+    let workspace_id = Uuid::new_v4();
+    let locked_doc_id = Uuid::new_v4();
+    let open_doc_id = Uuid::new_v4();
+    let actor_user_id = Uuid::new_v4();
+    authz.grant_for_tests(workspace_id, actor_user_id, WorkspaceRole::Editor).await;
+    authz
+        .override_for_tests(
+            workspace_id,
+            locked_doc_id,
+            "user",
+            &actor_user_id.to_string(),
+            WorkspaceRole::Viewer,
+            None,
+        )
+        .await;
+
+    session_store
+        .create_session_with_actor(
+            session_id,
+            workspace_id,
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4().to_string(),
+            Uuid::new_v4().to_string(),
+            Utc::now() + Duration::minutes(15),
+            Utc::now() + Duration::minutes(10),
+            Some(actor_user_id),
+            None,
+            None,
+        )
+        .await;
+    for doc_id in [locked_doc_id, open_doc_id] {
+        handle_subscribe_message(&session_store, &doc_store, &authz, session_id, doc_id, None)
+            .await
+            .expect("viewers may subscribe");
+    }
+
+    let error = handle_yjs_update_message(
+        &session_store,
+        &doc_store,
+        &authz,
+        session_id,
+        locked_doc_id,
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        0,
+        "update-payload-b64".to_string(),
+    )
+    .await
+    .expect_err("locked doc should reject writes");
+    match error {
+        WsMessage::Error { code, doc_id, .. } => {
+            assert_eq!(code, "AUTH_FORBIDDEN");
+            assert_eq!(doc_id, Some(locked_doc_id));
+        }
+        other => panic!("expected forbidden error, got {other:?}"),
+    }
+    assert!(doc_store
+        .build_state_sync_messages(workspace_id, locked_doc_id, None)
+        .await
+        .is_empty());
+
+    handle_yjs_update_message(
+        &session_store,
+        &doc_store,
+        &authz,
+        session_id,
+        open_doc_id,
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        0,
+        "update-payload-b64".to_string(),
+    )
+    .await
+    .expect("docs without overrides keep the workspace role");
+}
+
+#[tokio::test]
+async fn yjs_update_reuses_subscribe_role_until_it_expires() {
+    let session_store = SyncSessionStore::default();
+    let doc_store = DocSyncStore::default();
+    let authz = AuthorizationService::for_tests();
+    let session_id = Uuid::new_v4();
+    let workspace_id = Uuid::new_v4();
+    let doc_id = Uuid::new_v4();
+    let actor_user_id = Uuid::new_v4();
+    authz.grant_for_tests(workspace_id, actor_user_id, WorkspaceRole::Editor).await;
+
+    session_store
+        .create_session_with_actor(
+            session_id,
+            workspace_id,
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4().to_string(),
+            Uuid::new_v4().to_string(),
+            Utc::now() + Duration::minutes(15),
+            Utc::now() + Duration::minutes(10),
+            Some(actor_user_id),
+            None,
+            None,
+        )
+        .await;
+    handle_subscribe_message(&session_store, &doc_store, &authz, session_id, doc_id, None)
+        .await
+        .expect("subscribe should succeed");
+    authz
+        .override_for_tests(
+            workspace_id,
+            doc_id,
+            "user",
+            &actor_user_id.to_string(),
+            WorkspaceRole::Viewer,
+            None,
+        )
+        .await;
+
+    let update = |client_update_id| {
+        handle_yjs_update_message(
+            &session_store,
+            &doc_store,
+            &authz,
+            session_id,
+            doc_id,
+            Uuid::new_v4(),
+            client_update_id,
+            0,
+            "update-payload-b64".to_string(),
+        )
+    };
+    update(Uuid::new_v4()).await.expect("role resolved at subscribe should be reused");
+
+    session_store.expire_doc_roles_for_tests(session_id).await;
+    match update(Uuid::new_v4()).await.expect_err("expired role should be resolved again") {
+        WsMessage::Error { code, .. } => assert_eq!(code, "AUTH_FORBIDDEN"),
+        other => panic!("expected forbidden error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn subscribe_is_forbidden_for_removed_workspace_members() {
+    let session_store = SyncSessionStore::default();
+    let doc_store = DocSyncStore::default();
+    let authz = AuthorizationService::for_tests();
+    let session_id = Uuid::new_v4();
+    let workspace_id = Uuid::new_v4();
+    let doc_id = Uuid::new_v4();
+    let agent_user_id = Uuid::new_v4();
+    authz
+        .override_for_tests(workspace_id, doc_id, "agent", "claude-1", WorkspaceRole::Editor, None)
+        .await;
+
+    session_store
+        .create_session_with_actor(
+            session_id,
+            workspace_id,
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4().to_string(),
+            Uuid::new_v4().to_string(),
+            Utc::now() + Duration::minutes(15),
+            Utc::now() + Duration::minutes(10),
+            Some(agent_user_id),
+            Some("claude-1".to_string()),
+            None,
+        )
+        .await;
+
+    let error =
+        handle_subscribe_message(&session_store, &doc_store, &authz, session_id, doc_id, None)
+            .await
+            .expect_err("agent overrides do not grant workspace membership");
+    match error {
+        WsMessage::Error { code, message, .. } => {
+            assert_eq!(code, "AUTH_FORBIDDEN");
+            assert_eq!(message, "caller lacks workspace access");
+        }
+        other => panic!("expected forbidden error, got {other:?}"),
+    }
+}
+
 #[tokio::test]
 async fn create_sync_session_rejects_unsupported_protocol_with_upgrade_required() {
     let jwt_service =
         Arc::new(JwtAccessTokenService::new(TEST_SECRET).expect("jwt service should initialize"));
     let session_store = Arc::new(SyncSessionStore::default());
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let user_id = Uuid::new_v4();
     let workspace_id = Uuid::new_v4();
-    membership_store.grant_for_tests(workspace_id, user_id, WorkspaceRole::Editor).await;
+    authz.grant_for_tests(workspace_id, user_id, WorkspaceRole::Editor).await;
 
     let app = router(
         jwt_service.clone(),
         session_store,
         Arc::new(DocSyncStore::default()),
         Arc::new(AwarenessStore::default()),
-        membership_store,
+        authz,
         "ws://localhost:8080".to_string(),
     );
 
@@ -2103,7 +2253,7 @@ async fn create_sync_session_rejects_unsupported_protocol_with_upgrade_required(
 async fn awareness_update_stores_and_aggregates_peers() {
     let session_store = SyncSessionStore::default();
     let awareness_store = AwarenessStore::default();
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let doc_store = DocSyncStore::default();
     let session_id = Uuid::new_v4();
     let workspace_id = Uuid::new_v4();
@@ -2121,16 +2271,9 @@ async fn awareness_update_stores_and_aggregates_peers() {
             Utc::now() + Duration::minutes(10),
         )
         .await;
-    handle_subscribe_message(
-        &session_store,
-        &doc_store,
-        &membership_store,
-        session_id,
-        doc_id,
-        None,
-    )
-    .await
-    .expect("subscribe should succeed");
+    handle_subscribe_message(&session_store, &doc_store, &authz, session_id, doc_id, None)
+        .await
+        .expect("subscribe should succeed");
 
     let peers = vec![serde_json::json!({"user": "alice", "cursor": 42})];
     let result =
@@ -2373,7 +2516,7 @@ async fn disconnect_clears_subscriptions_and_outbound() {
 async fn disconnect_clears_awareness_for_subscribed_docs() {
     let session_store = SyncSessionStore::default();
     let awareness_store = AwarenessStore::default();
-    let membership_store = WorkspaceMembershipStore::for_tests();
+    let authz = AuthorizationService::for_tests();
     let doc_store = DocSyncStore::default();
     let session_id = Uuid::new_v4();
     let workspace_id = Uuid::new_v4();
@@ -2394,16 +2537,9 @@ async fn disconnect_clears_awareness_for_subscribed_docs() {
 
     // Subscribe and add awareness.
     session_store.mark_connected(session_id).await;
-    handle_subscribe_message(
-        &session_store,
-        &doc_store,
-        &membership_store,
-        session_id,
-        doc_id,
-        None,
-    )
-    .await
-    .expect("subscribe should succeed");
+    handle_subscribe_message(&session_store, &doc_store, &authz, session_id, doc_id, None)
+        .await
+        .expect("subscribe should succeed");
 
     awareness_store
         .update(
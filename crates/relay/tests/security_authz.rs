@@ -1,6 +1,11 @@
 const API_SOURCE: &str = include_str!("../src/api/mod.rs");
 const DOCUMENTS_SOURCE: &str = include_str!("../src/api/documents.rs");
 const OAUTH_SOURCE: &str = include_str!("../src/auth/oauth.rs");
+const AUTHZ_SOURCE: &str = include_str!("../src/auth/authz.rs");
+const COMMENTS_SOURCE: &str = include_str!("../src/api/comments.rs");
+const SEARCH_SOURCE: &str = include_str!("../src/api/search.rs");
+const WS_HANDLER_SOURCE: &str = include_str!("../src/ws/handler.rs");
+const WS_TESTS_SOURCE: &str = include_str!("../src/ws/tests.rs");
 
 #[test]
 fn authz_guards_require_workspace_roles_and_reject_non_members() {
@@ -38,7 +43,7 @@ fn share_link_permission_checks_cover_disabled_revoked_expired_and_exhausted() {
 fn acl_override_management_enforces_workspace_authorization() {
     assert!(
         DOCUMENTS_SOURCE
-            .contains("require_workspace_role(&state.store, &user, ws_id, WorkspaceRole::Editor)"),
+            .contains("require_workspace_role(&state.authz, &user, ws_id, WorkspaceRole::Editor)"),
         "ACL override handlers must enforce at least workspace editor role"
     );
     assert!(
@@ -57,7 +62,7 @@ fn acl_override_management_enforces_workspace_authorization() {
     );
     assert!(
         DOCUMENTS_SOURCE.contains(
-            "require_document_role(&state.store, &user, ws_id, doc_id, WorkspaceRole::Owner)"
+            "require_document_role(&state.authz, &user, ws_id, doc_id, WorkspaceRole::Owner)"
         ),
         "destructive document routes must enforce owner role"
     );
@@ -82,3 +87,57 @@ fn refresh_token_security_rejects_expired_and_revoked_tokens() {
         "logout path should revoke refresh sessions"
     );
 }
+
+#[test]
+fn acl_overrides_are_resolved_with_expiry_and_agent_precedence() {
+    assert!(
+        AUTHZ_SOURCE.contains("expires_at IS NULL OR expires_at > now()"),
+        "expired ACL overrides must be ignored"
+    );
+    assert!(
+        AUTHZ_SOURCE.contains("(subject_type = 'agent') DESC, created_at DESC"),
+        "agent overrides must win over user overrides, newest first"
+    );
+    assert!(
+        AUTHZ_SOURCE.contains("non_members_have_no_effective_role_even_with_override"),
+        "overrides must not grant access to non-members"
+    );
+}
+
+#[test]
+fn every_document_entry_point_uses_the_authorization_service() {
+    for (name, source) in [
+        ("documents", DOCUMENTS_SOURCE),
+        ("comments", COMMENTS_SOURCE),
+        ("search", SEARCH_SOURCE),
+        ("ws handler", WS_HANDLER_SOURCE),
+    ] {
+        assert!(
+            source.contains("AuthorizationService"),
+            "{name} must authorize through AuthorizationService"
+        );
+        assert!(
+            !source.contains("fn workspace_role_for_user"),
+            "{name} must not resolve roles outside AuthorizationService"
+        );
+    }
+    assert!(
+        COMMENTS_SOURCE.contains(
+            "require_thread_role(&state, &user, ws_id, thread_id, WorkspaceRole::Editor)"
+        ),
+        "comment mutations must check the thread document's effective role"
+    );
+    assert!(
+        SEARCH_SOURCE.contains(".effective_roles(workspace_id, &doc_ids, &Principal::from(&user))"),
+        "search must filter hits by effective document role"
+    );
+    assert_eq!(
+        WS_HANDLER_SOURCE.matches("authorize_doc_access(").count(),
+        3,
+        "websocket subscribe and yjs_update must both authorize against the document"
+    );
+    assert!(
+        WS_TESTS_SOURCE.contains("yjs_update_is_forbidden_when_acl_override_locks_doc_to_viewer"),
+        "regression test for viewer-locked sync writes must exist"
+    );
+}
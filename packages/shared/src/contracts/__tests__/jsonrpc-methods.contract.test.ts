@@ -17,24 +17,42 @@ const rpcMethodKeys: Record<keyof RpcParamsMap, true> = {
   "workspace.list": true,
   "workspace.open": true,
   "workspace.create": true,
+  "workspace.context_pack": true,
   "doc.create": true,
   "doc.read": true,
   "doc.edit": true,
   "doc.edit_section": true,
+  "doc.replace": true,
+  "doc.batch": true,
   "doc.sections": true,
   "doc.tree": true,
   "doc.search": true,
   "doc.diff": true,
   "doc.history": true,
+  "doc.findings": true,
+  "doc.staleness": true,
   "agent.whoami": true,
   "agent.status": true,
   "agent.conflicts": true,
   "agent.list": true,
   "agent.claim": true,
+  "agent.plan": true,
+  "agent.undo": true,
+  "agent.redo": true,
+  "agent.notes.set": true,
+  "agent.notes.get": true,
+  "agent.notes.list": true,
+  "agent.notes.append": true,
   "doc.bundle": true,
   "git.status": true,
   "git.sync": true,
   "git.configure": true,
+  "git.job_status": true,
+  "git.jobs": true,
+  "git.reconciliations": true,
+  "git.resolve_reconciliation": true,
+  "git.set_doc_sync": true,
+  "git.blame": true,
 };
 
 describe("jsonrpc-methods contract", () => {
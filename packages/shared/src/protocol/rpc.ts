@@ -28,14 +28,16 @@ export type RpcBundleInclude =
 
 export type AgentClaimMode = "exclusive" | "shared";
 
-export type GitSyncMode = "commit" | "commit_and_push";
+export type GitSyncMode = "commit" | "commit_and_push" | "pull";
 
-export type GitSyncJobStatus = "queued" | "running" | "completed" | "failed";
+export type GitSyncJobStatus = "queued" | "running" | "retrying" | "succeeded" | "failed";
 
 export type YjsOps = unknown;
 
 export type GitSyncPolicy = "disabled" | "manual" | "auto_rebase";
 
+export type GitStrategy = "direct" | "branch_per_session" | "pull_request";
+
 export interface RpcWorkspace {
   id: string;
   slug: string;
@@ -149,11 +151,43 @@ export interface DocCreateResult {
   document: RpcDocument;
 }
 
+export type DocReadMode = "full" | "outline" | "excerpt" | "section_range";
+
 export interface DocReadParams {
   workspace_id: string;
   doc_id: string;
   include_content?: boolean;
   include_backlinks?: boolean;
+  mode?: DocReadMode;
+  excerpt_tokens?: number;
+  from_section_id?: string;
+  to_section_id?: string;
+  token_budget?: number;
+}
+
+export interface DocOutlineEntry {
+  section_id: string;
+  heading: string;
+  level: number;
+  parent_id?: string;
+  tokens: number;
+  subtree_tokens: number;
+}
+
+export interface DocSectionExcerpt {
+  section_id: string;
+  heading: string;
+  text: string;
+  tokens: number;
+  truncated: boolean;
+}
+
+export interface DocReadRange {
+  from_section_id?: string;
+  to_section_id?: string;
+  next_section_id?: string;
+  tokens: number;
+  truncated: boolean;
 }
 
 export interface DocReadResult {
@@ -163,6 +197,10 @@ export interface DocReadResult {
   sections: RpcSection[];
   attributions?: RpcSectionAttribution[];
   degraded: boolean;
+  total_tokens?: number;
+  outline?: DocOutlineEntry[];
+  excerpts?: DocSectionExcerpt[];
+  range?: DocReadRange;
 }
 
 export interface DocEditParams {
@@ -174,6 +212,7 @@ export interface DocEditParams {
   content_md?: string;
   if_etag?: string;
   agent_id?: string;
+  dry_run?: boolean;
 }
 
 export interface DocEditResult {
@@ -181,6 +220,43 @@ export interface DocEditResult {
   head_seq: number;
 }
 
+export interface DryRunSectionChange {
+  change: "added" | "removed" | "modified";
+  section: RpcSection;
+}
+
+export interface DryRunConflict {
+  kind: "lease" | "overlap" | "plan";
+  section_id: string;
+  agent_id: string;
+  expires_at: string;
+}
+
+export interface GuardFinding {
+  kind: string;
+  line: number;
+  start: number;
+  end: number;
+  preview: string;
+}
+
+export interface DryRunDoc {
+  doc_id?: string;
+  doc_path: string;
+  created: boolean;
+  diff: string;
+  sections: DryRunSectionChange[];
+  conflicts: DryRunConflict[];
+  /** Secrets and PII the edit would introduce. */
+  findings: GuardFinding[];
+}
+
+/** Returned instead of the normal result when an edit RPC gets `dry_run: true`. */
+export interface DryRunResult {
+  dry_run: true;
+  docs: DryRunDoc[];
+}
+
 export interface DocEditSectionParams {
   workspace_id: string;
   doc_id: string;
@@ -188,6 +264,7 @@ export interface DocEditSectionParams {
   content: string;
   agent: string;
   summary?: string;
+  dry_run?: boolean;
 }
 
 export interface DocEditSectionResult {
@@ -198,6 +275,70 @@ export interface DocEditSectionResult {
   etag: string;
 }
 
+export interface DocReplaceParams {
+  workspace_id: string;
+  doc_id: string;
+  old_text: string;
+  new_text: string;
+  section_id?: string;
+  occurrence?: number;
+  if_etag?: string;
+  agent_id?: string;
+  summary?: string;
+  dry_run?: boolean;
+}
+
+export interface DocReplaceResult {
+  etag: string;
+  head_seq: number;
+  doc_path: string;
+  section_id?: string;
+  matches: number;
+  offset: number;
+}
+
+export type DocBatchOp =
+  | { op: "create"; path: string; title?: string; content_md?: string }
+  | { op: "edit"; doc_id: string; content_md: string; if_etag?: string }
+  | {
+      op: "replace";
+      doc_id: string;
+      old_text: string;
+      new_text: string;
+      section_id?: string;
+      occurrence?: number;
+      if_etag?: string;
+    }
+  | {
+      op: "edit_section";
+      doc_id: string;
+      section: string;
+      content: string;
+      if_etag?: string;
+    };
+
+export interface DocBatchParams {
+  workspace_id: string;
+  ops: DocBatchOp[];
+  agent_id?: string;
+  summary?: string;
+  dry_run?: boolean;
+}
+
+export interface DocBatchDocResult {
+  doc_id: string;
+  path: string;
+  etag: string;
+  head_seq: number;
+  ops: number;
+  created: boolean;
+}
+
+export interface DocBatchResult {
+  summary: string;
+  docs: DocBatchDocResult[];
+}
+
 export interface DocSectionsParams {
   workspace_id: string;
   doc_id: string;
@@ -300,6 +441,60 @@ export interface DocHistoryResult {
   events: DocHistoryEvent[];
 }
 
+export interface DocFindingsParams {
+  workspace_id: string;
+  doc_id?: string;
+  limit?: number;
+}
+
+export interface DocFinding {
+  id: number;
+  doc_id?: string;
+  path: string;
+  kind: string;
+  line: number;
+  preview: string;
+  source: "rpc" | "watcher" | "yjs_ws";
+  action: "warned" | "blocked" | "redacted";
+  agent_id?: string;
+  ts: string;
+}
+
+export interface DocFindingsResult {
+  items: DocFinding[];
+}
+
+export interface DocStalenessParams {
+  workspace_id: string;
+  doc_id?: string;
+}
+
+export interface StaleCommit {
+  commit: string;
+  author: string;
+  committed_at: string;
+  subject: string;
+}
+
+export interface StaleReference {
+  path: string;
+  line?: number;
+  commits: StaleCommit[];
+}
+
+export interface StaleSection {
+  doc_id: string;
+  path: string;
+  section_id: string;
+  last_edited_at: string;
+  last_edited_by?: string;
+  references: StaleReference[];
+}
+
+export interface DocStalenessResult {
+  items: StaleSection[];
+}
+
 export type AgentWhoamiParams = Record<string, never>;
 
 export interface AgentWhoamiResult {
@@ -309,11 +504,79 @@ export interface AgentWhoamiResult {
 
 export interface AgentStatusParams {
   workspace_id: string;
+  /** Include this agent's scratchpad notes and recent journal. */
+  agent_id?: string;
 }
 
 export interface AgentStatusResult {
   active_sessions: RpcAgentSession[];
   change_token: string;
+  notes?: AgentNotesListResult;
+}
+
+export interface RpcAgentNote {
+  key: string;
+  value: string;
+  doc_id?: string;
+  section_id?: string;
+  updated_at: string;
+}
+
+export interface RpcJournalEntry {
+  id: number;
+  entry: string;
+  doc_id?: string;
+  section_id?: string;
+  ts: string;
+}
+
+/** `agent_id` defaults to the daemon's own agent in every `agent.notes.*` call. */
+export interface AgentNotesSetParams {
+  workspace_id: string;
+  agent_id?: string;
+  key: string;
+  value: string;
+  doc_id?: string;
+  section_id?: string;
+}
+
+export interface AgentNotesGetParams {
+  workspace_id: string;
+  agent_id?: string;
+  key: string;
+}
+
+export interface AgentNoteResult {
+  agent_id: string;
+  note: RpcAgentNote | null;
+}
+
+export interface AgentNotesListParams {
+  workspace_id: string;
+  agent_id?: string;
+  doc_id?: string;
+  section_id?: string;
+  journal_limit?: number;
+}
+
+export interface AgentNotesListResult {
+  agent_id: string;
+  notes: RpcAgentNote[];
+  /** Newest entries, oldest first. */
+  journal: RpcJournalEntry[];
+}
+
+export interface AgentNotesAppendParams {
+  workspace_id: string;
+  agent_id?: string;
+  entry: string;
+  doc_id?: string;
+  section_id?: string;
+}
+
+export interface AgentNotesAppendResult {
+  agent_id: string;
+  entry: RpcJournalEntry;
 }
 
 export interface AgentConflictsParams {
@@ -359,6 +622,68 @@ export interface AgentClaimResult {
   conflicts: AgentClaimConflict[];
 }
 
+export interface AgentPlanEntry {
+  /** Either doc_id or a workspace-relative path. */
+  doc_id?: string;
+  path?: string;
+  section_id: string;
+  intent: string;
+}
+
+export interface AgentPlanParams {
+  workspace_id: string;
+  agent_id?: string;
+  ttl_sec?: number;
+  entries: AgentPlanEntry[];
+}
+
+export interface AgentPlanOverlap {
+  kind: "plan" | "lease" | "recent_edit";
+  agent_id: string;
+  section_id: string;
+  intent?: string;
+  at: string;
+}
+
+export interface AgentPlanEntryResult {
+  doc_id: string;
+  path: string;
+  section_id: string;
+  intent: string;
+  overlaps: AgentPlanOverlap[];
+}
+
+export interface AgentPlanResult {
+  agent_id: string;
+  expires_at: string;
+  entries: AgentPlanEntryResult[];
+  conflicts: number;
+}
+
+export interface AgentUndoParams {
+  workspace_id: string;
+  agent_id: string;
+  doc_id?: string;
+  steps?: number;
+  actor_id?: string;
+  summary?: string;
+}
+
+export interface AgentUndoDocResult {
+  doc_id: string;
+  doc_path: string;
+  steps: number;
+  etag: string;
+  head_seq: number;
+}
+
+export interface AgentUndoResult {
+  agent_id: string;
+  steps_applied: number;
+  docs: AgentUndoDocResult[];
+  summary: string;
+}
+
 export interface DocBundleParams {
   workspace_id: string;
   doc_id: string;
@@ -384,6 +709,42 @@ export interface DocBundleResult {
   section_content: string;
   context: DocBundleContext;
   tokens_used: number;
+  stale: StaleSection[];
+}
+
+export interface ContextPackSeed {
+  doc_id?: string;
+  path?: string;
+  section_id?: string;
+}
+
+export interface WorkspaceContextPackParams {
+  workspace_id: string;
+  query?: string;
+  seeds?: ContextPackSeed[];
+  token_budget?: number;
+  limit?: number;
+}
+
+export type ContextPackReason = "seed" | "search" | "backlink" | "parent";
+
+export interface ContextPackItem {
+  citation: string;
+  doc_id: string;
+  path: string;
+  section_id?: string;
+  heading?: string;
+  reason: ContextPackReason;
+  score: number;
+  tokens: number;
+  content_md: string;
+}
+
+export interface WorkspaceContextPackResult {
+  items: ContextPackItem[];
+  tokens_used: number;
+  token_budget: number;
+  omitted: number;
 }
 
 export interface GitStatusParams {
@@ -407,6 +768,56 @@ export interface GitStatusResult {
   ai_configured?: boolean;
   commit_interval_sec?: number;
   status_output?: string;
+  strategy?: GitStrategy;
+  excluded_docs?: GitExcludedDoc[];
+}
+
+export type GitSyncOverride = "include" | "exclude";
+
+export type GitExclusionReason =
+  | { reason: "doc_override" }
+  | { reason: "exclude_glob"; pattern: string }
+  | { reason: "not_included" };
+
+export type GitExcludedDoc = {
+  doc_id: string;
+  path: string;
+} & GitExclusionReason;
+
+export interface GitSetDocSyncParams {
+  workspace_id: string;
+  doc_id: string;
+  sync: GitSyncOverride | null;
+}
+
+export type GitDocSyncResult = {
+  doc_id: string;
+  path: string;
+  sync: GitSyncOverride | null;
+  excluded: boolean;
+} & Partial<GitExclusionReason>;
+
+export interface GitBlameParams {
+  workspace_id: string;
+  path: string;
+  rev?: string;
+}
+
+export interface GitBlameLine {
+  line_number: number;
+  agent: string;
+  author_type?: RpcAuthorType;
+  summary?: string;
+  timestamp: string;
+  content: string;
+  commit: string;
+  section_id?: string;
+}
+
+export interface GitBlameResult {
+  doc_path: string;
+  rev: string;
+  lines: GitBlameLine[];
 }
 
 export interface GitSyncParams {
@@ -443,52 +854,160 @@ export interface GitConfigureResult {
   commit_interval_sec?: number;
 }
 
+export interface GitJobStatusParams {
+  workspace_id: string;
+  job_id: string;
+}
+
+export interface GitJobsParams {
+  workspace_id: string;
+  state?: GitSyncJobStatus;
+  limit?: number;
+}
+
+export interface GitJobError {
+  code?: string;
+  message: string;
+  stderr?: string;
+}
+
+export interface GitJob {
+  job_id: string;
+  state: GitSyncJobStatus;
+  action: GitSyncMode;
+  message: string;
+  trigger_type?: string;
+  branch?: string;
+  pull_request_url?: string;
+  attempt_count: number;
+  next_attempt_at?: string;
+  last_error?: GitJobError;
+  created_at: string;
+  updated_at: string;
+  finished_at?: string;
+}
+
+export interface GitJobsResult {
+  items: GitJob[];
+}
+
+export type ReconciliationResolution = "local" | "remote" | "both";
+
+export interface GitReconciliationsParams {
+  workspace_id: string;
+  doc_id?: string;
+  include_resolved?: boolean;
+  limit?: number;
+}
+
+export interface GitResolveReconciliationParams {
+  workspace_id: string;
+  id: number;
+  resolution: ReconciliationResolution;
+}
+
+export interface ReconciliationItem {
+  id: number;
+  doc_id: string;
+  path: string;
+  section_id?: string;
+  start_line: number;
+  base: string;
+  local: string;
+  remote: string;
+  remote_author?: string;
+  remote_commit: string;
+  created_at: string;
+  resolution?: ReconciliationResolution;
+  resolved_at?: string;
+}
+
+export interface GitReconciliationsResult {
+  items: ReconciliationItem[];
+}
+
 export interface RpcParamsMap {
   "workspace.list": WorkspaceListParams;
   "workspace.open": WorkspaceOpenParams;
   "workspace.create": WorkspaceCreateParams;
+  "workspace.context_pack": WorkspaceContextPackParams;
   "doc.create": DocCreateParams;
   "doc.read": DocReadParams;
   "doc.edit": DocEditParams;
   "doc.edit_section": DocEditSectionParams;
+  "doc.replace": DocReplaceParams;
+  "doc.batch": DocBatchParams;
   "doc.sections": DocSectionsParams;
   "doc.tree": DocTreeParams;
   "doc.search": DocSearchParams;
   "doc.diff": DocDiffParams;
   "doc.history": DocHistoryParams;
+  "doc.findings": DocFindingsParams;
+  "doc.staleness": DocStalenessParams;
   "agent.whoami": AgentWhoamiParams;
   "agent.status": AgentStatusParams;
   "agent.conflicts": AgentConflictsParams;
   "agent.list": AgentListParams;
   "agent.claim": AgentClaimParams;
+  "agent.plan": AgentPlanParams;
+  "agent.undo": AgentUndoParams;
+  "agent.redo": AgentUndoParams;
+  "agent.notes.set": AgentNotesSetParams;
+  "agent.notes.get": AgentNotesGetParams;
+  "agent.notes.list": AgentNotesListParams;
+  "agent.notes.append": AgentNotesAppendParams;
   "doc.bundle": DocBundleParams;
   "git.status": GitStatusParams;
   "git.sync": GitSyncParams;
   "git.configure": GitConfigureParams;
+  "git.job_status": GitJobStatusParams;
+  "git.jobs": GitJobsParams;
+  "git.reconciliations": GitReconciliationsParams;
+  "git.resolve_reconciliation": GitResolveReconciliationParams;
+  "git.set_doc_sync": GitSetDocSyncParams;
+  "git.blame": GitBlameParams;
 }
 
 export interface RpcResultMap {
   "workspace.list": WorkspaceListResult;
   "workspace.open": WorkspaceOpenResult;
   "workspace.create": WorkspaceCreateResult;
+  "workspace.context_pack": WorkspaceContextPackResult;
   "doc.create": DocCreateResult;
   "doc.read": DocReadResult;
   "doc.edit": DocEditResult;
   "doc.edit_section": DocEditSectionResult;
+  "doc.replace": DocReplaceResult;
+  "doc.batch": DocBatchResult;
   "doc.sections": DocSectionsResult;
   "doc.tree": DocTreeResult;
   "doc.search": DocSearchResult;
   "doc.diff": DocDiffResult;
   "doc.history": DocHistoryResult;
+  "doc.findings": DocFindingsResult;
+  "doc.staleness": DocStalenessResult;
   "agent.whoami": AgentWhoamiResult;
   "agent.status": AgentStatusResult;
   "agent.conflicts": AgentConflictsResult;
   "agent.list": AgentListResult;
   "agent.claim": AgentClaimResult;
+  "agent.plan": AgentPlanResult;
+  "agent.undo": AgentUndoResult;
+  "agent.redo": AgentUndoResult;
+  "agent.notes.set": AgentNoteResult;
+  "agent.notes.get": AgentNoteResult;
+  "agent.notes.list": AgentNotesListResult;
+  "agent.notes.append": AgentNotesAppendResult;
   "doc.bundle": DocBundleResult;
   "git.status": GitStatusResult;
   "git.sync": GitSyncResult;
   "git.configure": GitConfigureResult;
+  "git.job_status": GitJob;
+  "git.jobs": GitJobsResult;
+  "git.reconciliations": GitReconciliationsResult;
+  "git.resolve_reconciliation": ReconciliationItem;
+  "git.set_doc_sync": GitDocSyncResult;
+  "git.blame": GitBlameResult;
 }
 
 export type RpcMethod = keyof RpcParamsMap;